@@ -0,0 +1,7 @@
+mod retrieval;
+mod session;
+mod single_prompt;
+mod storage;
+
+pub use session::session;
+pub use single_prompt::single_prompt;
@@ -0,0 +1,240 @@
+//! Grounds the chat system prompt in the migration handbook and, optionally, the
+//! user's own Solana program, instead of leaving the assistant to answer from
+//! training-data recall alone. Indexing happens once per session; each user
+//! message is then scored against the index so only the handful of chunks
+//! actually relevant to that question are prepended to the system prompt.
+//!
+//! This is deliberately a simple TF-IDF cosine-similarity ranker rather than the
+//! BM25 index the MCP server's `search_handbook` tool uses (see
+//! `mcp::resources::bm25`): that one is tuned for ranking whole handbook
+//! chapters against a search query, while this only needs to pick a few chunks
+//! out of a session-local corpus per turn.
+
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Points at a checkout of the migration handbook (a directory of `.md` files).
+pub(crate) const HANDBOOK_DIR_ENV_VAR: &str = "STYLUS_PORT_HANDBOOK_DIR";
+/// Points at the Solana program the user is porting, so guidance can reference
+/// their actual types and instructions instead of generic advice.
+pub(crate) const SOLANA_REPO_DIR_ENV_VAR: &str = "STYLUS_PORT_SOLANA_REPO";
+
+/// Chunks longer than this many lines are split, so a single large file doesn't
+/// crowd out everything else once it's the top match.
+const CHUNK_LINES: usize = 60;
+/// How many chunks to prepend to the system prompt per user message.
+const TOP_K: usize = 5;
+
+struct Chunk {
+    source: String,
+    text: String,
+    term_freqs: HashMap<String, f64>,
+}
+
+/// A TF-IDF index over handbook and repo chunks, built once at `SessionCtx::init`
+/// and queried on every `send_user_message`.
+pub(crate) struct ChunkIndex {
+    chunks: Vec<Chunk>,
+    idf: HashMap<String, f64>,
+}
+
+impl ChunkIndex {
+    /// Indexes whichever of `STYLUS_PORT_HANDBOOK_DIR` and
+    /// `STYLUS_PORT_SOLANA_REPO` are set. Both are optional: an unset env var
+    /// just contributes no chunks from that source, since most sessions won't
+    /// have a repo checkout handy.
+    pub(crate) fn build() -> Self {
+        Self::build_with(env::var(SOLANA_REPO_DIR_ENV_VAR).ok())
+    }
+
+    /// Like `build`, but indexes `repo_dir` in place of `STYLUS_PORT_SOLANA_REPO`
+    /// when given, so a one-shot prompt can point the assistant at the specific
+    /// repo the user is porting without exporting an env var first. Indexing
+    /// only happens once per call, so callers that reuse the returned index
+    /// across several prompts (as a multi-turn session does) never re-embed.
+    pub(crate) fn build_with(repo_dir: Option<String>) -> Self {
+        let mut raw_chunks = Vec::new();
+
+        if let Ok(dir) = env::var(HANDBOOK_DIR_ENV_VAR) {
+            collect_chunks(Path::new(&dir), "md", &mut raw_chunks);
+        }
+        if let Some(dir) = repo_dir.or_else(|| env::var(SOLANA_REPO_DIR_ENV_VAR).ok()) {
+            collect_chunks(Path::new(&dir), "rs", &mut raw_chunks);
+        }
+
+        let idf = document_frequencies(&raw_chunks);
+        let chunks = raw_chunks
+            .into_iter()
+            .map(|(source, text)| {
+                let term_freqs = term_freqs(&text);
+                Chunk {
+                    source,
+                    text,
+                    term_freqs,
+                }
+            })
+            .collect();
+
+        Self { chunks, idf }
+    }
+
+    /// Returns the up-to-`top_k` chunks most relevant to `query`, each paired
+    /// with its source path, ordered by descending relevance.
+    fn top_chunks(&self, query: &str, top_k: usize) -> Vec<(&str, &str)> {
+        let query_freqs = term_freqs(query);
+        let query_vec = tfidf_vector(&query_freqs, &self.idf);
+
+        let mut scored: Vec<(f64, &Chunk)> = self
+            .chunks
+            .iter()
+            .map(|chunk| {
+                let chunk_vec = tfidf_vector(&chunk.term_freqs, &self.idf);
+                (cosine_similarity(&query_vec, &chunk_vec), chunk)
+            })
+            .filter(|(score, _)| *score > 0.0)
+            .collect();
+
+        scored.sort_unstable_by(|a, b| b.0.total_cmp(&a.0));
+        scored
+            .into_iter()
+            .take(top_k)
+            .map(|(_, chunk)| (chunk.source.as_str(), chunk.text.as_str()))
+            .collect()
+    }
+}
+
+/// Prepends the chunks most relevant to `query` to `base`, labelled with their
+/// source paths, or returns `base` unchanged if nothing was indexed or nothing
+/// scored above zero.
+pub(crate) fn grounded_system_prompt(base: &str, index: &ChunkIndex, query: &str) -> String {
+    grounded_system_prompt_with(base, index, query, TOP_K)
+}
+
+/// Like `grounded_system_prompt`, but retrieves `top_k` chunks instead of the
+/// default `TOP_K`.
+pub(crate) fn grounded_system_prompt_with(
+    base: &str,
+    index: &ChunkIndex,
+    query: &str,
+    top_k: usize,
+) -> String {
+    let chunks = index.top_chunks(query, top_k);
+    if chunks.is_empty() {
+        return base.to_owned();
+    }
+
+    let mut prompt = "The following excerpts may be relevant to the user's question:\n".to_owned();
+    for (source, text) in chunks {
+        prompt.push_str(&format!("\n--- {source} ---\n{text}\n"));
+    }
+    prompt.push('\n');
+    prompt.push_str(base);
+    prompt
+}
+
+/// Recursively walks `dir`, splitting every file with extension `ext` into
+/// `CHUNK_LINES`-line chunks and pushing `(source, text)` pairs onto `out`.
+/// Missing or unreadable paths are skipped rather than treated as an error,
+/// since the env vars pointing at them are optional.
+fn collect_chunks(dir: &Path, ext: &str, out: &mut Vec<(String, String)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut paths: Vec<PathBuf> = entries.filter_map(|entry| Some(entry.ok()?.path())).collect();
+    paths.sort();
+
+    for path in paths {
+        if path.is_dir() {
+            collect_chunks(&path, ext, out);
+            continue;
+        }
+
+        if path.extension().and_then(|e| e.to_str()) != Some(ext) {
+            continue;
+        }
+
+        let Ok(text) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let lines: Vec<&str> = text.lines().collect();
+        for (i, chunk_lines) in lines.chunks(CHUNK_LINES).enumerate() {
+            let start = i * CHUNK_LINES + 1;
+            let end = start + chunk_lines.len() - 1;
+            let source = format!("{}#L{start}-L{end}", path.display());
+            out.push((source, chunk_lines.join("\n")));
+        }
+    }
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_ascii_lowercase())
+}
+
+fn term_freqs(text: &str) -> HashMap<String, f64> {
+    let mut freqs = HashMap::new();
+    let mut total = 0usize;
+
+    for term in tokenize(text) {
+        *freqs.entry(term).or_insert(0.0) += 1.0;
+        total += 1;
+    }
+
+    if total > 0 {
+        for count in freqs.values_mut() {
+            *count /= total as f64;
+        }
+    }
+
+    freqs
+}
+
+fn document_frequencies(chunks: &[(String, String)]) -> HashMap<String, f64> {
+    let n = chunks.len() as f64;
+    let mut doc_freqs: HashMap<String, f64> = HashMap::new();
+
+    for (_, text) in chunks {
+        let mut seen = std::collections::HashSet::new();
+        for term in tokenize(text) {
+            if seen.insert(term.clone()) {
+                *doc_freqs.entry(term).or_insert(0.0) += 1.0;
+            }
+        }
+    }
+
+    doc_freqs
+        .into_iter()
+        .map(|(term, df)| (term, (1.0 + n / (1.0 + df)).ln()))
+        .collect()
+}
+
+fn tfidf_vector(term_freqs: &HashMap<String, f64>, idf: &HashMap<String, f64>) -> HashMap<String, f64> {
+    term_freqs
+        .iter()
+        .map(|(term, tf)| (term.clone(), tf * idf.get(term).copied().unwrap_or(0.0)))
+        .collect()
+}
+
+fn cosine_similarity(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    let (smaller, larger) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+
+    let dot: f64 = smaller
+        .iter()
+        .filter_map(|(term, weight)| Some(weight * larger.get(term)?))
+        .sum();
+
+    let norm_a: f64 = a.values().map(|w| w * w).sum::<f64>().sqrt();
+    let norm_b: f64 = b.values().map(|w| w * w).sum::<f64>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
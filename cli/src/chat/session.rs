@@ -1,4 +1,4 @@
-use std::{cmp, sync::mpsc, time::Duration};
+use std::{cmp, sync::mpsc, sync::Arc, time::Duration};
 
 use color_eyre::Result;
 use ratatui::{
@@ -13,9 +13,17 @@ use rayon::{ThreadPool, ThreadPoolBuilder};
 use throbber_widgets_tui::{Throbber, ThrobberState, BRAILLE_EIGHT_DOUBLE};
 use tui_textarea::TextArea;
 
-use crate::llm;
-
-type LlmResult<T> = Result<T, llm::Error>;
+use crate::{
+    chat::{
+        retrieval::{grounded_system_prompt, ChunkIndex},
+        storage::ChatStore,
+    },
+    llm::{
+        self,
+        backend::{current_backend, LlmBackend},
+        Message, Role, StreamChunk,
+    },
+};
 
 /// Period between UI refreshes.
 const TICK: Duration = Duration::from_millis(100);
@@ -24,27 +32,39 @@ const PROMPT_WORKER_POOL_SIZE: usize = 2;
 const BORDERS: u16 = 2;
 const TITLE_HEIGHT: u16 = 1;
 
-// TODO: generate system prompt based on handbook and any specified solana repo
-const SYSTEM_PROMPT: &str = "You are a helpful assistant";
+/// Prefixed with the handbook/repo excerpts `retrieval::grounded_system_prompt`
+/// finds relevant to each user message before it's sent to the backend.
+const BASE_SYSTEM_PROMPT: &str = "You are a helpful assistant";
+
+/// The `Message` role a rendered chat item carries in the rolling history, or
+/// `None` for the spinner placeholder, which isn't part of the conversation.
+fn role_for(kind: ChatItemKind) -> Option<llm::Role> {
+    match kind {
+        ChatItemKind::User => Some(llm::Role::User),
+        ChatItemKind::Llm => Some(llm::Role::Assistant),
+        ChatItemKind::Spinner => None,
+    }
+}
 
 struct PromptWorkerPool {
     pool: ThreadPool,
+    backend: Arc<dyn LlmBackend + Send + Sync>,
 }
 
 impl PromptWorkerPool {
-    fn init() -> Result<Self> {
+    fn init(backend: Arc<dyn LlmBackend + Send + Sync>) -> Result<Self> {
         let pool = ThreadPoolBuilder::new()
             .num_threads(PROMPT_WORKER_POOL_SIZE)
             .build()?;
 
-        Ok(Self { pool })
+        Ok(Self { pool, backend })
     }
 
-    // TODO: Pass previous messages and responses to append to the context
-    fn start(&self, prompt: String) -> PromptWorker {
+    fn start(&self, system_prompt: String, messages: Vec<Message>) -> PromptWorker {
         let (tx, rx) = mpsc::channel();
+        let backend = Arc::clone(&self.backend);
         self.pool.spawn(move || {
-            tx.send(llm::execute(SYSTEM_PROMPT, &prompt)).ok();
+            backend.execute_stream(&system_prompt, &messages, &tx);
         });
         PromptWorker { rx }
     }
@@ -52,11 +72,11 @@ impl PromptWorkerPool {
 
 #[derive(Debug)]
 struct PromptWorker {
-    rx: mpsc::Receiver<LlmResult<String>>,
+    rx: mpsc::Receiver<StreamChunk>,
 }
 
 impl PromptWorker {
-    fn try_recv(&self) -> Option<LlmResult<String>> {
+    fn try_recv(&self) -> Option<StreamChunk> {
         self.rx.try_recv().ok()
     }
 }
@@ -103,6 +123,42 @@ struct SessionCtx {
     throbber_state: ThrobberState,
     prompt_text_area: TextArea<'static>,
     title_line: String,
+    store: ChatStore,
+    conversation_id: i64,
+    chunk_index: ChunkIndex,
+}
+
+/// Offers to resume the most recent conversation, if one exists, over a plain stdin
+/// prompt (the TUI hasn't been initialized yet). Returns the conversation to append
+/// to and the chat items to seed the list with.
+fn resume_or_start_conversation(store: &ChatStore, model: &str) -> Result<(i64, Vec<ChatItem>)> {
+    if let Some(summary) = store.most_recent_conversation()? {
+        println!(
+            "Resume previous conversation from {} ({})? [Y/n] ",
+            summary.created_at, summary.model
+        );
+
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+
+        if !answer.trim().eq_ignore_ascii_case("n") {
+            let chat_list_items = store
+                .load_turns(summary.id)?
+                .into_iter()
+                .map(|message| ChatItem {
+                    kind: match message.role {
+                        Role::User => ChatItemKind::User,
+                        Role::Assistant => ChatItemKind::Llm,
+                    },
+                    message: message.content,
+                })
+                .collect();
+
+            return Ok((summary.id, chat_list_items));
+        }
+    }
+
+    Ok((store.create_conversation(model)?, vec![]))
 }
 
 impl SessionCtx {
@@ -115,18 +171,29 @@ impl SessionCtx {
                 .yellow(),
         );
 
-        let model = llm::model()?;
-        let title_line = format!("❯ StylusPort::Chat - {model}");
+        let backend: Arc<dyn LlmBackend + Send + Sync> = Arc::from(current_backend()?);
+        let title_line = format!(
+            "❯ StylusPort::Chat - {} ({})",
+            backend.model_name(),
+            backend.label()
+        );
+
+        let store = ChatStore::open_default()?;
+        let (conversation_id, chat_list_items) =
+            resume_or_start_conversation(&store, backend.model_name())?;
 
         Ok(Self {
-            prompt_worker_pool: PromptWorkerPool::init()?,
+            prompt_worker_pool: PromptWorkerPool::init(backend)?,
             chat_status: ChatStatus::Idle,
-            chat_list_items: vec![],
+            chat_list_items,
             chat_list_area: Rect::default(),
             chat_list_scroll_y: 0,
             throbber_state: ThrobberState::default(),
             prompt_text_area,
             title_line,
+            store,
+            conversation_id,
+            chunk_index: ChunkIndex::build(),
         })
     }
 
@@ -202,17 +269,72 @@ impl SessionCtx {
         self.chat_list_area = chat_area;
     }
 
-    fn cancel_spinner(&mut self) {
+    /// Discards the in-flight response, whether it's still the spinner placeholder
+    /// (no tokens yet) or a partially-streamed `Llm` item (cancelled mid-stream).
+    fn cancel_in_flight_response(&mut self) {
         assert!(
             self.chat_list_items
                 .pop()
-                .is_some_and(|i| i.kind.is_spinner()),
-            "if the chat status is waiting the last item in the list is always a spinner"
+                .is_some_and(|i| matches!(i.kind, ChatItemKind::Spinner | ChatItemKind::Llm)),
+            "if the chat status is waiting the last item in the list is the spinner or a partial Llm item"
         );
         self.chat_status = ChatStatus::Idle;
     }
 
-    fn send_user_message(&mut self) {
+    /// Appends one streamed token to the live response, replacing the spinner
+    /// placeholder with a fresh `Llm` item on the first token.
+    fn append_stream_token(&mut self, token: &str) {
+        match self.chat_list_items.last_mut() {
+            Some(item) if matches!(item.kind, ChatItemKind::Llm) => item.message.push_str(token),
+            _ => {
+                assert!(
+                    self.chat_list_items
+                        .pop()
+                        .is_some_and(|i| i.kind.is_spinner()),
+                    "the first token always finds the spinner placeholder at the end of the list"
+                );
+                self.chat_list_items.push(ChatItem {
+                    kind: ChatItemKind::Llm,
+                    message: token.to_owned(),
+                });
+            }
+        }
+
+        self.chat_list_scroll_y = u16::MAX;
+    }
+
+    /// Persists the completed response and marks the chat idle again. If the
+    /// stream ended without producing a single token, a trailing empty `Llm` item
+    /// replaces the spinner, matching what `append_stream_token` would have left
+    /// behind.
+    fn finish_stream(&mut self) -> Result<()> {
+        if self
+            .chat_list_items
+            .last()
+            .is_some_and(|item| item.kind.is_spinner())
+        {
+            self.chat_list_items.pop();
+            self.chat_list_items.push(ChatItem {
+                kind: ChatItemKind::Llm,
+                message: String::new(),
+            });
+        }
+
+        let message = self
+            .chat_list_items
+            .last()
+            .expect("just ensured a trailing Llm item")
+            .message
+            .clone();
+
+        self.store
+            .append_turn(self.conversation_id, &Message::assistant(message))?;
+        self.chat_status = ChatStatus::Idle;
+
+        Ok(())
+    }
+
+    fn send_user_message(&mut self) -> Result<()> {
         // cut the message from the prompt input
         self.prompt_text_area.select_all();
         self.prompt_text_area.cut();
@@ -225,11 +347,28 @@ impl SessionCtx {
             kind: ChatItemKind::Spinner,
             message: String::new(),
         });
+        self.store
+            .append_turn(self.conversation_id, &Message::user(prompt.clone()))?;
         // scroll the chat list to the bottom when a new user message is sent.
         self.chat_list_scroll_y = u16::MAX;
-        // execute prompt on a background thread
-        let worker = self.prompt_worker_pool.start(prompt);
+        // execute prompt on a background thread, carrying the full conversation so
+        // far so follow-up questions can build on prior context.
+        let messages = self
+            .chat_list_items
+            .iter()
+            .filter_map(|item| {
+                role_for(item.kind).map(|role| Message {
+                    role,
+                    content: item.message.clone(),
+                })
+            })
+            .collect();
+        let system_prompt =
+            grounded_system_prompt(BASE_SYSTEM_PROMPT, &self.chunk_index, &prompt);
+        let worker = self.prompt_worker_pool.start(system_prompt, messages);
         self.chat_status = ChatStatus::Waiting(worker);
+
+        Ok(())
     }
 
     // returns true if the session should quit
@@ -241,7 +380,7 @@ impl SessionCtx {
 
             KeyEvent {
                 code: KeyCode::Esc, ..
-            } => self.cancel_spinner(),
+            } => self.cancel_in_flight_response(),
 
             KeyEvent {
                 code: KeyCode::Up, ..
@@ -282,7 +421,7 @@ impl SessionCtx {
                 modifiers: KeyModifiers::CONTROL,
                 ..
             } if !self.prompt_text_area.is_empty() && !self.chat_status.is_waiting() => {
-                self.send_user_message()
+                self.send_user_message()?
             }
 
             input => {
@@ -293,22 +432,33 @@ impl SessionCtx {
         Ok(false)
     }
 
+    /// Drains every chunk the worker has buffered since the last tick, appending
+    /// tokens to the live response as they arrive instead of waiting for the whole
+    /// completion, so long generations render incrementally.
     fn check_for_response(&mut self) -> Result<()> {
         let ChatStatus::Waiting(ref worker) = self.chat_status else {
             return Ok(());
         };
 
-        let Some(llm_response) = worker.try_recv().transpose()? else {
-            // drive the spinner
-            self.throbber_state.calc_next();
-            return Ok(());
-        };
+        let mut received_any = false;
 
-        self.cancel_spinner();
-        self.chat_list_items.push(ChatItem {
-            kind: ChatItemKind::Llm,
-            message: llm_response,
-        });
+        while let Some(chunk) = worker.try_recv() {
+            received_any = true;
+
+            match chunk {
+                StreamChunk::Token(token) => self.append_stream_token(&token),
+                StreamChunk::Done => return self.finish_stream(),
+                StreamChunk::Err(err) => {
+                    self.cancel_in_flight_response();
+                    return Err(err.into());
+                }
+            }
+        }
+
+        if !received_any {
+            // drive the spinner while waiting for the first token
+            self.throbber_state.calc_next();
+        }
 
         Ok(())
     }
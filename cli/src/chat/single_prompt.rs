@@ -1,44 +1,64 @@
-use std::{sync::mpsc, thread, time::Duration};
+use std::{
+    sync::{mpsc, Arc},
+    thread,
+    time::Duration,
+};
 
 use color_eyre::Result;
 use ratatui::{
     crossterm::event,
+    layout::Rect,
     style::{Color, Style, Stylize},
     text::Line,
-    widgets::{Block, Padding, Paragraph, Widget, Wrap},
+    widgets::{Block, Borders, Padding, Paragraph, Widget, Wrap},
     DefaultTerminal, TerminalOptions, Viewport,
 };
 use throbber_widgets_tui::{Throbber, ThrobberState, BRAILLE_EIGHT_DOUBLE};
-
-use crate::llm;
-
-type LlmResult<T> = Result<T, llm::Error>;
+use tui_textarea::TextArea;
+
+use crate::{
+    chat::retrieval::{grounded_system_prompt_with, ChunkIndex},
+    llm::{
+        self,
+        backend::{current_backend, LlmBackend},
+        Message, StreamChunk,
+    },
+};
 
 /// Period between UI refreshes.
 const TICK: Duration = Duration::from_millis(100);
 const BORDER: u16 = 2;
-const SPINNER_HEIGHT: u16 = 1;
+const FOLLOW_UP_SIGIL: &str = "❯ Follow-up (Ctrl+Space to send, Esc to quit)";
+const FOLLOW_UP_MIN_HEIGHT: u16 = 3;
 
-// TODO: generate system prompt based on handbook and any specified solana repo
-const SYSTEM_PROMPT: &str = "You are a helpful assistant";
+/// Prefixed with the handbook/repo excerpts `retrieval::grounded_system_prompt_with`
+/// finds relevant to the prompt before it's sent to the backend.
+const BASE_SYSTEM_PROMPT: &str = "You are a helpful assistant";
 
 struct PromptWorker {
-    rx: mpsc::Receiver<LlmResult<String>>,
+    rx: mpsc::Receiver<StreamChunk>,
 }
 
 impl PromptWorker {
-    fn start(prompt: String) -> Self {
+    fn start(backend: Arc<dyn LlmBackend + Send + Sync>, system_prompt: String, messages: Vec<Message>) -> Self {
         let (tx, rx) = mpsc::channel();
-        thread::spawn(move || tx.send(llm::execute(SYSTEM_PROMPT, &prompt)));
+        thread::spawn(move || backend.execute_stream(&system_prompt, &messages, &tx));
         Self { rx }
     }
 
-    fn try_recv(&self) -> Option<LlmResult<String>> {
+    fn try_recv(&self) -> Option<StreamChunk> {
         self.rx.try_recv().ok()
     }
 }
 
-fn draw_prompt_and_spinner(f: &mut ratatui::Frame, prompt: &str, throbber_state: &ThrobberState) {
+/// Renders `prompt` followed by whatever of the answer has streamed in so far,
+/// trailing the throbber until `StreamChunk::Done` arrives.
+fn draw_prompt_and_spinner(
+    f: &mut ratatui::Frame,
+    prompt: &str,
+    partial_answer: &str,
+    throbber_state: &ThrobberState,
+) {
     let mut lines: Vec<_> = prompt
         .lines()
         .map(|line| Line::from(line).fg(Color::Yellow))
@@ -48,6 +68,8 @@ fn draw_prompt_and_spinner(f: &mut ratatui::Frame, prompt: &str, throbber_state:
         '-'.to_string().repeat((f.area().width - BORDER) as usize),
     ));
 
+    lines.extend(tui_markdown::from_str(partial_answer).lines);
+
     lines.push(
         Throbber::default()
             .throbber_style(Style::default().fg(Color::Blue))
@@ -62,7 +84,50 @@ fn draw_prompt_and_spinner(f: &mut ratatui::Frame, prompt: &str, throbber_state:
     f.render_widget(widget, f.area());
 }
 
-fn display_answer(mut terminal: DefaultTerminal, prompt: &str, answer: &str) -> Result<()> {
+/// How tall the inline viewport needs to be to show `prompt` plus whatever of
+/// the answer has streamed in so far, with one extra line reserved for the
+/// throbber.
+fn streaming_viewport_height(prompt: &str, partial_answer: &str) -> u16 {
+    let answer_lines = tui_markdown::from_str(partial_answer).lines.len() as u16;
+    prompt.lines().count() as u16 + BORDER + answer_lines + 1
+}
+
+fn resize_viewport(terminal: &mut DefaultTerminal, height: u16) -> Result<()> {
+    let width = terminal.size()?.width;
+    terminal.resize(Rect::new(0, 0, width, height))?;
+    Ok(())
+}
+
+/// Streams one turn's answer into the inline viewport, growing it as tokens
+/// arrive, until `StreamChunk::Done` (returns the full answer), the request
+/// errors, or the user cancels with Ctrl+C (returns `None`).
+fn stream_answer(terminal: &mut DefaultTerminal, worker: &PromptWorker, prompt: &str) -> Result<Option<String>> {
+    let mut throbber_state = ThrobberState::default();
+    let mut answer = String::new();
+
+    loop {
+        resize_viewport(terminal, streaming_viewport_height(prompt, &answer))?;
+        terminal.draw(|f| draw_prompt_and_spinner(f, prompt, &answer, &throbber_state))?;
+
+        match worker.try_recv() {
+            Some(StreamChunk::Token(token)) => answer.push_str(&token),
+            Some(StreamChunk::Done) => return Ok(Some(answer)),
+            Some(StreamChunk::Err(err)) => return Err(err.into()),
+            None => {
+                throbber_state.calc_next();
+                thread::sleep(TICK);
+            }
+        }
+
+        if is_ctrl_c_pressed()? {
+            return Ok(None);
+        }
+    }
+}
+
+/// Scrolls the finished exchange into the terminal's permanent history, above
+/// wherever the next turn (or follow-up prompt) will be drawn.
+fn display_answer(terminal: &mut DefaultTerminal, prompt: &str, answer: &str) -> Result<()> {
     let required_height = prompt.lines().count() as u16 + answer.lines().count() as u16 + BORDER;
 
     terminal.insert_before(required_height, |buf| {
@@ -87,6 +152,54 @@ fn display_answer(mut terminal: DefaultTerminal, prompt: &str, answer: &str) ->
     Ok(())
 }
 
+/// Keeps the terminal open after an answer to accept a follow-up prompt,
+/// mirroring `session.rs`'s Ctrl+Space-to-send textarea. Returns `None` if the
+/// user presses Esc instead, ending the session.
+fn read_follow_up_prompt(terminal: &mut DefaultTerminal) -> Result<Option<String>> {
+    let mut text_area = TextArea::default();
+    text_area.set_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(FOLLOW_UP_SIGIL)
+            .yellow(),
+    );
+
+    loop {
+        let height = text_area.lines().len().max(FOLLOW_UP_MIN_HEIGHT as usize) as u16 + BORDER;
+        resize_viewport(terminal, height)?;
+        terminal.draw(|f| f.render_widget(&text_area, f.area()))?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+
+        let event::Event::Key(key_event) = event::read()? else {
+            continue;
+        };
+
+        match key_event {
+            event::KeyEvent {
+                code: event::KeyCode::Esc,
+                ..
+            } => return Ok(None),
+
+            event::KeyEvent {
+                code: event::KeyCode::Char(' '),
+                modifiers: event::KeyModifiers::CONTROL,
+                ..
+            } if !text_area.is_empty() => {
+                text_area.select_all();
+                text_area.cut();
+                return Ok(Some(text_area.yank_text()));
+            }
+
+            input => {
+                text_area.input(input);
+            }
+        }
+    }
+}
+
 fn is_ctrl_c_pressed() -> Result<bool> {
     if !event::poll(Duration::from_millis(1))? {
         return Ok(false);
@@ -102,33 +215,45 @@ fn is_ctrl_c_pressed() -> Result<bool> {
     }
 }
 
-pub fn single_prompt(prompt: &str, plain_output: bool) -> Result<()> {
+pub fn single_prompt(
+    prompt: &str,
+    plain_output: bool,
+    repo_path: Option<String>,
+    top_k: usize,
+) -> Result<()> {
+    let chunk_index = ChunkIndex::build_with(repo_path);
+
     if plain_output {
-        println!("{}", llm::execute(SYSTEM_PROMPT, prompt)?);
+        let system_prompt = grounded_system_prompt_with(BASE_SYSTEM_PROMPT, &chunk_index, prompt, top_k);
+        println!("{}", llm::execute(&system_prompt, &[Message::user(prompt.to_owned())])?);
         return Ok(());
     }
 
-    let mut throbber_state = ThrobberState::default();
-    let worker = PromptWorker::start(prompt.to_owned());
+    let backend: Arc<dyn LlmBackend + Send + Sync> = Arc::from(current_backend()?);
 
     let mut terminal = ratatui::init_with_options(TerminalOptions {
-        viewport: Viewport::Inline(prompt.lines().count() as u16 + BORDER + SPINNER_HEIGHT),
+        viewport: Viewport::Inline(streaming_viewport_height(prompt, "")),
     });
 
-    loop {
-        terminal.draw(|f| draw_prompt_and_spinner(f, prompt, &throbber_state))?;
+    let mut history = Vec::new();
+    let mut prompt = prompt.to_owned();
 
-        if let Some(answer) = worker.try_recv().transpose()? {
-            display_answer(terminal, prompt, &answer)?;
-            break;
-        }
+    'turns: loop {
+        history.push(Message::user(prompt.clone()));
 
-        throbber_state.calc_next();
+        let system_prompt = grounded_system_prompt_with(BASE_SYSTEM_PROMPT, &chunk_index, &prompt, top_k);
+        let worker = PromptWorker::start(Arc::clone(&backend), system_prompt, history.clone());
 
-        thread::sleep(TICK);
+        let Some(answer) = stream_answer(&mut terminal, &worker, &prompt)? else {
+            break 'turns;
+        };
 
-        if is_ctrl_c_pressed()? {
-            break;
+        history.push(Message::assistant(answer.clone()));
+        display_answer(&mut terminal, &prompt, &answer)?;
+
+        match read_follow_up_prompt(&mut terminal)? {
+            Some(next) => prompt = next,
+            None => break 'turns,
         }
     }
 
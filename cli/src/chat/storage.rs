@@ -0,0 +1,190 @@
+//! Persists chat sessions to a local SQLite database so a conversation
+//! survives quitting the TUI, modeled as two normalized tables rather than
+//! one ad-hoc blob per session: `conversations` (one row per session) and
+//! `turns` (one row per message, in order, referencing its conversation).
+//!
+//! This keeps each turn queryable/exportable on its own (by role, by time,
+//! by conversation) instead of requiring the whole history to be
+//! deserialized just to read one message.
+
+use std::{env, path::PathBuf};
+
+use rusqlite::Connection;
+
+use crate::llm::{Message, Role};
+
+const CHAT_DB_PATH_ENV_VAR: &str = "STYLUS_PORT_CHAT_DB_PATH";
+const DEFAULT_CHAT_DB_PATH: &str = "stylusport_chat.db";
+
+fn role_label(role: Role) -> &'static str {
+    match role {
+        Role::User => "user",
+        Role::Assistant => "assistant",
+    }
+}
+
+fn parse_role(label: &str) -> rusqlite::Result<Role> {
+    match label {
+        "user" => Ok(Role::User),
+        "assistant" => Ok(Role::Assistant),
+        other => Err(rusqlite::Error::InvalidColumnType(
+            0,
+            format!("unknown role {other}"),
+            rusqlite::types::Type::Text,
+        )),
+    }
+}
+
+/// A previously-saved conversation, offered for resume at startup.
+pub struct ConversationSummary {
+    pub id: i64,
+    pub model: String,
+    pub created_at: String,
+}
+
+pub struct ChatStore {
+    conn: Connection,
+}
+
+impl ChatStore {
+    /// Opens the chat database at `STYLUS_PORT_CHAT_DB_PATH` (or
+    /// `stylusport_chat.db` in the working directory), creating the schema if
+    /// this is the first run.
+    pub fn open_default() -> rusqlite::Result<Self> {
+        let path = env::var(CHAT_DB_PATH_ENV_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(DEFAULT_CHAT_DB_PATH));
+
+        Self::open(&path)
+    }
+
+    pub fn open(path: &std::path::Path) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+
+        conn.execute_batch(
+            "
+            CREATE TABLE IF NOT EXISTS conversations (
+                id INTEGER PRIMARY KEY,
+                model TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+            );
+            CREATE TABLE IF NOT EXISTS turns (
+                id INTEGER PRIMARY KEY,
+                conversation_id INTEGER NOT NULL REFERENCES conversations(id),
+                role TEXT NOT NULL,
+                content TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+            );
+            ",
+        )?;
+
+        Ok(Self { conn })
+    }
+
+    /// The most recently created conversation, if any, offered for resume.
+    pub fn most_recent_conversation(&self) -> rusqlite::Result<Option<ConversationSummary>> {
+        self.conn
+            .query_row(
+                "SELECT id, model, created_at FROM conversations ORDER BY id DESC LIMIT 1",
+                (),
+                |row| {
+                    Ok(ConversationSummary {
+                        id: row.get(0)?,
+                        model: row.get(1)?,
+                        created_at: row.get(2)?,
+                    })
+                },
+            )
+            .map(Some)
+            .or_else(|err| match err {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                err => Err(err),
+            })
+    }
+
+    pub fn create_conversation(&self, model: &str) -> rusqlite::Result<i64> {
+        self.conn.execute(
+            "INSERT INTO conversations (model) VALUES (?1)",
+            (model,),
+        )?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Appends one turn to `conversation_id`, persisting it immediately so a
+    /// crash mid-session loses at most the in-flight reply.
+    pub fn append_turn(&self, conversation_id: i64, message: &Message) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT INTO turns (conversation_id, role, content) VALUES (?1, ?2, ?3)",
+            (conversation_id, role_label(message.role), &message.content),
+        )?;
+
+        Ok(())
+    }
+
+    /// Loads every turn of `conversation_id`, oldest first.
+    pub fn load_turns(&self, conversation_id: i64) -> rusqlite::Result<Vec<Message>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT role, content FROM turns WHERE conversation_id = ?1 ORDER BY id ASC",
+        )?;
+
+        stmt.query_map((conversation_id,), |row| {
+            let role: String = row.get(0)?;
+            let content: String = row.get(1)?;
+            Ok((role, content))
+        })?
+        .map(|result| {
+            let (role, content) = result?;
+            Ok(Message {
+                role: parse_role(&role)?,
+                content,
+            })
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> ChatStore {
+        ChatStore::open(std::path::Path::new(":memory:")).unwrap()
+    }
+
+    #[test]
+    fn fresh_store_has_no_recent_conversation() {
+        assert!(store().most_recent_conversation().unwrap().is_none());
+    }
+
+    #[test]
+    fn round_trips_turns_in_order() {
+        let store = store();
+        let conversation_id = store.create_conversation("gpt-4").unwrap();
+
+        store
+            .append_turn(conversation_id, &Message::user("hello"))
+            .unwrap();
+        store
+            .append_turn(conversation_id, &Message::assistant("hi there"))
+            .unwrap();
+
+        let turns = store.load_turns(conversation_id).unwrap();
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].role, Role::User);
+        assert_eq!(turns[0].content, "hello");
+        assert_eq!(turns[1].role, Role::Assistant);
+        assert_eq!(turns[1].content, "hi there");
+    }
+
+    #[test]
+    fn most_recent_conversation_is_the_latest_created() {
+        let store = store();
+        store.create_conversation("gpt-4").unwrap();
+        let latest = store.create_conversation("claude-3").unwrap();
+
+        let summary = store.most_recent_conversation().unwrap().unwrap();
+        assert_eq!(summary.id, latest);
+        assert_eq!(summary.model, "claude-3");
+    }
+}
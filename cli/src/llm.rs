@@ -1,101 +1,733 @@
-use std::{borrow::Cow, env};
+use std::{borrow::Cow, env, thread, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
+pub(crate) mod backend;
+mod template;
+
 const LLM_URL_ENV_VAR: &str = "STYLUS_PORT_LLM_URL";
 const LLM_URL_API_KEY_VAR: &str = "STYLUS_PORT_LLM_API_KEY";
 const LLM_MODEL_ENV_VAR: &str = "STYLUS_PORT_LLM_MODEL";
+const LLM_PROVIDER_ENV_VAR: &str = "STYLUS_PORT_LLM_PROVIDER";
+const LLM_TEMPERATURE_ENV_VAR: &str = "STYLUS_PORT_LLM_TEMPERATURE";
+const LLM_TOP_P_ENV_VAR: &str = "STYLUS_PORT_LLM_TOP_P";
+const LLM_MAX_TOKENS_ENV_VAR: &str = "STYLUS_PORT_LLM_MAX_TOKENS";
+const LLM_MAX_RETRIES_ENV_VAR: &str = "STYLUS_PORT_LLM_MAX_RETRIES";
 
 const OLLAMA_DUMMY_API_KEY: &str = "ollama";
 const GENERATE_ISSUE_TEMPERATURE: f32 = 0.5;
+const DEFAULT_TOP_P: f32 = 1.0;
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+const DEFAULT_MAX_RETRIES: u32 = 4;
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("{0} is not set")]
     EnvVarNotSet(&'static str),
+    #[error("unknown LLM provider: {0}, expected one of: openai, anthropic, ollama, bedrock")]
+    UnknownProvider(String),
     #[error("Invalid response with {0} messages")]
     InvalidResponse(usize),
-    #[error("LLM request error: {0}")]
-    Request(#[from] ureq::Error),
+    #[error("LLM request error after {attempts} attempt(s): {source}")]
+    Request {
+        attempts: u32,
+        #[source]
+        source: ureq::Error,
+    },
+    #[error("LLM endpoint returned HTTP {status} after {attempts} attempt(s)")]
+    Status { status: u16, attempts: u32 },
+    #[error("failed to decode LLM response: {0}")]
+    Decode(#[from] serde_json::Error),
+    #[error("failed to read LLM response body: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Whether an HTTP status is worth retrying: rate limiting and transient server errors.
+fn is_retryable_status(status: u16) -> bool {
+    status == 429 || (500..600).contains(&status)
+}
+
+/// Exponential backoff, doubling the base delay per attempt and capping at
+/// `RETRY_MAX_DELAY`. `attempt` is zero-indexed (0 = first retry).
+fn backoff_delay(attempt: u32) -> Duration {
+    RETRY_BASE_DELAY
+        .saturating_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX))
+        .min(RETRY_MAX_DELAY)
+}
+
+/// Who sent one `Message` in the rolling history passed to `execute`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+/// One message in the ordered history `execute`/`execute_many` send to the backend,
+/// so a follow-up prompt carries its prior exchange instead of starting fresh.
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub role: Role,
+    pub content: String,
+}
+
+impl Message {
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::User,
+            content: content.into(),
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::Assistant,
+            content: content.into(),
+        }
+    }
+}
+
+/// One incremental piece of a streamed response, as sent over `PromptWorker`'s
+/// channel in place of blocking until the whole completion arrives.
+#[derive(Debug)]
+pub enum StreamChunk {
+    /// A piece of completion text to append to the live chat item.
+    Token(String),
+    /// The stream has finished; no more `Token`s will follow.
+    Done,
+    /// The request failed; no more chunks will follow.
+    Err(Error),
+}
+
+/// The result of parsing one line of a provider's streamed response body, as
+/// distinct from `StreamChunk`: this is the wire-level line, not yet folded into
+/// the channel that `LlmBackend::execute_stream` callers read from.
+enum StreamLine {
+    Delta(String),
+    Done,
+    Ignore,
+}
+
+impl Role {
+    fn anthropic_role(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Assistant => "assistant",
+        }
+    }
+}
+
+impl From<Role> for OpenAiRole {
+    fn from(role: Role) -> Self {
+        match role {
+            Role::User => Self::User,
+            Role::Assistant => Self::Assistant,
+        }
+    }
+}
+
+/// Sampling parameters shared across providers, read from env with the defaults this
+/// client has always used baked in.
+#[derive(Debug, Clone, Copy)]
+struct SamplingParams {
+    temperature: f32,
+    top_p: f32,
+    max_tokens: u32,
+}
+
+impl SamplingParams {
+    fn from_env() -> Self {
+        fn parsed<T: std::str::FromStr>(var: &str, default: T) -> T {
+            env::var(var)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        }
+
+        Self {
+            temperature: parsed(LLM_TEMPERATURE_ENV_VAR, GENERATE_ISSUE_TEMPERATURE),
+            top_p: parsed(LLM_TOP_P_ENV_VAR, DEFAULT_TOP_P),
+            max_tokens: parsed(LLM_MAX_TOKENS_ENV_VAR, DEFAULT_MAX_TOKENS),
+        }
+    }
+}
+
+/// Selects which wire format `execute`/`execute_many` speak to `STYLUS_PORT_LLM_URL`,
+/// chosen via `STYLUS_PORT_LLM_PROVIDER`. Defaults to `OpenAi`, which is the shape this
+/// client has always spoken and is also what Ollama's own OpenAI-compatible endpoint
+/// expects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Provider {
+    #[default]
+    OpenAi,
+    Anthropic,
+    Ollama,
+    /// AWS Bedrock's Converse API, authenticated with a Bedrock API key (a
+    /// plain bearer token - <https://docs.aws.amazon.com/bedrock/latest/userguide/api-keys.html> -
+    /// rather than full SigV4 request signing).
+    Bedrock,
+}
+
+impl Provider {
+    fn from_env() -> Result<Self, Error> {
+        match env::var(LLM_PROVIDER_ENV_VAR) {
+            Err(_) => Ok(Self::default()),
+            Ok(value) => match value.to_lowercase().as_str() {
+                "openai" => Ok(Self::OpenAi),
+                "anthropic" => Ok(Self::Anthropic),
+                "ollama" => Ok(Self::Ollama),
+                "bedrock" => Ok(Self::Bedrock),
+                _ => Err(Error::UnknownProvider(value)),
+            },
+        }
+    }
+
+    /// Whether this provider can return several candidate completions from a single
+    /// request (OpenAI's `n`). Providers without that notion are driven by issuing `n`
+    /// sequential requests instead, see `execute_many`.
+    fn supports_native_n(self) -> bool {
+        matches!(self, Self::OpenAi)
+    }
+
+    /// Whether this provider's streamed response can be parsed incrementally by
+    /// `parse_stream_line`. Bedrock's Converse API streams its response as AWS's
+    /// binary `application/vnd.amazon.eventstream` framing rather than
+    /// newline-delimited text, which isn't worth hand-rolling here, so it falls back
+    /// to a single blocking request, see `backend::ProviderBackend::stream`.
+    fn supports_streaming(self) -> bool {
+        !matches!(self, Self::Bedrock)
+    }
+
+    /// Builds the same request body as `request_body`, with streaming turned on.
+    fn stream_request_body(
+        self,
+        system: &str,
+        messages: &[Message],
+        model: &str,
+        sampling: &SamplingParams,
+    ) -> serde_json::Value {
+        let mut body = self.request_body(system, messages, model, sampling, 1);
+        if let Some(object) = body.as_object_mut() {
+            object.insert("stream".to_owned(), serde_json::Value::Bool(true));
+        }
+        body
+    }
+
+    /// Parses one line of a streamed response body, as produced by `send_stream`.
+    fn parse_stream_line(self, line: &str) -> Result<StreamLine, Error> {
+        let line = line.trim();
+
+        if line.is_empty() {
+            return Ok(StreamLine::Ignore);
+        }
+
+        match self {
+            Self::OpenAi => {
+                let Some(data) = line.strip_prefix("data: ") else {
+                    return Ok(StreamLine::Ignore);
+                };
+
+                if data == "[DONE]" {
+                    return Ok(StreamLine::Done);
+                }
+
+                #[derive(Deserialize)]
+                struct Delta {
+                    content: Option<String>,
+                }
+                #[derive(Deserialize)]
+                struct Choice {
+                    delta: Delta,
+                }
+                #[derive(Deserialize)]
+                struct Chunk {
+                    choices: Vec<Choice>,
+                }
+
+                let chunk: Chunk = serde_json::from_str(data)?;
+                Ok(chunk
+                    .choices
+                    .into_iter()
+                    .next()
+                    .and_then(|choice| choice.delta.content)
+                    .map_or(StreamLine::Ignore, StreamLine::Delta))
+            }
+            Self::Anthropic => {
+                let Some(data) = line.strip_prefix("data: ") else {
+                    return Ok(StreamLine::Ignore);
+                };
+
+                #[derive(Deserialize)]
+                struct TextDelta {
+                    text: Option<String>,
+                }
+                #[derive(Deserialize)]
+                struct Event {
+                    #[serde(rename = "type")]
+                    kind: String,
+                    delta: Option<TextDelta>,
+                }
+
+                let event: Event = serde_json::from_str(data)?;
+                match event.kind.as_str() {
+                    "content_block_delta" => Ok(event
+                        .delta
+                        .and_then(|delta| delta.text)
+                        .map_or(StreamLine::Ignore, StreamLine::Delta)),
+                    "message_stop" => Ok(StreamLine::Done),
+                    _ => Ok(StreamLine::Ignore),
+                }
+            }
+            Self::Ollama => {
+                #[derive(Deserialize)]
+                struct ChunkMessage {
+                    content: String,
+                }
+                #[derive(Deserialize)]
+                struct Chunk {
+                    message: ChunkMessage,
+                    done: bool,
+                }
+
+                let chunk: Chunk = serde_json::from_str(line)?;
+                if chunk.done {
+                    Ok(StreamLine::Done)
+                } else if chunk.message.content.is_empty() {
+                    Ok(StreamLine::Ignore)
+                } else {
+                    Ok(StreamLine::Delta(chunk.message.content))
+                }
+            }
+            Self::Bedrock => Ok(StreamLine::Ignore),
+        }
+    }
+
+    fn request_body(
+        self,
+        system: &str,
+        messages: &[Message],
+        model: &str,
+        sampling: &SamplingParams,
+        n: u32,
+    ) -> serde_json::Value {
+        match self {
+            Self::OpenAi => {
+                let mut wire_messages = vec![OpenAiMessage {
+                    role: OpenAiRole::System,
+                    content: Cow::Borrowed(system),
+                }];
+                wire_messages.extend(messages.iter().map(|message| OpenAiMessage {
+                    role: message.role.into(),
+                    content: Cow::Borrowed(message.content.as_str()),
+                }));
+
+                serde_json::to_value(OpenAiRequest {
+                    model,
+                    temperature: sampling.temperature,
+                    top_p: sampling.top_p,
+                    max_tokens: sampling.max_tokens,
+                    n,
+                    messages: wire_messages,
+                })
+            }
+            Self::Anthropic => serde_json::to_value(AnthropicRequest {
+                model,
+                system,
+                max_tokens: sampling.max_tokens,
+                temperature: sampling.temperature,
+                top_p: sampling.top_p,
+                messages: messages
+                    .iter()
+                    .map(|message| AnthropicMessage {
+                        role: message.role.anthropic_role(),
+                        content: message.content.as_str(),
+                    })
+                    .collect(),
+            }),
+            Self::Ollama => {
+                let mut wire_messages = vec![OllamaMessage {
+                    role: OpenAiRole::System,
+                    content: system,
+                }];
+                wire_messages.extend(messages.iter().map(|message| OllamaMessage {
+                    role: message.role.into(),
+                    content: message.content.as_str(),
+                }));
+
+                serde_json::to_value(OllamaRequest {
+                    model,
+                    stream: false,
+                    options: OllamaOptions {
+                        temperature: sampling.temperature,
+                        top_p: sampling.top_p,
+                        num_predict: sampling.max_tokens,
+                    },
+                    messages: wire_messages,
+                })
+            }
+            Self::Bedrock => serde_json::to_value(BedrockRequest {
+                system: vec![BedrockText { text: system }],
+                messages: messages
+                    .iter()
+                    .map(|message| BedrockMessage {
+                        role: message.role.anthropic_role(),
+                        content: vec![BedrockText {
+                            text: message.content.as_str(),
+                        }],
+                    })
+                    .collect(),
+                inference_config: BedrockInferenceConfig {
+                    temperature: sampling.temperature,
+                    top_p: sampling.top_p,
+                    max_tokens: sampling.max_tokens,
+                },
+            }),
+        }
+        .expect("infallible serialization")
+    }
+
+    /// Parses a provider's response body into however many candidate completions it
+    /// carries (always one, except for `OpenAi` which may carry several when `n > 1`).
+    fn parse_candidates(self, body: &str) -> Result<Vec<String>, Error> {
+        match self {
+            Self::OpenAi => {
+                let res: OpenAiResponse = serde_json::from_str(body)?;
+                Ok(res
+                    .choices
+                    .into_iter()
+                    .map(|choice| choice.message.content.into_owned())
+                    .collect())
+            }
+            Self::Anthropic => {
+                let res: AnthropicResponse = serde_json::from_str(body)?;
+
+                if res.content.len() != 1 {
+                    return Err(Error::InvalidResponse(res.content.len()));
+                }
+
+                Ok(vec![res.content.into_iter().next().unwrap().text.into_owned()])
+            }
+            Self::Ollama => {
+                let res: OllamaResponse = serde_json::from_str(body)?;
+                Ok(vec![res.message.content.into_owned()])
+            }
+            Self::Bedrock => {
+                let res: BedrockResponse = serde_json::from_str(body)?;
+
+                if res.output.message.content.len() != 1 {
+                    return Err(Error::InvalidResponse(res.output.message.content.len()));
+                }
+
+                Ok(vec![res
+                    .output
+                    .message
+                    .content
+                    .into_iter()
+                    .next()
+                    .unwrap()
+                    .text
+                    .into_owned()])
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
-enum Role {
+enum OpenAiRole {
     Assistant,
     System,
     User,
 }
 
 #[derive(Serialize, Deserialize)]
-struct Message<'a> {
-    role: Role,
+struct OpenAiMessage<'a> {
+    role: OpenAiRole,
     content: Cow<'a, str>,
 }
 
 #[derive(Serialize)]
-struct Request<'a> {
+struct OpenAiRequest<'a> {
     model: &'a str,
     temperature: f32,
+    top_p: f32,
+    max_tokens: u32,
     n: u32,
-    messages: Vec<Message<'a>>,
+    messages: Vec<OpenAiMessage<'a>>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiChoice<'a> {
+    message: OpenAiMessage<'a>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiResponse<'a> {
+    #[serde(borrow)]
+    choices: Vec<OpenAiChoice<'a>>,
+}
+
+#[derive(Serialize)]
+struct AnthropicMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Serialize)]
+struct AnthropicRequest<'a> {
+    model: &'a str,
+    system: &'a str,
+    max_tokens: u32,
+    temperature: f32,
+    top_p: f32,
+    messages: Vec<AnthropicMessage<'a>>,
 }
 
 #[derive(Deserialize)]
-struct Choice<'a> {
-    message: Message<'a>,
+struct AnthropicContentBlock<'a> {
+    text: Cow<'a, str>,
 }
 
 #[derive(Deserialize)]
-struct Response<'a> {
-    choices: Vec<Choice<'a>>,
+struct AnthropicResponse<'a> {
+    #[serde(borrow)]
+    content: Vec<AnthropicContentBlock<'a>>,
+}
+
+#[derive(Serialize)]
+struct OllamaOptions {
+    temperature: f32,
+    top_p: f32,
+    num_predict: u32,
+}
+
+#[derive(Serialize)]
+struct OllamaMessage<'a> {
+    role: OpenAiRole,
+    content: &'a str,
+}
+
+#[derive(Serialize)]
+struct OllamaRequest<'a> {
+    model: &'a str,
+    stream: bool,
+    options: OllamaOptions,
+    messages: Vec<OllamaMessage<'a>>,
+}
+
+#[derive(Deserialize)]
+struct OllamaResponseMessage<'a> {
+    content: Cow<'a, str>,
+}
+
+#[derive(Deserialize)]
+struct OllamaResponse<'a> {
+    #[serde(borrow)]
+    message: OllamaResponseMessage<'a>,
+}
+
+/// AWS Bedrock's Converse API shape: <https://docs.aws.amazon.com/bedrock/latest/APIReference/API_runtime_Converse.html>.
+#[derive(Serialize)]
+struct BedrockText<'a> {
+    text: &'a str,
+}
+
+#[derive(Serialize)]
+struct BedrockMessage<'a> {
+    role: &'a str,
+    content: Vec<BedrockText<'a>>,
+}
+
+#[derive(Serialize)]
+struct BedrockInferenceConfig {
+    temperature: f32,
+    top_p: f32,
+    max_tokens: u32,
+}
+
+#[derive(Serialize)]
+struct BedrockRequest<'a> {
+    system: Vec<BedrockText<'a>>,
+    messages: Vec<BedrockMessage<'a>>,
+    #[serde(rename = "inferenceConfig")]
+    inference_config: BedrockInferenceConfig,
+}
+
+#[derive(Deserialize)]
+struct BedrockResponseText<'a> {
+    text: Cow<'a, str>,
+}
+
+#[derive(Deserialize)]
+struct BedrockResponseMessage<'a> {
+    #[serde(borrow)]
+    content: Vec<BedrockResponseText<'a>>,
+}
+
+#[derive(Deserialize)]
+struct BedrockResponseOutput<'a> {
+    #[serde(borrow)]
+    message: BedrockResponseMessage<'a>,
+}
+
+#[derive(Deserialize)]
+struct BedrockResponse<'a> {
+    #[serde(borrow)]
+    output: BedrockResponseOutput<'a>,
 }
 
 pub fn model() -> Result<String, Error> {
     env::var(LLM_MODEL_ENV_VAR).map_err(|_| Error::EnvVarNotSet(LLM_MODEL_ENV_VAR))
 }
 
-pub fn execute(system: &str, user: &str) -> Result<String, Error> {
-    let url = env::var(LLM_URL_ENV_VAR).map_err(|_| Error::EnvVarNotSet(LLM_URL_ENV_VAR))?;
+fn max_retries() -> u32 {
+    env::var(LLM_MAX_RETRIES_ENV_VAR)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_RETRIES)
+}
 
-    let api_key = env::var(LLM_URL_API_KEY_VAR).unwrap_or_else(|_| OLLAMA_DUMMY_API_KEY.to_owned());
+fn send(url: &str, api_key: &str, req: &serde_json::Value) -> Result<String, Error> {
+    let max_retries = max_retries();
+    let mut attempts = 0;
 
-    let model = &env::var(LLM_MODEL_ENV_VAR).map_err(|_| Error::EnvVarNotSet(LLM_MODEL_ENV_VAR))?;
+    loop {
+        attempts += 1;
 
-    let req = Request {
-        model,
-        temperature: GENERATE_ISSUE_TEMPERATURE,
-        messages: vec![
-            Message {
-                role: Role::System,
-                content: Cow::Borrowed(system),
-            },
-            Message {
-                role: Role::User,
-                content: Cow::Borrowed(user),
-            },
-        ],
-        n: 1,
-    };
+        let result = ureq::post(url)
+            .header("Authorization", format!("Bearer {api_key}"))
+            .header("x-api-key", api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .send_json(req);
+
+        let mut response = match result {
+            Ok(response) => response,
+            // Transport-level failures (connection reset, DNS, TLS, ...) are not
+            // classifiable by status code, so fail fast rather than guess.
+            Err(source) => return Err(Error::Request { attempts, source }),
+        };
 
-    let res: Response = ureq::post(url)
-        .header("Authorization", format!("Bearer {api_key}"))
-        .send_json(&req)?
-        .body_mut()
-        .read_json()?;
+        let status = response.status().as_u16();
 
-    if res.choices.len() != 1 {
-        return Err(Error::InvalidResponse(res.choices.len()));
+        if (200..300).contains(&status) {
+            return Ok(response.body_mut().read_to_string()?);
+        }
+
+        if !is_retryable_status(status) || attempts > max_retries {
+            return Err(Error::Status { status, attempts });
+        }
+
+        let retry_after = response
+            .headers()
+            .get("Retry-After")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+            .map(Duration::from_secs);
+
+        thread::sleep(retry_after.unwrap_or_else(|| backoff_delay(attempts - 1)));
     }
+}
+
+/// Like `send`, but for a streamed request: returns the response body as an
+/// iterator of lines instead of buffering it into one `String`. Only the initial
+/// connect is retried (mirroring `send`'s retry-on-status behaviour); once the body
+/// starts streaming a failure is surfaced to the caller rather than restarted,
+/// since tokens may have already been forwarded to the UI.
+fn send_stream(
+    url: &str,
+    api_key: &str,
+    req: &serde_json::Value,
+) -> Result<impl Iterator<Item = std::io::Result<String>>, Error> {
+    let max_retries = max_retries();
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+
+        let result = ureq::post(url)
+            .header("Authorization", format!("Bearer {api_key}"))
+            .header("x-api-key", api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .send_json(req);
+
+        let response = match result {
+            Ok(response) => response,
+            Err(source) => return Err(Error::Request { attempts, source }),
+        };
+
+        let status = response.status().as_u16();
+
+        if (200..300).contains(&status) {
+            use std::io::BufRead;
+            return Ok(std::io::BufReader::new(response.into_body().into_reader()).lines());
+        }
 
-    let message_str = res
-        .choices
+        if !is_retryable_status(status) || attempts > max_retries {
+            return Err(Error::Status { status, attempts });
+        }
+
+        let retry_after = response
+            .headers()
+            .get("Retry-After")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+            .map(Duration::from_secs);
+
+        thread::sleep(retry_after.unwrap_or_else(|| backoff_delay(attempts - 1)));
+    }
+}
+
+pub fn execute(system: &str, messages: &[Message]) -> Result<String, Error> {
+    Ok(execute_many(system, messages, 1)?
         .into_iter()
         .next()
-        .unwrap()
-        .message
-        .content
-        .into_owned();
+        .expect("execute_many(.., 1) always returns exactly one candidate"))
+}
+
+/// Requests `n` candidate completions for `system` plus the rolling `messages`
+/// history. Providers that can return several candidates from a single call
+/// (currently only OpenAI, via `n`) do so; providers that can't are driven by
+/// issuing `n` sequential requests instead.
+pub fn execute_many(system: &str, messages: &[Message], n: u32) -> Result<Vec<String>, Error> {
+    let provider = Provider::from_env()?;
+    let sampling = SamplingParams::from_env();
+
+    let url = env::var(LLM_URL_ENV_VAR).map_err(|_| Error::EnvVarNotSet(LLM_URL_ENV_VAR))?;
+    let api_key = env::var(LLM_URL_API_KEY_VAR).unwrap_or_else(|_| OLLAMA_DUMMY_API_KEY.to_owned());
+    let model = &env::var(LLM_MODEL_ENV_VAR).map_err(|_| Error::EnvVarNotSet(LLM_MODEL_ENV_VAR))?;
+
+    if provider.supports_native_n() {
+        let req = provider.request_body(system, messages, model, &sampling, n);
+        let body = send(&url, &api_key, &req)?;
+        let candidates = provider.parse_candidates(&body)?;
+
+        if candidates.is_empty() {
+            return Err(Error::InvalidResponse(candidates.len()));
+        }
+
+        return Ok(candidates);
+    }
+
+    let mut candidates = Vec::with_capacity(n as usize);
+    for _ in 0..n {
+        let req = provider.request_body(system, messages, model, &sampling, 1);
+        let body = send(&url, &api_key, &req)?;
+        let mut parsed = provider.parse_candidates(&body)?;
+
+        if parsed.is_empty() {
+            return Err(Error::InvalidResponse(parsed.len()));
+        }
+
+        candidates.push(parsed.remove(0));
+    }
 
-    Ok(message_str)
+    Ok(candidates)
 }
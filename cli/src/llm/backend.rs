@@ -0,0 +1,214 @@
+//! Named LLM backends the TUI can be pointed at, selected via
+//! `STYLUS_PORT_LLM_BACKEND` at `SessionCtx::init` rather than assuming the single
+//! hidden endpoint `execute`/`model` read from `STYLUS_PORT_LLM_PROVIDER`. Each
+//! backend pairs a `super::Provider` wire format with a short label so
+//! `SessionCtx`'s `title_line` can report which one is actually in use, and streams
+//! its response incrementally where the provider's wire format allows it.
+
+use std::{env, sync::mpsc::Sender};
+
+use super::{
+    template::{self, PromptTemplate},
+    Error, Message, Provider, SamplingParams, StreamChunk,
+};
+
+const LLM_BACKEND_ENV_VAR: &str = "STYLUS_PORT_LLM_BACKEND";
+
+/// Adapts the shared `Message` list to one endpoint's request/response format and
+/// reports enough about itself for the TUI to display.
+pub(crate) trait LlmBackend {
+    /// Streams `system` + `messages` to completion, sending a `StreamChunk::Token`
+    /// for each incremental piece of text followed by exactly one
+    /// `StreamChunk::Done`, or a `StreamChunk::Err` in place of `Done` if the
+    /// request fails at any point.
+    fn execute_stream(&self, system: &str, messages: &[Message], tx: &Sender<StreamChunk>);
+    fn label(&self) -> &str;
+    fn model_name(&self) -> &str;
+}
+
+/// A backend driven entirely by `super::Provider`'s existing wire-format dispatch,
+/// reading its endpoint URL and API key from the usual `STYLUS_PORT_LLM_*` env vars
+/// (the user points those at whichever of these this backend names).
+struct ProviderBackend {
+    label: &'static str,
+    provider: Provider,
+    model: String,
+}
+
+impl ProviderBackend {
+    fn stream(&self, system: &str, messages: &[Message], tx: &Sender<StreamChunk>) -> Result<(), Error> {
+        if !self.provider.supports_streaming() {
+            let text = self.execute_blocking(system, messages)?;
+            tx.send(StreamChunk::Token(text)).ok();
+            return Ok(());
+        }
+
+        let url = env::var(super::LLM_URL_ENV_VAR)
+            .map_err(|_| Error::EnvVarNotSet(super::LLM_URL_ENV_VAR))?;
+        let api_key = env::var(super::LLM_URL_API_KEY_VAR)
+            .unwrap_or_else(|_| super::OLLAMA_DUMMY_API_KEY.to_owned());
+        let sampling = SamplingParams::from_env();
+
+        let req = self
+            .provider
+            .stream_request_body(system, messages, &self.model, &sampling);
+
+        for line in super::send_stream(&url, &api_key, &req)? {
+            match self.provider.parse_stream_line(&line?)? {
+                super::StreamLine::Delta(text) => {
+                    tx.send(StreamChunk::Token(text)).ok();
+                }
+                super::StreamLine::Done => break,
+                super::StreamLine::Ignore => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The non-streaming fallback used by providers `stream` can't parse
+    /// incrementally (currently only Bedrock, see `Provider::supports_streaming`).
+    fn execute_blocking(&self, system: &str, messages: &[Message]) -> Result<String, Error> {
+        let url = env::var(super::LLM_URL_ENV_VAR)
+            .map_err(|_| Error::EnvVarNotSet(super::LLM_URL_ENV_VAR))?;
+        let api_key = env::var(super::LLM_URL_API_KEY_VAR)
+            .unwrap_or_else(|_| super::OLLAMA_DUMMY_API_KEY.to_owned());
+        let sampling = SamplingParams::from_env();
+
+        let req = self
+            .provider
+            .request_body(system, messages, &self.model, &sampling, 1);
+        let body = super::send(&url, &api_key, &req)?;
+        let mut candidates = self.provider.parse_candidates(&body)?;
+
+        if candidates.is_empty() {
+            return Err(Error::InvalidResponse(candidates.len()));
+        }
+
+        Ok(candidates.remove(0))
+    }
+}
+
+impl LlmBackend for ProviderBackend {
+    fn execute_stream(&self, system: &str, messages: &[Message], tx: &Sender<StreamChunk>) {
+        match self.stream(system, messages, tx) {
+            Ok(()) => {
+                tx.send(StreamChunk::Done).ok();
+            }
+            Err(err) => {
+                tx.send(StreamChunk::Err(err)).ok();
+            }
+        }
+    }
+
+    fn label(&self) -> &str {
+        self.label
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+/// A backend for a raw-completion endpoint - currently llama.cpp's `/completion` -
+/// that, unlike a chat-completion API, doesn't apply a model's chat template
+/// server-side. `template::template_for` picks that template from the model name,
+/// and the rendered prompt text is sent as a flat `"prompt"` field rather than a
+/// structured message list.
+struct RawCompletionBackend {
+    model: String,
+    template: Box<dyn PromptTemplate>,
+}
+
+impl RawCompletionBackend {
+    fn stream(&self, system: &str, messages: &[Message], tx: &Sender<StreamChunk>) -> Result<(), Error> {
+        let url = env::var(super::LLM_URL_ENV_VAR)
+            .map_err(|_| Error::EnvVarNotSet(super::LLM_URL_ENV_VAR))?;
+        let api_key = env::var(super::LLM_URL_API_KEY_VAR)
+            .unwrap_or_else(|_| super::OLLAMA_DUMMY_API_KEY.to_owned());
+        let sampling = SamplingParams::from_env();
+
+        let req = serde_json::json!({
+            "prompt": self.template.render(system, messages),
+            "temperature": sampling.temperature,
+            "top_p": sampling.top_p,
+            "n_predict": sampling.max_tokens,
+            "stream": true,
+        });
+
+        #[derive(serde::Deserialize)]
+        struct Chunk {
+            content: String,
+            stop: bool,
+        }
+
+        for line in super::send_stream(&url, &api_key, &req)? {
+            let line = line?;
+            let Some(data) = line.trim().strip_prefix("data: ") else {
+                continue;
+            };
+
+            let chunk: Chunk = serde_json::from_str(data)?;
+            if !chunk.content.is_empty() {
+                tx.send(StreamChunk::Token(chunk.content)).ok();
+            }
+            if chunk.stop {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl LlmBackend for RawCompletionBackend {
+    fn execute_stream(&self, system: &str, messages: &[Message], tx: &Sender<StreamChunk>) {
+        match self.stream(system, messages, tx) {
+            Ok(()) => {
+                tx.send(StreamChunk::Done).ok();
+            }
+            Err(err) => {
+                tx.send(StreamChunk::Err(err)).ok();
+            }
+        }
+    }
+
+    fn label(&self) -> &str {
+        "llama-cpp"
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+/// Selects a backend for `STYLUS_PORT_LLM_BACKEND`, defaulting to the
+/// OpenAI-compatible wire format. `ollama` speaks Ollama's native chat format
+/// (`Provider::Ollama`), not its OpenAI-compatible endpoint. `llama-cpp` is the odd one out: it targets llama.cpp's
+/// raw `/completion` endpoint instead of its OpenAI-compatible one, so locally
+/// served base models get their chat template applied instead of degraded output
+/// from a server that assumes one.
+pub(crate) fn current_backend() -> Result<Box<dyn LlmBackend + Send + Sync>, Error> {
+    let model = super::model()?;
+    let backend_name = env::var(LLM_BACKEND_ENV_VAR).unwrap_or_else(|_| "openai".to_owned());
+
+    let (label, provider) = match backend_name.to_lowercase().as_str() {
+        "openai" => ("openai", Provider::OpenAi),
+        "ollama" => ("ollama", Provider::Ollama),
+        "anthropic" => ("anthropic", Provider::Anthropic),
+        "bedrock" => ("bedrock", Provider::Bedrock),
+        "llama-cpp" => {
+            return Ok(Box::new(RawCompletionBackend {
+                template: template::template_for(&model),
+                model,
+            }))
+        }
+        _ => return Err(Error::UnknownProvider(backend_name)),
+    };
+
+    Ok(Box::new(ProviderBackend {
+        label,
+        provider,
+        model,
+    }))
+}
@@ -0,0 +1,99 @@
+//! Renders the ordered `Message` history into the flat prompt text a given model
+//! family expects, for backends that hit a raw completion endpoint rather than a
+//! chat-completion API that applies its own templating server-side (see
+//! `backend::RawCompletionBackend`, the only caller: `ProviderBackend`'s
+//! OpenAI/Anthropic/Ollama/Bedrock wire formats all take structured messages and
+//! never need this).
+
+use super::{Message, Role};
+
+/// Renders `system` plus the rolling `messages` history into the flat prompt text
+/// one model family expects, special tokens included.
+pub(crate) trait PromptTemplate {
+    fn render(&self, system: &str, messages: &[Message]) -> String;
+}
+
+fn role_name(role: Role) -> &'static str {
+    match role {
+        Role::User => "user",
+        Role::Assistant => "assistant",
+    }
+}
+
+/// Meta's Llama 3 instruct template: <https://www.llama.com/docs/model-cards-and-prompt-formats/meta-llama-3/>.
+struct Llama3Template;
+
+impl PromptTemplate for Llama3Template {
+    fn render(&self, system: &str, messages: &[Message]) -> String {
+        fn turn(role: &str, content: &str) -> String {
+            format!("<|start_header_id|>{role}<|end_header_id|>\n\n{content}<|eot_id|>")
+        }
+
+        let mut prompt = "<|begin_of_text|>".to_owned();
+        prompt.push_str(&turn("system", system));
+
+        for message in messages {
+            prompt.push_str(&turn(role_name(message.role), &message.content));
+        }
+
+        prompt.push_str("<|start_header_id|>assistant<|end_header_id|>\n\n");
+        prompt
+    }
+}
+
+/// ChatML, used by Qwen, several Mistral fine-tunes, and most other models served
+/// through a raw completion endpoint without a bespoke template of their own.
+struct ChatMlTemplate;
+
+impl PromptTemplate for ChatMlTemplate {
+    fn render(&self, system: &str, messages: &[Message]) -> String {
+        let mut prompt = format!("<|im_start|>system\n{system}<|im_end|>\n");
+
+        for message in messages {
+            prompt.push_str(&format!(
+                "<|im_start|>{}\n{}<|im_end|>\n",
+                role_name(message.role),
+                message.content
+            ));
+        }
+
+        prompt.push_str("<|im_start|>assistant\n");
+        prompt
+    }
+}
+
+/// Falls back to plainly-labelled turns for model families this client doesn't have
+/// a dedicated template for. No special tokens, but still separates turns clearly,
+/// which is the main thing a base model needs to avoid running them together.
+struct GenericTemplate;
+
+impl PromptTemplate for GenericTemplate {
+    fn render(&self, system: &str, messages: &[Message]) -> String {
+        let mut prompt = format!("System: {system}\n\n");
+
+        for message in messages {
+            let role = match message.role {
+                Role::User => "User",
+                Role::Assistant => "Assistant",
+            };
+            prompt.push_str(&format!("{role}: {}\n\n", message.content));
+        }
+
+        prompt.push_str("Assistant:");
+        prompt
+    }
+}
+
+/// Picks a template by matching on the model name, since a raw completion endpoint
+/// doesn't otherwise say which chat format it expects.
+pub(crate) fn template_for(model: &str) -> Box<dyn PromptTemplate> {
+    let model = model.to_lowercase();
+
+    if model.contains("llama-3") || model.contains("llama3") {
+        Box::new(Llama3Template)
+    } else if model.contains("qwen") || model.contains("chatml") || model.contains("mistral") {
+        Box::new(ChatMlTemplate)
+    } else {
+        Box::new(GenericTemplate)
+    }
+}
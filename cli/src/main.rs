@@ -21,12 +21,23 @@ enum Commands {
         /// Simply print the answer from the AI with no fancy stuff
         #[arg(short, long, default_value_t = false)]
         plain_output: bool,
+        /// Path to the Solana program repo being ported, indexed alongside the
+        /// handbook so the answer can reference the user's own code. Only
+        /// applies to `--message`; overrides `STYLUS_PORT_SOLANA_REPO`.
+        #[arg(long)]
+        repo_path: Option<String>,
+        /// How many indexed chunks to ground the answer in. Only applies to
+        /// `--message`.
+        #[arg(long, default_value_t = DEFAULT_TOP_K)]
+        top_k: usize,
     },
 }
 
-fn chat(message: Option<String>, plain_output: bool) -> Result<()> {
+const DEFAULT_TOP_K: usize = 5;
+
+fn chat(message: Option<String>, plain_output: bool, repo_path: Option<String>, top_k: usize) -> Result<()> {
     match message {
-        Some(msg) => chat::single_prompt(&msg, plain_output),
+        Some(msg) => chat::single_prompt(&msg, plain_output, repo_path, top_k),
         None => chat::session(),
     }
 }
@@ -44,6 +55,8 @@ fn main() -> Result<()> {
         Commands::Chat {
             message,
             plain_output,
-        } => chat(message, plain_output),
+            repo_path,
+            top_k,
+        } => chat(message, plain_output, repo_path, top_k),
     }
 }
@@ -19,6 +19,10 @@ sol! {
     error NoUnlocksAvailable();
     #[derive(Debug)]
     error Unauthorized();
+    #[derive(Debug)]
+    error UnlockTransferFailed();
+    #[derive(Debug)]
+    error StakingCallFailed();
 
     event ScheduleCreated(
         uint256 schedule_id,
@@ -46,6 +50,35 @@ sol! {
         address old_owner,
         address new_owner,
     );
+
+    event LinearScheduleCreated(
+        uint256 schedule_id,
+        address token,
+        address owner,
+        address destination,
+        uint64 start,
+        uint64 period,
+        uint32 period_count,
+        uint256 per_period,
+    );
+
+    event ScheduleRevoked(uint256 schedule_id, uint256 returned_amount);
+
+    event ScheduleForceRemoved(uint256 schedule_id, address refund_address, uint256 returned_amount);
+
+    event Staked(uint256 schedule_id, address staking_pool, uint256 amount);
+
+    event StakeWithdrawn(uint256 schedule_id, uint256 amount);
+}
+
+sol_interface! {
+    /// Minimal external staking-pool surface `stake`/`withdraw_stake` call into. `stake` pulls `amount` of `token`
+    /// from the caller (this contract, which approves the pool for `amount` first) into the pool; `withdraw` pays
+    /// `amount` of `token` back to the caller.
+    interface IStakingPool {
+        function stake(address token, uint256 amount) external;
+        function withdraw(address token, uint256 amount) external;
+    }
 }
 
 #[derive(SolidityError, Debug)]
@@ -57,6 +90,8 @@ pub enum ContractError {
     ScheduleNotFound(ScheduleNotFound),
     NoUnlocksAvailable(NoUnlocksAvailable),
     Unauthorized(Unauthorized),
+    UnlockTransferFailed(UnlockTransferFailed),
+    StakingCallFailed(StakingCallFailed),
 }
 
 #[storage]
@@ -67,6 +102,20 @@ pub struct Schedule {
     amount: StorageU256,
 }
 
+#[storage]
+pub struct LinearSchedule {
+    /// Timestamp the first period starts at (it completes at `start + period`)
+    start: StorageU64,
+    /// Duration of a single period, in seconds
+    period: StorageU64,
+    /// Total number of periods that unlock `per_period` each
+    period_count: StorageU32,
+    /// Amount unlocked per completed period
+    per_period: StorageU256,
+    /// Total already transferred out, so `unlock` only pays out the delta
+    claimed: StorageU256,
+}
+
 #[storage]
 #[entrypoint]
 pub struct TokenVestingContract {
@@ -80,10 +129,78 @@ pub struct TokenVestingContract {
     destination: StorageMap<U256, StorageAddress>,
     /// Scheduled token unlocks
     schedule: StorageMap<U256, StorageVec<Schedule>>,
+    /// Whether `schedule_id` was created by `create_linear` rather than
+    /// `create` - `unlock` branches on this to know which of `schedule` or
+    /// `linear_schedule` to read.
+    is_linear: StorageMap<U256, StorageBool>,
+    /// Graded/linear vesting parameters, populated only for `schedule_id`s
+    /// where `is_linear` is set. A linear schedule costs O(1) storage
+    /// reads/writes per `unlock` regardless of its duration, unlike
+    /// `schedule`'s one `StorageVec` entry per tranche.
+    linear_schedule: StorageMap<U256, LinearSchedule>,
+    /// Timestamp before which `unlock` always returns `NoUnlocksAvailable`,
+    /// regardless of how much of `schedule`/`linear_schedule` has matured.
+    /// Zero (the default for an unset entry) means no cliff was requested.
+    cliff: StorageMap<U256, StorageU64>,
+    /// Index of the first tranche in `schedule` not yet fully matured. `unlock_tranches` starts scanning here
+    /// instead of index 0, so settled tranches below the cursor are never re-read or re-written once passed.
+    /// Tranches at an index `< cursor` are fully unlocked regardless of the `amount` still held in their slot.
+    cursor: StorageMap<U256, StorageU256>,
+    /// Whether `schedule_id`'s owner may `revoke` it before it fully matures, clawing back whatever hasn't yet
+    /// vested. False (the default for an unset entry) means the grant is irrevocable once created.
+    revocable: StorageMap<U256, StorageBool>,
+    /// Privileged address set in `constructor`, the only caller `force_remove` accepts regardless of who owns the
+    /// schedule being removed.
+    admin: StorageAddress,
+    /// Non-zero only for schedules created by `create_non_custodial`: the account `unlock` pulls matured tokens
+    /// from via `transferFrom` instead of paying out of the contract's own balance. Zero (the default for an
+    /// unset entry) means `schedule_id` is an ordinary, pre-funded escrow schedule.
+    source: StorageMap<U256, StorageAddress>,
+    /// Amount of `schedule_id`'s unreleased balance currently delegated to `staking_pool` via `stake`, rather than
+    /// held in this contract's own balance. `unlock` never pays out more than `remaining_unvested - staked_balance`,
+    /// so a beneficiary can't unlock tokens that are off earning rewards in the pool.
+    staked_balance: StorageMap<U256, StorageU256>,
+    /// The pool `schedule_id`'s `staked_balance` was delegated to. Reset to zero once `staked_balance` returns to
+    /// zero, so a fully-withdrawn schedule is free to stake into a different pool next time.
+    staking_pool: StorageMap<U256, StorageAddress>,
+}
+
+/// Interleaves two already-chronologically-sorted tranche lists into one, summing amounts that share a timestamp.
+/// Used by `merge` to combine two schedules' tranches without requiring either list be re-sorted.
+fn merge_tranches(
+    tranches_1: Vec<(u64, U256)>,
+    tranches_2: Vec<(u64, U256)>,
+) -> Result<Vec<(u64, U256)>, ContractError> {
+    let mut merged = Vec::with_capacity(tranches_1.len() + tranches_2.len());
+    let (mut tranches_1, mut tranches_2) = (tranches_1.into_iter().peekable(), tranches_2.into_iter().peekable());
+
+    loop {
+        let next = match (tranches_1.peek(), tranches_2.peek()) {
+            (Some(&(t1, a1)), Some(&(t2, a2))) if t1 == t2 => {
+                tranches_1.next();
+                tranches_2.next();
+                (t1, a1.checked_add(a2).ok_or(InvalidSchedule {})?)
+            }
+            (Some(&(t1, _)), Some(&(t2, _))) if t1 < t2 => tranches_1.next().unwrap(),
+            (Some(_), Some(_)) => tranches_2.next().unwrap(),
+            (Some(_), None) => tranches_1.next().unwrap(),
+            (None, Some(_)) => tranches_2.next().unwrap(),
+            (None, None) => break,
+        };
+
+        merged.push(next);
+    }
+
+    Ok(merged)
 }
 
 #[public]
 impl TokenVestingContract {
+    #[constructor]
+    pub fn constructor(&mut self, admin: Address) {
+        self.admin.set(admin);
+    }
+
     /// Create a vesting schedule for the specified `token` and initial `destination`, returning the schedule identifier.
     /// Attempts to transfer the total amount of tokens scheduled from the sender to this contract.
     ///
@@ -92,7 +209,7 @@ impl TokenVestingContract {
     /// # Errors
     /// - InvalidToken: if the provided token address is zero
     /// - InvalidDestination: if the provided destination address is zero
-    /// - InvalidSchedule: if the provided schedule is empty, contains a zero amount, is not ordered chronologically or the total amount overflows 256 bits.
+    /// - InvalidSchedule: if the provided schedule is empty, contains a zero amount, is not ordered chronologically, the total amount overflows 256 bits, `cliff` is after the first tranche's timestamp, or `revocable` is set with a zero `owner`.
     /// - TokenDepositTransferFailed: if there is an error transferring the total vesting amount from the sender to the contract
     pub fn create(
         &mut self,
@@ -100,6 +217,8 @@ impl TokenVestingContract {
         owner: Address,
         destination: Address,
         schedule: Vec<(u64, U256)>,
+        cliff: u64,
+        revocable: bool,
     ) -> Result<U256, ContractError> {
         if token == Address::ZERO {
             return Err(InvalidToken {}.into());
@@ -109,39 +228,20 @@ impl TokenVestingContract {
             return Err(InvalidDestination {}.into());
         }
 
-        if schedule.is_empty() {
+        if revocable && owner == Address::ZERO {
             return Err(InvalidSchedule {}.into());
         }
 
         let schedule_id = self.schedule_count.get() + U256::ONE;
-
-        let mut schedule_store = self.schedule.setter(schedule_id);
-        let mut total_vested_amount = U256::ZERO;
-        let mut last_timestamp = 0u64;
-        let mut timestamps = Vec::with_capacity(schedule.len());
-        let mut amounts = Vec::with_capacity(schedule.len());
-        for (timestamp, amount) in schedule {
-            if amount.is_zero() || timestamp < last_timestamp {
-                return Err(InvalidSchedule {}.into());
-            }
-
-            last_timestamp = timestamp;
-            total_vested_amount = total_vested_amount
-                .checked_add(amount)
-                .ok_or(InvalidSchedule {})?;
-
-            timestamps.push(timestamp);
-            amounts.push(amount);
-
-            let mut schedule_item = schedule_store.grow();
-            schedule_item.timestamp.set(U64::from(timestamp));
-            schedule_item.amount.set(amount);
-        }
+        let (total_vested_amount, timestamps, amounts) =
+            self.store_tranche_schedule(schedule_id, schedule, cliff)?;
 
         self.schedule_count.set(schedule_id);
         self.token.insert(schedule_id, token);
         self.owner.insert(schedule_id, owner);
         self.destination.insert(schedule_id, destination);
+        self.cliff.insert(schedule_id, U64::from(cliff));
+        self.revocable.insert(schedule_id, revocable);
 
         log(
             self.vm(),
@@ -164,251 +264,2274 @@ impl TokenVestingContract {
         Ok(schedule_id)
     }
 
-    /// Unlock any vested tokens associated with the `schedule_id` and transfers them to the set `destination`
+    /// Like `create`, but pulls the total vested amount from an explicit `funder` instead of always `msg_sender()`
+    /// - lets a calling contract (e.g. a payroll or DAO treasury contract) create a vesting schedule on behalf of a
+    /// beneficiary in one call, attributing the grant to the resolved `owner`/`destination` rather than the funder.
     ///
     /// # Errors
-    /// - ScheduleNotFound: if the provided `schedule_id` is not associated with a schedule
-    /// - NoUnlocksAvailable: if there a zero unlocked tokens to transfer
-    pub fn unlock(&mut self, schedule_id: U256) -> Result<(), ContractError> {
-        let token = self.token.get(schedule_id);
-
-        if token.is_zero() {
-            return Err(ScheduleNotFound {}.into());
+    /// - InvalidToken: if the provided token address is zero
+    /// - InvalidDestination: if the provided destination address is zero
+    /// - InvalidSchedule: if the provided schedule is empty, contains a zero amount, is not ordered chronologically, the total amount overflows 256 bits, or `cliff` is after the first tranche's timestamp.
+    /// - Unauthorized: if `funder` has not approved this contract to spend at least the total vested amount
+    /// - TokenDepositTransferFailed: if there is an error transferring the total vesting amount from `funder` to the contract
+    pub fn vested_transfer(
+        &mut self,
+        token: Address,
+        funder: Address,
+        owner: Address,
+        destination: Address,
+        schedule: Vec<(u64, U256)>,
+        cliff: u64,
+    ) -> Result<U256, ContractError> {
+        if token == Address::ZERO {
+            return Err(InvalidToken {}.into());
         }
 
-        let now = U64::from(self.vm().block_timestamp());
-
-        let mut schedule = self.schedule.setter(schedule_id);
-        let mut idx = 0;
-        let mut unlocked_token_amount = U256::ZERO;
-        loop {
-            let Some(mut schedule_item) = schedule.setter(idx) else {
-                break;
-            };
-
-            idx += 1;
-
-            if schedule_item.timestamp.get() > now {
-                break;
-            }
-
-            let amount = schedule_item.amount.get();
-
-            if amount.is_zero() {
-                continue;
-            }
+        if destination == Address::ZERO {
+            return Err(InvalidDestination {}.into());
+        }
 
-            schedule_item.amount.set(U256::ZERO);
+        let schedule_id = self.schedule_count.get() + U256::ONE;
+        let (total_vested_amount, timestamps, amounts) =
+            self.store_tranche_schedule(schedule_id, schedule, cliff)?;
 
-            // Overflow not possible because: escrow total <= U256::MAX checked during creation
-            unlocked_token_amount += amount;
-        }
+        let contract_addr = self.vm().contract_address();
+        let allowance = Erc20Interface::new(token)
+            .allowance(self, funder, contract_addr)
+            .map_err(|_| Unauthorized {})?;
 
-        if unlocked_token_amount.is_zero() {
-            return Err(NoUnlocksAvailable {}.into());
+        if allowance < total_vested_amount {
+            return Err(Unauthorized {}.into());
         }
 
-        let destination = self.destination.get(schedule_id);
+        self.schedule_count.set(schedule_id);
+        self.token.insert(schedule_id, token);
+        self.owner.insert(schedule_id, owner);
+        self.destination.insert(schedule_id, destination);
+        self.cliff.insert(schedule_id, U64::from(cliff));
 
         log(
             self.vm(),
-            TokensUnlocked {
+            ScheduleCreated {
                 schedule_id,
+                token,
+                owner,
                 destination,
-                unlocked_token_amount,
+                timestamps,
+                amounts,
             },
         );
 
         Erc20Interface::new(token)
-            .transfer(self, destination, unlocked_token_amount)
-            .expect("Invariant: the contract always has sufficient balance to satisfy unlocks");
+            .transfer_from(self, funder, contract_addr, total_vested_amount)
+            .map_err(|_| TokenDepositTransferFailed {})?;
 
-        Ok(())
+        Ok(schedule_id)
     }
 
-    /// Change the `destination` associated with the `schedule_id`, this can only be called by the associated `owner`.
+    /// Like `create`, but escrows nothing up front: `source` keeps custody of the tokens, and `unlock` pulls each
+    /// matured amount from `source` via `transferFrom` as it comes due instead of paying out of a balance deposited
+    /// at creation. Lets a treasury schedule a stream against a standing allowance without locking capital up front.
     ///
     /// # Errors
-    /// - ScheduleNotFound: if the provided `schedule_id` is not associated with a schedule
+    /// - InvalidToken: if the provided token address is zero
     /// - InvalidDestination: if the provided destination address is zero
-    /// - Unauthorized: if the caller is not the owner of the schedule
-    pub fn change_destination(
+    /// - InvalidSchedule: if `source` is the zero address, or the provided schedule is empty, contains a zero amount, is not ordered chronologically, the total amount overflows 256 bits, or `cliff` is after the first tranche's timestamp
+    pub fn create_non_custodial(
         &mut self,
-        schedule_id: U256,
-        new_destination: Address,
-    ) -> Result<(), ContractError> {
-        if new_destination == Address::ZERO {
-            return Err(InvalidDestination {}.into());
+        token: Address,
+        source: Address,
+        owner: Address,
+        destination: Address,
+        schedule: Vec<(u64, U256)>,
+        cliff: u64,
+    ) -> Result<U256, ContractError> {
+        if token == Address::ZERO {
+            return Err(InvalidToken {}.into());
         }
 
-        if self.token.get(schedule_id).is_zero() {
-            return Err(ScheduleNotFound {}.into());
+        if destination == Address::ZERO {
+            return Err(InvalidDestination {}.into());
         }
 
-        if self.vm().msg_sender() != self.owner.get(schedule_id) {
-            return Err(Unauthorized {}.into());
+        if source == Address::ZERO {
+            return Err(InvalidSchedule {}.into());
         }
 
-        let old_destination = self.destination.replace(schedule_id, new_destination);
+        let schedule_id = self.schedule_count.get() + U256::ONE;
+        let (_, timestamps, amounts) = self.store_tranche_schedule(schedule_id, schedule, cliff)?;
+
+        self.schedule_count.set(schedule_id);
+        self.token.insert(schedule_id, token);
+        self.owner.insert(schedule_id, owner);
+        self.destination.insert(schedule_id, destination);
+        self.cliff.insert(schedule_id, U64::from(cliff));
+        self.source.insert(schedule_id, source);
 
         log(
             self.vm(),
-            DestinationChanged {
+            ScheduleCreated {
                 schedule_id,
-                old_destination,
-                new_destination,
+                token,
+                owner,
+                destination,
+                timestamps,
+                amounts,
             },
         );
 
-        Ok(())
+        Ok(schedule_id)
     }
 
-    /// Change the `owner` associated with the `schedule_id`, this can only be called by the current `owner`.
-    ///
-    /// Note: setting a zero address for `owner` means the `destination` is now immutable.
-    ///
-    /// # Errors
-    /// - ScheduleNotFound: if the provided `schedule_id` is not associated with a schedule
-    /// - Unauthorized: if the caller is not the owner of the schedule
-    pub fn change_owner(
+    /// Validates `schedule` (non-empty, non-zero amounts, chronologically ordered, total within 256 bits, `cliff`
+    /// not after the first tranche) and stores it under `schedule_id`, returning the total vested amount plus the
+    /// timestamp/amount arrays `ScheduleCreated` expects. Shared by `create`, `vested_transfer` and
+    /// `create_non_custodial`, which differ only in who the total is ultimately pulled from, and when.
+    fn store_tranche_schedule(
         &mut self,
         schedule_id: U256,
-        new_owner: Address,
-    ) -> Result<(), ContractError> {
-        if self.token.get(schedule_id).is_zero() {
-            return Err(ScheduleNotFound {}.into());
+        schedule: Vec<(u64, U256)>,
+        cliff: u64,
+    ) -> Result<(U256, Vec<u64>, Vec<U256>), ContractError> {
+        if schedule.is_empty() {
+            return Err(InvalidSchedule {}.into());
         }
 
-        if self.vm().msg_sender() != self.owner.get(schedule_id) {
-            return Err(Unauthorized {}.into());
+        if cliff > schedule[0].0 {
+            return Err(InvalidSchedule {}.into());
         }
 
-        let old_owner = self.owner.replace(schedule_id, new_owner);
+        let mut schedule_store = self.schedule.setter(schedule_id);
+        let mut total_vested_amount = U256::ZERO;
+        let mut last_timestamp = 0u64;
+        let mut timestamps = Vec::with_capacity(schedule.len());
+        let mut amounts = Vec::with_capacity(schedule.len());
+        for (timestamp, amount) in schedule {
+            if amount.is_zero() || timestamp < last_timestamp {
+                return Err(InvalidSchedule {}.into());
+            }
 
-        log(
-            self.vm(),
-            OwnerChanged {
-                schedule_id,
-                old_owner,
-                new_owner,
-            },
-        );
+            last_timestamp = timestamp;
+            total_vested_amount = total_vested_amount
+                .checked_add(amount)
+                .ok_or(InvalidSchedule {})?;
 
-        Ok(())
-    }
+            timestamps.push(timestamp);
+            amounts.push(amount);
 
-    // View functions
-    fn schedule_count(&self) -> U256 {
-        self.schedule_count.get()
-    }
+            let mut schedule_item = schedule_store.grow();
+            schedule_item.timestamp.set(U64::from(timestamp));
+            schedule_item.amount.set(amount);
+        }
 
-    fn token(&self, schedule_id: U256) -> Address {
-        self.token.get(schedule_id)
+        Ok((total_vested_amount, timestamps, amounts))
     }
 
-    fn destination(&self, schedule_id: U256) -> Address {
-        self.destination.get(schedule_id)
-    }
+    /// Create a compact graded/linear vesting schedule for the specified `token` and initial `destination`,
+    /// returning the schedule identifier. Unlike `create`, which stores one `Schedule` entry per tranche, this
+    /// stores only the schedule's parameters, so `unlock` costs O(1) storage reads/writes regardless of
+    /// `period_count` - the right choice for long linear vests (e.g. per-second streaming) where an explicit
+    /// tranche list would be prohibitively large.
+    ///
+    /// `period_count` periods of `period` seconds each unlock `per_period` tokens once complete, starting at
+    /// `start`.
+    ///
+    /// # Errors
+    /// - InvalidToken: if the provided token address is zero
+    /// - InvalidDestination: if the provided destination address is zero
+    /// - InvalidSchedule: if `period`, `period_count`, or `per_period` is zero, `per_period * period_count` overflows 256 bits, `cliff` is after `start`, or `revocable` is set with a zero `owner`.
+    /// - TokenDepositTransferFailed: if there is an error transferring the total vesting amount from the sender to the contract
+    pub fn create_linear(
+        &mut self,
+        token: Address,
+        owner: Address,
+        destination: Address,
+        start: u64,
+        period: u64,
+        period_count: u32,
+        per_period: U256,
+        cliff: u64,
+        revocable: bool,
+    ) -> Result<U256, ContractError> {
+        if token == Address::ZERO {
+            return Err(InvalidToken {}.into());
+        }
 
-    fn owner(&self, schedule_id: U256) -> Address {
-        self.owner.get(schedule_id)
-    }
+        if destination == Address::ZERO {
+            return Err(InvalidDestination {}.into());
+        }
 
-    fn schedule(&self, schedule_id: U256) -> Vec<(U64, U256)> {
-        if self.token(schedule_id).is_zero() {
-            return vec![];
+        if period == 0 || period_count == 0 || per_period.is_zero() {
+            return Err(InvalidSchedule {}.into());
         }
 
-        let schedule_store = self.schedule.getter(schedule_id);
+        if cliff > start {
+            return Err(InvalidSchedule {}.into());
+        }
 
-        let mut schedule = vec![];
-        let mut idx = 0;
-        while let Some(schedule_item) = schedule_store.getter(idx) {
-            schedule.push((schedule_item.timestamp.get(), schedule_item.amount.get()));
-            idx += 1;
+        if revocable && owner == Address::ZERO {
+            return Err(InvalidSchedule {}.into());
         }
 
-        schedule
-    }
-}
+        let total_vested_amount = per_period
+            .checked_mul(U256::from(period_count))
+            .ok_or(InvalidSchedule {})?;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let schedule_id = self.schedule_count.get() + U256::ONE;
 
-    use alloy_primitives::{Address, U256, U64};
-    use motsu::prelude::*;
-    use openzeppelin_stylus::token::erc20::{Erc20, IErc20};
+        self.is_linear.insert(schedule_id, true);
 
-    pub const TOTAL_SUPPLY: u64 = 1_000_000;
+        let mut linear_schedule = self.linear_schedule.setter(schedule_id);
+        linear_schedule.start.set(U64::from(start));
+        linear_schedule.period.set(U64::from(period));
+        linear_schedule.period_count.set(U32::from(period_count));
+        linear_schedule.per_period.set(per_period);
+        linear_schedule.claimed.set(U256::ZERO);
 
-    fn setup_env(token: &Contract<Erc20>, source: Address) {
-        // Environment always starts at timestamp 1 for simplicity
-        VM::context().set_block_timestamp(1);
+        self.schedule_count.set(schedule_id);
+        self.token.insert(schedule_id, token);
+        self.owner.insert(schedule_id, owner);
+        self.destination.insert(schedule_id, destination);
+        self.cliff.insert(schedule_id, U64::from(cliff));
+        self.revocable.insert(schedule_id, revocable);
+
+        log(
+            self.vm(),
+            LinearScheduleCreated {
+                schedule_id,
+                token,
+                owner,
+                destination,
+                start,
+                period,
+                period_count,
+                per_period,
+            },
+        );
+
+        let contract_addr = self.vm().contract_address();
+        let sender = self.vm().msg_sender();
+        Erc20Interface::new(token)
+            .transfer_from(self, sender, contract_addr, total_vested_amount)
+            .map_err(|_| TokenDepositTransferFailed {})?;
+
+        Ok(schedule_id)
+    }
+
+    /// Unlock any vested tokens associated with the `schedule_id` and transfers them to the set `destination`. For a
+    /// `create_non_custodial` schedule, this pulls the unlocked amount from `source` via `transferFrom` instead of
+    /// paying out of the contract's own balance.
+    ///
+    /// # Errors
+    /// - ScheduleNotFound: if the provided `schedule_id` is not associated with a schedule
+    /// - NoUnlocksAvailable: if there a zero unlocked tokens to transfer, `schedule_id`'s cliff has not yet passed,
+    ///   or everything currently matured is delegated to a staking pool via `stake`
+    /// - UnlockTransferFailed: for a non-custodial schedule, if `source` has revoked the contract's allowance or lacks sufficient balance
+    pub fn unlock(&mut self, schedule_id: U256) -> Result<(), ContractError> {
+        let token = self.token.get(schedule_id);
+
+        if token.is_zero() {
+            return Err(ScheduleNotFound {}.into());
+        }
+
+        let now = self.vm().block_timestamp();
+
+        if now < self.cliff.get(schedule_id).to::<u64>() {
+            return Err(NoUnlocksAvailable {}.into());
+        }
+
+        // Overflow not possible: `stake` never lets `staked_balance` exceed `remaining_unvested`.
+        let available = self.remaining_unvested(schedule_id) - self.staked_balance.get(schedule_id);
+
+        let unlocked_token_amount = if self.is_linear.get(schedule_id) {
+            self.unlock_linear(schedule_id, now, available)
+        } else {
+            self.unlock_tranches(schedule_id, U64::from(now), available)
+        };
+
+        if unlocked_token_amount.is_zero() {
+            return Err(NoUnlocksAvailable {}.into());
+        }
+
+        let destination = self.destination.get(schedule_id);
+
+        log(
+            self.vm(),
+            TokensUnlocked {
+                schedule_id,
+                destination,
+                unlocked_token_amount,
+            },
+        );
+
+        let source = self.source.get(schedule_id);
+        if source.is_zero() {
+            Erc20Interface::new(token)
+                .transfer(self, destination, unlocked_token_amount)
+                .expect("Invariant: the contract always has sufficient balance to satisfy unlocks");
+        } else {
+            Erc20Interface::new(token)
+                .transfer_from(self, source, destination, unlocked_token_amount)
+                .map_err(|_| UnlockTransferFailed {})?;
+        }
+
+        Ok(())
+    }
+
+    /// Advances `schedule_id`'s cursor past every tranche matured as of `now`, without letting the running total
+    /// exceed `available`, and returns the newly-unlocked total - the tranche half of what `unlock` used to do
+    /// unconditionally before `create_linear` added a second schedule shape. Starts scanning at the cursor rather
+    /// than index 0 and only persists the cursor's advance, so a tranche is read and written at most once across
+    /// the lifetime of the schedule. Stops (without advancing past) the first tranche that would push the running
+    /// total over `available`, leaving it to mature on a later call once more of the schedule's balance is unstaked.
+    fn unlock_tranches(&mut self, schedule_id: U256, now: U64, available: U256) -> U256 {
+        let schedule = self.schedule.getter(schedule_id);
+        let cursor = self.cursor.get(schedule_id).to::<usize>();
+        let mut idx = cursor;
+        let mut unlocked_token_amount = U256::ZERO;
+
+        while let Some(schedule_item) = schedule.getter(idx) {
+            if schedule_item.timestamp.get() > now {
+                break;
+            }
+
+            let amount = schedule_item.amount.get();
+            if unlocked_token_amount + amount > available {
+                break;
+            }
+
+            // Overflow not possible because: escrow total <= U256::MAX checked during creation
+            unlocked_token_amount += amount;
+            idx += 1;
+        }
+
+        if idx > cursor {
+            self.cursor.insert(schedule_id, U256::from(idx));
+        }
+
+        unlocked_token_amount
+    }
+
+    /// Like `unlock_tranches`, but also gates on `schedule_id`'s cliff, returning zero without scanning if it
+    /// hasn't been crossed yet. Used by `merge`, which flushes whatever's already matured on each source schedule
+    /// before combining what's left, rather than silently carrying an already-due payout into the merged result.
+    /// Unbounded (`merge` already refuses to combine a schedule with a nonzero `staked_balance`).
+    fn unlock_matured_tranches(&mut self, schedule_id: U256, now: u64) -> U256 {
+        if now < self.cliff.get(schedule_id).to::<u64>() {
+            return U256::ZERO;
+        }
+
+        self.unlock_tranches(schedule_id, U64::from(now), U256::MAX)
+    }
+
+    /// Computes `schedule_id`'s linear-schedule unlock in O(1): the whole periods elapsed since `start`, capped at
+    /// `period_count` so rounding never unlocks more than `per_period * period_count` in total, scaled by
+    /// `per_period`, netted against what's already been claimed, and capped again at `available` so a call never
+    /// claims more than the schedule's currently-unstaked balance - the shortfall remains claimable on a later call.
+    fn unlock_linear(&mut self, schedule_id: U256, now: u64, available: U256) -> U256 {
+        let mut linear_schedule = self.linear_schedule.setter(schedule_id);
+
+        let start = linear_schedule.start.get().to::<u64>();
+        let period = linear_schedule.period.get().to::<u64>();
+        let period_count = u64::from(linear_schedule.period_count.get());
+        let claimed = linear_schedule.claimed.get();
+
+        let periods_elapsed = if now < start {
+            0
+        } else {
+            period_count.min((now - start) / period)
+        };
+
+        let unlocked_total = linear_schedule
+            .per_period
+            .get()
+            .checked_mul(U256::from(periods_elapsed))
+            .expect("Invariant: per_period * period_count <= U256::MAX, checked during creation");
+
+        // Overflow not possible: claimed is always the total unlocked as of the last call, which is monotonic.
+        let claimable = unlocked_total - claimed;
+        let to_release = claimable.min(available);
+
+        linear_schedule.claimed.set(claimed + to_release);
+
+        to_release
+    }
+
+    /// Revoke `schedule_id`, clawing back whatever hasn't vested yet. Pays out everything matured as of now to
+    /// `destination` exactly as `unlock` would, then returns the remaining, still-unvested amount to `owner` and
+    /// retires the schedule. The standard grant-clawback for employee vesting when a grant is terminated early.
+    ///
+    /// # Errors
+    /// - ScheduleNotFound: if the provided `schedule_id` is not associated with a schedule
+    /// - Unauthorized: if the caller is not the owner of the schedule, or the schedule was not created as revocable
+    /// - InvalidSchedule: if `schedule_id` is a non-custodial (`create_non_custodial`) schedule, or has a nonzero `staked_balance`
+    pub fn revoke(&mut self, schedule_id: U256) -> Result<(), ContractError> {
+        let token = self.token.get(schedule_id);
+
+        if token.is_zero() {
+            return Err(ScheduleNotFound {}.into());
+        }
+
+        if !self.source.get(schedule_id).is_zero() || !self.staked_balance.get(schedule_id).is_zero() {
+            return Err(InvalidSchedule {}.into());
+        }
+
+        let owner = self.owner.get(schedule_id);
+        if self.vm().msg_sender() != owner || !self.revocable.get(schedule_id) {
+            return Err(Unauthorized {}.into());
+        }
+
+        let returned_amount = self.settle_schedule(schedule_id, owner);
+
+        log(
+            self.vm(),
+            ScheduleRevoked {
+                schedule_id,
+                returned_amount,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Forcibly retire `schedule_id` regardless of its owner or `revocable` flag, callable only by `admin`. Pays
+    /// out everything matured as of now to `destination` exactly as `unlock`/`revoke` would, then sends the
+    /// remaining, still-unvested amount to `refund_address` instead of back to the schedule's owner. Mirrors the
+    /// original program's `force_remove_vesting` instruction, giving a privileged party a way to wind a schedule
+    /// down that doesn't depend on the owner having marked it `revocable`.
+    ///
+    /// # Errors
+    /// - ScheduleNotFound: if the provided `schedule_id` is not associated with a schedule
+    /// - Unauthorized: if the caller is not `admin`
+    /// - InvalidSchedule: if `schedule_id` is a non-custodial (`create_non_custodial`) schedule, or has a nonzero `staked_balance`
+    pub fn force_remove(
+        &mut self,
+        schedule_id: U256,
+        refund_address: Address,
+    ) -> Result<(), ContractError> {
+        let token = self.token.get(schedule_id);
+
+        if token.is_zero() {
+            return Err(ScheduleNotFound {}.into());
+        }
+
+        if !self.source.get(schedule_id).is_zero() || !self.staked_balance.get(schedule_id).is_zero() {
+            return Err(InvalidSchedule {}.into());
+        }
+
+        if self.vm().msg_sender() != self.admin.get() {
+            return Err(Unauthorized {}.into());
+        }
+
+        let returned_amount = self.settle_schedule(schedule_id, refund_address);
+
+        log(
+            self.vm(),
+            ScheduleForceRemoved {
+                schedule_id,
+                refund_address,
+                returned_amount,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Delegates `amount` of `schedule_id`'s still-unreleased balance to `staking_pool` so it can earn rewards
+    /// while vesting continues: approves `staking_pool` for `amount`, then calls its `stake` entry point, which is
+    /// expected to pull the tokens via `transferFrom`. `unlock` never pays out more than what's left unstaked, so
+    /// a beneficiary can't withdraw tokens that are currently delegated.
+    ///
+    /// Note: a schedule can only have one staking pool delegated to at a time - call `withdraw_stake` down to zero
+    /// before delegating to a different pool.
+    ///
+    /// # Errors
+    /// - ScheduleNotFound: if the provided `schedule_id` is not associated with a schedule
+    /// - Unauthorized: if the caller is not the owner of the schedule
+    /// - InvalidSchedule: if `amount` exceeds `schedule_id`'s currently-unstaked balance, or `staking_pool` differs from one already in use with a nonzero `staked_balance`
+    /// - StakingCallFailed: if the call into `staking_pool`'s `stake` entry point reverts
+    pub fn stake(
+        &mut self,
+        schedule_id: U256,
+        staking_pool: Address,
+        amount: U256,
+    ) -> Result<(), ContractError> {
+        let token = self.token.get(schedule_id);
+
+        if token.is_zero() {
+            return Err(ScheduleNotFound {}.into());
+        }
+
+        if self.vm().msg_sender() != self.owner.get(schedule_id) {
+            return Err(Unauthorized {}.into());
+        }
+
+        let staked_balance = self.staked_balance.get(schedule_id);
+        let current_pool = self.staking_pool.get(schedule_id);
+        if !staked_balance.is_zero() && current_pool != staking_pool {
+            return Err(InvalidSchedule {}.into());
+        }
+
+        // Overflow not possible: staked_balance is only ever set to an amount <= remaining_unvested.
+        let available = self.remaining_unvested(schedule_id) - staked_balance;
+        if amount > available {
+            return Err(InvalidSchedule {}.into());
+        }
+
+        self.staking_pool.insert(schedule_id, staking_pool);
+        self.staked_balance.insert(schedule_id, staked_balance + amount);
+
+        Erc20Interface::new(token)
+            .approve(self, staking_pool, amount)
+            .map_err(|_| StakingCallFailed {})?;
+        IStakingPool::new(staking_pool)
+            .stake(self, token, amount)
+            .map_err(|_| StakingCallFailed {})?;
+
+        log(
+            self.vm(),
+            Staked {
+                schedule_id,
+                staking_pool,
+                amount,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Withdraws `amount` of `schedule_id`'s delegated stake back from its staking pool into this contract,
+    /// restoring it to `unlock`'s payable balance.
+    ///
+    /// # Errors
+    /// - ScheduleNotFound: if the provided `schedule_id` is not associated with a schedule
+    /// - Unauthorized: if the caller is not the owner of the schedule
+    /// - InvalidSchedule: if `amount` exceeds `schedule_id`'s `staked_balance`
+    /// - StakingCallFailed: if the call into the staking pool's `withdraw` entry point reverts
+    pub fn withdraw_stake(&mut self, schedule_id: U256, amount: U256) -> Result<(), ContractError> {
+        let token = self.token.get(schedule_id);
+
+        if token.is_zero() {
+            return Err(ScheduleNotFound {}.into());
+        }
+
+        if self.vm().msg_sender() != self.owner.get(schedule_id) {
+            return Err(Unauthorized {}.into());
+        }
+
+        let staked_balance = self.staked_balance.get(schedule_id);
+        if amount > staked_balance {
+            return Err(InvalidSchedule {}.into());
+        }
+
+        let staking_pool = self.staking_pool.get(schedule_id);
+        let remaining_staked = staked_balance - amount;
+        self.staked_balance.insert(schedule_id, remaining_staked);
+        if remaining_staked.is_zero() {
+            self.staking_pool.insert(schedule_id, Address::ZERO);
+        }
+
+        IStakingPool::new(staking_pool)
+            .withdraw(self, token, amount)
+            .map_err(|_| StakingCallFailed {})?;
+
+        log(self.vm(), StakeWithdrawn { schedule_id, amount });
+
+        Ok(())
+    }
+
+    /// Pays out everything matured under `schedule_id` as of now to `destination`, then sends whatever's left
+    /// unvested to `refund_address` and retires the schedule. Shared by `revoke` (`refund_address` is the
+    /// schedule's own `owner`) and `force_remove` (an arbitrary admin-supplied `refund_address`), which differ
+    /// only in who's allowed to call and where the unvested remainder ends up.
+    fn settle_schedule(&mut self, schedule_id: U256, refund_address: Address) -> U256 {
+        let token = self.token.get(schedule_id);
+        let now = self.vm().block_timestamp();
+        let past_cliff = now >= self.cliff.get(schedule_id).to::<u64>();
+
+        let matured_amount = if !past_cliff {
+            U256::ZERO
+        } else if self.is_linear.get(schedule_id) {
+            self.unlock_linear(schedule_id, now, U256::MAX)
+        } else {
+            self.unlock_tranches(schedule_id, U64::from(now), U256::MAX)
+        };
+
+        let destination = self.destination.get(schedule_id);
+
+        if !matured_amount.is_zero() {
+            log(
+                self.vm(),
+                TokensUnlocked {
+                    schedule_id,
+                    destination,
+                    unlocked_token_amount: matured_amount,
+                },
+            );
+
+            Erc20Interface::new(token)
+                .transfer(self, destination, matured_amount)
+                .expect("Invariant: the contract always has sufficient balance to satisfy unlocks");
+        }
+
+        let returned_amount = self.remaining_unvested(schedule_id);
+
+        self.token.insert(schedule_id, Address::ZERO);
+
+        if !returned_amount.is_zero() {
+            Erc20Interface::new(token)
+                .transfer(self, refund_address, returned_amount)
+                .expect("Invariant: the contract always has sufficient balance to satisfy the refund");
+        }
+
+        returned_amount
+    }
+
+    /// Computes `schedule_id`'s still-unvested amount, assuming `unlock_tranches`/`unlock_linear` has already run
+    /// for the current timestamp so every matured tranche/period is reflected in the cursor/`claimed` state.
+    fn remaining_unvested(&self, schedule_id: U256) -> U256 {
+        if self.is_linear.get(schedule_id) {
+            let linear_schedule = self.linear_schedule.getter(schedule_id);
+            let total = linear_schedule
+                .per_period
+                .get()
+                .checked_mul(U256::from(linear_schedule.period_count.get()))
+                .expect("Invariant: per_period * period_count <= U256::MAX, checked during creation");
+
+            total - linear_schedule.claimed.get()
+        } else {
+            let schedule = self.schedule.getter(schedule_id);
+            let cursor = self.cursor.get(schedule_id).to::<usize>();
+
+            let mut remaining = U256::ZERO;
+            let mut idx = cursor;
+            while let Some(schedule_item) = schedule.getter(idx) {
+                remaining += schedule_item.amount.get();
+                idx += 1;
+            }
+
+            remaining
+        }
+    }
+
+    /// Change the `destination` associated with the `schedule_id`, this can only be called by the associated `owner`.
+    ///
+    /// # Errors
+    /// - ScheduleNotFound: if the provided `schedule_id` is not associated with a schedule
+    /// - InvalidDestination: if the provided destination address is zero
+    /// - Unauthorized: if the caller is not the owner of the schedule
+    pub fn change_destination(
+        &mut self,
+        schedule_id: U256,
+        new_destination: Address,
+    ) -> Result<(), ContractError> {
+        if new_destination == Address::ZERO {
+            return Err(InvalidDestination {}.into());
+        }
+
+        if self.token.get(schedule_id).is_zero() {
+            return Err(ScheduleNotFound {}.into());
+        }
+
+        if self.vm().msg_sender() != self.owner.get(schedule_id) {
+            return Err(Unauthorized {}.into());
+        }
+
+        let old_destination = self.destination.replace(schedule_id, new_destination);
+
+        log(
+            self.vm(),
+            DestinationChanged {
+                schedule_id,
+                old_destination,
+                new_destination,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Change the `owner` associated with the `schedule_id`, this can only be called by the current `owner`.
+    ///
+    /// Note: setting a zero address for `owner` means the `destination` is now immutable.
+    ///
+    /// # Errors
+    /// - ScheduleNotFound: if the provided `schedule_id` is not associated with a schedule
+    /// - Unauthorized: if the caller is not the owner of the schedule
+    pub fn change_owner(
+        &mut self,
+        schedule_id: U256,
+        new_owner: Address,
+    ) -> Result<(), ContractError> {
+        if self.token.get(schedule_id).is_zero() {
+            return Err(ScheduleNotFound {}.into());
+        }
+
+        if self.vm().msg_sender() != self.owner.get(schedule_id) {
+            return Err(Unauthorized {}.into());
+        }
+
+        let old_owner = self.owner.replace(schedule_id, new_owner);
+
+        log(
+            self.vm(),
+            OwnerChanged {
+                schedule_id,
+                old_owner,
+                new_owner,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Fold `schedule_id_1` and `schedule_id_2`, both owned by the caller and vesting the same `token` to the same
+    /// `destination`, into a single merged schedule, returning its identifier. The merged tranche list interleaves
+    /// both originals in chronological order, summing amounts that share a timestamp, and both originals are
+    /// retired. Keeps per-unlock gas bounded once a user has accumulated several separate grants, instead of
+    /// requiring one `unlock` call per schedule.
+    ///
+    /// # Errors
+    /// - ScheduleNotFound: if either provided `schedule_id` is not associated with a schedule
+    /// - Unauthorized: if the caller does not own both schedules
+    /// - InvalidSchedule: if the schedules don't share a `token`/`destination`, either is a linear, non-custodial, or currently-staked schedule, or the merged total overflows 256 bits
+    pub fn merge(&mut self, schedule_id_1: U256, schedule_id_2: U256) -> Result<U256, ContractError> {
+        let token = self.token.get(schedule_id_1);
+        if token.is_zero() || self.token.get(schedule_id_2).is_zero() {
+            return Err(ScheduleNotFound {}.into());
+        }
+
+        let sender = self.vm().msg_sender();
+        if sender != self.owner.get(schedule_id_1) || sender != self.owner.get(schedule_id_2) {
+            return Err(Unauthorized {}.into());
+        }
+
+        if token != self.token.get(schedule_id_2) {
+            return Err(InvalidSchedule {}.into());
+        }
+
+        let destination = self.destination.get(schedule_id_1);
+        if destination != self.destination.get(schedule_id_2) {
+            return Err(InvalidSchedule {}.into());
+        }
+
+        if self.is_linear.get(schedule_id_1) || self.is_linear.get(schedule_id_2) {
+            return Err(InvalidSchedule {}.into());
+        }
+
+        if !self.source.get(schedule_id_1).is_zero() || !self.source.get(schedule_id_2).is_zero() {
+            return Err(InvalidSchedule {}.into());
+        }
+
+        if !self.staked_balance.get(schedule_id_1).is_zero() || !self.staked_balance.get(schedule_id_2).is_zero() {
+            return Err(InvalidSchedule {}.into());
+        }
+
+        let owner = self.owner.get(schedule_id_1);
+        let cliff = self.cliff.get(schedule_id_1).max(self.cliff.get(schedule_id_2));
+        let revocable = self.revocable.get(schedule_id_1) && self.revocable.get(schedule_id_2);
+
+        // Flush whatever's already matured on either source schedule to `destination` first, so the merged
+        // schedule only tracks still-locked tranches rather than carrying an already-due payout forward.
+        let now = self.vm().block_timestamp();
+        let matured_1 = self.unlock_matured_tranches(schedule_id_1, now);
+        let matured_2 = self.unlock_matured_tranches(schedule_id_2, now);
+
+        if !matured_1.is_zero() {
+            log(
+                self.vm(),
+                TokensUnlocked {
+                    schedule_id: schedule_id_1,
+                    destination,
+                    unlocked_token_amount: matured_1,
+                },
+            );
+        }
+        if !matured_2.is_zero() {
+            log(
+                self.vm(),
+                TokensUnlocked {
+                    schedule_id: schedule_id_2,
+                    destination,
+                    unlocked_token_amount: matured_2,
+                },
+            );
+        }
+
+        let matured_amount = matured_1 + matured_2;
+        if !matured_amount.is_zero() {
+            Erc20Interface::new(token)
+                .transfer(self, destination, matured_amount)
+                .expect("Invariant: the contract always has sufficient balance to satisfy unlocks");
+        }
+
+        let tranches_1 = self.read_tranches(schedule_id_1);
+        let tranches_2 = self.read_tranches(schedule_id_2);
+        let merged_tranches = merge_tranches(tranches_1, tranches_2)?;
+
+        let schedule_id = self.schedule_count.get() + U256::ONE;
+
+        let mut schedule_store = self.schedule.setter(schedule_id);
+        let mut timestamps = Vec::with_capacity(merged_tranches.len());
+        let mut amounts = Vec::with_capacity(merged_tranches.len());
+        for (timestamp, amount) in merged_tranches {
+            timestamps.push(timestamp);
+            amounts.push(amount);
+
+            let mut schedule_item = schedule_store.grow();
+            schedule_item.timestamp.set(U64::from(timestamp));
+            schedule_item.amount.set(amount);
+        }
+
+        self.schedule_count.set(schedule_id);
+        self.token.insert(schedule_id, token);
+        self.owner.insert(schedule_id, owner);
+        self.destination.insert(schedule_id, destination);
+        self.cliff.insert(schedule_id, cliff);
+        self.revocable.insert(schedule_id, revocable);
+
+        self.token.insert(schedule_id_1, Address::ZERO);
+        self.token.insert(schedule_id_2, Address::ZERO);
+
+        log(
+            self.vm(),
+            ScheduleCreated {
+                schedule_id,
+                token,
+                owner,
+                destination,
+                timestamps,
+                amounts,
+            },
+        );
+
+        Ok(schedule_id)
+    }
+
+    /// Reads `schedule_id`'s explicit tranche list as plain values, for callers (like `merge`) that need to combine
+    /// it with another schedule's rather than just reporting it, as `schedule` does.
+    fn read_tranches(&self, schedule_id: U256) -> Vec<(u64, U256)> {
+        let schedule_store = self.schedule.getter(schedule_id);
+        let cursor = self.cursor.get(schedule_id).to::<usize>();
+
+        let mut tranches = vec![];
+        let mut idx = 0;
+        while let Some(schedule_item) = schedule_store.getter(idx) {
+            let amount = if idx < cursor {
+                U256::ZERO
+            } else {
+                schedule_item.amount.get()
+            };
+            tranches.push((schedule_item.timestamp.get().to::<u64>(), amount));
+            idx += 1;
+        }
+
+        tranches
+    }
+
+    // View functions
+    fn schedule_count(&self) -> U256 {
+        self.schedule_count.get()
+    }
+
+    fn token(&self, schedule_id: U256) -> Address {
+        self.token.get(schedule_id)
+    }
+
+    fn destination(&self, schedule_id: U256) -> Address {
+        self.destination.get(schedule_id)
+    }
+
+    fn owner(&self, schedule_id: U256) -> Address {
+        self.owner.get(schedule_id)
+    }
+
+    /// Timestamp before which `unlock`/`revoke`/`force_remove` treat nothing as matured. Zero, the default for an
+    /// unset entry, means `schedule_id` either doesn't exist or was created without a cliff.
+    fn cliff(&self, schedule_id: U256) -> U64 {
+        self.cliff.get(schedule_id)
+    }
+
+    fn schedule(&self, schedule_id: U256) -> Vec<(U64, U256)> {
+        if self.token(schedule_id).is_zero() {
+            return vec![];
+        }
+
+        let schedule_store = self.schedule.getter(schedule_id);
+        let cursor = self.cursor.get(schedule_id).to::<usize>();
+
+        let mut schedule = vec![];
+        let mut idx = 0;
+        while let Some(schedule_item) = schedule_store.getter(idx) {
+            let amount = if idx < cursor {
+                U256::ZERO
+            } else {
+                schedule_item.amount.get()
+            };
+            schedule.push((schedule_item.timestamp.get(), amount));
+            idx += 1;
+        }
+
+        schedule
+    }
+
+    fn is_linear(&self, schedule_id: U256) -> bool {
+        self.is_linear.get(schedule_id)
+    }
+
+    fn revocable(&self, schedule_id: U256) -> bool {
+        self.revocable.get(schedule_id)
+    }
+
+    fn admin(&self) -> Address {
+        self.admin.get()
+    }
+
+    /// The account `unlock` pulls from for a `create_non_custodial` schedule. Zero means `schedule_id` either
+    /// doesn't exist or is an ordinary, pre-funded escrow schedule.
+    fn source(&self, schedule_id: U256) -> Address {
+        self.source.get(schedule_id)
+    }
+
+    /// Amount of `schedule_id`'s unreleased balance currently delegated to a staking pool via `stake`.
+    fn staked_balance(&self, schedule_id: U256) -> U256 {
+        self.staked_balance.get(schedule_id)
+    }
+
+    /// The pool `schedule_id`'s `staked_balance` is delegated to. Zero means nothing is currently staked.
+    fn staking_pool(&self, schedule_id: U256) -> Address {
+        self.staking_pool.get(schedule_id)
+    }
+
+    fn linear_schedule(&self, schedule_id: U256) -> (U64, U64, u32, U256, U256) {
+        let linear_schedule = self.linear_schedule.getter(schedule_id);
+        (
+            linear_schedule.start.get(),
+            linear_schedule.period.get(),
+            linear_schedule.period_count.get().to::<u32>(),
+            linear_schedule.per_period.get(),
+            linear_schedule.claimed.get(),
+        )
+    }
+
+    /// `schedule_id`'s linear-schedule vested total as of `timestamp`, clamped to `[0, per_period * period_count]`
+    /// by the same whole-periods-elapsed floor `unlock_linear` unlocks against - lets a caller preview a claim at
+    /// an arbitrary time without submitting a transaction. Zero for non-linear or nonexistent schedules.
+    fn vested_amount(&self, schedule_id: U256, timestamp: u64) -> U256 {
+        if !self.is_linear.get(schedule_id) {
+            return U256::ZERO;
+        }
+
+        let linear_schedule = self.linear_schedule.getter(schedule_id);
+        let start = linear_schedule.start.get().to::<u64>();
+        let period = linear_schedule.period.get().to::<u64>();
+        let period_count = u64::from(linear_schedule.period_count.get());
+
+        let periods_elapsed = if timestamp < start {
+            0
+        } else {
+            period_count.min((timestamp - start) / period)
+        };
+
+        linear_schedule
+            .per_period
+            .get()
+            .checked_mul(U256::from(periods_elapsed))
+            .expect("Invariant: per_period * period_count <= U256::MAX, checked during creation")
+    }
+
+    /// `schedule_id`'s vested-but-not-yet-claimed amount right now. `unlock` transfers at most this much: it also
+    /// holds back whatever part of the schedule is currently staked, which this view does not subtract. Zero for
+    /// non-linear or never-created schedules; a linear schedule retired by `revoke`/`force_remove` still reports
+    /// its vested total minus what was claimed.
+    fn releasable(&self, schedule_id: U256) -> U256 {
+        if !self.is_linear.get(schedule_id) {
+            return U256::ZERO;
+        }
+
+        let now = self.vm().block_timestamp();
+        let claimed = self.linear_schedule.getter(schedule_id).claimed.get();
+
+        self.vested_amount(schedule_id, now) - claimed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloy_primitives::{Address, U256, U64};
+    use motsu::prelude::*;
+    use openzeppelin_stylus::token::erc20::{Erc20, IErc20};
+
+    /// A minimal staking pool standing in for an external protocol in tests: `stake` pulls `amount` of `token`
+    /// from the caller via `transferFrom`, `withdraw` pays `amount` back out.
+    #[storage]
+    #[entrypoint]
+    pub struct MockStakingPool {}
+
+    #[public]
+    impl MockStakingPool {
+        pub fn stake(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
+            let caller = self.vm().msg_sender();
+            let this = self.vm().contract_address();
+            Erc20Interface::new(token).transfer_from(self, caller, this, amount)?;
+            Ok(())
+        }
+
+        pub fn withdraw(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
+            let caller = self.vm().msg_sender();
+            Erc20Interface::new(token).transfer(self, caller, amount)?;
+            Ok(())
+        }
+    }
+
+    pub const TOTAL_SUPPLY: u64 = 1_000_000;
+
+    fn setup_env(token: &Contract<Erc20>, source: Address) {
+        // Environment always starts at timestamp 1 for simplicity
+        VM::context().set_block_timestamp(1);
 
         // Mint total supply of tokens to source account
         token
             .sender(source)
-            ._mint(source, U256::from(TOTAL_SUPPLY))
-            .motsu_unwrap();
+            ._mint(source, U256::from(TOTAL_SUPPLY))
+            .motsu_unwrap();
+    }
+
+    #[motsu::test]
+    fn test_create_vesting_schedule(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        // Approve vesting contract to transfer tokens
+        let vesting_amount = U256::from(60u64);
+        token
+            .sender(source)
+            .approve(vesting.address(), vesting_amount)
+            .motsu_unwrap();
+
+        // Create vesting schedule with 3 unlocks
+        let schedule = vec![
+            (0u64, U256::from(20u64)),   // Immediate unlock
+            (100u64, U256::from(20u64)), // After timestamp 100
+            (200u64, U256::from(20u64)), // After timestamp 200
+        ];
+
+        let schedule_id = vesting
+            .sender(source)
+            .create(token.address(), owner, destination, schedule.clone(), 0, false)
+            .motsu_unwrap();
+
+        // Verify schedule was created
+        assert_eq!(schedule_id, U256::from(1u64));
+        assert_eq!(vesting.sender(source).schedule_count(), U256::from(1u64));
+        assert_eq!(vesting.sender(source).token(schedule_id), token.address());
+        assert_eq!(vesting.sender(source).owner(schedule_id), owner);
+        assert_eq!(vesting.sender(source).destination(schedule_id), destination);
+
+        // Verify schedule details
+        let stored_schedule = vesting.sender(source).schedule(schedule_id);
+        assert_eq!(stored_schedule.len(), 3);
+        assert_eq!(stored_schedule[0], (U64::from(0u64), U256::from(20u64)));
+        assert_eq!(stored_schedule[1], (U64::from(100u64), U256::from(20u64)));
+        assert_eq!(stored_schedule[2], (U64::from(200u64), U256::from(20u64)));
+
+        // Verify tokens were transferred to vesting contract
+        assert_eq!(
+            token.sender(source).balance_of(vesting.address()),
+            vesting_amount
+        );
+        assert_eq!(
+            token.sender(source).balance_of(source),
+            U256::from(TOTAL_SUPPLY) - vesting_amount
+        );
+    }
+
+    #[motsu::test]
+    fn test_unlock_tokens(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        let vesting_amount = U256::from(60u64);
+        token
+            .sender(source)
+            .approve(vesting.address(), vesting_amount)
+            .motsu_unwrap();
+
+        let schedule = vec![
+            (0u64, U256::from(20u64)),
+            (100u64, U256::from(20u64)),
+            (200u64, U256::from(20u64)),
+        ];
+
+        let schedule_id = vesting
+            .sender(source)
+            .create(token.address(), owner, destination, schedule, 0, false)
+            .motsu_unwrap();
+
+        // Test 1: Unlock at timestamp 1 (immediate unlock for first tranche)
+        vesting.sender(source).unlock(schedule_id).motsu_unwrap();
+
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            U256::from(20u64)
+        );
+        assert_eq!(
+            token.sender(source).balance_of(vesting.address()),
+            U256::from(40u64)
+        );
+
+        // Verify first unlock is now zero in schedule
+        let stored_schedule = vesting.sender(source).schedule(schedule_id);
+        assert_eq!(stored_schedule[0].1, U256::ZERO);
+
+        // Test 2: Try to unlock again at same timestamp (should fail - no unlocks available)
+        let err = vesting
+            .sender(source)
+            .unlock(schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::NoUnlocksAvailable(_)));
+
+        // Test 3: Unlock at timestamp 150 (should unlock second tranche)
+        VM::context().set_block_timestamp(150);
+        vesting.sender(source).unlock(schedule_id).motsu_unwrap();
+
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            U256::from(40u64)
+        );
+        assert_eq!(
+            token.sender(source).balance_of(vesting.address()),
+            U256::from(20u64)
+        );
+
+        // Test 4: Unlock at timestamp 250 (should unlock final tranche)
+        VM::context().set_block_timestamp(250);
+        vesting.sender(source).unlock(schedule_id).motsu_unwrap();
+
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            U256::from(60u64)
+        );
+        assert_eq!(
+            token.sender(source).balance_of(vesting.address()),
+            U256::ZERO
+        );
+
+        // All tokens should be unlocked now
+        let final_schedule = vesting.sender(source).schedule(schedule_id);
+        assert!(final_schedule.iter().all(|(_, amount)| amount.is_zero()));
+    }
+
+    #[motsu::test]
+    fn test_unlock_multiple_at_once(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        let vesting_amount = U256::from(60u64);
+        token
+            .sender(source)
+            .approve(vesting.address(), vesting_amount)
+            .motsu_unwrap();
+
+        let schedule = vec![
+            (50u64, U256::from(20u64)),
+            (100u64, U256::from(20u64)),
+            (150u64, U256::from(20u64)),
+        ];
+
+        let schedule_id = vesting
+            .sender(source)
+            .create(token.address(), owner, destination, schedule, 0, false)
+            .motsu_unwrap();
+
+        // Jump to timestamp 120 - should unlock first two tranches at once
+        VM::context().set_block_timestamp(120);
+        vesting.sender(source).unlock(schedule_id).motsu_unwrap();
+
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            U256::from(40u64)
+        );
+        assert_eq!(
+            token.sender(source).balance_of(vesting.address()),
+            U256::from(20u64)
+        );
+    }
+
+    #[motsu::test]
+    fn test_change_destination(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+        new_destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        let vesting_amount = U256::from(40u64);
+        token
+            .sender(source)
+            .approve(vesting.address(), vesting_amount)
+            .motsu_unwrap();
+
+        let schedule = vec![(100u64, U256::from(20u64)), (200u64, U256::from(20u64))];
+
+        let schedule_id = vesting
+            .sender(source)
+            .create(token.address(), owner, destination, schedule, 0, false)
+            .motsu_unwrap();
+
+        // Test 1: Unauthorized change (not owner)
+        let err = vesting
+            .sender(source)
+            .change_destination(schedule_id, new_destination)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized(_)));
+
+        // Test 2: Authorized change by owner
+        vesting
+            .sender(owner)
+            .change_destination(schedule_id, new_destination)
+            .motsu_unwrap();
+
+        assert_eq!(
+            vesting.sender(owner).destination(schedule_id),
+            new_destination
+        );
+
+        // Test 3: Unlock tokens to new destination
+        VM::context().set_block_timestamp(150);
+        vesting.sender(owner).unlock(schedule_id).motsu_unwrap();
+
+        assert_eq!(
+            token.sender(source).balance_of(new_destination),
+            U256::from(20u64)
+        );
+        assert_eq!(token.sender(source).balance_of(destination), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn test_change_owner(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        new_owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(20u64))
+            .motsu_unwrap();
+
+        let schedule = vec![(100u64, U256::from(20u64))];
+
+        let schedule_id = vesting
+            .sender(source)
+            .create(token.address(), owner, destination, schedule, 0, false)
+            .motsu_unwrap();
+
+        // Test 1: Unauthorized change
+        let err = vesting
+            .sender(source)
+            .change_owner(schedule_id, new_owner)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized(_)));
+
+        // Test 2: Authorized change by current owner
+        vesting
+            .sender(owner)
+            .change_owner(schedule_id, new_owner)
+            .motsu_unwrap();
+
+        assert_eq!(vesting.sender(new_owner).owner(schedule_id), new_owner);
+
+        // Test 3: New owner can now change destination
+        let another_destination = Address::from([5u8; 20]);
+        vesting
+            .sender(new_owner)
+            .change_destination(schedule_id, another_destination)
+            .motsu_unwrap();
+
+        assert_eq!(
+            vesting.sender(new_owner).destination(schedule_id),
+            another_destination
+        );
+    }
+
+    #[motsu::test]
+    fn test_create_validation_errors(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        // Test 1: Invalid token (zero address)
+        let err = vesting
+            .sender(source)
+            .create(
+                Address::ZERO,
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidToken(_)));
+
+        // Test 3: Invalid destination (zero address)
+        let err = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                Address::ZERO,
+                vec![(100u64, U256::from(20u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidDestination(_)));
+
+        // Test 4: Empty schedule
+        let err = vesting
+            .sender(source)
+            .create(token.address(), owner, destination, vec![], 0, false)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
+
+        // Test 5: Zero amount in schedule
+        let err = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::ZERO)],
+                0,
+                false,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
+
+        // Test 6: Non-chronological schedule
+        let err = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![
+                    (200u64, U256::from(10u64)),
+                    (100u64, U256::from(10u64)), // Earlier timestamp after later one
+                ],
+                0,
+                false,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
+
+        // Test 7: Insufficient allowance
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(10u64))
+            .motsu_unwrap();
+
+        let err = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))], // Needs 20 but only approved 10
+                0,
+                false,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::TokenDepositFailed(_)));
+
+        // Test 8: Cliff after the first tranche's timestamp
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(20u64))
+            .motsu_unwrap();
+
+        let err = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                101,
+                false,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
+    }
+
+    #[motsu::test]
+    fn test_multiple_schedules(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner1: Address,
+        owner2: Address,
+        source: Address,
+        destination1: Address,
+        destination2: Address,
+    ) {
+        setup_env(&token, source);
+
+        // Create first schedule
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(30u64))
+            .motsu_unwrap();
+
+        let schedule_id1 = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner1,
+                destination1,
+                vec![(100u64, U256::from(30u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        // Create second schedule
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(50u64))
+            .motsu_unwrap();
+
+        let schedule_id2 = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner2,
+                destination2,
+                vec![(200u64, U256::from(50u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        // Verify separate schedule IDs
+        assert_eq!(schedule_id1, U256::from(1u64));
+        assert_eq!(schedule_id2, U256::from(2u64));
+        assert_eq!(vesting.sender(source).schedule_count(), U256::from(2u64));
+
+        // Verify schedules are independent
+        assert_eq!(vesting.sender(source).owner(schedule_id1), owner1);
+        assert_eq!(vesting.sender(source).owner(schedule_id2), owner2);
+        assert_eq!(
+            vesting.sender(source).destination(schedule_id1),
+            destination1
+        );
+        assert_eq!(
+            vesting.sender(source).destination(schedule_id2),
+            destination2
+        );
+
+        // Unlock first schedule
+        VM::context().set_block_timestamp(150);
+        vesting.sender(source).unlock(schedule_id1).motsu_unwrap();
+        assert_eq!(
+            token.sender(source).balance_of(destination1),
+            U256::from(30u64)
+        );
+        assert_eq!(token.sender(source).balance_of(destination2), U256::ZERO);
+
+        // Unlock second schedule
+        VM::context().set_block_timestamp(200);
+        vesting.sender(source).unlock(schedule_id2).motsu_unwrap();
+        assert_eq!(
+            token.sender(source).balance_of(destination1),
+            U256::from(30u64)
+        );
+        assert_eq!(
+            token.sender(source).balance_of(destination2),
+            U256::from(50u64)
+        );
+    }
+
+    #[motsu::test]
+    fn test_nonexistent_schedule_operations(
+        vesting: Contract<TokenVestingContract>,
+        caller: Address,
+        new_destination: Address,
+        new_owner: Address,
+    ) {
+        let nonexistent_id = U256::from(999u64);
+
+        // Test unlock on nonexistent schedule
+        let err = vesting
+            .sender(caller)
+            .unlock(nonexistent_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
+
+        // Test change_destination on nonexistent schedule
+        let err = vesting
+            .sender(caller)
+            .change_destination(nonexistent_id, new_destination)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
+
+        // Test change_owner on nonexistent schedule
+        let err = vesting
+            .sender(caller)
+            .change_owner(nonexistent_id, new_owner)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
+
+        // Test view functions return sensible defaults
+        assert_eq!(vesting.sender(caller).token(nonexistent_id), Address::ZERO);
+        assert_eq!(vesting.sender(caller).owner(nonexistent_id), Address::ZERO);
+        assert_eq!(
+            vesting.sender(caller).destination(nonexistent_id),
+            Address::ZERO
+        );
+        assert_eq!(vesting.sender(caller).schedule(nonexistent_id), vec![]);
+        assert_eq!(vesting.sender(caller).cliff(nonexistent_id), U64::ZERO);
+    }
+
+    #[motsu::test]
+    fn test_create_linear_schedule(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        // 10 periods of 100 tokens each, 50 seconds apart, starting at timestamp 100
+        let total = U256::from(1_000u64);
+        token
+            .sender(source)
+            .approve(vesting.address(), total)
+            .motsu_unwrap();
+
+        let schedule_id = vesting
+            .sender(source)
+            .create_linear(
+                token.address(),
+                owner,
+                destination,
+                100,
+                50,
+                10,
+                U256::from(100u64),
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        assert!(vesting.sender(source).is_linear(schedule_id));
+        assert_eq!(
+            vesting.sender(source).linear_schedule(schedule_id),
+            (
+                U64::from(100u64),
+                U64::from(50u64),
+                10,
+                U256::from(100u64),
+                U256::ZERO
+            )
+        );
+        assert_eq!(token.sender(source).balance_of(vesting.address()), total);
+    }
+
+    #[motsu::test]
+    fn test_unlock_linear_schedule(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(1_000u64))
+            .motsu_unwrap();
+
+        let schedule_id = vesting
+            .sender(source)
+            .create_linear(
+                token.address(),
+                owner,
+                destination,
+                100,
+                50,
+                10,
+                U256::from(100u64),
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        // Before the first period completes, nothing is unlocked.
+        VM::context().set_block_timestamp(120);
+        assert_eq!(vesting.sender(source).releasable(schedule_id), U256::ZERO);
+        let err = vesting
+            .sender(source)
+            .unlock(schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::NoUnlocksAvailable(_)));
+
+        // Three whole periods have completed (at 150, 200, 250): 3 * 100 = 300 tokens.
+        VM::context().set_block_timestamp(250);
+        assert_eq!(
+            vesting.sender(source).vested_amount(schedule_id, 250),
+            U256::from(300u64)
+        );
+        assert_eq!(
+            vesting.sender(source).releasable(schedule_id),
+            U256::from(300u64)
+        );
+        vesting.sender(source).unlock(schedule_id).motsu_unwrap();
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            U256::from(300u64)
+        );
+        assert_eq!(vesting.sender(source).releasable(schedule_id), U256::ZERO);
+
+        // Calling again before another period completes unlocks nothing new.
+        let err = vesting
+            .sender(source)
+            .unlock(schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::NoUnlocksAvailable(_)));
+
+        // Past the end of the schedule, the remaining 700 tokens unlock, capped at period_count.
+        VM::context().set_block_timestamp(10_000);
+        vesting.sender(source).unlock(schedule_id).motsu_unwrap();
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            U256::from(1_000u64)
+        );
+        assert_eq!(token.sender(source).balance_of(vesting.address()), U256::ZERO);
+
+        // Fully claimed - no further unlocks regardless of how much more time passes.
+        let err = vesting
+            .sender(source)
+            .unlock(schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::NoUnlocksAvailable(_)));
+    }
+
+    #[motsu::test]
+    fn test_cliff_blocks_unlock_until_crossed(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        let vesting_amount = U256::from(40u64);
+        token
+            .sender(source)
+            .approve(vesting.address(), vesting_amount)
+            .motsu_unwrap();
+
+        // Both tranches mature well before the cliff.
+        let schedule = vec![(10u64, U256::from(20u64)), (20u64, U256::from(20u64))];
+
+        let schedule_id = vesting
+            .sender(source)
+            .create(token.address(), owner, destination, schedule, 100, false)
+            .motsu_unwrap();
+
+        assert_eq!(vesting.sender(source).cliff(schedule_id), U64::from(100u64));
+
+        // Past both tranche timestamps, but before the cliff: nothing unlocks.
+        VM::context().set_block_timestamp(50);
+        let err = vesting
+            .sender(source)
+            .unlock(schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::NoUnlocksAvailable(_)));
+
+        // Once the cliff is crossed, everything accrued so far unlocks at once.
+        VM::context().set_block_timestamp(100);
+        vesting.sender(source).unlock(schedule_id).motsu_unwrap();
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            vesting_amount
+        );
+    }
+
+    #[motsu::test]
+    fn test_merge_schedules(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(60u64))
+            .motsu_unwrap();
+
+        let schedule_id_1 = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::from(10u64)), (300u64, U256::from(10u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        let schedule_id_2 = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::from(10u64)), (200u64, U256::from(30u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        let merged_id = vesting
+            .sender(owner)
+            .merge(schedule_id_1, schedule_id_2)
+            .motsu_unwrap();
+
+        // Both originals are retired.
+        assert_eq!(vesting.sender(owner).schedule(schedule_id_1), vec![]);
+        assert_eq!(vesting.sender(owner).schedule(schedule_id_2), vec![]);
+
+        // Same-timestamp tranches (100) are summed; the rest are interleaved in order.
+        assert_eq!(
+            vesting.sender(owner).schedule(merged_id),
+            vec![
+                (U64::from(100u64), U256::from(20u64)),
+                (U64::from(200u64), U256::from(30u64)),
+                (U64::from(300u64), U256::from(10u64)),
+            ]
+        );
+
+        VM::context().set_block_timestamp(300);
+        vesting.sender(owner).unlock(merged_id).motsu_unwrap();
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            U256::from(60u64)
+        );
+    }
+
+    #[motsu::test]
+    fn test_merge_flushes_already_matured_tranches_first(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(70u64))
+            .motsu_unwrap();
+
+        let schedule_id_1 = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(50u64, U256::from(10u64)), (300u64, U256::from(20u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        let schedule_id_2 = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(50u64, U256::from(15u64)), (250u64, U256::from(25u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        // Both schedules' first tranche has already matured by the time they're merged.
+        VM::context().set_block_timestamp(100);
+        let merged_id = vesting
+            .sender(owner)
+            .merge(schedule_id_1, schedule_id_2)
+            .motsu_unwrap();
+
+        // The matured 10 + 15 went straight to destination rather than being carried into the merged schedule.
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            U256::from(25u64)
+        );
+
+        // Only the still-locked tranches remain.
+        assert_eq!(
+            vesting.sender(owner).schedule(merged_id),
+            vec![
+                (U64::from(50u64), U256::ZERO),
+                (U64::from(250u64), U256::from(25u64)),
+                (U64::from(300u64), U256::from(20u64)),
+            ]
+        );
+
+        VM::context().set_block_timestamp(300);
+        vesting.sender(owner).unlock(merged_id).motsu_unwrap();
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            U256::from(70u64)
+        );
+    }
+
+    #[motsu::test]
+    fn test_merge_validation_errors(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        other_owner: Address,
+        source: Address,
+        destination: Address,
+        other_destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(40u64))
+            .motsu_unwrap();
+
+        let schedule_id_1 = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::from(10u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        let schedule_id_2 = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                other_owner,
+                destination,
+                vec![(100u64, U256::from(10u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        // Test 1: caller doesn't own both schedules.
+        let err = vesting
+            .sender(owner)
+            .merge(schedule_id_1, schedule_id_2)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized(_)));
+
+        let schedule_id_3 = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                other_destination,
+                vec![(100u64, U256::from(10u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        // Test 2: mismatched destinations.
+        let err = vesting
+            .sender(owner)
+            .merge(schedule_id_1, schedule_id_3)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
+
+        // Test 3: nonexistent schedule.
+        let err = vesting
+            .sender(owner)
+            .merge(schedule_id_1, U256::from(999u64))
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
+
+        // Test 4: a linear schedule can't be merged with a tranche-based one.
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(100u64))
+            .motsu_unwrap();
+        let linear_schedule_id = vesting
+            .sender(source)
+            .create_linear(token.address(), owner, destination, 100, 50, 2, U256::from(50u64), 0, false)
+            .motsu_unwrap();
+
+        let err = vesting
+            .sender(owner)
+            .merge(schedule_id_1, linear_schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
+    }
+
+    #[motsu::test]
+    fn test_vested_transfer(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        payroll: Address,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        let vesting_amount = U256::from(40u64);
+        token
+            .sender(source)
+            .approve(vesting.address(), vesting_amount)
+            .motsu_unwrap();
+
+        // `payroll` initiates the schedule, but `source` is the one actually funding it.
+        let schedule_id = vesting
+            .sender(payroll)
+            .vested_transfer(
+                token.address(),
+                source,
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64)), (200u64, U256::from(20u64))],
+                0,
+            )
+            .motsu_unwrap();
+
+        assert_eq!(vesting.sender(payroll).owner(schedule_id), owner);
+        assert_eq!(
+            vesting.sender(payroll).destination(schedule_id),
+            destination
+        );
+        assert_eq!(
+            token.sender(source).balance_of(vesting.address()),
+            vesting_amount
+        );
+        assert_eq!(
+            token.sender(source).balance_of(source),
+            U256::from(TOTAL_SUPPLY) - vesting_amount
+        );
+
+        VM::context().set_block_timestamp(200);
+        vesting.sender(owner).unlock(schedule_id).motsu_unwrap();
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            vesting_amount
+        );
+    }
+
+    #[motsu::test]
+    fn test_vested_transfer_requires_funder_allowance(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        payroll: Address,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        // `source` never approved the vesting contract.
+        let err = vesting
+            .sender(payroll)
+            .vested_transfer(
+                token.address(),
+                source,
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized(_)));
+
+        // Approving less than the schedule total is still insufficient.
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(10u64))
+            .motsu_unwrap();
+
+        let err = vesting
+            .sender(payroll)
+            .vested_transfer(
+                token.address(),
+                source,
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized(_)));
+    }
+
+    #[motsu::test]
+    fn test_non_custodial_unlock_pulls_from_source(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        // No upfront deposit: the contract never holds a balance for this schedule.
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(40u64))
+            .motsu_unwrap();
+
+        let schedule_id = vesting
+            .sender(source)
+            .create_non_custodial(
+                token.address(),
+                source,
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64)), (200u64, U256::from(20u64))],
+                0,
+            )
+            .motsu_unwrap();
+
+        assert_eq!(vesting.sender(source).source(schedule_id), source);
+        assert_eq!(token.sender(source).balance_of(vesting.address()), U256::ZERO);
+
+        VM::context().set_block_timestamp(150);
+        vesting.sender(owner).unlock(schedule_id).motsu_unwrap();
+
+        // The first tranche moved straight from `source` to `destination`, never touching the contract.
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            U256::from(20u64)
+        );
+        assert_eq!(
+            token.sender(source).balance_of(source),
+            U256::from(TOTAL_SUPPLY) - U256::from(20u64)
+        );
+        assert_eq!(token.sender(source).balance_of(vesting.address()), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn test_non_custodial_unlock_fails_cleanly_without_allowance(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        let schedule_id = vesting
+            .sender(source)
+            .create_non_custodial(
+                token.address(),
+                source,
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+            )
+            .motsu_unwrap();
+
+        // `source` never granted an allowance.
+        VM::context().set_block_timestamp(150);
+        let err = vesting
+            .sender(owner)
+            .unlock(schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::UnlockTransferFailed(_)));
+
+        // `source` revokes after once having granted enough.
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(20u64))
+            .motsu_unwrap();
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::ZERO)
+            .motsu_unwrap();
+
+        let err = vesting
+            .sender(owner)
+            .unlock(schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::UnlockTransferFailed(_)));
+    }
+
+    #[motsu::test]
+    fn test_non_custodial_validation_errors(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
+
+        // Test 1: invalid token.
+        let err = vesting
+            .sender(source)
+            .create_non_custodial(
+                Address::ZERO,
+                source,
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidToken(_)));
+
+        // Test 2: invalid destination.
+        let err = vesting
+            .sender(source)
+            .create_non_custodial(
+                token.address(),
+                source,
+                owner,
+                Address::ZERO,
+                vec![(100u64, U256::from(20u64))],
+                0,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidDestination(_)));
+
+        // Test 3: zero-address source.
+        let err = vesting
+            .sender(source)
+            .create_non_custodial(
+                token.address(),
+                Address::ZERO,
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
+
+        // Test 4: empty schedule.
+        let err = vesting
+            .sender(source)
+            .create_non_custodial(token.address(), source, owner, destination, vec![], 0)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
     }
 
     #[motsu::test]
-    fn test_create_vesting_schedule(
+    fn test_non_custodial_schedule_rejects_revoke_force_remove_and_merge(
         token: Contract<Erc20>,
         vesting: Contract<TokenVestingContract>,
+        admin: Address,
         owner: Address,
         source: Address,
         destination: Address,
+        refund_address: Address,
     ) {
         setup_env(&token, source);
 
-        // Approve vesting contract to transfer tokens
-        let vesting_amount = U256::from(60u64);
+        vesting.sender(admin).constructor(admin);
+
         token
             .sender(source)
-            .approve(vesting.address(), vesting_amount)
+            .approve(vesting.address(), U256::from(40u64))
             .motsu_unwrap();
 
-        // Create vesting schedule with 3 unlocks
-        let schedule = vec![
-            (0u64, U256::from(20u64)),   // Immediate unlock
-            (100u64, U256::from(20u64)), // After timestamp 100
-            (200u64, U256::from(20u64)), // After timestamp 200
-        ];
-
         let schedule_id = vesting
             .sender(source)
-            .create(token.address(), owner, destination, schedule.clone())
+            .create_non_custodial(
+                token.address(),
+                source,
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+            )
             .motsu_unwrap();
 
-        // Verify schedule was created
-        assert_eq!(schedule_id, U256::from(1u64));
-        assert_eq!(vesting.sender(source).schedule_count(), U256::from(1u64));
-        assert_eq!(vesting.sender(source).token(schedule_id), token.address());
-        assert_eq!(vesting.sender(source).owner(schedule_id), owner);
-        assert_eq!(vesting.sender(source).destination(schedule_id), destination);
+        let err = vesting
+            .sender(owner)
+            .revoke(schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
 
-        // Verify schedule details
-        let stored_schedule = vesting.sender(source).schedule(schedule_id);
-        assert_eq!(stored_schedule.len(), 3);
-        assert_eq!(stored_schedule[0], (U64::from(0u64), U256::from(20u64)));
-        assert_eq!(stored_schedule[1], (U64::from(100u64), U256::from(20u64)));
-        assert_eq!(stored_schedule[2], (U64::from(200u64), U256::from(20u64)));
+        let err = vesting
+            .sender(admin)
+            .force_remove(schedule_id, refund_address)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
 
-        // Verify tokens were transferred to vesting contract
-        assert_eq!(
-            token.sender(source).balance_of(vesting.address()),
-            vesting_amount
-        );
-        assert_eq!(
-            token.sender(source).balance_of(source),
-            U256::from(TOTAL_SUPPLY) - vesting_amount
-        );
+        let other_schedule_id = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        let err = vesting
+            .sender(owner)
+            .merge(schedule_id, other_schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
     }
 
     #[motsu::test]
-    fn test_unlock_tokens(
+    fn test_revoke_schedule(
         token: Contract<Erc20>,
         vesting: Contract<TokenVestingContract>,
         owner: Address,
@@ -417,428 +2540,465 @@ mod tests {
     ) {
         setup_env(&token, source);
 
-        let vesting_amount = U256::from(60u64);
         token
             .sender(source)
-            .approve(vesting.address(), vesting_amount)
+            .approve(vesting.address(), U256::from(60u64))
             .motsu_unwrap();
 
-        let schedule = vec![
-            (0u64, U256::from(20u64)),
-            (100u64, U256::from(20u64)),
-            (200u64, U256::from(20u64)),
-        ];
-
         let schedule_id = vesting
             .sender(source)
-            .create(token.address(), owner, destination, schedule)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![
+                    (100u64, U256::from(20u64)),
+                    (200u64, U256::from(20u64)),
+                    (300u64, U256::from(20u64)),
+                ],
+                0,
+                true,
+            )
             .motsu_unwrap();
 
-        // Test 1: Unlock at timestamp 1 (immediate unlock for first tranche)
-        vesting.sender(source).unlock(schedule_id).motsu_unwrap();
+        // Only the first tranche has matured.
+        VM::context().set_block_timestamp(150);
+        vesting.sender(owner).revoke(schedule_id).motsu_unwrap();
 
+        // Matured amount went to destination, the rest was clawed back to owner.
         assert_eq!(
             token.sender(source).balance_of(destination),
             U256::from(20u64)
         );
-        assert_eq!(
-            token.sender(source).balance_of(vesting.address()),
-            U256::from(40u64)
-        );
-
-        // Verify first unlock is now zero in schedule
-        let stored_schedule = vesting.sender(source).schedule(schedule_id);
-        assert_eq!(stored_schedule[0].1, U256::ZERO);
+        assert_eq!(token.sender(source).balance_of(owner), U256::from(40u64));
+        assert_eq!(token.sender(source).balance_of(vesting.address()), U256::ZERO);
 
-        // Test 2: Try to unlock again at same timestamp (should fail - no unlocks available)
+        // The schedule is retired.
+        assert_eq!(vesting.sender(owner).schedule(schedule_id), vec![]);
         let err = vesting
-            .sender(source)
+            .sender(owner)
             .unlock(schedule_id)
             .motsu_unwrap_err();
-        assert!(matches!(err, ContractError::NoUnlocksAvailable(_)));
+        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
+    }
 
-        // Test 3: Unlock at timestamp 150 (should unlock second tranche)
-        VM::context().set_block_timestamp(150);
-        vesting.sender(source).unlock(schedule_id).motsu_unwrap();
+    #[motsu::test]
+    fn test_revoke_before_cliff_returns_everything_to_owner(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        owner: Address,
+        source: Address,
+        destination: Address,
+    ) {
+        setup_env(&token, source);
 
-        assert_eq!(
-            token.sender(source).balance_of(destination),
-            U256::from(40u64)
-        );
-        assert_eq!(
-            token.sender(source).balance_of(vesting.address()),
-            U256::from(20u64)
-        );
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(20u64))
+            .motsu_unwrap();
 
-        // Test 4: Unlock at timestamp 250 (should unlock final tranche)
-        VM::context().set_block_timestamp(250);
-        vesting.sender(source).unlock(schedule_id).motsu_unwrap();
+        let schedule_id = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(10u64, U256::from(20u64))],
+                100,
+                true,
+            )
+            .motsu_unwrap();
 
-        assert_eq!(
-            token.sender(source).balance_of(destination),
-            U256::from(60u64)
-        );
-        assert_eq!(
-            token.sender(source).balance_of(vesting.address()),
-            U256::ZERO
-        );
+        // Past the tranche's timestamp, but before the cliff: nothing has matured yet.
+        VM::context().set_block_timestamp(50);
+        vesting.sender(owner).revoke(schedule_id).motsu_unwrap();
 
-        // All tokens should be unlocked now
-        let final_schedule = vesting.sender(source).schedule(schedule_id);
-        assert!(final_schedule.iter().all(|(_, amount)| amount.is_zero()));
+        assert_eq!(token.sender(source).balance_of(destination), U256::ZERO);
+        assert_eq!(token.sender(source).balance_of(owner), U256::from(20u64));
     }
 
     #[motsu::test]
-    fn test_unlock_multiple_at_once(
+    fn test_revoke_validation_errors(
         token: Contract<Erc20>,
         vesting: Contract<TokenVestingContract>,
         owner: Address,
         source: Address,
         destination: Address,
+        stranger: Address,
     ) {
         setup_env(&token, source);
 
-        let vesting_amount = U256::from(60u64);
+        // Test 1: nonexistent schedule.
+        let err = vesting
+            .sender(owner)
+            .revoke(U256::from(999u64))
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
+
         token
             .sender(source)
-            .approve(vesting.address(), vesting_amount)
+            .approve(vesting.address(), U256::from(20u64))
             .motsu_unwrap();
 
-        let schedule = vec![
-            (50u64, U256::from(20u64)),
-            (100u64, U256::from(20u64)),
-            (150u64, U256::from(20u64)),
-        ];
+        // Test 2: schedule wasn't created as revocable.
+        let schedule_id = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
+
+        let err = vesting
+            .sender(owner)
+            .revoke(schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized(_)));
+
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(20u64))
+            .motsu_unwrap();
+
+        let revocable_schedule_id = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+                true,
+            )
+            .motsu_unwrap();
+
+        // Test 3: caller is not the owner.
+        let err = vesting
+            .sender(stranger)
+            .revoke(revocable_schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized(_)));
+    }
+
+    #[motsu::test]
+    fn test_force_remove_schedule(
+        token: Contract<Erc20>,
+        vesting: Contract<TokenVestingContract>,
+        admin: Address,
+        owner: Address,
+        source: Address,
+        destination: Address,
+        refund_address: Address,
+    ) {
+        setup_env(&token, source);
+
+        vesting.sender(admin).constructor(admin);
 
+        token
+            .sender(source)
+            .approve(vesting.address(), U256::from(60u64))
+            .motsu_unwrap();
+
+        // Not marked revocable - force_remove doesn't care.
         let schedule_id = vesting
             .sender(source)
-            .create(token.address(), owner, destination, schedule)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![
+                    (100u64, U256::from(20u64)),
+                    (200u64, U256::from(20u64)),
+                    (300u64, U256::from(20u64)),
+                ],
+                0,
+                false,
+            )
             .motsu_unwrap();
 
-        // Jump to timestamp 120 - should unlock first two tranches at once
-        VM::context().set_block_timestamp(120);
-        vesting.sender(source).unlock(schedule_id).motsu_unwrap();
+        // Only the first tranche has matured.
+        VM::context().set_block_timestamp(150);
+        vesting
+            .sender(admin)
+            .force_remove(schedule_id, refund_address)
+            .motsu_unwrap();
 
         assert_eq!(
             token.sender(source).balance_of(destination),
-            U256::from(40u64)
+            U256::from(20u64)
         );
         assert_eq!(
-            token.sender(source).balance_of(vesting.address()),
-            U256::from(20u64)
+            token.sender(source).balance_of(refund_address),
+            U256::from(40u64)
         );
+        assert_eq!(token.sender(source).balance_of(vesting.address()), U256::ZERO);
+
+        let err = vesting
+            .sender(admin)
+            .unlock(schedule_id)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
     }
 
     #[motsu::test]
-    fn test_change_destination(
+    fn test_force_remove_validation_errors(
         token: Contract<Erc20>,
         vesting: Contract<TokenVestingContract>,
+        admin: Address,
         owner: Address,
         source: Address,
         destination: Address,
-        new_destination: Address,
+        stranger: Address,
+        refund_address: Address,
     ) {
         setup_env(&token, source);
 
-        let vesting_amount = U256::from(40u64);
-        token
-            .sender(source)
-            .approve(vesting.address(), vesting_amount)
-            .motsu_unwrap();
+        vesting.sender(admin).constructor(admin);
 
-        let schedule = vec![(100u64, U256::from(20u64)), (200u64, U256::from(20u64))];
+        // Test 1: nonexistent schedule.
+        let err = vesting
+            .sender(admin)
+            .force_remove(U256::from(999u64), refund_address)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
 
-        let schedule_id = vesting
+        token
             .sender(source)
-            .create(token.address(), owner, destination, schedule)
+            .approve(vesting.address(), U256::from(20u64))
             .motsu_unwrap();
 
-        // Test 1: Unauthorized change (not owner)
-        let err = vesting
+        let schedule_id = vesting
             .sender(source)
-            .change_destination(schedule_id, new_destination)
-            .motsu_unwrap_err();
-        assert!(matches!(err, ContractError::Unauthorized(_)));
-
-        // Test 2: Authorized change by owner
-        vesting
-            .sender(owner)
-            .change_destination(schedule_id, new_destination)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+                false,
+            )
             .motsu_unwrap();
 
-        assert_eq!(
-            vesting.sender(owner).destination(schedule_id),
-            new_destination
-        );
-
-        // Test 3: Unlock tokens to new destination
-        VM::context().set_block_timestamp(150);
-        vesting.sender(owner).unlock(schedule_id).motsu_unwrap();
-
-        assert_eq!(
-            token.sender(source).balance_of(new_destination),
-            U256::from(20u64)
-        );
-        assert_eq!(token.sender(source).balance_of(destination), U256::ZERO);
+        // Test 2: caller is not admin.
+        let err = vesting
+            .sender(stranger)
+            .force_remove(schedule_id, refund_address)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized(_)));
     }
 
     #[motsu::test]
-    fn test_change_owner(
+    fn test_stake_then_withdraw_round_trip(
         token: Contract<Erc20>,
         vesting: Contract<TokenVestingContract>,
+        pool: Contract<MockStakingPool>,
         owner: Address,
-        new_owner: Address,
         source: Address,
         destination: Address,
     ) {
         setup_env(&token, source);
 
+        let vesting_amount = U256::from(40u64);
         token
             .sender(source)
-            .approve(vesting.address(), U256::from(20u64))
+            .approve(vesting.address(), vesting_amount)
             .motsu_unwrap();
 
-        let schedule = vec![(100u64, U256::from(20u64))];
-
         let schedule_id = vesting
             .sender(source)
-            .create(token.address(), owner, destination, schedule)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64)), (200u64, U256::from(20u64))],
+                0,
+                false,
+            )
             .motsu_unwrap();
 
-        // Test 1: Unauthorized change
-        let err = vesting
-            .sender(source)
-            .change_owner(schedule_id, new_owner)
-            .motsu_unwrap_err();
-        assert!(matches!(err, ContractError::Unauthorized(_)));
-
-        // Test 2: Authorized change by current owner
         vesting
             .sender(owner)
-            .change_owner(schedule_id, new_owner)
+            .stake(schedule_id, pool.address(), U256::from(20u64))
             .motsu_unwrap();
 
-        assert_eq!(vesting.sender(new_owner).owner(schedule_id), new_owner);
+        assert_eq!(
+            vesting.sender(owner).staked_balance(schedule_id),
+            U256::from(20u64)
+        );
+        assert_eq!(vesting.sender(owner).staking_pool(schedule_id), pool.address());
+        assert_eq!(
+            token.sender(source).balance_of(vesting.address()),
+            U256::from(20u64)
+        );
+        assert_eq!(token.sender(source).balance_of(pool.address()), U256::from(20u64));
+
+        // Only the unstaked tranche can unlock while the rest is staked.
+        VM::context().set_block_timestamp(250);
+        vesting.sender(owner).unlock(schedule_id).motsu_unwrap();
+        assert_eq!(
+            token.sender(source).balance_of(destination),
+            U256::from(20u64)
+        );
 
-        // Test 3: New owner can now change destination
-        let another_destination = Address::from([5u8; 20]);
         vesting
-            .sender(new_owner)
-            .change_destination(schedule_id, another_destination)
+            .sender(owner)
+            .withdraw_stake(schedule_id, U256::from(20u64))
             .motsu_unwrap();
+        assert_eq!(vesting.sender(owner).staked_balance(schedule_id), U256::ZERO);
+        assert_eq!(vesting.sender(owner).staking_pool(schedule_id), Address::ZERO);
+        assert_eq!(
+            token.sender(source).balance_of(vesting.address()),
+            U256::from(20u64)
+        );
 
+        // The previously-blocked tranche is now free to unlock.
+        vesting.sender(owner).unlock(schedule_id).motsu_unwrap();
         assert_eq!(
-            vesting.sender(new_owner).destination(schedule_id),
-            another_destination
+            token.sender(source).balance_of(destination),
+            U256::from(40u64)
         );
     }
 
     #[motsu::test]
-    fn test_create_validation_errors(
+    fn test_stake_validation_errors(
         token: Contract<Erc20>,
         vesting: Contract<TokenVestingContract>,
+        pool: Contract<MockStakingPool>,
+        other_pool: Contract<MockStakingPool>,
         owner: Address,
+        stranger: Address,
         source: Address,
         destination: Address,
     ) {
         setup_env(&token, source);
 
-        // Test 1: Invalid token (zero address)
-        let err = vesting
+        token
             .sender(source)
-            .create(
-                Address::ZERO,
-                owner,
-                destination,
-                vec![(100u64, U256::from(20u64))],
-            )
-            .motsu_unwrap_err();
-        assert!(matches!(err, ContractError::InvalidToken(_)));
+            .approve(vesting.address(), U256::from(20u64))
+            .motsu_unwrap();
 
-        // Test 3: Invalid destination (zero address)
-        let err = vesting
+        let schedule_id = vesting
             .sender(source)
             .create(
                 token.address(),
                 owner,
-                Address::ZERO,
+                destination,
                 vec![(100u64, U256::from(20u64))],
+                0,
+                false,
             )
-            .motsu_unwrap_err();
-        assert!(matches!(err, ContractError::InvalidDestination(_)));
+            .motsu_unwrap();
 
-        // Test 4: Empty schedule
+        // Test 1: nonexistent schedule.
         let err = vesting
-            .sender(source)
-            .create(token.address(), owner, destination, vec![])
+            .sender(owner)
+            .stake(U256::from(999u64), pool.address(), U256::from(1u64))
             .motsu_unwrap_err();
-        assert!(matches!(err, ContractError::InvalidSchedule(_)));
+        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
 
-        // Test 5: Zero amount in schedule
+        // Test 2: caller is not owner.
         let err = vesting
-            .sender(source)
-            .create(
-                token.address(),
-                owner,
-                destination,
-                vec![(100u64, U256::ZERO)],
-            )
+            .sender(stranger)
+            .stake(schedule_id, pool.address(), U256::from(1u64))
             .motsu_unwrap_err();
-        assert!(matches!(err, ContractError::InvalidSchedule(_)));
+        assert!(matches!(err, ContractError::Unauthorized(_)));
 
-        // Test 6: Non-chronological schedule
+        // Test 3: amount exceeds remaining_unvested.
         let err = vesting
-            .sender(source)
-            .create(
-                token.address(),
-                owner,
-                destination,
-                vec![
-                    (200u64, U256::from(10u64)),
-                    (100u64, U256::from(10u64)), // Earlier timestamp after later one
-                ],
-            )
+            .sender(owner)
+            .stake(schedule_id, pool.address(), U256::from(21u64))
             .motsu_unwrap_err();
         assert!(matches!(err, ContractError::InvalidSchedule(_)));
 
-        // Test 7: Insufficient allowance
-        token
-            .sender(source)
-            .approve(vesting.address(), U256::from(10u64))
+        vesting
+            .sender(owner)
+            .stake(schedule_id, pool.address(), U256::from(10u64))
             .motsu_unwrap();
 
+        // Test 4: switching to a different pool while already staked.
         let err = vesting
-            .sender(source)
-            .create(
-                token.address(),
-                owner,
-                destination,
-                vec![(100u64, U256::from(20u64))], // Needs 20 but only approved 10
-            )
+            .sender(owner)
+            .stake(schedule_id, other_pool.address(), U256::from(1u64))
             .motsu_unwrap_err();
-        assert!(matches!(err, ContractError::TokenDepositFailed(_)));
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
+
+        // Test 5: withdraw_stake beyond staked_balance.
+        let err = vesting
+            .sender(owner)
+            .withdraw_stake(schedule_id, U256::from(11u64))
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
     }
 
     #[motsu::test]
-    fn test_multiple_schedules(
+    fn test_staked_schedule_rejects_revoke_force_remove_and_merge(
         token: Contract<Erc20>,
         vesting: Contract<TokenVestingContract>,
-        owner1: Address,
-        owner2: Address,
+        pool: Contract<MockStakingPool>,
+        admin: Address,
+        owner: Address,
         source: Address,
-        destination1: Address,
-        destination2: Address,
+        destination: Address,
+        refund_address: Address,
     ) {
         setup_env(&token, source);
 
-        // Create first schedule
-        token
-            .sender(source)
-            .approve(vesting.address(), U256::from(30u64))
-            .motsu_unwrap();
-
-        let schedule_id1 = vesting
-            .sender(source)
-            .create(
-                token.address(),
-                owner1,
-                destination1,
-                vec![(100u64, U256::from(30u64))],
-            )
-            .motsu_unwrap();
+        vesting.sender(admin).constructor(admin);
 
-        // Create second schedule
         token
             .sender(source)
-            .approve(vesting.address(), U256::from(50u64))
+            .approve(vesting.address(), U256::from(40u64))
             .motsu_unwrap();
 
-        let schedule_id2 = vesting
+        let schedule_id = vesting
             .sender(source)
             .create(
                 token.address(),
-                owner2,
-                destination2,
-                vec![(200u64, U256::from(50u64))],
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+                true,
             )
             .motsu_unwrap();
 
-        // Verify separate schedule IDs
-        assert_eq!(schedule_id1, U256::from(1u64));
-        assert_eq!(schedule_id2, U256::from(2u64));
-        assert_eq!(vesting.sender(source).schedule_count(), U256::from(2u64));
-
-        // Verify schedules are independent
-        assert_eq!(vesting.sender(source).owner(schedule_id1), owner1);
-        assert_eq!(vesting.sender(source).owner(schedule_id2), owner2);
-        assert_eq!(
-            vesting.sender(source).destination(schedule_id1),
-            destination1
-        );
-        assert_eq!(
-            vesting.sender(source).destination(schedule_id2),
-            destination2
-        );
-
-        // Unlock first schedule
-        VM::context().set_block_timestamp(150);
-        vesting.sender(source).unlock(schedule_id1).motsu_unwrap();
-        assert_eq!(
-            token.sender(source).balance_of(destination1),
-            U256::from(30u64)
-        );
-        assert_eq!(token.sender(source).balance_of(destination2), U256::ZERO);
-
-        // Unlock second schedule
-        VM::context().set_block_timestamp(200);
-        vesting.sender(source).unlock(schedule_id2).motsu_unwrap();
-        assert_eq!(
-            token.sender(source).balance_of(destination1),
-            U256::from(30u64)
-        );
-        assert_eq!(
-            token.sender(source).balance_of(destination2),
-            U256::from(50u64)
-        );
-    }
-
-    #[motsu::test]
-    fn test_nonexistent_schedule_operations(
-        vesting: Contract<TokenVestingContract>,
-        caller: Address,
-        new_destination: Address,
-        new_owner: Address,
-    ) {
-        let nonexistent_id = U256::from(999u64);
+        vesting
+            .sender(owner)
+            .stake(schedule_id, pool.address(), U256::from(10u64))
+            .motsu_unwrap();
 
-        // Test unlock on nonexistent schedule
         let err = vesting
-            .sender(caller)
-            .unlock(nonexistent_id)
+            .sender(owner)
+            .revoke(schedule_id)
             .motsu_unwrap_err();
-        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
 
-        // Test change_destination on nonexistent schedule
         let err = vesting
-            .sender(caller)
-            .change_destination(nonexistent_id, new_destination)
+            .sender(admin)
+            .force_remove(schedule_id, refund_address)
             .motsu_unwrap_err();
-        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
+
+        let other_schedule_id = vesting
+            .sender(source)
+            .create(
+                token.address(),
+                owner,
+                destination,
+                vec![(100u64, U256::from(20u64))],
+                0,
+                false,
+            )
+            .motsu_unwrap();
 
-        // Test change_owner on nonexistent schedule
         let err = vesting
-            .sender(caller)
-            .change_owner(nonexistent_id, new_owner)
+            .sender(owner)
+            .merge(schedule_id, other_schedule_id)
             .motsu_unwrap_err();
-        assert!(matches!(err, ContractError::ScheduleNotFound(_)));
-
-        // Test view functions return sensible defaults
-        assert_eq!(vesting.sender(caller).token(nonexistent_id), Address::ZERO);
-        assert_eq!(vesting.sender(caller).owner(nonexistent_id), Address::ZERO);
-        assert_eq!(
-            vesting.sender(caller).destination(nonexistent_id),
-            Address::ZERO
-        );
-        assert_eq!(vesting.sender(caller).schedule(nonexistent_id), vec![]);
+        assert!(matches!(err, ContractError::InvalidSchedule(_)));
     }
 }
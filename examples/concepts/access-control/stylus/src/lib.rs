@@ -1,16 +1,40 @@
 extern crate alloc;
 
 use stylus_sdk::{
-    alloy_primitives::{Address, U256, U64},
+    alloy_primitives::{Address, B256, U256, U64, U8},
     alloy_sol_types::sol,
+    crypto::keccak,
     prelude::*,
-    storage::{StorageAddress, StorageU256, StorageU64},
+    storage::{StorageAddress, StorageMap, StorageU256, StorageU64, StorageU8, StorageVec},
 };
 
+/// Mirrors the `MAX_SIGNERS` bound `spl_token_2022::state::Multisig` enforces on its own signer
+/// array - the fixed upper bound a proposal's per-signer approval bitmap is keyed off.
+const MAX_SIGNERS: usize = 11;
+
 #[storage]
 pub struct Config {
     authority: StorageAddress,
-    publisher: StorageAddress,
+    /// Set by `transfer_authority`; only this address may call `accept_authority`.
+    /// `Address::ZERO` means no transfer is in flight.
+    pending_authority: StorageAddress,
+}
+
+/// An M-of-N signer set gating `publish_price`, the Stylus analogue of
+/// `spl_token_2022::state::Multisig`'s fixed signer array plus its `m`/`n` threshold fields.
+#[storage]
+pub struct Multisig {
+    signers: StorageVec<StorageAddress>,
+    /// Approval threshold; invariant `1 <= m <= signers.len()`, enforced by `set_multisig`.
+    m: StorageU8,
+}
+
+impl Multisig {
+    /// The position of `address` among the registered signers, if it's one at all - the Stylus
+    /// equivalent of walking a native program's multisig signer array by pubkey.
+    fn signer_index(&self, address: Address) -> Option<usize> {
+        (0..self.signers.len()).find(|&i| self.signers.get(i) == Some(address))
+    }
 }
 
 #[storage]
@@ -20,55 +44,264 @@ pub struct Price {
     timestamp: StorageU64,
 }
 
+/// A proposed price awaiting `m` distinct signer approvals before it's committed to `last_price`.
+#[storage]
+pub struct Proposal {
+    base: StorageU256,
+    quote: StorageU256,
+    timestamp: StorageU64,
+    /// Bit `i` set means the signer at index `i` has approved this proposal. Cleared once the
+    /// proposal is committed, so a proposal hash can't be replayed into a second commit.
+    approvals: StorageU256,
+}
+
 #[storage]
 #[entrypoint]
 pub struct AccessControl {
     config: Config,
+    multisig: Multisig,
     last_price: Price,
+    /// Bumped every time a proposal is committed into `last_price`, so a caller can cheaply detect
+    /// a new price without re-reading and diffing the full `Price` tuple.
+    price_nonce: StorageU64,
+    proposals: StorageMap<B256, Proposal>,
 }
 
 sol! {
+    event AuthorityTransferProposed(address pending_authority);
+    event AuthorityTransferred(address previous_authority, address new_authority);
+    event MultisigConfigUpdated(uint8 signer_count, uint8 threshold);
+    event PriceProposed(bytes32 proposal_hash, uint256 base, uint256 quote);
+    event PriceCommitted(bytes32 proposal_hash, uint256 base, uint256 quote, uint64 nonce);
+
     #[derive(Debug, PartialEq, Eq)]
     error Unauthorized();
+    #[derive(Debug, PartialEq, Eq)]
+    error InvalidMultisigConfig();
+    #[derive(Debug, PartialEq, Eq)]
+    error ProposalNotFound();
+    #[derive(Debug, PartialEq, Eq)]
+    error AlreadyApproved();
 }
 
 #[derive(SolidityError, Debug, PartialEq, Eq)]
 pub enum AccessControlError {
     Unauthorized(Unauthorized),
+    InvalidMultisigConfig(InvalidMultisigConfig),
+    ProposalNotFound(ProposalNotFound),
+    AlreadyApproved(AlreadyApproved),
+}
+
+impl AccessControl {
+    fn require_authority(&self, caller: Address) -> Result<(), AccessControlError> {
+        if caller != self.config.authority.get() {
+            return Err(AccessControlError::Unauthorized(Unauthorized {}));
+        }
+
+        Ok(())
+    }
+
+    /// Errors unless `caller` is one of the registered multisig signers, otherwise returning its
+    /// index - the bit position `propose_price`/`approve_price` flip in a proposal's approval
+    /// bitmap.
+    fn require_signer(&self, caller: Address) -> Result<usize, AccessControlError> {
+        self.multisig
+            .signer_index(caller)
+            .ok_or(AccessControlError::Unauthorized(Unauthorized {}))
+    }
+
+    /// Replaces the registered signer set and approval threshold in one shot, so the two can never
+    /// be updated independently and drift out of sync (e.g. a threshold left referencing a signer
+    /// that was just removed).
+    fn set_multisig(&mut self, signers: Vec<Address>, m: U8) -> Result<(), AccessControlError> {
+        if signers.is_empty() || signers.len() > MAX_SIGNERS {
+            return Err(AccessControlError::InvalidMultisigConfig(InvalidMultisigConfig {}));
+        }
+        if m.is_zero() || m.to::<usize>() > signers.len() {
+            return Err(AccessControlError::InvalidMultisigConfig(InvalidMultisigConfig {}));
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            if signers[..i].contains(signer) {
+                return Err(AccessControlError::InvalidMultisigConfig(InvalidMultisigConfig {}));
+            }
+        }
+
+        while self.multisig.signers.pop().is_some() {}
+        for signer in &signers {
+            self.multisig.signers.push(*signer);
+        }
+        self.multisig.m.set(m);
+
+        log(
+            self.vm(),
+            MultisigConfigUpdated {
+                signer_count: U8::from(signers.len() as u8),
+                threshold: m,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn hash_proposal(base: U256, quote: U256, timestamp: u64) -> B256 {
+        let mut preimage = [0u8; 96];
+        preimage[0..32].copy_from_slice(&base.to_be_bytes::<32>());
+        preimage[32..64].copy_from_slice(&quote.to_be_bytes::<32>());
+        preimage[64..96].copy_from_slice(&U256::from(timestamp).to_be_bytes::<32>());
+
+        keccak(preimage)
+    }
 }
 
 #[public]
 impl AccessControl {
     #[constructor]
-    pub fn constructor(&mut self, authority: Address, publisher: Address) {
+    pub fn constructor(
+        &mut self,
+        authority: Address,
+        signers: Vec<Address>,
+        m: U8,
+    ) -> Result<(), AccessControlError> {
         self.config.authority.set(authority);
-        self.config.publisher.set(publisher);
+        self.set_multisig(signers, m)?;
+
+        Ok(())
     }
 
-    pub fn update_config(&mut self, publisher: Address) -> Result<(), AccessControlError> {
+    /// Rotates the signer set and approval threshold together; authority-gated.
+    pub fn update_config(&mut self, signers: Vec<Address>, m: U8) -> Result<(), AccessControlError> {
         let sender = self.vm().msg_sender();
+        self.require_authority(sender)?;
 
-        if sender != self.config.authority.get() {
-            return Err(AccessControlError::Unauthorized(Unauthorized {}));
-        }
+        self.set_multisig(signers, m)
+    }
 
-        self.config.publisher.set(publisher);
+    /// Nominates `proposed` as the next authority. The transfer doesn't take effect until
+    /// `proposed` itself calls [`AccessControl::accept_authority`] - unlike a one-step handover, a
+    /// typo'd or unreachable address can't brick the contract's authority.
+    pub fn transfer_authority(&mut self, proposed: Address) -> Result<(), AccessControlError> {
+        let sender = self.vm().msg_sender();
+        self.require_authority(sender)?;
+
+        self.config.pending_authority.set(proposed);
+
+        log(
+            self.vm(),
+            AuthorityTransferProposed {
+                pending_authority: proposed,
+            },
+        );
 
         Ok(())
     }
 
-    pub fn publish_price(&mut self, base: U256, quote: U256) -> Result<(), AccessControlError> {
+    /// Completes a transfer started by [`AccessControl::transfer_authority`]; only the nominated
+    /// `pending_authority` may call this.
+    pub fn accept_authority(&mut self) -> Result<(), AccessControlError> {
         let sender = self.vm().msg_sender();
+        let pending_authority = self.config.pending_authority.get();
 
-        if sender != self.config.publisher.get() {
+        if sender != pending_authority || pending_authority == Address::ZERO {
             return Err(AccessControlError::Unauthorized(Unauthorized {}));
         }
 
+        let previous_authority = self.config.authority.get();
+        self.config.authority.set(pending_authority);
+        self.config.pending_authority.set(Address::ZERO);
+
+        log(
+            self.vm(),
+            AuthorityTransferred {
+                previous_authority,
+                new_authority: pending_authority,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Hashes `(base, quote, block_timestamp)` into a new pending proposal and records the
+    /// caller's own approval as its first vote. Returns the proposal hash callers pass to
+    /// [`AccessControl::approve_price`].
+    ///
+    /// Re-proposing the same `(base, quote)` within the same block hashes to the same proposal,
+    /// so this only seeds the proposal the first time it's seen - it never overwrites an already
+    /// pending proposal, which would otherwise reset `approvals` and discard every other signer's
+    /// recorded vote.
+    pub fn propose_price(&mut self, base: U256, quote: U256) -> Result<B256, AccessControlError> {
+        let sender = self.vm().msg_sender();
+        let signer_index = self.require_signer(sender)?;
+
         let timestamp = self.vm().block_timestamp();
+        let proposal_hash = Self::hash_proposal(base, quote, timestamp);
+
+        let mut proposal = self.proposals.setter(proposal_hash);
+        if !proposal.timestamp.get().is_zero() {
+            return Ok(proposal_hash);
+        }
+
+        proposal.base.set(base);
+        proposal.quote.set(quote);
+        proposal.timestamp.set(U64::from(timestamp));
+        proposal.approvals.set(U256::from(1u8) << signer_index);
+
+        log(
+            self.vm(),
+            PriceProposed {
+                proposal_hash,
+                base,
+                quote,
+            },
+        );
+
+        Ok(proposal_hash)
+    }
+
+    /// Adds the caller's approval to a pending proposal. Once `m` distinct signers have approved,
+    /// the proposal is atomically committed into `last_price`, `price_nonce` is bumped, and the
+    /// proposal's approval state is cleared so its hash can't be replayed into a second commit.
+    pub fn approve_price(&mut self, proposal_hash: B256) -> Result<(), AccessControlError> {
+        let sender = self.vm().msg_sender();
+        let signer_index = self.require_signer(sender)?;
 
-        self.last_price.base.set(base);
-        self.last_price.quote.set(quote);
-        self.last_price.timestamp.set(U64::from(timestamp));
+        let mut proposal = self.proposals.setter(proposal_hash);
+        if proposal.timestamp.get().is_zero() {
+            return Err(AccessControlError::ProposalNotFound(ProposalNotFound {}));
+        }
+
+        let bit = U256::from(1u8) << signer_index;
+        let approvals = proposal.approvals.get();
+        if approvals & bit != U256::ZERO {
+            return Err(AccessControlError::AlreadyApproved(AlreadyApproved {}));
+        }
+        let approvals = approvals | bit;
+        proposal.approvals.set(approvals);
+
+        if approvals.count_ones() >= self.multisig.m.get().to::<usize>() {
+            let base = proposal.base.get();
+            let quote = proposal.quote.get();
+            let timestamp = proposal.timestamp.get();
+
+            proposal.approvals.set(U256::ZERO);
+            proposal.timestamp.set(U64::ZERO);
+
+            self.last_price.base.set(base);
+            self.last_price.quote.set(quote);
+            self.last_price.timestamp.set(timestamp);
+
+            let nonce = self.price_nonce.get() + U64::from(1);
+            self.price_nonce.set(nonce);
+
+            log(
+                self.vm(),
+                PriceCommitted {
+                    proposal_hash,
+                    base,
+                    quote,
+                    nonce,
+                },
+            );
+        }
 
         Ok(())
     }
@@ -77,8 +310,16 @@ impl AccessControl {
         self.config.authority.get()
     }
 
-    pub fn get_publisher(&self) -> Address {
-        self.config.publisher.get()
+    pub fn get_pending_authority(&self) -> Address {
+        self.config.pending_authority.get()
+    }
+
+    pub fn get_signer_count(&self) -> U8 {
+        U8::from(self.multisig.signers.len() as u8)
+    }
+
+    pub fn get_threshold(&self) -> U8 {
+        self.multisig.m.get()
     }
 
     pub fn get_last_price(&self) -> (U256, U256, U64) {
@@ -88,6 +329,20 @@ impl AccessControl {
             self.last_price.timestamp.get(),
         )
     }
+
+    pub fn get_price_nonce(&self) -> U64 {
+        self.price_nonce.get()
+    }
+
+    pub fn get_proposal_approval_count(&self, proposal_hash: B256) -> U8 {
+        U8::from(
+            self.proposals
+                .getter(proposal_hash)
+                .approvals
+                .get()
+                .count_ones() as u8,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -97,25 +352,41 @@ mod test {
     use stylus_sdk::{alloy_primitives::address, testing::*};
 
     static AUTHORITY_ADDRESS: Address = address!("0x1111111111111111111111111111111111111111");
-    static FIRST_PUBLISHER_ADDRESS: Address =
-        address!("0x2222222222222222222222222222222222222222");
-    static SECOND_PUBLISHER_ADDRESS: Address =
-        address!("0x3333333333333333333333333333333333333333");
+    static FIRST_SIGNER_ADDRESS: Address = address!("0x2222222222222222222222222222222222222222");
+    static SECOND_SIGNER_ADDRESS: Address = address!("0x3333333333333333333333333333333333333333");
+    static THIRD_SIGNER_ADDRESS: Address = address!("0x4444444444444444444444444444444444444444");
+
+    fn signers() -> Vec<Address> {
+        vec![
+            FIRST_SIGNER_ADDRESS,
+            SECOND_SIGNER_ADDRESS,
+            THIRD_SIGNER_ADDRESS,
+        ]
+    }
 
     #[test]
-    fn test_access_control_flow() {
+    fn test_multisig_publish_flow() {
         let vm = TestVM::default();
         let mut contract = AccessControl::from(&vm);
 
-        contract.constructor(AUTHORITY_ADDRESS, FIRST_PUBLISHER_ADDRESS);
-        assert_eq!(contract.get_authority(), AUTHORITY_ADDRESS);
-        assert_eq!(contract.get_publisher(), FIRST_PUBLISHER_ADDRESS);
-
-        vm.set_sender(FIRST_PUBLISHER_ADDRESS);
-        vm.set_block_timestamp(1600000000);
         assert!(contract
-            .publish_price(U256::from(1_000_000), U256::from(1_000_000))
+            .constructor(AUTHORITY_ADDRESS, signers(), U8::from(2))
             .is_ok());
+        assert_eq!(contract.get_signer_count(), U8::from(3));
+        assert_eq!(contract.get_threshold(), U8::from(2));
+
+        vm.set_sender(FIRST_SIGNER_ADDRESS);
+        vm.set_block_timestamp(1600000000);
+        let proposal_hash = contract
+            .propose_price(U256::from(1_000_000), U256::from(1_000_000))
+            .unwrap();
+        assert_eq!(contract.get_proposal_approval_count(proposal_hash), U8::from(1));
+
+        // A single approval is below the threshold of 2 - no commit yet.
+        assert_eq!(contract.get_last_price(), (U256::ZERO, U256::ZERO, U64::ZERO));
+
+        vm.set_sender(SECOND_SIGNER_ADDRESS);
+        assert!(contract.approve_price(proposal_hash).is_ok());
 
         assert_eq!(
             contract.get_last_price(),
@@ -125,30 +396,165 @@ mod test {
                 U64::from(1600000000)
             )
         );
+        assert_eq!(contract.get_price_nonce(), U64::from(1));
+    }
+
+    #[test]
+    fn test_propose_price_rejects_non_signer() {
+        let vm = TestVM::default();
+        let mut contract = AccessControl::from(&vm);
+        contract
+            .constructor(AUTHORITY_ADDRESS, signers(), U8::from(2))
+            .unwrap();
 
         vm.set_sender(AUTHORITY_ADDRESS);
-        assert!(contract.update_config(SECOND_PUBLISHER_ADDRESS).is_ok());
-        assert_eq!(contract.get_publisher(), SECOND_PUBLISHER_ADDRESS);
+        assert_eq!(
+            contract.propose_price(U256::from(1), U256::from(1)),
+            Err(AccessControlError::Unauthorized(Unauthorized {}))
+        );
+    }
+
+    #[test]
+    fn test_repropose_preserves_existing_approvals() {
+        let vm = TestVM::default();
+        let mut contract = AccessControl::from(&vm);
+        // Threshold 3, so two approvals leave the proposal pending instead of committing it.
+        contract
+            .constructor(AUTHORITY_ADDRESS, signers(), U8::from(3))
+            .unwrap();
+
+        vm.set_sender(FIRST_SIGNER_ADDRESS);
+        let proposal_hash = contract
+            .propose_price(U256::from(1_000_000), U256::from(1_000_000))
+            .unwrap();
+        assert_eq!(contract.get_proposal_approval_count(proposal_hash), U8::from(1));
+
+        vm.set_sender(SECOND_SIGNER_ADDRESS);
+        assert!(contract.approve_price(proposal_hash).is_ok());
+        assert_eq!(contract.get_proposal_approval_count(proposal_hash), U8::from(2));
+
+        // Re-proposing the same (base, quote) within the same block hashes to the same proposal -
+        // it must not reset the approvals the other signer already recorded.
+        vm.set_sender(THIRD_SIGNER_ADDRESS);
+        let same_hash = contract
+            .propose_price(U256::from(1_000_000), U256::from(1_000_000))
+            .unwrap();
+        assert_eq!(same_hash, proposal_hash);
+        assert_eq!(contract.get_proposal_approval_count(proposal_hash), U8::from(2));
+    }
+
+    #[test]
+    fn test_approve_price_rejects_double_approval() {
+        let vm = TestVM::default();
+        let mut contract = AccessControl::from(&vm);
+        contract
+            .constructor(AUTHORITY_ADDRESS, signers(), U8::from(2))
+            .unwrap();
+
+        vm.set_sender(FIRST_SIGNER_ADDRESS);
+        let proposal_hash = contract
+            .propose_price(U256::from(1), U256::from(1))
+            .unwrap();
+
+        assert_eq!(
+            contract.approve_price(proposal_hash),
+            Err(AccessControlError::AlreadyApproved(AlreadyApproved {}))
+        );
+    }
+
+    #[test]
+    fn test_approve_price_rejects_unknown_proposal() {
+        let vm = TestVM::default();
+        let mut contract = AccessControl::from(&vm);
+        contract
+            .constructor(AUTHORITY_ADDRESS, signers(), U8::from(2))
+            .unwrap();
+
+        vm.set_sender(FIRST_SIGNER_ADDRESS);
+        assert_eq!(
+            contract.approve_price(B256::ZERO),
+            Err(AccessControlError::ProposalNotFound(ProposalNotFound {}))
+        );
+    }
 
-        vm.set_sender(FIRST_PUBLISHER_ADDRESS);
+    #[test]
+    fn test_update_config_rejects_non_authority() {
+        let vm = TestVM::default();
+        let mut contract = AccessControl::from(&vm);
+        contract
+            .constructor(AUTHORITY_ADDRESS, signers(), U8::from(2))
+            .unwrap();
+
+        vm.set_sender(FIRST_SIGNER_ADDRESS);
         assert_eq!(
-            contract.publish_price(U256::from(2_000_000), U256::from(2_000_000)),
+            contract.update_config(signers(), U8::from(1)),
             Err(AccessControlError::Unauthorized(Unauthorized {}))
         );
+    }
+
+    #[test]
+    fn test_constructor_rejects_invalid_threshold() {
+        let vm = TestVM::default();
+        let mut contract = AccessControl::from(&vm);
 
-        // Test second publisher can now publish price successfully
-        vm.set_sender(SECOND_PUBLISHER_ADDRESS);
-        vm.set_block_timestamp(1700000000);
-        assert!(contract
-            .publish_price(U256::from(1_000_000), U256::from(2_000_000))
-            .is_ok());
         assert_eq!(
-            contract.get_last_price(),
-            (
-                U256::from(1_000_000),
-                U256::from(2_000_000),
-                U64::from(1700000000)
-            )
+            contract.constructor(AUTHORITY_ADDRESS, signers(), U8::from(4)),
+            Err(AccessControlError::InvalidMultisigConfig(
+                InvalidMultisigConfig {}
+            ))
+        );
+    }
+
+    #[test]
+    fn test_constructor_rejects_duplicate_signers() {
+        let vm = TestVM::default();
+        let mut contract = AccessControl::from(&vm);
+
+        assert_eq!(
+            contract.constructor(
+                AUTHORITY_ADDRESS,
+                vec![FIRST_SIGNER_ADDRESS, FIRST_SIGNER_ADDRESS],
+                U8::from(1)
+            ),
+            Err(AccessControlError::InvalidMultisigConfig(
+                InvalidMultisigConfig {}
+            ))
+        );
+    }
+
+    #[test]
+    fn test_transfer_then_accept_authority() {
+        let vm = TestVM::default();
+        let mut contract = AccessControl::from(&vm);
+        contract
+            .constructor(AUTHORITY_ADDRESS, signers(), U8::from(2))
+            .unwrap();
+
+        vm.set_sender(AUTHORITY_ADDRESS);
+        assert!(contract.transfer_authority(FIRST_SIGNER_ADDRESS).is_ok());
+        assert_eq!(contract.get_pending_authority(), FIRST_SIGNER_ADDRESS);
+
+        vm.set_sender(FIRST_SIGNER_ADDRESS);
+        assert!(contract.accept_authority().is_ok());
+        assert_eq!(contract.get_authority(), FIRST_SIGNER_ADDRESS);
+        assert_eq!(contract.get_pending_authority(), Address::ZERO);
+    }
+
+    #[test]
+    fn test_accept_authority_rejects_non_pending_caller() {
+        let vm = TestVM::default();
+        let mut contract = AccessControl::from(&vm);
+        contract
+            .constructor(AUTHORITY_ADDRESS, signers(), U8::from(2))
+            .unwrap();
+
+        vm.set_sender(AUTHORITY_ADDRESS);
+        contract.transfer_authority(FIRST_SIGNER_ADDRESS).unwrap();
+
+        vm.set_sender(SECOND_SIGNER_ADDRESS);
+        assert_eq!(
+            contract.accept_authority(),
+            Err(AccessControlError::Unauthorized(Unauthorized {}))
         );
     }
 }
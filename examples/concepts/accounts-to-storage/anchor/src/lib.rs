@@ -4,54 +4,121 @@ use anchor_lang::prelude::*;
 
 declare_id!("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
 
-#[derive(InitSpace)]
+pub static RECORD_PDA_SEED: &[u8] = b"record";
+pub const RECORD_VERSION: u8 = 1;
+
 #[account]
-pub struct Data {
-    pub bool: bool,
-    pub uint8: u8,
-    pub uint16: u16,
-    pub uint32: u32,
-    pub uint64: u64,
-    pub uint128: u128,
-    pub int8: i8,
-    pub int16: i16,
-    pub int32: i32,
-    pub int64: i64,
-    pub int128: i128,
-    #[max_len(200)]
-    pub string: String,
-    #[max_len(200)]
-    pub bytes: Vec<u8>,
-    pub address: Pubkey,
+pub struct Record {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub data: Vec<u8>,
+}
+
+impl Record {
+    /// Discriminator + `version` + `authority` + the 4-byte length prefix Anchor's
+    /// Borsh encoding puts ahead of `data`'s contents.
+    const BASE_SPACE: usize = 8 + 1 + 32 + 4;
 }
 
+#[error_code]
+pub enum RecordError {
+    #[msg("signer does not match the record's authority")]
+    AuthorityMismatch,
+}
+
+// Deterministic addressing is expressed the way every other PDA in this repo is -
+// `seeds`/`bump` - rather than the raw `create_with_seed` syscall the native program
+// reaches for; an empty `seed` still yields a stable, payer-scoped address.
 #[derive(Accounts)]
-#[instruction(data: Data)]
+#[instruction(authority: Pubkey, seed: String)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
         init,
         payer = payer,
-        space = 8 + Data::INIT_SPACE
+        space = Record::BASE_SPACE,
+        seeds = [RECORD_PDA_SEED, payer.key().as_ref(), seed.as_bytes()],
+        bump,
+    )]
+    pub record: Account<'info, Record>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(offset: u64, data: Vec<u8>)]
+pub struct Write<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = authority @ RecordError::AuthorityMismatch,
+        realloc = Record::BASE_SPACE + usize::try_from(offset).unwrap() + data.len(),
+        realloc::payer = payer,
+        realloc::zero = false,
     )]
-    pub data_account: Account<'info, Data>,
+    pub record: Account<'info, Record>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct SetAuthority<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut, has_one = authority @ RecordError::AuthorityMismatch)]
+    pub record: Account<'info, Record>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAccount<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut, has_one = authority @ RecordError::AuthorityMismatch, close = recipient)]
+    pub record: Account<'info, Record>,
+    /// CHECK: only ever credited with the closed record's lamports.
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
 #[program]
 pub mod data_storage {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, data: Data) -> Result<()> {
-        *ctx.accounts.data_account = data;
+    pub fn initialize(ctx: Context<Initialize>, authority: Pubkey, _seed: String) -> Result<()> {
+        ctx.accounts.record.version = RECORD_VERSION;
+        ctx.accounts.record.authority = authority;
+        ctx.accounts.record.data = Vec::new();
+        Ok(())
+    }
+
+    pub fn write(ctx: Context<Write>, offset: u64, data: Vec<u8>) -> Result<()> {
+        let offset = usize::try_from(offset).unwrap();
+        let end = offset + data.len();
+
+        let record_data = &mut ctx.accounts.record.data;
+        if record_data.len() < end {
+            record_data.resize(end, 0);
+        }
+        record_data[offset..end].copy_from_slice(&data);
+
+        Ok(())
+    }
+
+    pub fn set_authority(ctx: Context<SetAuthority>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.record.authority = new_authority;
+        Ok(())
+    }
+
+    pub fn close_account(_ctx: Context<CloseAccount>) -> Result<()> {
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{instruction::Initialize, Data, ID as PROGRAM_ID};
+    use super::{
+        instruction::{Initialize, Write},
+        ID as PROGRAM_ID, RECORD_PDA_SEED, RECORD_VERSION,
+    };
 
     use anchor_lang::{
         prelude::AccountMeta, solana_program::instruction::Instruction, InstructionData,
@@ -65,57 +132,73 @@ mod test {
     fn test_program() {
         let mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
 
-        let data_key = Pubkey::new_unique();
-
-        let initial_values: Data = Data {
-            bool: true,
-            uint8: u8::MAX,
-            uint16: u16::MAX,
-            uint32: u32::MAX,
-            uint64: u64::MAX,
-            uint128: u128::MAX,
-            int8: i8::MIN,
-            int16: i16::MIN,
-            int32: i32::MIN,
-            int64: i64::MIN,
-            int128: i128::MIN,
-            string: "StylusPort::Solana".to_owned(),
-            bytes: b"StylusPort::Solana".to_vec(),
-            address: data_key,
-        };
-
-        let init_instruction_data = Initialize {
-            data: initial_values,
-        }
-        .data();
-
         let payer_key = Pubkey::new_unique();
         let payer_lamports = 100_000_000;
         let payer_account = Account::new(payer_lamports, 0, &system_program::id());
 
+        let seed = String::new();
+        let (record_key, _bump) = Pubkey::find_program_address(
+            &[RECORD_PDA_SEED, payer_key.as_ref(), seed.as_bytes()],
+            &PROGRAM_ID,
+        );
+
+        let initialize_instruction_data = Initialize {
+            authority: payer_key,
+            seed: seed.clone(),
+        }
+        .data();
+
         let initialize_instruction = Instruction::new_with_bytes(
             PROGRAM_ID,
-            &init_instruction_data,
+            &initialize_instruction_data,
+            vec![
+                AccountMeta::new(payer_key, true),
+                AccountMeta::new(record_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let payload = b"StylusPort::Solana".to_vec();
+        let write_instruction_data = Write {
+            offset: 0,
+            data: payload.clone(),
+        }
+        .data();
+
+        let write_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &write_instruction_data,
             vec![
                 AccountMeta::new(payer_key, true),
-                AccountMeta::new(data_key, true),
+                AccountMeta::new_readonly(payer_key, true),
+                AccountMeta::new(record_key, false),
                 AccountMeta::new_readonly(system_program::id(), false),
             ],
         );
 
-        mollusk.process_and_validate_instruction(
-            &initialize_instruction,
+        let mut expected_record_data = vec![RECORD_VERSION];
+        expected_record_data.extend_from_slice(payer_key.as_ref());
+        expected_record_data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        expected_record_data.extend_from_slice(&payload);
+
+        mollusk.process_and_validate_instruction_chain(
             &[
-                (payer_key, payer_account),
-                (data_key, Account::default()),
-                keyed_account_for_system_program(),
+                (&initialize_instruction, &[Check::success()]),
+                (
+                    &write_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&record_key)
+                            .data_slice(8, &expected_record_data)
+                            .owner(&PROGRAM_ID)
+                            .build(),
+                    ],
+                ),
             ],
             &[
-                Check::success(),
-                Check::account(&data_key)
-                    .data_slice(8, &init_instruction_data[8..])
-                    .owner(&PROGRAM_ID)
-                    .build(),
+                (payer_key, payer_account),
+                (record_key, Account::default()),
+                keyed_account_for_system_program(),
             ],
         );
     }
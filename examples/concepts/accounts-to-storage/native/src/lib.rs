@@ -2,29 +2,59 @@
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, declare_id, entrypoint, entrypoint::ProgramResult, program::invoke,
-    program_error::ProgramError, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
+    account_info::{next_account_info, AccountInfo},
+    declare_id, entrypoint,
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
 };
 use solana_system_interface::instruction as system_instruction;
 
 declare_id!("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
-pub struct Data {
-    pub bool: bool,
-    pub uint8: u8,
-    pub uint16: u16,
-    pub uint32: u32,
-    pub uint64: u64,
-    pub uint128: u128,
-    pub int8: i8,
-    pub int16: i16,
-    pub int32: i32,
-    pub int64: i64,
-    pub int128: i128,
-    pub string: String,
-    pub bytes: Vec<u8>,
-    pub address: Pubkey,
+/// Version byte + 32-byte authority pubkey prepended to every record's payload,
+/// mirroring the SPL Record program's account layout.
+pub const HEADER_LEN: usize = 1 + 32;
+pub const RECORD_VERSION: u8 = 1;
+
+#[derive(Clone, Copy, Debug, thiserror::Error)]
+pub enum RecordError {
+    #[error("record account has not been initialized")]
+    Uninitialized,
+    #[error("record account version is not supported")]
+    UnsupportedVersion,
+    #[error("signer does not match the record's authority")]
+    AuthorityMismatch,
+}
+
+impl From<RecordError> for ProgramError {
+    fn from(error: RecordError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub enum Instruction {
+    /// Creates and initializes a record account, stamping its header with
+    /// `authority`. If `seed` is set, the account is derived and funded via
+    /// `create_account_with_seed` off `base_account` so clients can address it
+    /// deterministically instead of generating a fresh keypair.
+    Initialize {
+        authority: Pubkey,
+        seed: Option<String>,
+    },
+    /// Copies `data` into the record's payload at `offset`, after checking the
+    /// signer matches the stored authority. Reallocs the account (topping up or
+    /// refunding rent via a system-program transfer) to fit exactly
+    /// `HEADER_LEN + offset + data.len()`, so a write also resizes the record.
+    Write { offset: u64, data: Vec<u8> },
+    /// Transfers authority over the record to `new_authority`.
+    SetAuthority { new_authority: Pubkey },
+    /// Drains all lamports to `recipient` and zeroes the account's data.
+    CloseAccount,
 }
 
 pub fn process_instruction(
@@ -36,43 +66,234 @@ pub fn process_instruction(
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    if Data::try_from_slice(instruction_data).is_err() {
-        return Err(ProgramError::InvalidInstructionData);
-    };
+    let instruction = Instruction::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    match instruction {
+        Instruction::Initialize { authority, seed } => {
+            process_initialize(program_id, accounts, authority, seed)
+        }
+        Instruction::Write { offset, data } => process_write(accounts, offset, &data),
+        Instruction::SetAuthority { new_authority } => {
+            process_set_authority(accounts, new_authority)
+        }
+        Instruction::CloseAccount => process_close_account(accounts),
+    }
+}
+
+/// Reads/writes just the header prefix of a record account, leaving the rest of
+/// `account.data` to `Write`'s arbitrary-offset payload.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy)]
+struct RecordHeader {
+    version: u8,
+    authority: Pubkey,
+}
+
+impl RecordHeader {
+    fn read(account: &AccountInfo) -> Result<Self, ProgramError> {
+        let data = account.data.borrow();
+
+        if data.len() < HEADER_LEN {
+            return Err(RecordError::Uninitialized.into());
+        }
+
+        let header = Self::try_from_slice(&data[..HEADER_LEN])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if header.version != RECORD_VERSION {
+            return Err(RecordError::UnsupportedVersion.into());
+        }
+
+        Ok(header)
+    }
+
+    fn write(&self, account: &AccountInfo) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        let encoded = borsh::to_vec(self).map_err(|_| ProgramError::InvalidAccountData)?;
+        data[..encoded.len()].copy_from_slice(&encoded);
+        Ok(())
+    }
+}
+
+/// Checks `signer_account` is a signer and matches the record's stored authority.
+fn verify_authority(header: &RecordHeader, signer_account: &AccountInfo) -> ProgramResult {
+    if !signer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if header.authority != *signer_account.key {
+        return Err(RecordError::AuthorityMismatch.into());
+    }
 
-    let [payer, data_account, system_program] = accounts else {
-        return Err(ProgramError::InvalidAccountData);
+    Ok(())
+}
+
+fn process_initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    authority: Pubkey,
+    seed: Option<String>,
+) -> ProgramResult {
+    let [payer_account, base_account, record_account, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
     };
 
-    let lamports_required = Rent::get()?.minimum_balance(instruction_data.len());
+    if !payer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let lamports_required = Rent::get()?.minimum_balance(HEADER_LEN);
+
+    match seed {
+        Some(seed) => {
+            if !base_account.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            let expected_record_key =
+                Pubkey::create_with_seed(base_account.key, &seed, program_id)?;
+
+            if expected_record_key != *record_account.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+
+            invoke(
+                &system_instruction::create_account_with_seed(
+                    payer_account.key,
+                    record_account.key,
+                    base_account.key,
+                    &seed,
+                    lamports_required,
+                    HEADER_LEN as u64,
+                    program_id,
+                ),
+                &[
+                    payer_account.clone(),
+                    record_account.clone(),
+                    base_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+        None => {
+            invoke(
+                &system_instruction::create_account(
+                    payer_account.key,
+                    record_account.key,
+                    lamports_required,
+                    HEADER_LEN as u64,
+                    program_id,
+                ),
+                &[
+                    payer_account.clone(),
+                    record_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+    }
+
+    RecordHeader {
+        version: RECORD_VERSION,
+        authority,
+    }
+    .write(record_account)
+}
+
+fn process_write(accounts: &[AccountInfo], offset: u64, data: &[u8]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let record_account = next_account_info(account_info_iter)?;
+    let signer_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let header = RecordHeader::read(record_account)?;
+    verify_authority(&header, signer_account)?;
 
-    invoke(
-        &system_instruction::create_account(
-            payer.key,
-            data_account.key,
-            lamports_required,
-            instruction_data.len() as u64,
-            program_id,
-        ),
-        &[payer.clone(), data_account.clone(), system_program.clone()],
-    )?;
+    let offset = usize::try_from(offset).map_err(|_| ProgramError::ArithmeticOverflow)?;
+    let new_len = HEADER_LEN
+        .checked_add(offset)
+        .and_then(|end| end.checked_add(data.len()))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    let mut data_account_buffer = data_account.try_borrow_mut_data()?;
+    let current_len = record_account.data_len();
+    let current_lamports = record_account.lamports();
+    let lamports_required = Rent::get()?.minimum_balance(new_len);
+
+    match new_len.cmp(&current_len) {
+        std::cmp::Ordering::Greater => {
+            if lamports_required > current_lamports {
+                invoke(
+                    &system_instruction::transfer(
+                        payer_account.key,
+                        record_account.key,
+                        lamports_required - current_lamports,
+                    ),
+                    &[
+                        payer_account.clone(),
+                        record_account.clone(),
+                        system_program.clone(),
+                    ],
+                )?;
+            }
+
+            record_account.realloc(new_len, true)?;
+        }
+        std::cmp::Ordering::Less => {
+            record_account.realloc(new_len, false)?;
+
+            if current_lamports > lamports_required {
+                let refund = current_lamports - lamports_required;
+
+                **record_account.try_borrow_mut_lamports()? -= refund;
+                **payer_account.try_borrow_mut_lamports()? += refund;
+            }
+        }
+        std::cmp::Ordering::Equal => {}
+    }
 
-    data_account_buffer.copy_from_slice(instruction_data);
+    let mut record_data = record_account.try_borrow_mut_data()?;
+    record_data[HEADER_LEN + offset..new_len].copy_from_slice(data);
 
     Ok(())
 }
 
+fn process_set_authority(accounts: &[AccountInfo], new_authority: Pubkey) -> ProgramResult {
+    let [record_account, signer_account] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let mut header = RecordHeader::read(record_account)?;
+    verify_authority(&header, signer_account)?;
+
+    header.authority = new_authority;
+    header.write(record_account)
+}
+
+fn process_close_account(accounts: &[AccountInfo]) -> ProgramResult {
+    let [record_account, signer_account, recipient_account] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let header = RecordHeader::read(record_account)?;
+    verify_authority(&header, signer_account)?;
+
+    let lamports = record_account.lamports();
+    **record_account.try_borrow_mut_lamports()? -= lamports;
+    **recipient_account.try_borrow_mut_lamports()? += lamports;
+
+    record_account.realloc(0, false)
+}
+
 entrypoint!(process_instruction);
 
 #[cfg(test)]
 mod test {
-    use super::{Data, ID as PROGRAM_ID};
+    use super::{Instruction, ID as PROGRAM_ID, RECORD_VERSION};
 
     use mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk};
     use solana_account::Account;
-    use solana_program::instruction::{AccountMeta, Instruction};
+    use solana_program::instruction::{AccountMeta, Instruction as SolanaInstruction};
     use solana_pubkey::Pubkey;
     use solana_sdk_ids::system_program;
 
@@ -80,54 +301,72 @@ mod test {
     fn test_program() {
         let mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
 
-        let data_key = Pubkey::new_unique();
-
-        let initial_values: Data = Data {
-            bool: true,
-            uint8: u8::MAX,
-            uint16: u16::MAX,
-            uint32: u32::MAX,
-            uint64: u64::MAX,
-            uint128: u128::MAX,
-            int8: i8::MIN,
-            int16: i16::MIN,
-            int32: i32::MIN,
-            int64: i64::MIN,
-            int128: i128::MIN,
-            string: "StylusPort::Solana".to_owned(),
-            bytes: b"StylusPort::Solana".to_vec(),
-            address: data_key,
-        };
-
-        let init_instruction_data = borsh::to_vec(&initial_values).unwrap();
-
         let payer_key = Pubkey::new_unique();
         let payer_lamports = 100_000_000;
         let payer_account = Account::new(payer_lamports, 0, &system_program::id());
 
-        let initialize_instruction = Instruction::new_with_bytes(
+        let record_key = Pubkey::new_unique();
+
+        let initialize_instruction_data = borsh::to_vec(&Instruction::Initialize {
+            authority: payer_key,
+            seed: None,
+        })
+        .unwrap();
+
+        let initialize_instruction = SolanaInstruction::new_with_bytes(
             PROGRAM_ID,
-            &init_instruction_data,
+            &initialize_instruction_data,
             vec![
                 AccountMeta::new(payer_key, true),
-                AccountMeta::new(data_key, true),
+                AccountMeta::new(payer_key, true),
+                AccountMeta::new(record_key, true),
                 AccountMeta::new_readonly(system_program::id(), false),
             ],
         );
 
-        mollusk.process_and_validate_instruction(
-            &initialize_instruction,
+        let payload = b"StylusPort::Solana".to_vec();
+        let write_instruction_data = borsh::to_vec(&Instruction::Write {
+            offset: 0,
+            data: payload.clone(),
+        })
+        .unwrap();
+
+        let write_instruction = SolanaInstruction::new_with_bytes(
+            PROGRAM_ID,
+            &write_instruction_data,
+            vec![
+                AccountMeta::new(record_key, false),
+                AccountMeta::new_readonly(payer_key, true),
+                AccountMeta::new(payer_key, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let mut expected_record_data = vec![RECORD_VERSION];
+        expected_record_data.extend_from_slice(payer_key.as_ref());
+        expected_record_data.extend_from_slice(&payload);
+
+        mollusk.process_and_validate_instruction_chain(
             &[
-                (payer_key, payer_account),
-                (data_key, Account::default()),
-                keyed_account_for_system_program(),
+                (
+                    &initialize_instruction,
+                    &[Check::success(), Check::account(&record_key).owner(&PROGRAM_ID).build()],
+                ),
+                (
+                    &write_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&record_key)
+                            .data(&expected_record_data)
+                            .owner(&PROGRAM_ID)
+                            .build(),
+                    ],
+                ),
             ],
             &[
-                Check::success(),
-                Check::account(&data_key)
-                    .data(&init_instruction_data)
-                    .owner(&PROGRAM_ID)
-                    .build(),
+                (payer_key, payer_account),
+                (record_key, Account::default()),
+                keyed_account_for_system_program(),
             ],
         );
     }
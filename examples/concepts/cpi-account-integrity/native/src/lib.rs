@@ -0,0 +1,203 @@
+#![allow(unexpected_cfgs)]
+
+use solana_program::{
+    account_info::AccountInfo,
+    declare_id, entrypoint,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction as SolanaInstruction},
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use program_structure_native::{Instruction as CounterInstruction, ID as COUNTER_PROGRAM_ID};
+
+declare_id!("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
+
+/// Extends the error-enum pattern from the errors-events example with the
+/// two invariants Solana's own runtime checks on every account after an
+/// instruction returns: an account's owner may only change if its data was
+/// fully zeroed first, and the total lamports across every account handed
+/// to the instruction must be conserved.
+#[derive(Debug, Clone, Copy)]
+#[repr(u32)]
+pub enum ErrorCode {
+    OwnerChangedWithoutZeroing,
+    LamportsNotConserved,
+}
+
+impl From<ErrorCode> for ProgramError {
+    fn from(value: ErrorCode) -> Self {
+        Self::Custom(value as _)
+    }
+}
+
+/// Owner and lamports captured before a CPI, so `verify_post_cpi_invariants`
+/// can re-check them afterward the same way the runtime's own PreAccount
+/// verification does - this program trusts the callee no more than the
+/// runtime trusts any program.
+struct AccountSnapshot {
+    owner: Pubkey,
+    lamports: u64,
+}
+
+impl AccountSnapshot {
+    fn capture(account: &AccountInfo) -> Self {
+        Self {
+            owner: *account.owner,
+            lamports: account.lamports(),
+        }
+    }
+}
+
+/// Re-checks the runtime's PreAccount invariants on `accounts` against the
+/// `snapshots` captured immediately before the CPI that just ran.
+fn verify_post_cpi_invariants(
+    accounts: &[AccountInfo],
+    snapshots: &[AccountSnapshot],
+) -> ProgramResult {
+    let mut lamports_before: u128 = 0;
+    let mut lamports_after: u128 = 0;
+
+    for (account, snapshot) in accounts.iter().zip(snapshots) {
+        lamports_before += u128::from(snapshot.lamports);
+        lamports_after += u128::from(account.lamports());
+
+        let data_is_zeroed = account.try_borrow_data()?.iter().all(|&byte| byte == 0);
+
+        if *account.owner != snapshot.owner && !data_is_zeroed {
+            return Err(ErrorCode::OwnerChangedWithoutZeroing.into());
+        }
+    }
+
+    if lamports_before != lamports_after {
+        return Err(ErrorCode::LamportsNotConserved.into());
+    }
+
+    Ok(())
+}
+
+/// Drives the counter program's `Increment`, the same CPI `cpi-to-counter`
+/// makes, but snapshots `counter_state` first and manually re-verifies the
+/// runtime's own post-CPI account invariants on it afterward instead of
+/// trusting the callee the way a plain CPI proxy does.
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    if !check_id(program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let [counter_state] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let snapshots = [AccountSnapshot::capture(counter_state)];
+
+    invoke(
+        &SolanaInstruction {
+            program_id: COUNTER_PROGRAM_ID,
+            accounts: vec![AccountMeta::new(*counter_state.key, false)],
+            data: borsh::to_vec(&CounterInstruction::Increment)
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+        },
+        &[counter_state.clone()],
+    )?;
+
+    verify_post_cpi_invariants(accounts, &snapshots)
+}
+
+entrypoint!(process_instruction);
+
+#[cfg(test)]
+mod test {
+    use super::ID as PROGRAM_ID;
+
+    use mollusk_svm::{
+        program::{create_program_account_loader_v3, keyed_account_for_system_program},
+        result::Check,
+        Mollusk,
+    };
+    use program_structure_native::{
+        CounterState, Instruction as CounterInstruction, ID as COUNTER_PROGRAM_ID,
+    };
+    use solana_account::Account;
+    use solana_program::instruction::{AccountMeta, Instruction as SolanaInstruction};
+    use solana_pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    static STATE_PDA_SEED: &[u8] = b"state";
+
+    #[test]
+    fn test_program() {
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+
+        mollusk.add_program(
+            &COUNTER_PROGRAM_ID,
+            program_structure_native::PROGRAM_NAME,
+            &mollusk_svm::program::loader_keys::LOADER_V3,
+        );
+
+        let authority_key = Pubkey::new_unique();
+        let authority_account = Account::new(100_000_000, 0, &system_program::id());
+
+        let (counter_state_key, _) =
+            Pubkey::find_program_address(&[STATE_PDA_SEED], &COUNTER_PROGRAM_ID);
+
+        let initial_value = 41u64;
+        let initialize_instruction_data = borsh::to_vec(&CounterInstruction::Initialize {
+            value: initial_value,
+        })
+        .unwrap();
+
+        let initialize_instruction = SolanaInstruction::new_with_bytes(
+            COUNTER_PROGRAM_ID,
+            &initialize_instruction_data,
+            vec![
+                AccountMeta::new(counter_state_key, false),
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let drive_increment_instruction =
+            SolanaInstruction::new_with_bytes(PROGRAM_ID, &[], vec![AccountMeta::new(counter_state_key, false)]);
+
+        let expected_counter_data_post_increment = borsh::to_vec(&CounterState {
+            value: initial_value + 1,
+            authority: authority_key,
+        })
+        .unwrap();
+
+        mollusk.process_and_validate_instruction_chain(
+            &[
+                (&initialize_instruction, &[Check::success()]),
+                (
+                    &drive_increment_instruction,
+                    &[
+                        // Not just that the CPI succeeded, but that this
+                        // program's own post-CPI re-verification accepted
+                        // the callee's changes as consistent with the
+                        // runtime's PreAccount invariants.
+                        Check::success(),
+                        Check::account(&counter_state_key)
+                            .data(&expected_counter_data_post_increment)
+                            .owner(&COUNTER_PROGRAM_ID)
+                            .build(),
+                    ],
+                ),
+            ],
+            &[
+                (counter_state_key, Account::default()),
+                (authority_key, authority_account),
+                keyed_account_for_system_program(),
+                (
+                    COUNTER_PROGRAM_ID,
+                    create_program_account_loader_v3(&COUNTER_PROGRAM_ID),
+                ),
+            ],
+        );
+    }
+}
@@ -0,0 +1,175 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction as SolanaInstruction, program::invoke},
+};
+
+use program_structure_native::{Instruction as CounterInstruction, ID as COUNTER_PROGRAM_ID};
+
+declare_id!("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
+
+/// Builds the downstream CPI call into the (native) counter program, forwarding
+/// `accounts` as its own `AccountMeta`s unchanged - whatever signer/writable
+/// privileges the caller granted these `AccountInfo`s pass straight through to
+/// `invoke`. This is the same native `Instruction` enum and program ID the
+/// `cpi-to-counter` native proxy targets, so both variants drive the identical
+/// downstream instruction.
+fn build_counter_cpi(
+    counter_instruction: &CounterInstruction,
+    accounts: &[AccountInfo],
+) -> Result<SolanaInstruction> {
+    let account_metas = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: *account.key,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    Ok(SolanaInstruction {
+        program_id: COUNTER_PROGRAM_ID,
+        accounts: account_metas,
+        data: anchor_lang::prelude::borsh::to_vec(counter_instruction)
+            .map_err(|_| ProgramError::InvalidAccountData)?,
+    })
+}
+
+#[derive(Accounts)]
+pub struct DriveIncrement<'info> {
+    /// CHECK: forwarded to the counter program's `Increment`, which validates it.
+    #[account(mut)]
+    pub counter_state: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DriveSetValue<'info> {
+    /// CHECK: forwarded to the counter program's `SetValue`, which validates it.
+    #[account(mut)]
+    pub counter_state: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[program]
+pub mod cpi_to_counter {
+    use super::*;
+
+    /// Forwards to the counter program's `Increment`, which only needs its state PDA.
+    pub fn drive_increment(ctx: Context<DriveIncrement>) -> Result<()> {
+        let accounts = [ctx.accounts.counter_state.to_account_info()];
+        let instruction = build_counter_cpi(&CounterInstruction::Increment, &accounts)?;
+
+        invoke(&instruction, &accounts)?;
+        Ok(())
+    }
+
+    /// Forwards to the counter program's `SetValue`, preserving the caller-supplied
+    /// authority's signer status so the counter's own authority check still applies.
+    pub fn drive_set_value(ctx: Context<DriveSetValue>, new_value: u64) -> Result<()> {
+        let accounts = [
+            ctx.accounts.counter_state.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+        ];
+        let instruction =
+            build_counter_cpi(&CounterInstruction::SetValue { new_value }, &accounts)?;
+
+        invoke(&instruction, &accounts)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{instruction::DriveIncrement, ID as PROGRAM_ID};
+
+    use anchor_lang::{
+        prelude::AccountMeta, solana_program::instruction::Instruction, InstructionData,
+    };
+    use mollusk_svm::{
+        program::{create_program_account_loader_v3, keyed_account_for_system_program},
+        result::Check,
+        Mollusk,
+    };
+    use program_structure_native::{
+        CounterState, Instruction as CounterInstruction, ID as COUNTER_PROGRAM_ID,
+    };
+    use solana_account::Account;
+    use solana_pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    static STATE_PDA_SEED: &[u8] = b"state";
+
+    #[test]
+    fn test_program() {
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+
+        mollusk.add_program(
+            &COUNTER_PROGRAM_ID,
+            program_structure_native::PROGRAM_NAME,
+            &mollusk_svm::program::loader_keys::LOADER_V3,
+        );
+
+        let authority_key = Pubkey::new_unique();
+        let authority_lamports = 100_000_000;
+        let authority_account = Account::new(authority_lamports, 0, &system_program::id());
+
+        let (counter_state_key, _) =
+            Pubkey::find_program_address(&[STATE_PDA_SEED], &COUNTER_PROGRAM_ID);
+
+        let initial_value = 41u64;
+        let initialize_instruction_data = borsh::to_vec(&CounterInstruction::Initialize {
+            value: initial_value,
+        })
+        .unwrap();
+
+        let initialize_instruction = Instruction::new_with_bytes(
+            COUNTER_PROGRAM_ID,
+            &initialize_instruction_data,
+            vec![
+                AccountMeta::new(counter_state_key, false),
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let drive_increment_instruction_data = DriveIncrement {}.data();
+
+        let drive_increment_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &drive_increment_instruction_data,
+            vec![AccountMeta::new(counter_state_key, false)],
+        );
+
+        let expected_counter_data_post_increment = borsh::to_vec(&CounterState {
+            value: initial_value + 1,
+            authority: authority_key,
+        })
+        .unwrap();
+
+        mollusk.process_and_validate_instruction_chain(
+            &[
+                (&initialize_instruction, &[Check::success()]),
+                (
+                    &drive_increment_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&counter_state_key)
+                            .data(&expected_counter_data_post_increment)
+                            .owner(&COUNTER_PROGRAM_ID)
+                            .build(),
+                    ],
+                ),
+            ],
+            &[
+                (counter_state_key, Account::default()),
+                (authority_key, authority_account),
+                keyed_account_for_system_program(),
+                (
+                    COUNTER_PROGRAM_ID,
+                    create_program_account_loader_v3(&COUNTER_PROGRAM_ID),
+                ),
+            ],
+        );
+    }
+}
@@ -0,0 +1,185 @@
+#![allow(unexpected_cfgs)]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    declare_id, entrypoint,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction as SolanaInstruction},
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use program_structure_native::{Instruction as CounterInstruction, ID as COUNTER_PROGRAM_ID};
+
+declare_id!("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub enum Instruction {
+    DriveIncrement,
+    DriveSetValue { new_value: u64 },
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if !check_id(program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let Ok(ix) = Instruction::try_from_slice(instruction_data) else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    match ix {
+        Instruction::DriveIncrement => process_drive_increment(accounts),
+        Instruction::DriveSetValue { new_value } => process_drive_set_value(accounts, new_value),
+    }
+}
+
+/// Builds the downstream CPI call into the counter program, forwarding `accounts` as
+/// its own `AccountMeta`s unchanged - whatever signer/writable privileges the caller
+/// granted this proxy's `AccountInfo`s pass straight through to `invoke`. Were the
+/// signer a PDA this program itself controlled rather than a forwarded signer,
+/// `invoke_signed` with that PDA's seeds would replace `invoke` below.
+fn build_counter_cpi(
+    counter_instruction: &CounterInstruction,
+    accounts: &[AccountInfo],
+) -> Result<SolanaInstruction, ProgramError> {
+    let account_metas = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: *account.key,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    Ok(SolanaInstruction {
+        program_id: COUNTER_PROGRAM_ID,
+        accounts: account_metas,
+        data: borsh::to_vec(counter_instruction).map_err(|_| ProgramError::InvalidAccountData)?,
+    })
+}
+
+/// Forwards to the counter program's `Increment`, which only needs its state PDA.
+fn process_drive_increment(accounts: &[AccountInfo]) -> ProgramResult {
+    let [counter_state] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let instruction = build_counter_cpi(&CounterInstruction::Increment, accounts)?;
+
+    invoke(&instruction, &[counter_state.clone()])
+}
+
+/// Forwards to the counter program's `SetValue`, preserving the caller-supplied
+/// authority's signer status so the counter's own authority check still applies.
+fn process_drive_set_value(accounts: &[AccountInfo], new_value: u64) -> ProgramResult {
+    let [counter_state, authority] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let instruction = build_counter_cpi(&CounterInstruction::SetValue { new_value }, accounts)?;
+
+    invoke(&instruction, &[counter_state.clone(), authority.clone()])
+}
+
+entrypoint!(process_instruction);
+
+#[cfg(test)]
+mod test {
+    use super::{Instruction, ID as PROGRAM_ID};
+
+    use mollusk_svm::{
+        program::{create_program_account_loader_v3, keyed_account_for_system_program},
+        result::Check,
+        Mollusk,
+    };
+    use program_structure_native::{
+        CounterState, Instruction as CounterInstruction, ID as COUNTER_PROGRAM_ID,
+    };
+    use solana_account::Account;
+    use solana_program::instruction::{AccountMeta, Instruction as SolanaInstruction};
+    use solana_pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    static STATE_PDA_SEED: &[u8] = b"state";
+
+    #[test]
+    fn test_program() {
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+
+        mollusk.add_program(
+            &COUNTER_PROGRAM_ID,
+            program_structure_native::PROGRAM_NAME,
+            &mollusk_svm::program::loader_keys::LOADER_V3,
+        );
+
+        let authority_key = Pubkey::new_unique();
+        let authority_lamports = 100_000_000;
+        let authority_account = Account::new(authority_lamports, 0, &system_program::id());
+
+        let (counter_state_key, _) =
+            Pubkey::find_program_address(&[STATE_PDA_SEED], &COUNTER_PROGRAM_ID);
+
+        let initial_value = 41u64;
+        let initialize_instruction_data = borsh::to_vec(&CounterInstruction::Initialize {
+            value: initial_value,
+        })
+        .unwrap();
+
+        let initialize_instruction = SolanaInstruction::new_with_bytes(
+            COUNTER_PROGRAM_ID,
+            &initialize_instruction_data,
+            vec![
+                AccountMeta::new(counter_state_key, false),
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let drive_increment_instruction_data =
+            borsh::to_vec(&Instruction::DriveIncrement).unwrap();
+
+        let drive_increment_instruction = SolanaInstruction::new_with_bytes(
+            PROGRAM_ID,
+            &drive_increment_instruction_data,
+            vec![AccountMeta::new(counter_state_key, false)],
+        );
+
+        let expected_counter_data_post_increment = borsh::to_vec(&CounterState {
+            value: initial_value + 1,
+            authority: authority_key,
+        })
+        .unwrap();
+
+        mollusk.process_and_validate_instruction_chain(
+            &[
+                (&initialize_instruction, &[Check::success()]),
+                (
+                    &drive_increment_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&counter_state_key)
+                            .data(&expected_counter_data_post_increment)
+                            .owner(&COUNTER_PROGRAM_ID)
+                            .build(),
+                    ],
+                ),
+            ],
+            &[
+                (counter_state_key, Account::default()),
+                (authority_key, authority_account),
+                keyed_account_for_system_program(),
+                (
+                    COUNTER_PROGRAM_ID,
+                    create_program_account_loader_v3(&COUNTER_PROGRAM_ID),
+                ),
+            ],
+        );
+    }
+}
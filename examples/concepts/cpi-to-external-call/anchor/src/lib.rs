@@ -1,14 +1,13 @@
 #![allow(unexpected_cfgs)]
 
-use ::borsh::BorshDeserialize;
 use anchor_lang::{
     prelude::*,
     solana_program::{
-        instruction::Instruction,
-        program::{get_return_data, invoke},
+        instruction::{AccountMeta, Instruction},
+        program::invoke,
     },
 };
-use cpi_to_external_call_solana_adder::{Args as AdderArgs, Response, ID as ADDER_PROGRAM_ID};
+use cpi_to_external_call_solana_adder::{Args as AdderArgs, COUNTER_LEN, ID as ADDER_PROGRAM_ID};
 
 pub static LAST_RESULT_ACCOUNT_SEED: &[u8] = b"last_result";
 
@@ -20,25 +19,15 @@ pub struct Args {
     pub b: u64,
 }
 
-#[derive(InitSpace)]
-#[account]
-pub struct LastResultAccount {
-    pub last_result: u128,
-}
-
 #[derive(Accounts)]
 #[instruction(data: Args)]
 pub struct Add<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(
-        init_if_needed,
-        payer = payer,
-        space = 8 + LastResultAccount::INIT_SPACE,
-        seeds = [LAST_RESULT_ACCOUNT_SEED],
-        bump,
-    )]
-    pub last_result: Account<'info, LastResultAccount>,
+    /// Owned by the adder program once created, since that's the program
+    /// that writes the counter into it; Anchor can only see it as raw bytes.
+    #[account(mut, seeds = [LAST_RESULT_ACCOUNT_SEED], bump)]
+    pub last_result: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub adder_program: UncheckedAccount<'info>,
 }
@@ -52,6 +41,27 @@ pub mod cpi {
             return Err(ProgramError::InvalidAccountData.into());
         }
 
+        let last_result = &ctx.accounts.last_result;
+
+        if *last_result.owner != ADDER_PROGRAM_ID {
+            let rent = Rent::get()?;
+            let required_lamports = rent.minimum_balance(COUNTER_LEN);
+
+            anchor_lang::system_program::create_account(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::CreateAccount {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: last_result.to_account_info(),
+                    },
+                )
+                .with_signer(&[&[LAST_RESULT_ACCOUNT_SEED, &[ctx.bumps.last_result]]]),
+                required_lamports,
+                COUNTER_LEN as u64,
+                &ADDER_PROGRAM_ID,
+            )?;
+        }
+
         let adder_instruction_data = ::borsh::to_vec(&AdderArgs {
             a: args.a,
             b: args.b,
@@ -61,22 +71,15 @@ pub mod cpi {
         invoke(
             &Instruction {
                 program_id: ADDER_PROGRAM_ID,
-                accounts: vec![],
+                accounts: vec![AccountMeta::new(last_result.key(), false)],
                 data: adder_instruction_data,
             },
-            &[ctx.accounts.adder_program.to_account_info()],
+            &[last_result.to_account_info()],
         )?;
 
-        let (invoked_program, data) = get_return_data().expect("return data is some after invoke");
-
-        assert_eq!(
-            invoked_program, ADDER_PROGRAM_ID,
-            "expected return data from {ADDER_PROGRAM_ID}, received from {invoked_program}"
-        );
-
-        let Response { result } = Response::try_from_slice(&data)?;
-
-        ctx.accounts.last_result.last_result = result;
+        let data = last_result.try_borrow_data()?;
+        let result = u128::from_le_bytes(data[..COUNTER_LEN].try_into().unwrap());
+        msg!("adder wrote back {}", result);
 
         Ok(())
     }
@@ -84,13 +87,10 @@ pub mod cpi {
 
 #[cfg(test)]
 mod test {
-    use super::{
-        instruction::Add, Args, LastResultAccount, ID as PROGRAM_ID, LAST_RESULT_ACCOUNT_SEED,
-    };
+    use super::{instruction::Add, Args, ID as PROGRAM_ID, LAST_RESULT_ACCOUNT_SEED};
 
     use anchor_lang::{
-        prelude::AccountMeta, solana_program::instruction::Instruction, AnchorSerialize,
-        InstructionData,
+        prelude::AccountMeta, solana_program::instruction::Instruction, InstructionData,
     };
     use mollusk_svm::{
         program::{create_program_account_loader_v3, keyed_account_for_system_program},
@@ -134,8 +134,6 @@ mod test {
             ],
         );
 
-        let expected_account_data = LastResultAccount { last_result: 15 }.try_to_vec().unwrap();
-
         mollusk.process_and_validate_instruction(
             &initialize_instruction,
             &[
@@ -150,8 +148,8 @@ mod test {
             &[
                 Check::success(),
                 Check::account(&last_result_pda_account_key)
-                    .data_slice(8, &expected_account_data)
-                    .owner(&PROGRAM_ID)
+                    .data(&15u128.to_le_bytes())
+                    .owner(&cpi_to_external_call_solana_adder::ID)
                     .build(),
             ],
         );
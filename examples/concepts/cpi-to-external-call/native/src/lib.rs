@@ -1,11 +1,13 @@
 #![allow(unexpected_cfgs)]
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::BorshDeserialize;
 use solana_program::{
     account_info::AccountInfo,
     declare_id, entrypoint,
     entrypoint::ProgramResult,
-    program::{get_return_data, invoke, invoke_signed},
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program::{invoke, invoke_signed},
     program_error::ProgramError,
     pubkey::Pubkey,
     rent::Rent,
@@ -13,17 +15,12 @@ use solana_program::{
 };
 use solana_system_interface::instruction as system_instruction;
 
-use cpi_to_external_call_solana_adder::{Args as AdderArgs, Response, ID as ADDER_PROGRAM_ID};
+use cpi_to_external_call_solana_adder::{Args as AdderArgs, COUNTER_LEN, ID as ADDER_PROGRAM_ID};
 
 declare_id!("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
 
 pub static LAST_RESULT_ACCOUNT_SEED: &[u8] = b"last_result";
 
-#[derive(BorshSerialize, BorshDeserialize)]
-pub struct LastResultAccount {
-    pub last_result: u128,
-}
-
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -37,14 +34,29 @@ pub fn process_instruction(
         return Err(ProgramError::InvalidInstructionData);
     };
 
-    let [payer, last_result_account, system_program, adder_program] = accounts else {
-        return Err(ProgramError::InvalidAccountData);
+    // The 5-account shape passes `last_result_account` a second time, as
+    // `last_result_account_alias`, to exercise how Solana dedupes repeated
+    // pubkeys within one instruction's accounts into a single shared
+    // `AccountInfo` buffer rather than two independent copies.
+    let (payer, last_result_accounts, system_program, adder_program) = match accounts {
+        [payer, last_result_account, system_program, adder_program] => {
+            (payer, vec![last_result_account], system_program, adder_program)
+        }
+        [payer, last_result_account, last_result_account_alias, system_program, adder_program] => (
+            payer,
+            vec![last_result_account, last_result_account_alias],
+            system_program,
+            adder_program,
+        ),
+        _ => return Err(ProgramError::InvalidAccountData),
     };
 
     if *adder_program.key != ADDER_PROGRAM_ID {
         return Err(ProgramError::InvalidAccountData);
     }
 
+    let last_result_account = last_result_accounts[0];
+
     // Find the expected PDA and bump
     let (expected_pda, bump) =
         Pubkey::find_program_address(&[LAST_RESULT_ACCOUNT_SEED], program_id);
@@ -54,40 +66,19 @@ pub fn process_instruction(
         return Err(ProgramError::InvalidSeeds);
     }
 
-    invoke(
-        &solana_program::instruction::Instruction {
-            program_id: cpi_to_external_call_solana_adder::ID,
-            accounts: vec![],
-            data: instruction_data.to_owned(),
-        },
-        &[adder_program.clone()],
-    )?;
-
-    let (invoked_program, data) = get_return_data().expect("return data is some after invoke");
-
-    assert_eq!(
-        invoked_program, ADDER_PROGRAM_ID,
-        "expected return data from {ADDER_PROGRAM_ID}, received from {invoked_program}"
-    );
-
-    let Response { result } = Response::try_from_slice(&data)?;
-
-    let last_result_account_data = borsh::to_vec(&LastResultAccount {
-        last_result: result,
-    })?;
-
-    // Check if LastResult PDA Account needs to be created
-    if last_result_account.owner != program_id {
+    // The adder program owns this account once created, since it's the one
+    // that writes the counter into it; this program only funds/creates it.
+    if *last_result_account.owner != ADDER_PROGRAM_ID {
         let rent = Rent::get()?;
-        let required_lamports = rent.minimum_balance(last_result_account_data.len());
+        let required_lamports = rent.minimum_balance(COUNTER_LEN);
 
         invoke_signed(
             &system_instruction::create_account(
                 payer.key,
                 last_result_account.key,
                 required_lamports,
-                last_result_account_data.len() as u64,
-                program_id,
+                COUNTER_LEN as u64,
+                &ADDER_PROGRAM_ID,
             ),
             &[
                 payer.clone(),
@@ -98,9 +89,32 @@ pub fn process_instruction(
         )?;
     }
 
-    last_result_account
-        .try_borrow_mut_data()?
-        .copy_from_slice(&last_result_account_data);
+    // Forward as many writable accounts as were supplied, so the aliasing
+    // test's duplicate pair reaches the adder's `invoke` unchanged.
+    let adder_account_metas = last_result_accounts
+        .iter()
+        .map(|account| AccountMeta::new(*account.key, false))
+        .collect();
+    let adder_account_infos: Vec<AccountInfo> = last_result_accounts
+        .iter()
+        .map(|account| (*account).clone())
+        .collect();
+
+    invoke(
+        &Instruction {
+            program_id: ADDER_PROGRAM_ID,
+            accounts: adder_account_metas,
+            data: instruction_data.to_owned(),
+        },
+        &adder_account_infos,
+    )?;
+
+    // Read the adder's write-back directly off the account instead of
+    // through `get_return_data`, now that it's the account itself (not the
+    // CPI's return data) that carries the result.
+    let data = last_result_account.try_borrow_data()?;
+    let result = u128::from_le_bytes(data[..COUNTER_LEN].try_into().unwrap());
+    msg!("adder wrote back {}", result);
 
     Ok(())
 }
@@ -121,8 +135,7 @@ mod test {
     use solana_pubkey::Pubkey;
     use solana_sdk_ids::system_program;
 
-    #[test]
-    fn test_program() {
+    fn mollusk_with_adder() -> Mollusk {
         let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
 
         mollusk.add_program(
@@ -131,16 +144,26 @@ mod test {
             &mollusk_svm::program::loader_keys::LOADER_V3,
         );
 
+        mollusk
+    }
+
+    fn payer() -> (Pubkey, Account) {
         let payer_key = Pubkey::new_unique();
-        let payer_lamports = 100_000_000;
-        let payer_account = Account::new(payer_lamports, 0, &system_program::id());
+        let payer_account = Account::new(100_000_000, 0, &system_program::id());
+        (payer_key, payer_account)
+    }
+
+    #[test]
+    fn test_program() {
+        let mollusk = mollusk_with_adder();
+        let (payer_key, payer_account) = payer();
 
         let (last_result_pda_account_key, _) =
             Pubkey::find_program_address(&[LAST_RESULT_ACCOUNT_SEED], &PROGRAM_ID);
 
         let instruction_data = borsh::to_vec(&AdderArgs { a: 5, b: 10 }).unwrap();
 
-        let initialize_instruction = Instruction::new_with_bytes(
+        let instruction = Instruction::new_with_bytes(
             PROGRAM_ID,
             &instruction_data,
             vec![
@@ -151,10 +174,55 @@ mod test {
             ],
         );
 
-        let expected_account_data = borsh::to_vec(&LastResultAccount { last_result: 15 }).unwrap();
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (payer_key, payer_account),
+                (last_result_pda_account_key, Account::default()),
+                keyed_account_for_system_program(),
+                (
+                    cpi_to_external_call_solana_adder::ID,
+                    create_program_account_loader_v3(&cpi_to_external_call_solana_adder::ID),
+                ),
+            ],
+            &[
+                Check::success(),
+                Check::account(&last_result_pda_account_key)
+                    .data(&15u128.to_le_bytes())
+                    .owner(&cpi_to_external_call_solana_adder::ID)
+                    .build(),
+            ],
+        );
+    }
+
+    /// Passes the PDA twice in the same instruction's accounts, the same way
+    /// Solana lets any pubkey appear multiple times in an `AccountMeta` list.
+    /// Both entries alias the same underlying account, so the adder's single
+    /// write-through is visible from both and the sum isn't double-counted.
+    #[test]
+    fn test_program_with_aliased_accounts() {
+        let mollusk = mollusk_with_adder();
+        let (payer_key, payer_account) = payer();
+
+        let (last_result_pda_account_key, _) =
+            Pubkey::find_program_address(&[LAST_RESULT_ACCOUNT_SEED], &PROGRAM_ID);
+
+        let instruction_data = borsh::to_vec(&AdderArgs { a: 5, b: 10 }).unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &instruction_data,
+            vec![
+                AccountMeta::new(payer_key, true),
+                AccountMeta::new(last_result_pda_account_key, false),
+                AccountMeta::new(last_result_pda_account_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(cpi_to_external_call_solana_adder::ID, false),
+            ],
+        );
 
         mollusk.process_and_validate_instruction(
-            &initialize_instruction,
+            &instruction,
             &[
                 (payer_key, payer_account),
                 (last_result_pda_account_key, Account::default()),
@@ -167,8 +235,8 @@ mod test {
             &[
                 Check::success(),
                 Check::account(&last_result_pda_account_key)
-                    .data(&expected_account_data)
-                    .owner(&PROGRAM_ID)
+                    .data(&15u128.to_le_bytes())
+                    .owner(&cpi_to_external_call_solana_adder::ID)
                     .build(),
             ],
         );
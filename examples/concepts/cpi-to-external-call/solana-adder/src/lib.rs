@@ -2,8 +2,8 @@
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, program::set_return_data,
-    program_error::ProgramError, pubkey::Pubkey,
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
 };
 
 declare_id!("JAQ5MVHbCkSYRzXunsrNuM2m1LS859PGveHfoYPAmcvZ");
@@ -11,17 +11,19 @@ declare_id!("JAQ5MVHbCkSYRzXunsrNuM2m1LS859PGveHfoYPAmcvZ");
 #[cfg(feature = "no-entrypoint")]
 pub static PROGRAM_NAME: &str = env!("CARGO_CRATE_NAME");
 
+/// Size of the `u128` counter stored in the account this program is handed.
+pub const COUNTER_LEN: usize = 16;
+
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct Args {
     pub a: u64,
     pub b: u64,
 }
 
-#[derive(BorshSerialize, BorshDeserialize)]
-pub struct Response {
-    pub result: u128,
-}
-
+/// Adds `a + b` into the caller-supplied counter account's stored `u128`
+/// instead of handing the sum back through `set_return_data`, so a caller can
+/// read the write-back directly off the account after `invoke` rather than
+/// only seeing the result for the lifetime of this one cross-program call.
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -35,15 +37,18 @@ pub fn process_instruction(
         return Err(ProgramError::InvalidInstructionData);
     };
 
-    if !accounts.is_empty() {
+    let [counter_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let mut data = counter_account.try_borrow_mut_data()?;
+    if data.len() != COUNTER_LEN {
         return Err(ProgramError::InvalidAccountData);
     }
 
-    let result = u128::from(args.a) + u128::from(args.b);
-
-    let return_data = borsh::to_vec(&Response { result }).expect("infallible serialization");
-
-    set_return_data(&return_data);
+    let existing = u128::from_le_bytes(data[..COUNTER_LEN].try_into().unwrap());
+    let updated = existing + u128::from(args.a) + u128::from(args.b);
+    data[..COUNTER_LEN].copy_from_slice(&updated.to_le_bytes());
 
     Ok(())
 }
@@ -53,26 +58,37 @@ solana_program::entrypoint!(process_instruction);
 
 #[cfg(test)]
 mod test {
-    use super::{Args, Response, ID as PROGRAM_ID};
+    use super::{Args, COUNTER_LEN, ID as PROGRAM_ID};
+
     use mollusk_svm::{result::Check, Mollusk};
-    use solana_program::instruction::Instruction;
+    use solana_account::Account;
+    use solana_program::instruction::{AccountMeta, Instruction};
+    use solana_pubkey::Pubkey;
 
     #[test]
     fn test_program() {
         let mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
 
-        let args = Args { a: 5, b: 10 };
-        let instruction_data = borsh::to_vec(&args).unwrap();
+        let counter_key = Pubkey::new_unique();
+        let counter_account = Account::new(0, COUNTER_LEN, &PROGRAM_ID);
 
-        let instruction = Instruction::new_with_bytes(PROGRAM_ID, &instruction_data, vec![]);
+        let instruction_data = borsh::to_vec(&Args { a: 5, b: 10 }).unwrap();
 
-        let expected_response = Response { result: 15 };
-        let expected_return_data = borsh::to_vec(&expected_response).unwrap();
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &instruction_data,
+            vec![AccountMeta::new(counter_key, false)],
+        );
 
         mollusk.process_and_validate_instruction(
             &instruction,
-            &[],
-            &[Check::success(), Check::return_data(&expected_return_data)],
+            &[(counter_key, counter_account)],
+            &[
+                Check::success(),
+                Check::account(&counter_key)
+                    .data(&15u128.to_le_bytes())
+                    .build(),
+            ],
         );
     }
 }
@@ -11,9 +11,14 @@ fn add_calldata(a: u64, b: u64) -> Vec<u8> {
     .concat()
 }
 
-// function add(uint64 a, uint64 b) external view returns (uint128);
-// returns a big-endian u128 (16 bytes) padded to 32 bytes
-fn parse_add_returndata(returndata: &[u8]) -> Option<u128> {
+fn counter_calldata() -> Vec<u8> {
+    [97u8, 188u8, 34u8, 26u8].to_vec() // keccak(b"counter()")[..4]
+}
+
+// function add(uint64 a, uint64 b) external returns (uint128);
+// function counter() external view returns (uint128);
+// both return a big-endian u128 (16 bytes) padded to 32 bytes
+fn parse_u128_returndata(returndata: &[u8]) -> Option<u128> {
     if returndata.len() != 32 {
         return None;
     }
@@ -37,20 +42,38 @@ impl ExternalCaller {
         self.adder_address.set(adder_address);
     }
 
+    /// Mutates the adder contract's own persistent counter rather than
+    /// round-tripping `a + b` through return data: a Stylus/Solidity call
+    /// can't hand the callee a slice of the caller's storage the way a
+    /// Solana instruction hands over an account, so the shared counter here
+    /// lives in the callee's own storage, and this caller reads it back with
+    /// a second call afterward, the EVM analogue of reading a Solana account
+    /// after a CPI mutates it. There's no EVM equivalent of Solana's
+    /// duplicate-account aliasing, since a call can't reference the same
+    /// storage slot twice the way an instruction's account list can
+    /// reference the same pubkey twice.
     pub fn add(&mut self, a: u64, b: u64) -> u128 {
-        // low-level static call used to allow unit testing
+        // low-level calls used to allow unit testing
         // sol_interface! generated interfaces can only be tested in a WASM runtime
         // see: https://github.com/OffchainLabs/stylus-sdk-rs/issues/301
+        self.vm()
+            .call(
+                &calls::context::Call::new(),
+                self.get_adder_address(),
+                &add_calldata(a, b),
+            )
+            .expect("valid contract call");
+
         let returndata = self
             .vm()
             .static_call(
                 &calls::context::Call::new(),
                 self.get_adder_address(),
-                &add_calldata(a, b),
+                &counter_calldata(),
             )
             .expect("valid contract call");
 
-        let result = parse_add_returndata(&returndata).expect("valid return data");
+        let result = parse_u128_returndata(&returndata).expect("valid return data");
 
         self.last_result.set(I256::unchecked_from(result));
 
@@ -77,7 +100,8 @@ mod test {
 
         let adder_address = Address::from([0x05; 20]);
 
-        vm.mock_static_call(adder_address, add_calldata(5, 10), Ok(abi::encode(&15u128)));
+        vm.mock_call(adder_address, add_calldata(5, 10), Ok(vec![]));
+        vm.mock_static_call(adder_address, counter_calldata(), Ok(abi::encode(&15u128)));
 
         let mut c = ExternalCaller::from(&vm);
 
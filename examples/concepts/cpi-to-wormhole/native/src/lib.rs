@@ -0,0 +1,239 @@
+#![allow(unexpected_cfgs)]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    declare_id, entrypoint,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction as SolanaInstruction},
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use solana_system_interface::instruction as system_instruction;
+
+use cpi_to_wormhole_wormhole_core_bridge_stub::{BridgeConfig, PostMessage, ID as CORE_BRIDGE_ID};
+
+declare_id!("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
+
+/// Seeds this program's emitter PDA off, so the core bridge can trust that only this
+/// program (not an arbitrary caller) can post messages under that emitter identity.
+pub static EMITTER_PDA_SEED: &[u8] = b"emitter";
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub enum Instruction {
+    /// Posts a cross-chain message through the Wormhole core bridge: pays the
+    /// message fee, then CPIs into `post_message` with this program's emitter PDA
+    /// signing on its own behalf.
+    PostMessage {
+        nonce: u32,
+        payload: Vec<u8>,
+        consistency_level: u8,
+    },
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if !check_id(program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let Ok(ix) = Instruction::try_from_slice(instruction_data) else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    match ix {
+        Instruction::PostMessage {
+            nonce,
+            payload,
+            consistency_level,
+        } => process_post_message(program_id, accounts, nonce, payload, consistency_level),
+    }
+}
+
+/// Follows the core bridge's `post_message` account order: its config, a fresh
+/// message account, this program's emitter PDA, the emitter's sequence tracker, the
+/// payer, the fee collector, the Clock/Rent sysvars, and the system program (for the
+/// fee transfer below and any account creation the bridge itself performs).
+fn process_post_message(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    nonce: u32,
+    payload: Vec<u8>,
+    consistency_level: u8,
+) -> ProgramResult {
+    let [bridge_config, message, emitter, sequence, payer, fee_collector, clock, rent, system_program, core_bridge_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if *core_bridge_program.key != CORE_BRIDGE_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (expected_emitter, emitter_bump) =
+        Pubkey::find_program_address(&[EMITTER_PDA_SEED], program_id);
+
+    if *emitter.key != expected_emitter {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // The core bridge won't accept a message until its fixed fee is paid to its fee
+    // collector account.
+    let message_fee = BridgeConfig::try_from_slice(&bridge_config.try_borrow_data()?)?.fee;
+
+    invoke(
+        &system_instruction::transfer(payer.key, fee_collector.key, message_fee),
+        &[payer.clone(), fee_collector.clone(), system_program.clone()],
+    )?;
+
+    let post_message_instruction = SolanaInstruction {
+        program_id: CORE_BRIDGE_ID,
+        accounts: vec![
+            AccountMeta::new(*bridge_config.key, false),
+            AccountMeta::new(*message.key, message.is_signer),
+            AccountMeta::new_readonly(*emitter.key, true),
+            AccountMeta::new(*sequence.key, false),
+            AccountMeta::new(*payer.key, true),
+            AccountMeta::new(*fee_collector.key, false),
+            AccountMeta::new_readonly(*clock.key, false),
+            AccountMeta::new_readonly(*rent.key, false),
+            AccountMeta::new_readonly(*system_program.key, false),
+        ],
+        data: borsh::to_vec(&PostMessage {
+            nonce,
+            payload,
+            consistency_level,
+        })?,
+    };
+
+    invoke_signed(
+        &post_message_instruction,
+        &[
+            bridge_config.clone(),
+            message.clone(),
+            emitter.clone(),
+            sequence.clone(),
+            payer.clone(),
+            fee_collector.clone(),
+            clock.clone(),
+            rent.clone(),
+            system_program.clone(),
+        ],
+        &[&[EMITTER_PDA_SEED, &[emitter_bump]]],
+    )
+}
+
+entrypoint!(process_instruction);
+
+#[cfg(test)]
+mod test {
+    use super::{Instruction, ID as PROGRAM_ID, EMITTER_PDA_SEED};
+
+    use cpi_to_wormhole_wormhole_core_bridge_stub::{BridgeConfig, ID as CORE_BRIDGE_ID};
+    use mollusk_svm::{
+        program::{create_program_account_loader_v3, keyed_account_for_system_program},
+        result::Check,
+        Mollusk,
+    };
+    use solana_account::Account;
+    use solana_program::instruction::{AccountMeta, Instruction as SolanaInstruction};
+    use solana_pubkey::Pubkey;
+    use solana_sdk_ids::{clock, rent, system_program};
+
+    #[test]
+    fn test_program() {
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+
+        mollusk.add_program(
+            &CORE_BRIDGE_ID,
+            cpi_to_wormhole_wormhole_core_bridge_stub::PROGRAM_NAME,
+            &mollusk_svm::program::loader_keys::LOADER_V3,
+        );
+
+        let payer_key = Pubkey::new_unique();
+        let payer_lamports = 100_000_000;
+        let payer_account = Account::new(payer_lamports, 0, &system_program::id());
+
+        let bridge_config_key = Pubkey::new_unique();
+        let message_fee = 100;
+        let bridge_config_data = borsh::to_vec(&BridgeConfig {
+            guardian_set_index: 0,
+            fee: message_fee,
+        })
+        .unwrap();
+        let bridge_config_account = Account {
+            lamports: 1_000_000,
+            data: bridge_config_data,
+            owner: CORE_BRIDGE_ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        let message_key = Pubkey::new_unique();
+
+        let (emitter_key, _) = Pubkey::find_program_address(&[EMITTER_PDA_SEED], &PROGRAM_ID);
+
+        let sequence_key = Pubkey::new_unique();
+
+        let fee_collector_key = Pubkey::new_unique();
+        let fee_collector_account = Account::new(0, 0, &system_program::id());
+
+        let post_message_instruction_data = borsh::to_vec(&Instruction::PostMessage {
+            nonce: 0,
+            payload: b"hello guardians".to_vec(),
+            consistency_level: 1,
+        })
+        .unwrap();
+
+        let post_message_instruction = SolanaInstruction::new_with_bytes(
+            PROGRAM_ID,
+            &post_message_instruction_data,
+            vec![
+                AccountMeta::new(bridge_config_key, false),
+                AccountMeta::new(message_key, false),
+                AccountMeta::new_readonly(emitter_key, false),
+                AccountMeta::new(sequence_key, false),
+                AccountMeta::new(payer_key, true),
+                AccountMeta::new(fee_collector_key, false),
+                AccountMeta::new_readonly(clock::id(), false),
+                AccountMeta::new_readonly(rent::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(CORE_BRIDGE_ID, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &post_message_instruction,
+            &[
+                (payer_key, payer_account),
+                (bridge_config_key, bridge_config_account),
+                (message_key, Account::default()),
+                (emitter_key, Account::default()),
+                (sequence_key, Account::default()),
+                (fee_collector_key, fee_collector_account),
+                mollusk_svm::sysvar::Sysvars::default().keyed_account_for_clock_sysvar(),
+                mollusk_svm::sysvar::Sysvars::default().keyed_account_for_rent_sysvar(),
+                keyed_account_for_system_program(),
+                (
+                    CORE_BRIDGE_ID,
+                    create_program_account_loader_v3(&CORE_BRIDGE_ID),
+                ),
+            ],
+            &[
+                Check::success(),
+                Check::account(&fee_collector_key)
+                    .lamports(message_fee)
+                    .build(),
+            ],
+        );
+    }
+}
@@ -0,0 +1,74 @@
+#![allow(unexpected_cfgs)]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+declare_id!("WormSGXHAdr3jYXbyPTAKpfN6TvQWQgJE8MTYQnKYqu");
+
+#[cfg(feature = "no-entrypoint")]
+pub static PROGRAM_NAME: &str = env!("CARGO_CRATE_NAME");
+
+/// A minimal stand-in for the real Wormhole core bridge's config account - just the
+/// fee (in lamports) a `PostMessage` CPI must pay before it's accepted.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct BridgeConfig {
+    pub guardian_set_index: u32,
+    pub fee: u64,
+}
+
+/// Mirrors the real core bridge's `post_message` account order and instruction data,
+/// just enough to let `cpi-to-wormhole` exercise a realistic CPI against something
+/// other than the actual deployed program.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct PostMessage {
+    pub nonce: u32,
+    pub payload: Vec<u8>,
+    pub consistency_level: u8,
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if !check_id(program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if PostMessage::try_from_slice(instruction_data).is_err() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let [_bridge_config, _message, emitter, _sequence, payer, fee_collector, _clock, _rent, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The real core bridge requires the emitter to sign every `PostMessage` - the
+    // one check this stub exists to exercise, since `cpi-to-wormhole` must forward
+    // that signature via `invoke_signed` with its emitter PDA's seeds.
+    if !emitter.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if system_program.key != &solana_program::system_program::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if fee_collector.owner != &solana_program::system_program::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
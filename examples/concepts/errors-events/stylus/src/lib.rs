@@ -1,7 +1,10 @@
 extern crate alloc;
 
 use stylus_sdk::{
-    alloy_primitives::U256, alloy_sol_types::sol, prelude::*, storage::StorageAddress,
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    storage::StorageAddress,
 };
 
 sol! {
@@ -9,6 +12,21 @@ sol! {
     error Unauthorized(address account);
 
     event ItChanged(address previous_it, address current_it);
+
+    /// Mirrors `sol_log`/`sol_log_64`/`sol_log_compute_units`/`sol_log_slice`:
+    /// on Solana each of those just appends an unstructured line to the
+    /// program log, so they all collapse into this one free-text event here.
+    event RawMessage(string message);
+    /// Mirrors `sol_log_data`, which base64-encodes a list of byte slices
+    /// into one program-log line for an off-chain indexer to decode; an EVM
+    /// event's `bytes` data payload is the direct structured-data analogue.
+    event Data(bytes data);
+    /// Mirrors `sol_log_params`, which logs every account key/signer/writable
+    /// flag plus the instruction data. Indexing the account list hashes the
+    /// whole list into one extra topic, so an indexer can filter on that exact
+    /// list of accounts (not on a single account key); the instruction data
+    /// stays un-indexed in `bytes`, same as `sol_log_data` above.
+    event Params(address[] indexed accounts, bytes instruction_data);
 }
 
 #[storage]
@@ -52,4 +70,87 @@ impl ErrorsEvents {
             },
         );
     }
+
+    /// Reproduces every `sol_log*` mode the native program's `Log`
+    /// instruction exercises, as the EVM-log analogue an indexer ported from
+    /// Solana would look for.
+    pub fn log_examples(&mut self, accounts: Vec<Address>, instruction_data: Vec<u8>) {
+        log(
+            self.vm(),
+            RawMessage {
+                message: "just a regular string".into(),
+            },
+        );
+
+        log(
+            self.vm(),
+            Data {
+                data: b"some serialized structures as base64".to_vec().into(),
+            },
+        );
+
+        log(
+            self.vm(),
+            Params {
+                accounts,
+                instruction_data: instruction_data.into(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolEvent;
+    use stylus_sdk::testing::*;
+
+    #[test]
+    fn test_log_examples() {
+        let vm = TestVM::default();
+        let mut c = ErrorsEvents::from(&vm);
+
+        let accounts = vec![Address::from([0x01; 20]), Address::from([0x02; 20])];
+        let instruction_data = b"instruction data".to_vec();
+
+        c.log_examples(accounts.clone(), instruction_data.clone());
+
+        let logs = vm.get_emitted_logs();
+
+        assert_eq!(
+            logs[logs.len() - 3],
+            (
+                vec![RawMessage::SIGNATURE_HASH],
+                RawMessage {
+                    message: "just a regular string".into(),
+                }
+                .encode_data()
+            )
+        );
+
+        assert_eq!(
+            logs[logs.len() - 2],
+            (
+                vec![Data::SIGNATURE_HASH],
+                Data {
+                    data: b"some serialized structures as base64".to_vec().into(),
+                }
+                .encode_data()
+            )
+        );
+
+        // `accounts` is `indexed`, so it's hashed into a second topic rather
+        // than appearing in the data payload - only `instruction_data` does.
+        let (params_topics, params_data) = logs.last().unwrap();
+        assert_eq!(params_topics.len(), 2);
+        assert_eq!(params_topics[0], Params::SIGNATURE_HASH);
+        assert_eq!(
+            *params_data,
+            Params {
+                accounts,
+                instruction_data: instruction_data.into(),
+            }
+            .encode_data()
+        );
+    }
 }
@@ -3,8 +3,20 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
+    token_2022_extensions::transfer_fee::{
+        harvest_withheld_tokens_to_mint, transfer_checked_with_fee,
+        withdraw_withheld_tokens_from_mint, HarvestWithheldTokensToMint, TransferCheckedWithFee,
+        WithdrawWithheldTokensFromMint,
+    },
     token_interface::{
-        mint_to, transfer_checked, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+        freeze_account as freeze_token_account, mint_to, set_authority as set_token_authority,
+        spl_token_2022::{
+            extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+            instruction::AuthorityType as SplAuthorityType,
+            state::Mint as SplMint,
+        },
+        thaw_account as thaw_token_account, FreezeAccount, Mint, MintTo, SetAuthority,
+        ThawAccount, TokenAccount, TokenInterface,
     },
 };
 
@@ -12,14 +24,123 @@ declare_id!("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
 
 pub static MINT_PDA_SEED: &[u8] = b"mint";
 pub static STAKE_PDA_SEED: &[u8] = b"stake";
+pub static CONFIG_PDA_SEED: &[u8] = b"config";
+pub static METADATA_PDA_SEED: &[u8] = b"metadata";
 pub const DECIMALS: u8 = 6;
 pub const TOTAL_SUPPLY: u64 = 1_000_000_000_000_000; // 1B tokens
 
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const MAX_URI_LEN: usize = 200;
+pub const MAX_SELLER_FEE_BASIS_POINTS: u16 = 10_000;
+pub const MAX_CREATOR_LIMIT: usize = 5;
+pub const CREATOR_SHARE_TOTAL: u8 = 100;
+
+/// Mirrors SPL Token's `AuthorityType`, narrowed to the variants that apply to a mint (the only
+/// kind of account this program's `set_authority` targets).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthorityType {
+    MintTokens,
+    FreezeAccount,
+}
+
+impl From<AuthorityType> for SplAuthorityType {
+    fn from(authority_type: AuthorityType) -> Self {
+        match authority_type {
+            AuthorityType::MintTokens => SplAuthorityType::MintTokens,
+            AuthorityType::FreezeAccount => SplAuthorityType::FreezeAccount,
+        }
+    }
+}
+
+/// One beneficiary of a mint's `seller_fee_basis_points`, mirroring Metaplex token-metadata's
+/// `Creator`. `verified` may only be set by a `create_metadata`/`update_metadata` call that
+/// includes the creator's own signature among its remaining accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Debug, PartialEq, Eq)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+/// Validates `name`/`symbol`/`uri`/`seller_fee_basis_points`/`creators` against the same limits
+/// the Metaplex token-metadata program enforces.
+fn assert_metadata_valid(
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    seller_fee_basis_points: u16,
+    creators: &Option<Vec<Creator>>,
+) -> Result<()> {
+    require!(name.len() <= MAX_NAME_LEN, ErrorCode::NameTooLong);
+    require!(symbol.len() <= MAX_SYMBOL_LEN, ErrorCode::SymbolTooLong);
+    require!(uri.len() <= MAX_URI_LEN, ErrorCode::UriTooLong);
+    require!(
+        seller_fee_basis_points <= MAX_SELLER_FEE_BASIS_POINTS,
+        ErrorCode::InvalidBasisPoints
+    );
+
+    if let Some(creators) = creators {
+        require!(
+            creators.len() <= MAX_CREATOR_LIMIT,
+            ErrorCode::TooManyCreators
+        );
+
+        let share_total: u16 = creators.iter().map(|creator| creator.share as u16).sum();
+        require!(
+            share_total == CREATOR_SHARE_TOTAL as u16,
+            ErrorCode::ShareTotalMustBe100
+        );
+    }
+
+    Ok(())
+}
+
+/// Computes the fee the token program will withhold from a transfer of `amount`, reading the
+/// mint's `TransferFeeConfig` extension directly since `InterfaceAccount<Mint>` only exposes the
+/// base mint fields. A mint with no such extension charges no fee.
+fn calculate_transfer_fee(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let mint_info = mint.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<SplMint>::unpack(&mint_data)?;
+
+    let fee = match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => transfer_fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ErrorCode::TransferFeeCalculationFailed)?,
+        Err(_) => 0,
+    };
+
+    Ok(fee)
+}
+
+/// Rejects any creator marked `verified` whose address isn't among the signers in
+/// `remaining_accounts`, the same co-signing requirement Metaplex's own metadata program imposes.
+fn assert_creators_signed(creators: &[Creator], remaining_accounts: &[AccountInfo]) -> Result<()> {
+    for creator in creators {
+        if creator.verified {
+            let signed = remaining_accounts
+                .iter()
+                .any(|account| account.key == &creator.address && account.is_signer);
+            require!(signed, ErrorCode::CreatorSignatureRequired);
+        }
+    }
+
+    Ok(())
+}
+
 #[program]
 pub mod fungible_tokens {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        _transfer_fee_basis_points: u16,
+        _maximum_fee: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.signer.key();
+
         mint_to(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
@@ -36,11 +157,122 @@ pub mod fungible_tokens {
         Ok(())
     }
 
+    /// Changes or permanently disables (`new_authority = None`) the mint's mint/freeze authority,
+    /// gated by `config.authority`. Once an authority is disabled the underlying token-program CPI
+    /// itself rejects any further `set_authority` call for that authority type, since there's no
+    /// longer a matching on-chain authority to sign it.
+    pub fn set_authority(
+        ctx: Context<SetAuthority>,
+        authority_type: AuthorityType,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        set_token_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.mint.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                &[&[MINT_PDA_SEED, &[ctx.bumps.mint]]],
+            ),
+            authority_type.into(),
+            new_authority,
+        )?;
+
+        Ok(())
+    }
+
+    /// Freezes an arbitrary token account of the mint, gated by `config.authority`. The CPI is
+    /// signed by the mint PDA, the freeze authority set at `initialize`; once `set_authority` has
+    /// moved or disabled that authority, the token program rejects this call.
+    pub fn freeze_account(ctx: Context<FreezeTokenAccount>) -> Result<()> {
+        freeze_token_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            FreezeAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.mint.to_account_info(),
+            },
+            &[&[MINT_PDA_SEED, &[ctx.bumps.mint]]],
+        ))?;
+
+        Ok(())
+    }
+
+    /// Thaws a token account of the mint previously frozen by `freeze_account`.
+    pub fn thaw_account(ctx: Context<ThawTokenAccount>) -> Result<()> {
+        thaw_token_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            ThawAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.mint.to_account_info(),
+            },
+            &[&[MINT_PDA_SEED, &[ctx.bumps.mint]]],
+        ))?;
+
+        Ok(())
+    }
+
+    /// Creates the mint's on-chain metadata, gated by `config.authority` rather than by the
+    /// mint's own authority (the mint PDA from `initialize` until `set_authority` moves it).
+    pub fn create_metadata(
+        ctx: Context<CreateMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+        seller_fee_basis_points: u16,
+        creators: Option<Vec<Creator>>,
+    ) -> Result<()> {
+        assert_metadata_valid(&name, &symbol, &uri, seller_fee_basis_points, &creators)?;
+        if let Some(creators) = &creators {
+            assert_creators_signed(creators, ctx.remaining_accounts)?;
+        }
+
+        let metadata = &mut ctx.accounts.metadata;
+        metadata.mint = ctx.accounts.mint.key();
+        metadata.name = name;
+        metadata.symbol = symbol;
+        metadata.uri = uri;
+        metadata.seller_fee_basis_points = seller_fee_basis_points;
+        metadata.creators = creators.unwrap_or_default();
+
+        Ok(())
+    }
+
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+        seller_fee_basis_points: u16,
+        creators: Option<Vec<Creator>>,
+    ) -> Result<()> {
+        assert_metadata_valid(&name, &symbol, &uri, seller_fee_basis_points, &creators)?;
+        if let Some(creators) = &creators {
+            assert_creators_signed(creators, ctx.remaining_accounts)?;
+        }
+
+        let metadata = &mut ctx.accounts.metadata;
+        metadata.name = name;
+        metadata.symbol = symbol;
+        metadata.uri = uri;
+        metadata.seller_fee_basis_points = seller_fee_basis_points;
+        metadata.creators = creators.unwrap_or_default();
+
+        Ok(())
+    }
+
+    /// Stakes `amount` into the caller's stake account. `amount` is the sum leaving
+    /// `from_account`; on a fee-bearing mint the stake account is credited `amount` minus the
+    /// token program's withheld transfer fee.
     pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
-        transfer_checked(
+        let fee = calculate_transfer_fee(&ctx.accounts.mint, amount)?;
+
+        transfer_checked_with_fee(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                TransferChecked {
+                TransferCheckedWithFee {
                     from: ctx.accounts.from_account.to_account_info(),
                     mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.stake_account.to_account_info(),
@@ -49,16 +281,21 @@ pub mod fungible_tokens {
             ),
             amount,
             DECIMALS,
+            fee,
         )?;
 
         Ok(())
     }
 
+    /// Unstakes `amount` out of the caller's stake account, crediting `unstake_to_account` with
+    /// `amount` minus the token program's withheld transfer fee.
     pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
-        transfer_checked(
+        let fee = calculate_transfer_fee(&ctx.accounts.mint, amount)?;
+
+        transfer_checked_with_fee(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                TransferChecked {
+                TransferCheckedWithFee {
                     from: ctx.accounts.stake_account.to_account_info(),
                     mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.unstake_to_account.to_account_info(),
@@ -72,6 +309,42 @@ pub mod fungible_tokens {
             ),
             amount,
             DECIMALS,
+            fee,
+        )?;
+
+        Ok(())
+    }
+
+    /// Sweeps transfer fees withheld on individual token accounts of the mint into the mint's own
+    /// withheld-fee balance. Permissionless, matching the token program's own instruction, since it
+    /// only consolidates fees already owed to `withdraw_withheld`'s eventual caller.
+    pub fn harvest_withheld_to_mint(ctx: Context<HarvestWithheldToMint>) -> Result<()> {
+        harvest_withheld_tokens_to_mint(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                HarvestWithheldTokensToMint {
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        )?;
+
+        Ok(())
+    }
+
+    /// Withdraws the mint's accumulated withheld transfer fees to `treasury`, signed by the mint
+    /// PDA as the withdraw-withheld authority set at `initialize`. Gated by `config.authority`.
+    pub fn withdraw_withheld(ctx: Context<WithdrawWithheld>) -> Result<()> {
+        withdraw_withheld_tokens_from_mint(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                WithdrawWithheldTokensFromMint {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    destination: ctx.accounts.treasury.to_account_info(),
+                    authority: ctx.accounts.mint.to_account_info(),
+                },
+                &[&[MINT_PDA_SEED, &[ctx.bumps.mint]]],
+            ),
         )?;
 
         Ok(())
@@ -79,6 +352,7 @@ pub mod fungible_tokens {
 }
 
 #[derive(Accounts)]
+#[instruction(transfer_fee_basis_points: u16, maximum_fee: u64)]
 pub struct Initialize<'info> {
     #[account(
         init,
@@ -86,6 +360,10 @@ pub struct Initialize<'info> {
         mint::decimals = DECIMALS,
         mint::authority = mint.key(),
         mint::freeze_authority = mint.key(),
+        extensions::transfer_fee::transfer_fee_config_authority = mint.key(),
+        extensions::transfer_fee::withdraw_withheld_authority = mint.key(),
+        extensions::transfer_fee::transfer_fee_basis_points = transfer_fee_basis_points,
+        extensions::transfer_fee::maximum_fee = maximum_fee,
         seeds = [MINT_PDA_SEED],
         bump
     )]
@@ -98,6 +376,14 @@ pub struct Initialize<'info> {
         associated_token::token_program = token_program,
     )]
     pub mint_supply_to: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [CONFIG_PDA_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
     #[account(mut)]
     pub signer: Signer<'info>,
     pub token_program: Interface<'info, TokenInterface>,
@@ -143,15 +429,148 @@ pub struct Unstake<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct SetAuthority<'info> {
+    #[account(seeds = [CONFIG_PDA_SEED], bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    pub authority: Signer<'info>,
+    #[account(mut, seeds = [MINT_PDA_SEED], bump)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct FreezeTokenAccount<'info> {
+    #[account(seeds = [CONFIG_PDA_SEED], bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [MINT_PDA_SEED], bump)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ThawTokenAccount<'info> {
+    #[account(seeds = [CONFIG_PDA_SEED], bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [MINT_PDA_SEED], bump)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct HarvestWithheldToMint<'info> {
+    #[account(mut, seeds = [MINT_PDA_SEED], bump)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawWithheld<'info> {
+    #[account(seeds = [CONFIG_PDA_SEED], bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    pub authority: Signer<'info>,
+    #[account(mut, seeds = [MINT_PDA_SEED], bump)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMetadata<'info> {
+    #[account(seeds = [CONFIG_PDA_SEED], bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    pub authority: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Metadata::INIT_SPACE,
+        seeds = [METADATA_PDA_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub metadata: Account<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(seeds = [CONFIG_PDA_SEED], bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    pub authority: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [METADATA_PDA_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub metadata: Account<'info, Metadata>,
+}
+
+#[derive(InitSpace)]
+#[account]
+pub struct Config {
+    pub authority: Pubkey,
+}
+
+#[derive(InitSpace)]
+#[account]
+pub struct Metadata {
+    pub mint: Pubkey,
+    #[max_len(MAX_NAME_LEN)]
+    pub name: String,
+    #[max_len(MAX_SYMBOL_LEN)]
+    pub symbol: String,
+    #[max_len(MAX_URI_LEN)]
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    #[max_len(MAX_CREATOR_LIMIT)]
+    pub creators: Vec<Creator>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("name exceeds the maximum length")]
+    NameTooLong,
+    #[msg("symbol exceeds the maximum length")]
+    SymbolTooLong,
+    #[msg("uri exceeds the maximum length")]
+    UriTooLong,
+    #[msg("seller fee basis points exceeds the maximum")]
+    InvalidBasisPoints,
+    #[msg("metadata may have at most MAX_CREATOR_LIMIT creators")]
+    TooManyCreators,
+    #[msg("creator shares must sum to exactly 100")]
+    ShareTotalMustBe100,
+    #[msg("a creator may only be marked verified by its own signature")]
+    CreatorSignatureRequired,
+    #[msg("failed to calculate the mint's transfer fee for this amount")]
+    TransferFeeCalculationFailed,
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        instruction::{Initialize, Stake},
-        DECIMALS, ID as PROGRAM_ID, MINT_PDA_SEED, STAKE_PDA_SEED, TOTAL_SUPPLY,
+        instruction::{
+            CreateMetadata, FreezeAccount, HarvestWithheldToMint, Initialize, SetAuthority, Stake,
+            ThawAccount, UpdateMetadata, WithdrawWithheld,
+        },
+        AuthorityType, Config, Creator, ErrorCode, Metadata, CONFIG_PDA_SEED, DECIMALS,
+        ID as PROGRAM_ID, MAX_CREATOR_LIMIT, MAX_NAME_LEN, MAX_SELLER_FEE_BASIS_POINTS,
+        MAX_SYMBOL_LEN, MAX_URI_LEN, METADATA_PDA_SEED, MINT_PDA_SEED, STAKE_PDA_SEED,
+        TOTAL_SUPPLY,
     };
 
     use anchor_lang::{
-        prelude::AccountMeta, solana_program::instruction::Instruction, InstructionData,
+        prelude::{AccountMeta, ProgramError},
+        solana_program::instruction::Instruction,
+        AnchorSerialize, InstructionData,
     };
     use anchor_spl::{associated_token, token_2022::spl_token_2022};
     use mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk};
@@ -181,7 +600,14 @@ mod tests {
         );
         let mint_supply_to_account = Account::default();
 
-        let create_mint_instruction_data = Initialize {}.data();
+        let (config_pda_key, _) = Pubkey::find_program_address(&[CONFIG_PDA_SEED], &PROGRAM_ID);
+        let config_account = Account::default();
+
+        let create_mint_instruction_data = Initialize {
+            transfer_fee_basis_points: 0,
+            maximum_fee: 0,
+        }
+        .data();
 
         let create_mint_instruction = Instruction::new_with_bytes(
             PROGRAM_ID,
@@ -189,6 +615,7 @@ mod tests {
             vec![
                 AccountMeta::new(mint_pda_key, false),
                 AccountMeta::new(mint_supply_to_key, false),
+                AccountMeta::new(config_pda_key, false),
                 AccountMeta::new(signer_key, true),
                 AccountMeta::new_readonly(mollusk_svm_programs_token::token2022::ID, false),
                 AccountMeta::new_readonly(mollusk_svm_programs_token::associated_token::ID, false),
@@ -196,6 +623,12 @@ mod tests {
             ],
         );
 
+        let expected_config_data = Config {
+            authority: signer_key,
+        }
+        .try_to_vec()
+        .unwrap();
+
         let mut expected_mint_account_data =
             vec![0u8; spl_token_2022::state::Mint::get_packed_len()];
 
@@ -321,6 +754,330 @@ mod tests {
         )
         .unwrap();
 
+        let restake_instruction_data = Stake {
+            amount: TOTAL_SUPPLY,
+        }
+        .data();
+
+        let restake_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &restake_instruction_data,
+            vec![
+                AccountMeta::new(stake_pda_key, false),
+                AccountMeta::new(mint_supply_to_key, false),
+                AccountMeta::new(signer_key, true),
+                AccountMeta::new_readonly(mint_pda_key, false),
+                AccountMeta::new_readonly(mollusk_svm_programs_token::token2022::ID, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let mut expected_stake_account_data_post_restake =
+            vec![0u8; spl_token_2022::state::Account::get_packed_len()];
+
+        Pack::pack(
+            spl_token_2022::state::Account {
+                mint: mint_pda_key,
+                owner: stake_pda_key,
+                amount: TOTAL_SUPPLY,
+                ..Default::default()
+            },
+            &mut expected_stake_account_data_post_restake,
+        )
+        .unwrap();
+
+        let freeze_thaw_accounts = vec![
+            AccountMeta::new_readonly(config_pda_key, false),
+            AccountMeta::new_readonly(signer_key, true),
+            AccountMeta::new_readonly(mint_pda_key, false),
+            AccountMeta::new(stake_pda_key, false),
+            AccountMeta::new_readonly(mollusk_svm_programs_token::token2022::ID, false),
+        ];
+
+        let freeze_stake_account_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &FreezeAccount {}.data(),
+            freeze_thaw_accounts.clone(),
+        );
+
+        let thaw_stake_account_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &ThawAccount {}.data(),
+            freeze_thaw_accounts,
+        );
+
+        let mut expected_stake_account_data_post_thaw_unstake =
+            vec![0u8; spl_token_2022::state::Account::get_packed_len()];
+
+        Pack::pack(
+            spl_token_2022::state::Account {
+                mint: mint_pda_key,
+                owner: stake_pda_key,
+                amount: TOTAL_SUPPLY - (TOTAL_SUPPLY * 3) / 4,
+                ..Default::default()
+            },
+            &mut expected_stake_account_data_post_thaw_unstake,
+        )
+        .unwrap();
+
+        const ACCOUNT_STATE_OFFSET: usize = 32 + 32 + 8 + 4 + 32;
+
+        let new_mint_authority_key = Pubkey::new_unique();
+
+        let rotate_mint_authority_instruction_data = SetAuthority {
+            authority_type: AuthorityType::MintTokens,
+            new_authority: Some(new_mint_authority_key),
+        }
+        .data();
+
+        let disable_freeze_authority_instruction_data = SetAuthority {
+            authority_type: AuthorityType::FreezeAccount,
+            new_authority: None,
+        }
+        .data();
+
+        let set_authority_accounts = vec![
+            AccountMeta::new_readonly(config_pda_key, false),
+            AccountMeta::new_readonly(signer_key, true),
+            AccountMeta::new(mint_pda_key, false),
+            AccountMeta::new_readonly(mollusk_svm_programs_token::token2022::ID, false),
+        ];
+
+        let rotate_mint_authority_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &rotate_mint_authority_instruction_data,
+            set_authority_accounts.clone(),
+        );
+
+        let disable_freeze_authority_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &disable_freeze_authority_instruction_data,
+            set_authority_accounts.clone(),
+        );
+
+        let reject_disabled_freeze_authority_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &disable_freeze_authority_instruction_data,
+            set_authority_accounts,
+        );
+
+        let mut expected_mint_account_data_post_rotate = expected_mint_account_data.clone();
+        Pack::pack(
+            spl_token_2022::state::Mint {
+                mint_authority: Some(new_mint_authority_key).into(),
+                supply: TOTAL_SUPPLY,
+                decimals: DECIMALS,
+                is_initialized: true,
+                freeze_authority: Some(mint_pda_key).into(),
+            },
+            &mut expected_mint_account_data_post_rotate,
+        )
+        .unwrap();
+
+        let mut expected_mint_account_data_post_disable = expected_mint_account_data.clone();
+        Pack::pack(
+            spl_token_2022::state::Mint {
+                mint_authority: Some(new_mint_authority_key).into(),
+                supply: TOTAL_SUPPLY,
+                decimals: DECIMALS,
+                is_initialized: true,
+                freeze_authority: None.into(),
+            },
+            &mut expected_mint_account_data_post_disable,
+        )
+        .unwrap();
+
+        let (metadata_pda_key, _) =
+            Pubkey::find_program_address(&[METADATA_PDA_SEED, mint_pda_key.as_ref()], &PROGRAM_ID);
+        let metadata_account = Account::default();
+
+        let metadata_accounts = vec![
+            AccountMeta::new_readonly(config_pda_key, false),
+            AccountMeta::new_readonly(signer_key, true),
+            AccountMeta::new_readonly(mint_pda_key, false),
+            AccountMeta::new(metadata_pda_key, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+
+        let name = "Staked Token".to_string();
+        let symbol = "STK".to_string();
+        let uri = "https://example.com/metadata.json".to_string();
+        let seller_fee_basis_points = 500;
+        let creators = vec![Creator {
+            address: Pubkey::new_unique(),
+            verified: false,
+            share: 100,
+        }];
+
+        let name_too_long_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &CreateMetadata {
+                name: "a".repeat(MAX_NAME_LEN + 1),
+                symbol: symbol.clone(),
+                uri: uri.clone(),
+                seller_fee_basis_points,
+                creators: None,
+            }
+            .data(),
+            metadata_accounts.clone(),
+        );
+
+        let symbol_too_long_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &CreateMetadata {
+                name: name.clone(),
+                symbol: "a".repeat(MAX_SYMBOL_LEN + 1),
+                uri: uri.clone(),
+                seller_fee_basis_points,
+                creators: None,
+            }
+            .data(),
+            metadata_accounts.clone(),
+        );
+
+        let uri_too_long_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &CreateMetadata {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: "a".repeat(MAX_URI_LEN + 1),
+                seller_fee_basis_points,
+                creators: None,
+            }
+            .data(),
+            metadata_accounts.clone(),
+        );
+
+        let invalid_basis_points_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &CreateMetadata {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: uri.clone(),
+                seller_fee_basis_points: MAX_SELLER_FEE_BASIS_POINTS + 1,
+                creators: None,
+            }
+            .data(),
+            metadata_accounts.clone(),
+        );
+
+        let too_many_creators_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &CreateMetadata {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: uri.clone(),
+                seller_fee_basis_points,
+                creators: Some(
+                    (0..=MAX_CREATOR_LIMIT as u8)
+                        .map(|i| Creator {
+                            address: Pubkey::new_unique(),
+                            verified: false,
+                            share: if i == 0 { 100 } else { 0 },
+                        })
+                        .collect(),
+                ),
+            }
+            .data(),
+            metadata_accounts.clone(),
+        );
+
+        let share_total_must_be_100_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &CreateMetadata {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: uri.clone(),
+                seller_fee_basis_points,
+                creators: Some(vec![
+                    Creator {
+                        address: Pubkey::new_unique(),
+                        verified: false,
+                        share: 50,
+                    },
+                    Creator {
+                        address: Pubkey::new_unique(),
+                        verified: false,
+                        share: 40,
+                    },
+                ]),
+            }
+            .data(),
+            metadata_accounts.clone(),
+        );
+
+        let creator_signature_required_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &CreateMetadata {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: uri.clone(),
+                seller_fee_basis_points,
+                creators: Some(vec![Creator {
+                    address: Pubkey::new_unique(),
+                    verified: true,
+                    share: 100,
+                }]),
+            }
+            .data(),
+            metadata_accounts.clone(),
+        );
+
+        let create_metadata_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &CreateMetadata {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: uri.clone(),
+                seller_fee_basis_points,
+                creators: Some(creators.clone()),
+            }
+            .data(),
+            metadata_accounts,
+        );
+
+        let expected_metadata_data = Metadata {
+            mint: mint_pda_key,
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            seller_fee_basis_points,
+            creators: creators.clone(),
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let updated_uri = "https://example.com/metadata-v2.json".to_string();
+
+        let update_metadata_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &UpdateMetadata {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: updated_uri.clone(),
+                seller_fee_basis_points,
+                creators: None,
+            }
+            .data(),
+            vec![
+                AccountMeta::new_readonly(config_pda_key, false),
+                AccountMeta::new_readonly(signer_key, true),
+                AccountMeta::new_readonly(mint_pda_key, false),
+                AccountMeta::new(metadata_pda_key, false),
+            ],
+        );
+
+        let expected_metadata_data_post_update = Metadata {
+            mint: mint_pda_key,
+            name,
+            symbol,
+            uri: updated_uri,
+            seller_fee_basis_points,
+            creators: Vec::new(),
+        }
+        .try_to_vec()
+        .unwrap();
+
         mollusk.process_and_validate_instruction_chain(
             &[
                 (
@@ -328,13 +1085,17 @@ mod tests {
                     &[
                         Check::success(),
                         Check::account(&mint_pda_key)
-                            .data(&expected_mint_account_data)
+                            .data_slice(0, &expected_mint_account_data)
                             .owner(&mollusk_svm_programs_token::token2022::ID)
                             .build(),
                         Check::account(&mint_supply_to_key)
                             .data_slice(0, &expected_mint_supply_to_account_data[..32 + 32 + 8])
                             .owner(&mollusk_svm_programs_token::token2022::ID)
                             .build(),
+                        Check::account(&config_pda_key)
+                            .data_slice(8, &expected_config_data)
+                            .owner(&PROGRAM_ID)
+                            .build(),
                     ],
                 ),
                 (
@@ -383,11 +1144,341 @@ mod tests {
                         spl_token_2022::error::TokenError::InsufficientFunds.into(),
                     )],
                 ),
+                (
+                    &restake_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&stake_pda_key)
+                            .data_slice(
+                                0,
+                                &expected_stake_account_data_post_restake[..32 + 32 + 8],
+                            )
+                            .owner(&mollusk_svm_programs_token::token2022::ID)
+                            .build(),
+                    ],
+                ),
+                (
+                    &freeze_stake_account_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&stake_pda_key)
+                            .data_slice(
+                                ACCOUNT_STATE_OFFSET,
+                                &[spl_token_2022::state::AccountState::Frozen as u8],
+                            )
+                            .owner(&mollusk_svm_programs_token::token2022::ID)
+                            .build(),
+                    ],
+                ),
+                (
+                    &unstake_instruction,
+                    &[Check::err(
+                        spl_token_2022::error::TokenError::AccountFrozen.into(),
+                    )],
+                ),
+                (
+                    &thaw_stake_account_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&stake_pda_key)
+                            .data_slice(
+                                ACCOUNT_STATE_OFFSET,
+                                &[spl_token_2022::state::AccountState::Initialized as u8],
+                            )
+                            .owner(&mollusk_svm_programs_token::token2022::ID)
+                            .build(),
+                    ],
+                ),
+                (
+                    &unstake_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&stake_pda_key)
+                            .data_slice(
+                                0,
+                                &expected_stake_account_data_post_thaw_unstake[..32 + 32 + 8],
+                            )
+                            .owner(&mollusk_svm_programs_token::token2022::ID)
+                            .build(),
+                    ],
+                ),
+                (
+                    &rotate_mint_authority_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&mint_pda_key)
+                            .data_slice(0, &expected_mint_account_data_post_rotate)
+                            .owner(&mollusk_svm_programs_token::token2022::ID)
+                            .build(),
+                    ],
+                ),
+                (
+                    &disable_freeze_authority_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&mint_pda_key)
+                            .data_slice(0, &expected_mint_account_data_post_disable)
+                            .owner(&mollusk_svm_programs_token::token2022::ID)
+                            .build(),
+                    ],
+                ),
+                (
+                    &reject_disabled_freeze_authority_instruction,
+                    &[Check::err(
+                        spl_token_2022::error::TokenError::MintCannotFreeze.into(),
+                    )],
+                ),
+                (
+                    &name_too_long_instruction,
+                    &[Check::err(ProgramError::Custom(
+                        ErrorCode::NameTooLong as u32,
+                    ))],
+                ),
+                (
+                    &symbol_too_long_instruction,
+                    &[Check::err(ProgramError::Custom(
+                        ErrorCode::SymbolTooLong as u32,
+                    ))],
+                ),
+                (
+                    &uri_too_long_instruction,
+                    &[Check::err(ProgramError::Custom(ErrorCode::UriTooLong as u32))],
+                ),
+                (
+                    &invalid_basis_points_instruction,
+                    &[Check::err(ProgramError::Custom(
+                        ErrorCode::InvalidBasisPoints as u32,
+                    ))],
+                ),
+                (
+                    &too_many_creators_instruction,
+                    &[Check::err(ProgramError::Custom(
+                        ErrorCode::TooManyCreators as u32,
+                    ))],
+                ),
+                (
+                    &share_total_must_be_100_instruction,
+                    &[Check::err(ProgramError::Custom(
+                        ErrorCode::ShareTotalMustBe100 as u32,
+                    ))],
+                ),
+                (
+                    &creator_signature_required_instruction,
+                    &[Check::err(ProgramError::Custom(
+                        ErrorCode::CreatorSignatureRequired as u32,
+                    ))],
+                ),
+                (
+                    &create_metadata_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&metadata_pda_key)
+                            .data_slice(8, &expected_metadata_data)
+                            .owner(&PROGRAM_ID)
+                            .build(),
+                    ],
+                ),
+                (
+                    &update_metadata_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&metadata_pda_key)
+                            .data_slice(8, &expected_metadata_data_post_update)
+                            .owner(&PROGRAM_ID)
+                            .build(),
+                    ],
+                ),
+            ],
+            &[
+                (mint_pda_key, mint_account.clone()),
+                (mint_supply_to_key, mint_supply_to_account.clone()),
+                (config_pda_key, config_account.clone()),
+                (stake_pda_key, stake_account.clone()),
+                (metadata_pda_key, metadata_account.clone()),
+                (signer_key, signer_account.clone()),
+                mollusk_svm_programs_token::token2022::keyed_account(),
+                mollusk_svm_programs_token::associated_token::keyed_account(),
+                keyed_account_for_system_program(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_stake_and_harvest_withheld_fee() {
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+
+        mollusk_svm_programs_token::token2022::add_program(&mut mollusk);
+        mollusk_svm_programs_token::associated_token::add_program(&mut mollusk);
+
+        const TRANSFER_FEE_BASIS_POINTS: u16 = 100; // 1%
+        const MAXIMUM_FEE: u64 = u64::MAX;
+
+        let signer_key = Pubkey::new_unique();
+        let signer_lamports = 100_000_000;
+        let signer_account = Account::new(signer_lamports, 0, &system_program::id());
+
+        let (mint_pda_key, _) = Pubkey::find_program_address(&[MINT_PDA_SEED], &PROGRAM_ID);
+        let mint_account = Account::default();
+
+        let mint_supply_to_key = associated_token::get_associated_token_address_with_program_id(
+            &signer_key,
+            &mint_pda_key,
+            &mollusk_svm_programs_token::token2022::ID,
+        );
+        let mint_supply_to_account = Account::default();
+
+        let (config_pda_key, _) = Pubkey::find_program_address(&[CONFIG_PDA_SEED], &PROGRAM_ID);
+        let config_account = Account::default();
+
+        let create_mint_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &Initialize {
+                transfer_fee_basis_points: TRANSFER_FEE_BASIS_POINTS,
+                maximum_fee: MAXIMUM_FEE,
+            }
+            .data(),
+            vec![
+                AccountMeta::new(mint_pda_key, false),
+                AccountMeta::new(mint_supply_to_key, false),
+                AccountMeta::new(config_pda_key, false),
+                AccountMeta::new(signer_key, true),
+                AccountMeta::new_readonly(mollusk_svm_programs_token::token2022::ID, false),
+                AccountMeta::new_readonly(mollusk_svm_programs_token::associated_token::ID, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let (stake_pda_key, _) =
+            Pubkey::find_program_address(&[STAKE_PDA_SEED, signer_key.as_ref()], &PROGRAM_ID);
+        let stake_account = Account::default();
+
+        let stake_amount = 1_000_000u64;
+        let expected_fee = stake_amount * TRANSFER_FEE_BASIS_POINTS as u64 / 10_000;
+
+        let stake_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &Stake {
+                amount: stake_amount,
+            }
+            .data(),
+            vec![
+                AccountMeta::new(stake_pda_key, false),
+                AccountMeta::new(mint_supply_to_key, false),
+                AccountMeta::new(signer_key, true),
+                AccountMeta::new_readonly(mint_pda_key, false),
+                AccountMeta::new_readonly(mollusk_svm_programs_token::token2022::ID, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let mut expected_stake_account_data_post_stake =
+            vec![0u8; spl_token_2022::state::Account::get_packed_len()];
+
+        Pack::pack(
+            spl_token_2022::state::Account {
+                mint: mint_pda_key,
+                owner: stake_pda_key,
+                amount: stake_amount - expected_fee,
+                ..Default::default()
+            },
+            &mut expected_stake_account_data_post_stake,
+        )
+        .unwrap();
+
+        let harvest_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &HarvestWithheldToMint {}.data(),
+            vec![
+                AccountMeta::new(mint_pda_key, false),
+                AccountMeta::new_readonly(mollusk_svm_programs_token::token2022::ID, false),
+                AccountMeta::new(stake_pda_key, false),
+            ],
+        );
+
+        let treasury_key = Pubkey::new_unique();
+        let treasury_owner_key = Pubkey::new_unique();
+
+        let mut treasury_account_data =
+            vec![0u8; spl_token_2022::state::Account::get_packed_len()];
+
+        Pack::pack(
+            spl_token_2022::state::Account {
+                mint: mint_pda_key,
+                owner: treasury_owner_key,
+                amount: 0,
+                ..Default::default()
+            },
+            &mut treasury_account_data,
+        )
+        .unwrap();
+
+        let treasury_account = Account {
+            lamports: 100_000_000,
+            data: treasury_account_data,
+            owner: mollusk_svm_programs_token::token2022::ID,
+            ..Default::default()
+        };
+
+        let withdraw_withheld_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &WithdrawWithheld {}.data(),
+            vec![
+                AccountMeta::new_readonly(config_pda_key, false),
+                AccountMeta::new_readonly(signer_key, true),
+                AccountMeta::new(mint_pda_key, false),
+                AccountMeta::new(treasury_key, false),
+                AccountMeta::new_readonly(mollusk_svm_programs_token::token2022::ID, false),
+            ],
+        );
+
+        let mut expected_treasury_account_data_post_withdraw =
+            vec![0u8; spl_token_2022::state::Account::get_packed_len()];
+
+        Pack::pack(
+            spl_token_2022::state::Account {
+                mint: mint_pda_key,
+                owner: treasury_owner_key,
+                amount: expected_fee,
+                ..Default::default()
+            },
+            &mut expected_treasury_account_data_post_withdraw,
+        )
+        .unwrap();
+
+        mollusk.process_and_validate_instruction_chain(
+            &[
+                (&create_mint_instruction, &[Check::success()]),
+                (
+                    &stake_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&stake_pda_key)
+                            .data_slice(0, &expected_stake_account_data_post_stake[..32 + 32 + 8])
+                            .owner(&mollusk_svm_programs_token::token2022::ID)
+                            .build(),
+                    ],
+                ),
+                (&harvest_instruction, &[Check::success()]),
+                (
+                    &withdraw_withheld_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&treasury_key)
+                            .data_slice(
+                                0,
+                                &expected_treasury_account_data_post_withdraw[..32 + 32 + 8],
+                            )
+                            .owner(&mollusk_svm_programs_token::token2022::ID)
+                            .build(),
+                    ],
+                ),
             ],
             &[
                 (mint_pda_key, mint_account.clone()),
                 (mint_supply_to_key, mint_supply_to_account.clone()),
+                (config_pda_key, config_account.clone()),
                 (stake_pda_key, stake_account.clone()),
+                (treasury_key, treasury_account.clone()),
                 (signer_key, signer_account.clone()),
                 mollusk_svm_programs_token::token2022::keyed_account(),
                 mollusk_svm_programs_token::associated_token::keyed_account(),
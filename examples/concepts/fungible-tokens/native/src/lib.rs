@@ -2,7 +2,8 @@
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo,
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     declare_id, entrypoint,
     entrypoint::ProgramResult,
     program::invoke,
@@ -17,22 +18,267 @@ use solana_sdk_ids::system_program;
 use solana_system_interface::instruction as system_instruction;
 use spl_associated_token_account::instruction as associated_token_instruction;
 use spl_token_2022::{
-    instruction as token_instruction,
-    state::{Account as TokenAccount, Mint},
+    instruction::{self as token_instruction, AuthorityType},
+    state::{Account as TokenAccount, AccountState, Mint},
 };
 
 declare_id!("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
 
 pub static MINT_PDA_SEED: &[u8] = b"mint";
 pub static STAKE_PDA_SEED: &[u8] = b"stake";
+pub static POOL_MINT_PDA_SEED: &[u8] = b"pool_mint";
+pub static RESERVE_VAULT_PDA_SEED: &[u8] = b"reserve_vault";
+pub static POOL_STATE_PDA_SEED: &[u8] = b"pool_state";
+pub static MULTISIG_PDA_SEED: &[u8] = b"multisig";
+pub static STAKE_RECORD_PDA_SEED: &[u8] = b"stake_record";
 pub const DECIMALS: u8 = 6;
 pub const TOTAL_SUPPLY: u64 = 1_000_000_000_000_000; // 1B tokens
+/// Mirrors `spl_token_2022::instruction::MAX_SIGNERS` - the largest signer set this
+/// program's own multisig accounts accept.
+pub const MAX_SIGNERS: usize = 11;
+/// How long after `RequestUnstake` a withdrawal remains locked, mirroring the
+/// unstaking cooldown real stake-pool programs enforce against a validator's epoch.
+pub const COOLDOWN_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+#[derive(Clone, Copy, Debug, thiserror::Error)]
+pub enum FungibleTokenError {
+    #[error("account is not an initialized SPL token mint owned by the token program")]
+    InvalidMint,
+    #[error("token account's mint does not match the expected mint")]
+    MintMismatch,
+    #[error("transfer decimals do not match the mint's decimals")]
+    MintDecimalsMismatch,
+    #[error("token account is frozen")]
+    AccountFrozen,
+    #[error("unstake cooldown has not yet elapsed")]
+    CooldownNotElapsed,
+}
+
+impl From<FungibleTokenError> for ProgramError {
+    fn from(error: FungibleTokenError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+/// Unpacks `mint_account` as an SPL token mint, rejecting an account not owned by Token-2022
+/// or whose data isn't a valid mint, so decimals come from the mint rather than a hardcoded constant.
+fn unpack_mint(mint_account: &AccountInfo) -> Result<Mint, ProgramError> {
+    if mint_account.owner != &spl_token_2022::id() {
+        return Err(FungibleTokenError::InvalidMint.into());
+    }
+
+    Mint::unpack(&mint_account.data.borrow()).map_err(|_| FungibleTokenError::InvalidMint.into())
+}
+
+/// Verifies `token_account` is an initialized SPL token account for `expected_mint`.
+fn verify_token_account_mint(
+    token_account: &AccountInfo,
+    expected_mint: &Pubkey,
+) -> ProgramResult {
+    let unpacked = TokenAccount::unpack(&token_account.data.borrow())
+        .map_err(|_| ProgramError::from(FungibleTokenError::InvalidMint))?;
+
+    if unpacked.mint != *expected_mint {
+        return Err(FungibleTokenError::MintMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// Verifies the pool-token mint was initialized with the same decimals as the
+/// underlying mint it shadows, so share accounting stays 1:1 in precision.
+fn verify_pool_mint_decimals(pool_mint: &Mint, underlying_mint: &Mint) -> ProgramResult {
+    if pool_mint.decimals != underlying_mint.decimals {
+        return Err(FungibleTokenError::MintDecimalsMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// Tracks the exchange rate between the underlying reserve vault and outstanding
+/// pool-token shares, modeled on SPL stake-pool: `total_reserve / total_pool_tokens`
+/// rises whenever rewards are distributed without minting new shares. `authority` is
+/// pinned once, at `Initialize`, to whichever signer or program-owned multisig
+/// established the pool, so later privileged instructions can check the caller against
+/// it instead of trusting whatever signer/multisig account the caller happens to pass in.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+pub struct PoolState {
+    pub total_reserve: u64,
+    pub total_pool_tokens: u64,
+    pub authority: Pubkey,
+}
+
+impl PoolState {
+    const LEN: usize = 8 + 8 + 32;
+
+    fn read(account: &AccountInfo) -> Result<Self, ProgramError> {
+        Self::try_from_slice(&account.data.borrow()).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    fn write(&self, account: &AccountInfo) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        let encoded = borsh::to_vec(self).map_err(|_| ProgramError::InvalidAccountData)?;
+        data[..encoded.len()].copy_from_slice(&encoded);
+        Ok(())
+    }
+}
+
+/// Tracks a single staker's cooldown, PDA-seeded off their own key. `Stake` keeps
+/// `last_stake_ts` current; `RequestUnstake` stamps `unlock_ts` and the amount it
+/// covers, which `Unstake` checks before releasing funds.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+pub struct StakeRecord {
+    pub last_stake_ts: i64,
+    pub unlock_ts: i64,
+    pub requested_amount: u64,
+}
+
+impl StakeRecord {
+    const LEN: usize = 8 + 8 + 8;
+
+    fn read(account: &AccountInfo) -> Result<Self, ProgramError> {
+        Self::try_from_slice(&account.data.borrow()).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    fn write(&self, account: &AccountInfo) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        let encoded = borsh::to_vec(self).map_err(|_| ProgramError::InvalidAccountData)?;
+        data[..encoded.len()].copy_from_slice(&encoded);
+        Ok(())
+    }
+}
+
+/// An M-of-N signer set this program can require in place of a single signer, the way
+/// `spl_token_2022::state::Multisig` stands in for a single mint/freeze authority.
+/// `Initialize` accepts one of these as an additional, optional account: when present
+/// (owned by this program) it becomes `PoolState::authority` and `m` of its `signers`
+/// must co-sign; otherwise the lone `signer_account` becomes the authority instead.
+/// `Unstake`/`FreezeStake`/`ThawStake` accept the same shape of account again, but it
+/// must be the exact one `PoolState::authority` was pinned to - a caller can't substitute
+/// a different, self-service `MultisigState` of their own.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MultisigState {
+    pub m: u8,
+    pub signers: Vec<Pubkey>,
+}
+
+impl MultisigState {
+    fn packed_len(signer_count: usize) -> usize {
+        1 + 4 + 32 * signer_count
+    }
+
+    fn read(account: &AccountInfo) -> Result<Self, ProgramError> {
+        Self::try_from_slice(&account.data.borrow()).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    fn write(&self, account: &AccountInfo) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        let encoded = borsh::to_vec(self).map_err(|_| ProgramError::InvalidAccountData)?;
+        data[..encoded.len()].copy_from_slice(&encoded);
+        Ok(())
+    }
+
+    /// Requires `self.m` of `signer_infos` to both have signed and be registered
+    /// members of this multisig.
+    fn verify(&self, signer_infos: &[AccountInfo]) -> ProgramResult {
+        let co_signers = signer_infos
+            .iter()
+            .filter(|info| info.is_signer && self.signers.contains(info.key))
+            .count();
+
+        if co_signers < self.m as usize {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        Ok(())
+    }
+}
+
+/// Establishes the authority `PoolState` will pin going forward: requires `signer_account`
+/// to have signed, and if `multisig_account` is program-owned, requires `m` of its `signers`
+/// to co-sign and returns `multisig_account`'s key as the authority instead of
+/// `signer_account`'s. Only ever called from `Initialize`, before `PoolState` exists to pin
+/// an authority against - every later privileged instruction goes through `verify_authority`
+/// instead, which checks against the value this function returned.
+fn establish_initial_authority(
+    program_id: &Pubkey,
+    signer_account: &AccountInfo,
+    multisig_account: &AccountInfo,
+    remaining_signer_infos: &[AccountInfo],
+) -> Result<Pubkey, ProgramError> {
+    if !signer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if multisig_account.owner == program_id {
+        let multisig = MultisigState::read(multisig_account)?;
+        let mut co_signers = vec![signer_account.clone()];
+        co_signers.extend_from_slice(remaining_signer_infos);
+        multisig.verify(&co_signers)?;
+        return Ok(*multisig_account.key);
+    }
+
+    Ok(*signer_account.key)
+}
+
+/// Verifies the authority for a privileged action against `expected_authority` - the value
+/// `PoolState::authority` was pinned to at `Initialize` - falling back to a single signer
+/// check when `multisig_account` isn't owned by this program. Unlike `establish_initial_authority`,
+/// an arbitrary program-owned `MultisigState` the caller conjures up is not accepted: it must
+/// be the exact account `expected_authority` names.
+fn verify_authority(
+    program_id: &Pubkey,
+    expected_authority: &Pubkey,
+    signer_account: &AccountInfo,
+    multisig_account: &AccountInfo,
+    remaining_signer_infos: &[AccountInfo],
+) -> ProgramResult {
+    if !signer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if multisig_account.owner == program_id {
+        if multisig_account.key != expected_authority {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let multisig = MultisigState::read(multisig_account)?;
+        let mut co_signers = vec![signer_account.clone()];
+        co_signers.extend_from_slice(remaining_signer_infos);
+        return multisig.verify(&co_signers);
+    }
+
+    if signer_account.key != expected_authority {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    Ok(())
+}
 
 #[derive(BorshSerialize, BorshDeserialize)]
 pub enum Instruction {
-    Initialize,
+    Initialize {
+        decimals: u8,
+        initial_supply: u64,
+        /// `None` permanently revokes minting after `initial_supply` is minted,
+        /// producing a fixed-supply token.
+        mint_authority: Option<Pubkey>,
+        freeze_authority: Option<Pubkey>,
+    },
     Stake { amount: u64 },
-    Unstake { amount: u64 },
+    Unstake { shares: u64 },
+    DistributeRewards { amount: u64 },
+    /// Creates a program-owned multisig PDA that `Initialize`/`Unstake` can require as
+    /// their authority instead of a single signer.
+    InitializeMultisig { m: u8, signers: Vec<Pubkey> },
+    /// Freezes a token account using the freeze authority the mint PDA already holds,
+    /// for halting a compromised or sanctioned account in an emergency.
+    FreezeStake,
+    /// Reverses `FreezeStake`.
+    ThawStake,
+    /// Starts the unstake cooldown for `amount`, which `Unstake` may only withdraw
+    /// once it has elapsed.
+    RequestUnstake { amount: u64 },
 }
 
 pub fn process_instruction(
@@ -48,22 +294,61 @@ pub fn process_instruction(
         .map_err(|_| ProgramError::InvalidInstructionData)?;
 
     match instruction {
-        Instruction::Initialize => process_initialize(program_id, accounts),
+        Instruction::Initialize {
+            decimals,
+            initial_supply,
+            mint_authority,
+            freeze_authority,
+        } => process_initialize(
+            program_id,
+            accounts,
+            decimals,
+            initial_supply,
+            mint_authority,
+            freeze_authority,
+        ),
         Instruction::Stake { amount } => process_stake(program_id, accounts, amount),
-        Instruction::Unstake { amount } => process_unstake(program_id, accounts, amount),
+        Instruction::Unstake { shares } => process_unstake(program_id, accounts, shares),
+        Instruction::DistributeRewards { amount } => {
+            process_distribute_rewards(program_id, accounts, amount)
+        }
+        Instruction::InitializeMultisig { m, signers } => {
+            process_initialize_multisig(program_id, accounts, m, signers)
+        }
+        Instruction::FreezeStake => process_freeze_or_thaw_stake(program_id, accounts, true),
+        Instruction::ThawStake => process_freeze_or_thaw_stake(program_id, accounts, false),
+        Instruction::RequestUnstake { amount } => {
+            process_request_unstake(program_id, accounts, amount)
+        }
     }
 }
 
-fn process_initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    let [mint_account, mint_supply_to_account, signer_account, token_program, associated_token_program, system_program, rent_sysvar] =
-        accounts
-    else {
-        return Err(ProgramError::NotEnoughAccountKeys);
-    };
+fn process_initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    decimals: u8,
+    initial_supply: u64,
+    mint_authority: Option<Pubkey>,
+    freeze_authority: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let mint_supply_to_account = next_account_info(account_info_iter)?;
+    let signer_account = next_account_info(account_info_iter)?;
+    let pool_mint_account = next_account_info(account_info_iter)?;
+    let reserve_vault_account = next_account_info(account_info_iter)?;
+    let pool_state_account = next_account_info(account_info_iter)?;
+    let multisig_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let associated_token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let rent_sysvar = next_account_info(account_info_iter)?;
+    // Any accounts after the fixed set above are additional co-signers consulted only
+    // when `multisig_account` is a program-owned `MultisigState`.
+    let remaining_signer_infos = account_info_iter.as_slice();
 
-    if !signer_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    let authority =
+        establish_initial_authority(program_id, signer_account, multisig_account, remaining_signer_infos)?;
 
     if *token_program.key != spl_token_2022::id()
         || *associated_token_program.key != spl_associated_token_account::id()
@@ -104,6 +389,27 @@ fn process_initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramR
         return Err(ProgramError::InvalidAccountData);
     }
 
+    let (pool_mint_pda_key, pool_mint_bump) =
+        Pubkey::find_program_address(&[POOL_MINT_PDA_SEED], program_id);
+
+    if pool_mint_pda_key != *pool_mint_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let (reserve_vault_pda_key, reserve_vault_bump) =
+        Pubkey::find_program_address(&[RESERVE_VAULT_PDA_SEED], program_id);
+
+    if reserve_vault_pda_key != *reserve_vault_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let (pool_state_pda_key, pool_state_bump) =
+        Pubkey::find_program_address(&[POOL_STATE_PDA_SEED], program_id);
+
+    if pool_state_pda_key != *pool_state_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
     // Create mint account
     let space_required = Mint::get_packed_len();
     let lamports_required = Rent::get()?.minimum_balance(space_required);
@@ -124,14 +430,16 @@ fn process_initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramR
         &[&[MINT_PDA_SEED, &[mint_bump]]],
     )?;
 
-    // Initialize mint
+    // Initialize mint. The PDA is always the initial mint authority so it can sign the
+    // `initial_supply` mint below; it is replaced with `mint_authority` (or revoked
+    // entirely) once that mint has happened.
     invoke_signed(
         &token_instruction::initialize_mint(
             &spl_token_2022::id(),
             mint_account.key,
             mint_account.key,
-            Some(mint_account.key),
-            DECIMALS,
+            freeze_authority.as_ref(),
+            decimals,
         )?,
         &[mint_account.clone(), rent_sysvar.clone()],
         &[&[MINT_PDA_SEED, &[mint_bump]]],
@@ -157,7 +465,7 @@ fn process_initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramR
         &[&[MINT_PDA_SEED, &[mint_bump]]],
     )?;
 
-    // Mint total supply to the associated token account
+    // Mint the initial supply to the associated token account
     invoke_signed(
         &token_instruction::mint_to(
             &spl_token_2022::id(),
@@ -165,7 +473,7 @@ fn process_initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramR
             mint_supply_to_account.key,
             mint_account.key,
             &[],
-            TOTAL_SUPPLY,
+            initial_supply,
         )?,
         &[
             mint_account.clone(),
@@ -175,11 +483,114 @@ fn process_initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramR
         &[&[MINT_PDA_SEED, &[mint_bump]]],
     )?;
 
+    // Hand the mint authority to the caller-specified account, or revoke it entirely
+    // (producing a fixed-supply token) when none was given.
+    invoke_signed(
+        &token_instruction::set_authority(
+            &spl_token_2022::id(),
+            mint_account.key,
+            mint_authority.as_ref(),
+            AuthorityType::MintTokens,
+            mint_account.key,
+            &[],
+        )?,
+        &[mint_account.clone()],
+        &[&[MINT_PDA_SEED, &[mint_bump]]],
+    )?;
+
+    // Create the pool-token mint. Its authority is the underlying mint PDA, which acts
+    // as the overall program authority for both mints and the reserve vault.
+    let pool_mint_space = Mint::get_packed_len();
+    let pool_mint_lamports = Rent::get()?.minimum_balance(pool_mint_space);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            signer_account.key,
+            pool_mint_account.key,
+            pool_mint_lamports,
+            pool_mint_space as u64,
+            &spl_token_2022::id(),
+        ),
+        &[
+            signer_account.clone(),
+            pool_mint_account.clone(),
+            system_program.clone(),
+        ],
+        &[&[POOL_MINT_PDA_SEED, &[pool_mint_bump]]],
+    )?;
+
+    invoke_signed(
+        &token_instruction::initialize_mint(
+            &spl_token_2022::id(),
+            pool_mint_account.key,
+            mint_account.key,
+            None,
+            decimals,
+        )?,
+        &[pool_mint_account.clone(), rent_sysvar.clone()],
+        &[&[POOL_MINT_PDA_SEED, &[pool_mint_bump]]],
+    )?;
+
+    // Create the shared reserve vault, a single token account (not a per-user PDA) that
+    // backs every outstanding pool-token share.
+    let reserve_vault_space = TokenAccount::get_packed_len();
+    let reserve_vault_lamports = Rent::get()?.minimum_balance(reserve_vault_space);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            signer_account.key,
+            reserve_vault_account.key,
+            reserve_vault_lamports,
+            reserve_vault_space as u64,
+            &spl_token_2022::id(),
+        ),
+        &[
+            signer_account.clone(),
+            reserve_vault_account.clone(),
+            system_program.clone(),
+        ],
+        &[&[RESERVE_VAULT_PDA_SEED, &[reserve_vault_bump]]],
+    )?;
+
+    invoke_signed(
+        &token_instruction::initialize_account3(
+            &spl_token_2022::id(),
+            reserve_vault_account.key,
+            mint_account.key,
+            mint_account.key,
+        )?,
+        &[reserve_vault_account.clone(), mint_account.clone()],
+        &[&[RESERVE_VAULT_PDA_SEED, &[reserve_vault_bump]]],
+    )?;
+
+    // Create the pool state account, owned by this program.
+    invoke_signed(
+        &system_instruction::create_account(
+            signer_account.key,
+            pool_state_account.key,
+            Rent::get()?.minimum_balance(PoolState::LEN),
+            PoolState::LEN as u64,
+            program_id,
+        ),
+        &[
+            signer_account.clone(),
+            pool_state_account.clone(),
+            system_program.clone(),
+        ],
+        &[&[POOL_STATE_PDA_SEED, &[pool_state_bump]]],
+    )?;
+
+    PoolState {
+        authority,
+        ..Default::default()
+    }
+    .write(pool_state_account)?;
+
     Ok(())
 }
 
 fn process_stake(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
-    let [stake_account, from_account, signer_account, mint_account, token_program, system_program] =
+    let [stake_account, stake_record_account, from_account, signer_account, mint_account, pool_mint_account, reserve_vault_account, pool_state_account, token_program, system_program] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -193,7 +604,24 @@ fn process_stake(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) ->
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    // Verify stake PDA
+    let (mint_pda_key, mint_bump) = Pubkey::find_program_address(&[MINT_PDA_SEED], program_id);
+
+    if mint_pda_key != *mint_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let (pool_mint_pda_key, _pool_mint_bump) =
+        Pubkey::find_program_address(&[POOL_MINT_PDA_SEED], program_id);
+
+    if pool_mint_pda_key != *pool_mint_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mint = unpack_mint(mint_account)?;
+    verify_token_account_mint(from_account, mint_account.key)?;
+    verify_pool_mint_decimals(&unpack_mint(pool_mint_account)?, &mint)?;
+
+    // Verify the per-user PDA that will hold the minted pool-token shares.
     let (stake_pda_key, stake_bump) =
         Pubkey::find_program_address(&[STAKE_PDA_SEED, signer_account.key.as_ref()], program_id);
 
@@ -201,7 +629,7 @@ fn process_stake(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) ->
         return Err(ProgramError::InvalidSeeds);
     }
 
-    // Create stake account if it doesn't exist
+    // Create the pool-token holding account if it doesn't exist yet.
     if stake_account.data_is_empty() || *stake_account.owner != spl_token_2022::id() {
         let space_required = TokenAccount::get_packed_len();
         let lamports_required = Rent::get()?.minimum_balance(space_required);
@@ -222,58 +650,178 @@ fn process_stake(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) ->
             &[&[STAKE_PDA_SEED, signer_account.key.as_ref(), &[stake_bump]]],
         )?;
 
-        // Initialize the stake token account
         invoke_signed(
             &token_instruction::initialize_account3(
                 &spl_token_2022::id(),
                 stake_account.key,
-                mint_account.key,
+                pool_mint_account.key,
                 stake_account.key,
             )?,
-            &[stake_account.clone(), mint_account.clone()],
+            &[stake_account.clone(), pool_mint_account.clone()],
             &[&[STAKE_PDA_SEED, signer_account.key.as_ref(), &[stake_bump]]],
         )?;
+    } else {
+        verify_token_account_mint(stake_account, pool_mint_account.key)?;
+        reject_if_frozen(stake_account)?;
     }
 
-    // Transfer tokens from user's account to stake account
+    let (stake_record_pda_key, stake_record_bump) = Pubkey::find_program_address(
+        &[STAKE_RECORD_PDA_SEED, signer_account.key.as_ref()],
+        program_id,
+    );
+
+    if stake_record_pda_key != *stake_record_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let mut stake_record = if stake_record_account.data_is_empty() {
+        invoke_signed(
+            &system_instruction::create_account(
+                signer_account.key,
+                stake_record_account.key,
+                Rent::get()?.minimum_balance(StakeRecord::LEN),
+                StakeRecord::LEN as u64,
+                program_id,
+            ),
+            &[
+                signer_account.clone(),
+                stake_record_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[
+                STAKE_RECORD_PDA_SEED,
+                signer_account.key.as_ref(),
+                &[stake_record_bump],
+            ]],
+        )?;
+
+        StakeRecord::default()
+    } else {
+        StakeRecord::read(stake_record_account)?
+    };
+
+    stake_record.last_stake_ts = now;
+
+    // Move the underlying tokens into the shared reserve vault.
     invoke(
         &token_instruction::transfer_checked(
             &spl_token_2022::id(),
             from_account.key,
             mint_account.key,
-            stake_account.key,
+            reserve_vault_account.key,
             signer_account.key,
             &[],
             amount,
-            DECIMALS,
+            mint.decimals,
         )?,
         &[
             from_account.clone(),
             mint_account.clone(),
-            stake_account.clone(),
+            reserve_vault_account.clone(),
             signer_account.clone(),
         ],
     )?;
 
-    Ok(())
-}
+    let mut pool_state = PoolState::read(pool_state_account)?;
 
-fn process_unstake(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
-    let [stake_account, unstake_to_account, signer_account, mint_account, token_program, system_program] =
-        accounts
-    else {
-        return Err(ProgramError::NotEnoughAccountKeys);
+    let shares = if pool_state.total_pool_tokens == 0 {
+        amount
+    } else {
+        u128::from(amount)
+            .checked_mul(u128::from(pool_state.total_pool_tokens))
+            .and_then(|scaled| scaled.checked_div(u128::from(pool_state.total_reserve)))
+            .and_then(|shares| u64::try_from(shares).ok())
+            .ok_or(ProgramError::ArithmeticOverflow)?
     };
 
-    if !signer_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+    if shares == 0 {
+        return Err(ProgramError::InvalidInstructionData);
     }
 
+    invoke_signed(
+        &token_instruction::mint_to(
+            &spl_token_2022::id(),
+            pool_mint_account.key,
+            stake_account.key,
+            mint_account.key,
+            &[],
+            shares,
+        )?,
+        &[
+            pool_mint_account.clone(),
+            stake_account.clone(),
+            mint_account.clone(),
+        ],
+        &[&[MINT_PDA_SEED, &[mint_bump]]],
+    )?;
+
+    pool_state.total_reserve = pool_state
+        .total_reserve
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    pool_state.total_pool_tokens = pool_state
+        .total_pool_tokens
+        .checked_add(shares)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    pool_state.write(pool_state_account)?;
+    stake_record.write(stake_record_account)
+}
+
+fn process_unstake(program_id: &Pubkey, accounts: &[AccountInfo], shares: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let stake_account = next_account_info(account_info_iter)?;
+    let stake_record_account = next_account_info(account_info_iter)?;
+    let unstake_to_account = next_account_info(account_info_iter)?;
+    let signer_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let pool_mint_account = next_account_info(account_info_iter)?;
+    let reserve_vault_account = next_account_info(account_info_iter)?;
+    let pool_state_account = next_account_info(account_info_iter)?;
+    let multisig_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let remaining_signer_infos = account_info_iter.as_slice();
+
+    let (pool_state_pda_key, _pool_state_bump) =
+        Pubkey::find_program_address(&[POOL_STATE_PDA_SEED], program_id);
+
+    if pool_state_pda_key != *pool_state_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut pool_state = PoolState::read(pool_state_account)?;
+
+    verify_authority(
+        program_id,
+        &pool_state.authority,
+        signer_account,
+        multisig_account,
+        remaining_signer_infos,
+    )?;
+
     if *token_program.key != spl_token_2022::id() || *system_program.key != system_program::id() {
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    // Verify stake PDA
+    let (mint_pda_key, mint_bump) = Pubkey::find_program_address(&[MINT_PDA_SEED], program_id);
+
+    if mint_pda_key != *mint_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let (pool_mint_pda_key, _pool_mint_bump) =
+        Pubkey::find_program_address(&[POOL_MINT_PDA_SEED], program_id);
+
+    if pool_mint_pda_key != *pool_mint_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mint = unpack_mint(mint_account)?;
+    verify_token_account_mint(unstake_to_account, mint_account.key)?;
+
     let (stake_pda_key, stake_bump) =
         Pubkey::find_program_address(&[STAKE_PDA_SEED, signer_account.key.as_ref()], program_id);
 
@@ -281,36 +829,338 @@ fn process_unstake(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -
         return Err(ProgramError::InvalidSeeds);
     }
 
-    // Transfer tokens from stake account to user's account
+    reject_if_frozen(stake_account)?;
+
+    let (stake_record_pda_key, _stake_record_bump) = Pubkey::find_program_address(
+        &[STAKE_RECORD_PDA_SEED, signer_account.key.as_ref()],
+        program_id,
+    );
+
+    if stake_record_pda_key != *stake_record_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut stake_record = StakeRecord::read(stake_record_account)?;
+
+    if Clock::get()?.unix_timestamp < stake_record.unlock_ts {
+        return Err(FungibleTokenError::CooldownNotElapsed.into());
+    }
+
+    if shares > stake_record.requested_amount {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if pool_state.total_pool_tokens == 0 {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    let reserve_out = u128::from(shares)
+        .checked_mul(u128::from(pool_state.total_reserve))
+        .and_then(|scaled| scaled.checked_div(u128::from(pool_state.total_pool_tokens)))
+        .and_then(|out| u64::try_from(out).ok())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
     invoke_signed(
-        &token_instruction::transfer_checked(
+        &token_instruction::burn(
             &spl_token_2022::id(),
             stake_account.key,
-            mint_account.key,
-            unstake_to_account.key,
+            pool_mint_account.key,
             stake_account.key,
             &[],
-            amount,
-            DECIMALS,
+            shares,
         )?,
         &[
             stake_account.clone(),
-            mint_account.clone(),
-            unstake_to_account.clone(),
+            pool_mint_account.clone(),
             stake_account.clone(),
         ],
         &[&[STAKE_PDA_SEED, signer_account.key.as_ref(), &[stake_bump]]],
     )?;
 
+    invoke_signed(
+        &token_instruction::transfer_checked(
+            &spl_token_2022::id(),
+            reserve_vault_account.key,
+            mint_account.key,
+            unstake_to_account.key,
+            mint_account.key,
+            &[],
+            reserve_out,
+            mint.decimals,
+        )?,
+        &[
+            reserve_vault_account.clone(),
+            mint_account.clone(),
+            unstake_to_account.clone(),
+            mint_account.clone(),
+        ],
+        &[&[MINT_PDA_SEED, &[mint_bump]]],
+    )?;
+
+    pool_state.total_reserve = pool_state
+        .total_reserve
+        .checked_sub(reserve_out)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    pool_state.total_pool_tokens = pool_state
+        .total_pool_tokens
+        .checked_sub(shares)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    stake_record.requested_amount -= shares;
+    if stake_record.requested_amount == 0 {
+        stake_record.unlock_ts = i64::MAX;
+    }
+
+    pool_state.write(pool_state_account)?;
+    stake_record.write(stake_record_account)
+}
+
+/// Rejects `target_account` when the freeze authority has frozen it via `FreezeStake`.
+fn reject_if_frozen(target_account: &AccountInfo) -> ProgramResult {
+    let state = TokenAccount::unpack(&target_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?
+        .state;
+
+    if state == AccountState::Frozen {
+        return Err(FungibleTokenError::AccountFrozen.into());
+    }
+
     Ok(())
 }
 
+/// Freezes or thaws `target_account` using the freeze authority the mint PDA already
+/// holds, gated behind the same pinned-authority check as `Unstake`.
+fn process_freeze_or_thaw_stake(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    freeze: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let target_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let pool_state_account = next_account_info(account_info_iter)?;
+    let signer_account = next_account_info(account_info_iter)?;
+    let multisig_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let remaining_signer_infos = account_info_iter.as_slice();
+
+    let (pool_state_pda_key, _pool_state_bump) =
+        Pubkey::find_program_address(&[POOL_STATE_PDA_SEED], program_id);
+
+    if pool_state_pda_key != *pool_state_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let pool_state = PoolState::read(pool_state_account)?;
+
+    verify_authority(
+        program_id,
+        &pool_state.authority,
+        signer_account,
+        multisig_account,
+        remaining_signer_infos,
+    )?;
+
+    if *token_program.key != spl_token_2022::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (mint_pda_key, mint_bump) = Pubkey::find_program_address(&[MINT_PDA_SEED], program_id);
+
+    if mint_pda_key != *mint_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let instruction = if freeze {
+        token_instruction::freeze_account(
+            &spl_token_2022::id(),
+            target_account.key,
+            mint_account.key,
+            mint_account.key,
+            &[],
+        )?
+    } else {
+        token_instruction::thaw_account(
+            &spl_token_2022::id(),
+            target_account.key,
+            mint_account.key,
+            mint_account.key,
+            &[],
+        )?
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            target_account.clone(),
+            mint_account.clone(),
+            mint_account.clone(),
+        ],
+        &[&[MINT_PDA_SEED, &[mint_bump]]],
+    )
+}
+
+/// Starts the unstake cooldown for `amount`, recording it on the staker's
+/// [`StakeRecord`] so `Unstake` can check `unlock_ts` before releasing funds.
+fn process_request_unstake(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let [stake_record_account, signer_account] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (stake_record_pda_key, _stake_record_bump) = Pubkey::find_program_address(
+        &[STAKE_RECORD_PDA_SEED, signer_account.key.as_ref()],
+        program_id,
+    );
+
+    if stake_record_pda_key != *stake_record_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut stake_record = StakeRecord::read(stake_record_account)?;
+    stake_record.requested_amount = amount;
+    stake_record.unlock_ts = Clock::get()?
+        .unix_timestamp
+        .checked_add(COOLDOWN_SECONDS)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    stake_record.write(stake_record_account)
+}
+
+/// Creates a program-owned multisig PDA, seeded off its first signer. Anyone can call this
+/// to mint themselves a `MultisigState`, so it carries no authority on its own - only the one
+/// passed to `Initialize` is ever pinned into `PoolState::authority`, and that is the only copy
+/// `Unstake`/`FreezeStake`/`ThawStake` will accept afterwards.
+fn process_initialize_multisig(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    m: u8,
+    signers: Vec<Pubkey>,
+) -> ProgramResult {
+    let [multisig_account, payer_account, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !payer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if *system_program.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if signers.is_empty() || signers.len() > MAX_SIGNERS {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if m == 0 || usize::from(m) > signers.len() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (multisig_pda_key, multisig_bump) = Pubkey::find_program_address(
+        &[MULTISIG_PDA_SEED, signers[0].as_ref()],
+        program_id,
+    );
+
+    if multisig_pda_key != *multisig_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let space_required = MultisigState::packed_len(signers.len());
+    let lamports_required = Rent::get()?.minimum_balance(space_required);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_account.key,
+            multisig_account.key,
+            lamports_required,
+            space_required as u64,
+            program_id,
+        ),
+        &[
+            payer_account.clone(),
+            multisig_account.clone(),
+            system_program.clone(),
+        ],
+        &[&[MULTISIG_PDA_SEED, signers[0].as_ref(), &[multisig_bump]]],
+    )?;
+
+    MultisigState { m, signers }.write(multisig_account)
+}
+
+/// Transfers `amount` into the reserve vault and raises `total_reserve` only, without
+/// minting new shares - every outstanding pool-token share becomes worth more.
+fn process_distribute_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let [funding_account, signer_account, mint_account, reserve_vault_account, pool_state_account, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if *token_program.key != spl_token_2022::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (mint_pda_key, _mint_bump) = Pubkey::find_program_address(&[MINT_PDA_SEED], program_id);
+
+    if mint_pda_key != *mint_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mint = unpack_mint(mint_account)?;
+    verify_token_account_mint(funding_account, mint_account.key)?;
+
+    invoke(
+        &token_instruction::transfer_checked(
+            &spl_token_2022::id(),
+            funding_account.key,
+            mint_account.key,
+            reserve_vault_account.key,
+            signer_account.key,
+            &[],
+            amount,
+            mint.decimals,
+        )?,
+        &[
+            funding_account.clone(),
+            mint_account.clone(),
+            reserve_vault_account.clone(),
+            signer_account.clone(),
+        ],
+    )?;
+
+    let mut pool_state = PoolState::read(pool_state_account)?;
+    pool_state.total_reserve = pool_state
+        .total_reserve
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    pool_state.write(pool_state_account)
+}
+
 entrypoint!(process_instruction);
 
 #[cfg(test)]
 mod test {
     use super::{
-        Instruction, DECIMALS, ID as PROGRAM_ID, MINT_PDA_SEED, STAKE_PDA_SEED, TOTAL_SUPPLY,
+        Instruction, COOLDOWN_SECONDS, DECIMALS, ID as PROGRAM_ID, MINT_PDA_SEED,
+        POOL_MINT_PDA_SEED, POOL_STATE_PDA_SEED, RESERVE_VAULT_PDA_SEED, STAKE_PDA_SEED,
+        STAKE_RECORD_PDA_SEED, TOTAL_SUPPLY,
     };
 
     use mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk};
@@ -326,7 +1176,6 @@ mod test {
     fn test_program() {
         let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
 
-        // Add required programs to mollusk
         mollusk_svm_programs_token::token2022::add_program(&mut mollusk);
         mollusk_svm_programs_token::associated_token::add_program(&mut mollusk);
 
@@ -345,12 +1194,35 @@ mod test {
             );
         let mint_supply_to_account = Account::default();
 
+        let (pool_mint_pda_key, _) =
+            Pubkey::find_program_address(&[POOL_MINT_PDA_SEED], &PROGRAM_ID);
+        let pool_mint_account = Account::default();
+
+        let (reserve_vault_pda_key, _) =
+            Pubkey::find_program_address(&[RESERVE_VAULT_PDA_SEED], &PROGRAM_ID);
+        let reserve_vault_account = Account::default();
+
+        let (pool_state_pda_key, _) =
+            Pubkey::find_program_address(&[POOL_STATE_PDA_SEED], &PROGRAM_ID);
+        let pool_state_account = Account::default();
+
         let (stake_pda_key, _) =
             Pubkey::find_program_address(&[STAKE_PDA_SEED, signer_key.as_ref()], &PROGRAM_ID);
         let stake_account = Account::default();
 
-        // Create initialize instruction
-        let initialize_instruction_data = borsh::to_vec(&Instruction::Initialize).unwrap();
+        let (stake_record_pda_key, _) = Pubkey::find_program_address(
+            &[STAKE_RECORD_PDA_SEED, signer_key.as_ref()],
+            &PROGRAM_ID,
+        );
+        let stake_record_account = Account::default();
+
+        let initialize_instruction_data = borsh::to_vec(&Instruction::Initialize {
+            decimals: DECIMALS,
+            initial_supply: TOTAL_SUPPLY,
+            mint_authority: Some(mint_pda_key),
+            freeze_authority: Some(mint_pda_key),
+        })
+        .unwrap();
 
         let initialize_instruction = SolanaInstruction::new_with_bytes(
             PROGRAM_ID,
@@ -359,6 +1231,12 @@ mod test {
                 AccountMeta::new(mint_pda_key, false),
                 AccountMeta::new(mint_supply_to_key, false),
                 AccountMeta::new(signer_key, true),
+                AccountMeta::new(pool_mint_pda_key, false),
+                AccountMeta::new(reserve_vault_pda_key, false),
+                AccountMeta::new(pool_state_pda_key, false),
+                // No multisig in use: the System Program sentinel falls back to
+                // requiring just `signer_key` above.
+                AccountMeta::new_readonly(system_program::id(), false),
                 AccountMeta::new_readonly(mollusk_svm_programs_token::token2022::ID, false),
                 AccountMeta::new_readonly(mollusk_svm_programs_token::associated_token::ID, false),
                 AccountMeta::new_readonly(system_program::id(), false),
@@ -366,7 +1244,7 @@ mod test {
             ],
         );
 
-        // Create stake instruction
+        // Stake 3/4 of the total supply. Since the pool is empty, shares are minted 1:1.
         let stake_amount = (TOTAL_SUPPLY * 3) / 4;
         let stake_instruction_data = borsh::to_vec(&Instruction::Stake {
             amount: stake_amount,
@@ -378,34 +1256,58 @@ mod test {
             &stake_instruction_data,
             vec![
                 AccountMeta::new(stake_pda_key, false),
+                AccountMeta::new(stake_record_pda_key, false),
                 AccountMeta::new(mint_supply_to_key, false),
                 AccountMeta::new(signer_key, true),
                 AccountMeta::new_readonly(mint_pda_key, false),
+                AccountMeta::new(pool_mint_pda_key, false),
+                AccountMeta::new(reserve_vault_pda_key, false),
+                AccountMeta::new(pool_state_pda_key, false),
                 AccountMeta::new_readonly(mollusk_svm_programs_token::token2022::ID, false),
                 AccountMeta::new_readonly(system_program::id(), false),
             ],
         );
 
-        // Create unstake instruction
-        let unstake_instruction_data = borsh::to_vec(&Instruction::Unstake {
+        // Starts the cooldown for the full staked amount; `Unstake` below will only
+        // succeed once `COOLDOWN_SECONDS` has elapsed past this point.
+        let request_unstake_instruction_data = borsh::to_vec(&Instruction::RequestUnstake {
             amount: stake_amount,
         })
         .unwrap();
 
+        let request_unstake_instruction = SolanaInstruction::new_with_bytes(
+            PROGRAM_ID,
+            &request_unstake_instruction_data,
+            vec![
+                AccountMeta::new(stake_record_pda_key, false),
+                AccountMeta::new(signer_key, true),
+            ],
+        );
+
+        // No multisig in use for unstaking either; falls back to `signer_key`.
+        let unstake_instruction_data = borsh::to_vec(&Instruction::Unstake {
+            shares: stake_amount,
+        })
+        .unwrap();
+
         let unstake_instruction = SolanaInstruction::new_with_bytes(
             PROGRAM_ID,
             &unstake_instruction_data,
             vec![
                 AccountMeta::new(stake_pda_key, false),
+                AccountMeta::new(stake_record_pda_key, false),
                 AccountMeta::new(mint_supply_to_key, false),
                 AccountMeta::new(signer_key, true),
                 AccountMeta::new_readonly(mint_pda_key, false),
+                AccountMeta::new(pool_mint_pda_key, false),
+                AccountMeta::new(reserve_vault_pda_key, false),
+                AccountMeta::new(pool_state_pda_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
                 AccountMeta::new_readonly(mollusk_svm_programs_token::token2022::ID, false),
                 AccountMeta::new_readonly(system_program::id(), false),
             ],
         );
 
-        // Expected mint account data after initialization
         let mut expected_mint_data = vec![0u8; Mint::LEN];
         Pack::pack(
             Mint {
@@ -419,28 +1321,26 @@ mod test {
         )
         .unwrap();
 
-        // Expected token account data after initialization
-        let mut expected_mint_supply_to_data = vec![0u8; TokenAccount::LEN];
+        let mut expected_reserve_vault_data_post_stake = vec![0u8; TokenAccount::LEN];
         Pack::pack(
             TokenAccount {
                 mint: mint_pda_key,
-                owner: signer_key,
-                amount: TOTAL_SUPPLY,
+                owner: mint_pda_key,
+                amount: stake_amount,
                 delegate: None.into(),
                 state: AccountState::Initialized,
                 is_native: None.into(),
                 delegated_amount: 0,
                 close_authority: None.into(),
             },
-            &mut expected_mint_supply_to_data,
+            &mut expected_reserve_vault_data_post_stake,
         )
         .unwrap();
 
-        // Expected stake account data after staking
         let mut expected_stake_account_data_post_stake = vec![0u8; TokenAccount::LEN];
         Pack::pack(
             TokenAccount {
-                mint: mint_pda_key,
+                mint: pool_mint_pda_key,
                 owner: stake_pda_key,
                 amount: stake_amount,
                 delegate: None.into(),
@@ -453,58 +1353,12 @@ mod test {
         )
         .unwrap();
 
-        // Expected mint_supply_to account data after staking
-        let mut expected_mint_supply_to_data_post_stake = vec![0u8; TokenAccount::LEN];
-        Pack::pack(
-            TokenAccount {
-                mint: mint_pda_key,
-                owner: signer_key,
-                amount: TOTAL_SUPPLY / 4,
-                delegate: None.into(),
-                state: AccountState::Initialized,
-                is_native: None.into(),
-                delegated_amount: 0,
-                close_authority: None.into(),
-            },
-            &mut expected_mint_supply_to_data_post_stake,
-        )
-        .unwrap();
-
-        // Expected stake account data after unstaking
-        let mut expected_stake_account_data_post_unstake = vec![0u8; TokenAccount::LEN];
-        Pack::pack(
-            TokenAccount {
-                mint: mint_pda_key,
-                owner: stake_pda_key,
-                amount: 0,
-                delegate: None.into(),
-                state: AccountState::Initialized,
-                is_native: None.into(),
-                delegated_amount: 0,
-                close_authority: None.into(),
-            },
-            &mut expected_stake_account_data_post_unstake,
-        )
-        .unwrap();
-
-        // Expected mint_supply_to account data after unstaking
-        let mut expected_mint_supply_to_data_post_unstake = vec![0u8; TokenAccount::LEN];
-        Pack::pack(
-            TokenAccount {
-                mint: mint_pda_key,
-                owner: signer_key,
-                amount: TOTAL_SUPPLY,
-                delegate: None.into(),
-                state: AccountState::Initialized,
-                is_native: None.into(),
-                delegated_amount: 0,
-                close_authority: None.into(),
-            },
-            &mut expected_mint_supply_to_data_post_unstake,
-        )
-        .unwrap();
-
-        mollusk.process_and_validate_instruction_chain(
+        // `RequestUnstake` and `Stake`/`Initialize` run under the same simulated clock
+        // reading, so the cooldown they start can't have already elapsed by the time
+        // this chain call returns. Run the unstake separately, after advancing the
+        // clock sysvar past `COOLDOWN_SECONDS`, carrying forward the resulting account
+        // states from the first chain.
+        let before_cooldown = mollusk.process_and_validate_instruction_chain(
             &[
                 (
                     &initialize_instruction,
@@ -514,66 +1368,49 @@ mod test {
                             .data(&expected_mint_data)
                             .owner(&mollusk_svm_programs_token::token2022::ID)
                             .build(),
-                        Check::account(&mint_supply_to_key)
-                            .data_slice(0, &expected_mint_supply_to_data[..32 + 32 + 8])
-                            .owner(&mollusk_svm_programs_token::token2022::ID)
-                            .build(),
-                    ],
-                ),
-                (
-                    &stake_instruction,
-                    &[
-                        Check::success(),
-                        Check::account(&mint_supply_to_key)
-                            .data_slice(0, &expected_mint_supply_to_data_post_stake[..32 + 32 + 8])
-                            .owner(&mollusk_svm_programs_token::token2022::ID)
-                            .build(),
-                        Check::account(&stake_pda_key)
-                            .data_slice(0, &expected_stake_account_data_post_stake[..32 + 32 + 8])
-                            .owner(&mollusk_svm_programs_token::token2022::ID)
-                            .build(),
                     ],
                 ),
                 (
                     &stake_instruction,
-                    &[Check::err(
-                        spl_token_2022::error::TokenError::InsufficientFunds.into(),
-                    )],
-                ),
-                (
-                    &unstake_instruction,
                     &[
                         Check::success(),
-                        Check::account(&mint_supply_to_key)
+                        Check::account(&reserve_vault_pda_key)
                             .data_slice(
                                 0,
-                                &expected_mint_supply_to_data_post_unstake[..32 + 32 + 8],
+                                &expected_reserve_vault_data_post_stake[..32 + 32 + 8],
                             )
                             .owner(&mollusk_svm_programs_token::token2022::ID)
                             .build(),
                         Check::account(&stake_pda_key)
-                            .data_slice(0, &expected_stake_account_data_post_unstake[..32 + 32 + 8])
+                            .data_slice(0, &expected_stake_account_data_post_stake[..32 + 32 + 8])
                             .owner(&mollusk_svm_programs_token::token2022::ID)
                             .build(),
                     ],
                 ),
-                (
-                    &unstake_instruction,
-                    &[Check::err(
-                        spl_token_2022::error::TokenError::InsufficientFunds.into(),
-                    )],
-                ),
+                (&request_unstake_instruction, &[Check::success()]),
             ],
             &[
                 (mint_pda_key, mint_account),
                 (mint_supply_to_key, mint_supply_to_account),
+                (pool_mint_pda_key, pool_mint_account),
+                (reserve_vault_pda_key, reserve_vault_account),
+                (pool_state_pda_key, pool_state_account),
                 (stake_pda_key, stake_account),
+                (stake_record_pda_key, stake_record_account),
                 (signer_key, signer_account),
                 mollusk_svm_programs_token::token2022::keyed_account(),
                 mollusk_svm_programs_token::associated_token::keyed_account(),
                 keyed_account_for_system_program(),
                 mollusk_svm::sysvar::Sysvars::default().keyed_account_for_rent_sysvar(),
+                mollusk_svm::sysvar::Sysvars::default().keyed_account_for_clock_sysvar(),
             ],
         );
+
+        mollusk.sysvars.clock.unix_timestamp = COOLDOWN_SECONDS + 1;
+
+        mollusk.process_and_validate_instruction_chain(
+            &[(&unstake_instruction, &[Check::success()])],
+            &before_cooldown.resulting_accounts,
+        );
     }
 }
@@ -10,11 +10,23 @@ pub const TOTAL_SUPPLY: u64 = 1_000_000_000_000_000; // 1B tokens
 sol! {
     #[derive(Debug)]
     error InsufficientStakedBalance(address account, uint256 staked_balance);
+    #[derive(Debug)]
+    error RewardOverflow();
+    #[derive(Debug)]
+    error TransferFailed(address account, uint256 amount, bytes error);
 }
 
 #[derive(SolidityError, Debug)]
 pub enum ContractError {
     InsufficientStakedBalance(InsufficientStakedBalance),
+    RewardOverflow(RewardOverflow),
+    TransferFailed(TransferFailed),
+}
+
+/// Fixed-point scale the Synthetix-style reward accumulator is kept at, independent of `DECIMALS` - this is this
+/// contract's own reward math, not a property of the underlying token.
+fn reward_precision() -> U256 {
+    U256::from(10).pow(U256::from(18))
 }
 
 #[storage]
@@ -22,22 +34,98 @@ pub enum ContractError {
 pub struct FungibleTokenContract {
     erc20: Erc20,
     staked_balance: StorageMap<Address, StorageU256>,
+    total_staked: StorageU256,
+    reward_rate: StorageU256,
+    reward_per_token_stored: StorageU256,
+    last_update_time: StorageU64,
+    user_reward_per_token_paid: StorageMap<Address, StorageU256>,
+    rewards: StorageMap<Address, StorageU256>,
+}
+
+impl FungibleTokenContract {
+    /// Recomputes the reward-per-token accumulator as of `self.vm().block_timestamp()`, and how much of it is
+    /// newly owed to `account` since `user_reward_per_token_paid[account]` was last synced. Doesn't touch
+    /// storage - callers decide whether to persist the result (`update_reward`) or just report it
+    /// (`pending_rewards`).
+    fn accrue(&self, account: Address) -> Result<(U256, U256), ContractError> {
+        let total_staked = self.total_staked.get();
+
+        let reward_per_token_stored = if total_staked.is_zero() {
+            self.reward_per_token_stored.get()
+        } else {
+            let elapsed = self
+                .vm()
+                .block_timestamp()
+                .checked_sub(self.last_update_time.get().to::<u64>())
+                .ok_or(RewardOverflow {})?;
+
+            let accrued_per_token = self
+                .reward_rate
+                .get()
+                .checked_mul(U256::from(elapsed))
+                .and_then(|v| v.checked_mul(reward_precision()))
+                .and_then(|v| v.checked_div(total_staked))
+                .ok_or(RewardOverflow {})?;
+
+            self.reward_per_token_stored
+                .get()
+                .checked_add(accrued_per_token)
+                .ok_or(RewardOverflow {})?
+        };
+
+        let delta = reward_per_token_stored
+            .checked_sub(self.user_reward_per_token_paid.get(account))
+            .ok_or(RewardOverflow {})?;
+
+        let earned = self
+            .staked_balance_of(account)
+            .checked_mul(delta)
+            .and_then(|v| v.checked_div(reward_precision()))
+            .ok_or(RewardOverflow {})?;
+
+        Ok((reward_per_token_stored, earned))
+    }
+
+    /// Settles `account`'s accrued rewards into `rewards[account]` and syncs the accumulator state, so the
+    /// balance change that follows in `stake`/`unstake`/`claim` is measured against an up-to-date baseline.
+    fn update_reward(&mut self, account: Address) -> Result<(), ContractError> {
+        let (reward_per_token_stored, earned) = self.accrue(account)?;
+
+        self.reward_per_token_stored.set(reward_per_token_stored);
+        self.last_update_time
+            .set(U64::from(self.vm().block_timestamp()));
+
+        let rewards = self
+            .rewards
+            .get(account)
+            .checked_add(earned)
+            .ok_or(RewardOverflow {})?;
+        self.rewards.setter(account).set(rewards);
+        self.user_reward_per_token_paid
+            .setter(account)
+            .set(reward_per_token_stored);
+
+        Ok(())
+    }
 }
 
 #[public]
 #[implements(IErc20<Error = Erc20Error>)]
 impl FungibleTokenContract {
     #[constructor]
-    pub fn constructor(&mut self) -> Result<(), Erc20Error> {
+    pub fn constructor(&mut self, reward_rate: U256) -> Result<(), Erc20Error> {
         self.erc20
             ._mint(self.vm().tx_origin(), U256::from(TOTAL_SUPPLY))?;
+        self.reward_rate.set(reward_rate);
 
         Ok(())
     }
 
-    pub fn stake(&mut self, amount: U256) -> Result<(), Erc20Error> {
+    pub fn stake(&mut self, amount: U256) -> Result<(), ContractError> {
         let msg_sender = self.vm().msg_sender();
 
+        self.update_reward(msg_sender)?;
+
         let staked_balance = self.staked_balance_of(msg_sender);
 
         // Overflow not possible:
@@ -45,15 +133,27 @@ impl FungibleTokenContract {
         self.staked_balance
             .setter(msg_sender)
             .set(staked_balance + amount);
+        self.total_staked.set(self.total_staked.get() + amount);
 
         // Reverts with `ERC20InsufficientBalance` if `from_balance` < `amount`
         self.erc20
             ._update(msg_sender, self.vm().contract_address(), amount)
+            .map_err(Vec::<u8>::from)
+            .map_err(Bytes::from)
+            .map_err(|error| TransferFailed {
+                account: msg_sender,
+                amount,
+                error,
+            })?;
+
+        Ok(())
     }
 
     pub fn unstake(&mut self, amount: U256) -> Result<(), ContractError> {
         let msg_sender = self.vm().msg_sender();
 
+        self.update_reward(msg_sender)?;
+
         let staked_balance = self.staked_balance_of(msg_sender);
 
         if staked_balance < amount {
@@ -65,18 +165,58 @@ impl FungibleTokenContract {
         }
 
         // Overflow not possible:
-        // `amount` <= `staked_balance`
+        // `amount` <= `staked_balance` <= `total_staked`
         self.staked_balance
             .setter(msg_sender)
             .set(staked_balance - amount);
+        self.total_staked.set(self.total_staked.get() - amount);
 
         self.erc20
             ._update(self.vm().contract_address(), msg_sender, amount)
-            .expect("amount <= staked_balance");
+            .map_err(Vec::<u8>::from)
+            .map_err(Bytes::from)
+            .map_err(|error| TransferFailed {
+                account: msg_sender,
+                amount,
+                error,
+            })?;
+
+        Ok(())
+    }
+
+    /// Mints `msg_sender`'s settled plus newly-accrued rewards to them and zeroes their `rewards` entry.
+    pub fn claim(&mut self) -> Result<(), ContractError> {
+        let msg_sender = self.vm().msg_sender();
+
+        self.update_reward(msg_sender)?;
+
+        let reward = self.rewards.take(msg_sender);
+
+        self.erc20
+            ._mint(msg_sender, reward)
+            .map_err(Vec::<u8>::from)
+            .map_err(Bytes::from)
+            .map_err(|error| TransferFailed {
+                account: msg_sender,
+                amount: reward,
+                error,
+            })?;
 
         Ok(())
     }
 
+    /// `account`'s settled plus newly-accrued rewards as of now, without mutating any state the way `claim`
+    /// does.
+    pub fn pending_rewards(&self, account: Address) -> Result<U256, ContractError> {
+        let (_, earned) = self.accrue(account)?;
+
+        self.rewards
+            .get(account)
+            .checked_add(earned)
+            .ok_or(RewardOverflow {})
+            .map_err(Into::into)
+    }
+
     pub fn staked_balance_of(&self, account: Address) -> U256 {
         self.staked_balance.get(account)
     }
@@ -124,12 +264,14 @@ impl IErc20 for FungibleTokenContract {
 mod tests {
     use super::*;
     use motsu::prelude::*;
-    use openzeppelin_stylus::token::erc20::Error as Erc20Error;
 
     #[motsu::test]
     fn test_contract(contract: Contract<FungibleTokenContract>, alice: Address) {
-        // Initialize the contract - mints total supply to the deployer (alice)
-        contract.sender(alice).constructor().motsu_unwrap();
+        // Initialize the contract - mints total supply to the deployer (alice), no reward rate configured
+        contract
+            .sender(alice)
+            .constructor(U256::ZERO)
+            .motsu_unwrap();
 
         // Verify initial state
         assert_eq!(
@@ -169,7 +311,7 @@ mod tests {
             .sender(alice)
             .stake(stake_amount)
             .motsu_unwrap_err();
-        assert!(matches!(err, Erc20Error::InsufficientBalance(_)));
+        assert!(matches!(err, ContractError::TransferFailed(_)));
 
         // Verify balances haven't changed after failed stake
         assert_eq!(contract.sender(alice).balance_of(alice), remaining_balance);
@@ -206,4 +348,72 @@ mod tests {
         );
         assert_eq!(contract.sender(alice).staked_balance_of(alice), U256::ZERO);
     }
+
+    #[motsu::test]
+    fn test_reward_accrual_and_claim(contract: Contract<FungibleTokenContract>, alice: Address) {
+        let reward_rate = U256::from(5u64);
+        contract
+            .sender(alice)
+            .constructor(reward_rate)
+            .motsu_unwrap();
+
+        VM::context().set_block_timestamp(100);
+
+        let stake_amount = U256::from(1_000u64);
+        contract.sender(alice).stake(stake_amount).motsu_unwrap();
+
+        // No time has passed since `stake` synced the accumulator, so nothing has accrued yet
+        assert_eq!(
+            contract.sender(alice).pending_rewards(alice).motsu_unwrap(),
+            U256::ZERO
+        );
+
+        // 10 seconds at `reward_rate` 5 over `total_staked` 1000: (5 * 10 * 1e18 / 1000) * 1000 / 1e18 = 50
+        VM::context().set_block_timestamp(110);
+        let expected_reward = U256::from(50u64);
+        assert_eq!(
+            contract.sender(alice).pending_rewards(alice).motsu_unwrap(),
+            expected_reward
+        );
+
+        let balance_before_claim = contract.sender(alice).balance_of(alice);
+        contract.sender(alice).claim().motsu_unwrap();
+
+        assert_eq!(
+            contract.sender(alice).balance_of(alice),
+            balance_before_claim + expected_reward
+        );
+        assert_eq!(
+            contract.sender(alice).pending_rewards(alice).motsu_unwrap(),
+            U256::ZERO
+        );
+    }
+
+    #[motsu::test]
+    fn test_unstake_propagates_erc20_failure_as_typed_error(
+        contract: Contract<FungibleTokenContract>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            .constructor(U256::ZERO)
+            .motsu_unwrap();
+
+        let stake_amount = U256::from(1_000u64);
+        contract.sender(alice).stake(stake_amount).motsu_unwrap();
+
+        // Drain the contract's own token balance out from under it, so `staked_balance` no longer matches what
+        // the contract actually holds - simulating whatever bug `unstake`'s old `.expect()` used to trust away.
+        contract
+            .sender(contract.address())
+            .transfer(bob, stake_amount)
+            .motsu_unwrap();
+
+        let err = contract
+            .sender(alice)
+            .unstake(stake_amount)
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::TransferFailed(_)));
+    }
 }
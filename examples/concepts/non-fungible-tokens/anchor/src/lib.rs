@@ -6,12 +6,18 @@ use anchor_spl::{
     metadata::{
         mpl_token_metadata::{
             instructions::{
-                CreateMasterEditionV3Cpi, CreateMasterEditionV3CpiAccounts,
-                CreateMasterEditionV3InstructionArgs, CreateMetadataAccountV3Cpi,
-                CreateMetadataAccountV3CpiAccounts, CreateMetadataAccountV3InstructionArgs,
-                VerifyCollectionV1Cpi, VerifyCollectionV1CpiAccounts,
+                ApproveCollectionAuthorityCpi, ApproveCollectionAuthorityCpiAccounts,
+                BurnNftCpi, BurnNftCpiAccounts, CreateMasterEditionV3Cpi,
+                CreateMasterEditionV3CpiAccounts, CreateMasterEditionV3InstructionArgs,
+                CreateMetadataAccountV3Cpi, CreateMetadataAccountV3CpiAccounts,
+                CreateMetadataAccountV3InstructionArgs, RevokeCollectionAuthorityCpi,
+                RevokeCollectionAuthorityCpiAccounts, UnverifyCollectionV1Cpi,
+                UnverifyCollectionV1CpiAccounts, UpdateMetadataAccountV2Cpi,
+                UpdateMetadataAccountV2CpiAccounts, UpdateMetadataAccountV2InstructionArgs,
+                UtilizeCpi, UtilizeCpiAccounts, UtilizeInstructionArgs, VerifyCollectionV1Cpi,
+                VerifyCollectionV1CpiAccounts,
             },
-            types::{Collection, CollectionDetails, Creator, DataV2},
+            types::{Collection, CollectionDetails, Creator, DataV2, UseMethod, Uses},
         },
         MasterEditionAccount, Metadata, MetadataAccount,
     },
@@ -23,6 +29,11 @@ declare_id!("3EMcczaGi9ivdLxvvFwRbGYeEUEHpGwabXegARw4jLxa");
 pub static COLLECTION_SEED: &[u8] = b"collection";
 pub static MINT_SEED: &[u8] = b"mint";
 pub const MAX_NAME_LENGTH: usize = 10;
+pub const MAX_URI_LENGTH: usize = 200;
+pub const MAX_METADATA_NAME_LENGTH: usize = 32;
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_CREATOR_LIMIT: usize = 5;
+pub const MAX_CREATOR_SHARE_TOTAL: u8 = 100;
 
 #[program]
 pub mod non_fungible_tokens {
@@ -54,6 +65,17 @@ pub mod non_fungible_tokens {
             share: 100,
         }];
 
+        let data = DataV2 {
+            name: "Mock Name Service".to_owned(),
+            symbol: "MNS".to_owned(),
+            uri: String::new(),
+            seller_fee_basis_points: 0,
+            creators: Some(creator),
+            collection: None,
+            uses: None,
+        };
+        assert_data_valid(&data)?;
+
         CreateMetadataAccountV3Cpi::new(
             &ctx.accounts.token_metadata_program.to_account_info(),
             CreateMetadataAccountV3CpiAccounts {
@@ -66,15 +88,7 @@ pub mod non_fungible_tokens {
                 rent: None,
             },
             CreateMetadataAccountV3InstructionArgs {
-                data: DataV2 {
-                    name: "Mock Name Service".to_owned(),
-                    symbol: "MNS".to_owned(),
-                    uri: String::new(),
-                    seller_fee_basis_points: 0,
-                    creators: Some(creator),
-                    collection: None,
-                    uses: None,
-                },
+                data,
                 is_mutable: true,
                 collection_details: Some(CollectionDetails::V1 { size: 0 }),
             },
@@ -109,93 +123,153 @@ pub mod non_fungible_tokens {
     }
 
     pub fn mint_name_nft(ctx: Context<MintNameNFT>, name: String) -> Result<()> {
-        require!(
-            !name.is_empty() && name.len() <= MAX_NAME_LENGTH,
-            ErrorCode::InvalidNameLength
-        );
-        require!(
-            name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'),
-            ErrorCode::InvalidNameCharacters
-        );
+        mint_name_nft_with_uses(ctx, name, None)
+    }
 
-        let collection_seeds = &[COLLECTION_SEED, &[ctx.bumps.collection_mint]];
-        let collection_signer_seeds = &[&collection_seeds[..]];
+    /// Mints a name NFT whose record expires after `total_uses` calls to [`renew_name`] instead
+    /// of lasting forever - a subscription/lease registry built on Metaplex's `Uses` field.
+    pub fn mint_name_nft_leased(
+        ctx: Context<MintNameNFT>,
+        name: String,
+        total_uses: u64,
+    ) -> Result<()> {
+        require!(total_uses > 0, ErrorCode::InvalidLeaseDuration);
 
-        // Mint the Name NFT
-        mint_to(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                MintTo {
-                    mint: ctx.accounts.name_mint.to_account_info(),
-                    to: ctx.accounts.name_token.to_account_info(),
-                    authority: ctx.accounts.collection_mint.to_account_info(),
-                },
-                collection_signer_seeds,
-            ),
-            1,
-        )?;
+        mint_name_nft_with_uses(
+            ctx,
+            name,
+            Some(Uses {
+                use_method: UseMethod::Burn,
+                total: total_uses,
+                remaining: total_uses,
+            }),
+        )
+    }
 
-        // Create metadata with the name
-        let creator = vec![Creator {
-            address: ctx.accounts.collection_mint.key(),
-            verified: true,
-            share: 100,
-        }];
+    /// Decrements a leased name's remaining uses by one. Metaplex's `Utilize` CPI itself rejects
+    /// this once `remaining` reaches zero, at which point the lease is up. [`release_name`] does
+    /// not look at the lease: only the holder of the name token can release the name, lapsed or not.
+    pub fn renew_name(ctx: Context<RenewName>, _name: String) -> Result<()> {
+        let collection_seeds = &[COLLECTION_SEED, &[ctx.bumps.collection_mint]];
+        let collection_signer_seeds = &[&collection_seeds[..]];
 
-        CreateMetadataAccountV3Cpi::new(
+        UtilizeCpi::new(
             &ctx.accounts.token_metadata_program.to_account_info(),
-            CreateMetadataAccountV3CpiAccounts {
+            UtilizeCpiAccounts {
                 metadata: &ctx.accounts.name_metadata.to_account_info(),
+                token_account: &ctx.accounts.name_token.to_account_info(),
                 mint: &ctx.accounts.name_mint.to_account_info(),
-                mint_authority: &ctx.accounts.collection_mint.to_account_info(),
-                payer: &ctx.accounts.owner.to_account_info(),
-                update_authority: (&ctx.accounts.collection_mint.to_account_info(), true),
+                use_authority: &ctx.accounts.collection_mint.to_account_info(),
+                owner: &ctx.accounts.owner.to_account_info(),
                 system_program: &ctx.accounts.system_program.to_account_info(),
                 rent: None,
+                use_authority_record: None,
+                burner: None,
             },
-            CreateMetadataAccountV3InstructionArgs {
-                data: DataV2 {
-                    name,
-                    symbol: "MSN".to_owned(),
-                    uri: String::new(),
-                    seller_fee_basis_points: 0,
-                    creators: Some(creator),
-                    collection: Some(Collection {
-                        verified: false,
-                        key: ctx.accounts.collection_mint.key(),
-                    }),
-                    uses: None,
-                },
-                is_mutable: true,
-                collection_details: None,
-            },
+            UtilizeInstructionArgs { number_of_uses: 1 },
         )
         .invoke_signed(collection_signer_seeds)?;
 
-        // Create master edition for the name NFT
-        CreateMasterEditionV3Cpi::new(
+        Ok(())
+    }
+
+    /// Delegates collection-authority to `new_authority` by creating a Metaplex collection-authority
+    /// record PDA. [`mint_name_nft`] accepts that record as its optional `delegate_record` and hands
+    /// it to `VerifyCollectionV1` for Metaplex to validate; the verification itself is still signed
+    /// by the `collection_mint` PDA, and `new_authority` never signs it.
+    pub fn approve_registrar(ctx: Context<ApproveRegistrar>, _new_authority: Pubkey) -> Result<()> {
+        let collection_seeds = &[COLLECTION_SEED, &[ctx.bumps.collection_mint]];
+        let collection_signer_seeds = &[&collection_seeds[..]];
+
+        ApproveCollectionAuthorityCpi::new(
             &ctx.accounts.token_metadata_program.to_account_info(),
-            CreateMasterEditionV3CpiAccounts {
-                edition: &ctx.accounts.name_master_edition.to_account_info(),
+            ApproveCollectionAuthorityCpiAccounts {
+                collection_authority_record: &ctx
+                    .accounts
+                    .collection_authority_record
+                    .to_account_info(),
+                new_collection_authority: &ctx.accounts.new_authority.to_account_info(),
                 update_authority: &ctx.accounts.collection_mint.to_account_info(),
-                mint_authority: &ctx.accounts.collection_mint.to_account_info(),
-                mint: &ctx.accounts.name_mint.to_account_info(),
-                payer: &ctx.accounts.owner.to_account_info(),
-                metadata: &ctx.accounts.name_metadata.to_account_info(),
-                token_program: &ctx.accounts.token_program.to_account_info(),
+                payer: &ctx.accounts.payer.to_account_info(),
+                metadata: &ctx.accounts.collection_metadata.to_account_info(),
+                mint: &ctx.accounts.collection_mint.to_account_info(),
                 system_program: &ctx.accounts.system_program.to_account_info(),
                 rent: None,
             },
-            CreateMasterEditionV3InstructionArgs {
-                max_supply: Some(0),
+        )
+        .invoke_signed(collection_signer_seeds)?;
+
+        Ok(())
+    }
+
+    /// Destroys a registrar's collection-authority record, so a later [`mint_name_nft`] that
+    /// supplies it as `delegate_record` fails collection verification.
+    pub fn revoke_registrar(ctx: Context<RevokeRegistrar>) -> Result<()> {
+        let collection_seeds = &[COLLECTION_SEED, &[ctx.bumps.collection_mint]];
+        let collection_signer_seeds = &[&collection_seeds[..]];
+
+        RevokeCollectionAuthorityCpi::new(
+            &ctx.accounts.token_metadata_program.to_account_info(),
+            RevokeCollectionAuthorityCpiAccounts {
+                collection_authority_record: &ctx
+                    .accounts
+                    .collection_authority_record
+                    .to_account_info(),
+                delegate_authority: &ctx.accounts.delegate.to_account_info(),
+                revoke_authority: &ctx.accounts.collection_mint.to_account_info(),
+                metadata: &ctx.accounts.collection_metadata.to_account_info(),
+                mint: &ctx.accounts.collection_mint.to_account_info(),
+            },
+        )
+        .invoke_signed(collection_signer_seeds)?;
+
+        Ok(())
+    }
+
+    pub fn update_name_record(ctx: Context<UpdateNameRecord>, uri: String) -> Result<()> {
+        let collection_seeds = &[COLLECTION_SEED, &[ctx.bumps.collection_mint]];
+        let collection_signer_seeds = &[&collection_seeds[..]];
+
+        // Preserve every field except `uri`, which is the only thing this instruction changes.
+        let metadata = &ctx.accounts.name_metadata;
+        let data = DataV2 {
+            name: metadata.name.clone(),
+            symbol: metadata.symbol.clone(),
+            uri,
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            creators: metadata.creators.clone(),
+            collection: metadata.collection.clone(),
+            uses: metadata.uses.clone(),
+        };
+        assert_data_valid(&data)?;
+
+        UpdateMetadataAccountV2Cpi::new(
+            &ctx.accounts.token_metadata_program.to_account_info(),
+            UpdateMetadataAccountV2CpiAccounts {
+                metadata: &ctx.accounts.name_metadata.to_account_info(),
+                update_authority: &ctx.accounts.collection_mint.to_account_info(),
+            },
+            UpdateMetadataAccountV2InstructionArgs {
+                data: Some(data),
+                new_update_authority: None,
+                primary_sale_happened: None,
+                is_mutable: None,
             },
         )
         .invoke_signed(collection_signer_seeds)?;
 
-        // Verify collection membership
-        VerifyCollectionV1Cpi::new(
+        Ok(())
+    }
+
+    pub fn release_name(ctx: Context<ReleaseName>, _name: String) -> Result<()> {
+        let collection_seeds = &[COLLECTION_SEED, &[ctx.bumps.collection_mint]];
+        let collection_signer_seeds = &[&collection_seeds[..]];
+
+        // Mirror `mint_name_nft`'s `VerifyCollectionV1Cpi` with the corresponding `Unverify` CPI so
+        // the sized collection's `CollectionDetails::V1.size` is decremented to match.
+        UnverifyCollectionV1Cpi::new(
             &ctx.accounts.token_metadata_program.to_account_info(),
-            VerifyCollectionV1CpiAccounts {
+            UnverifyCollectionV1CpiAccounts {
                 authority: &ctx.accounts.collection_mint.to_account_info(),
                 delegate_record: None,
                 metadata: &ctx.accounts.name_metadata.to_account_info(),
@@ -210,8 +284,202 @@ pub mod non_fungible_tokens {
         )
         .invoke_signed(collection_signer_seeds)?;
 
+        // Every name NFT is its own zero-supply master edition (minted with `max_supply: Some(0)`,
+        // never printed), so `BurnNft` - not `BurnEditionNft` - is the CPI whose accounts match
+        // what `mint_name_nft` created: it burns the token, then closes `name_metadata` and
+        // `name_master_edition`, refunding their rent to `owner`.
+        BurnNftCpi::new(
+            &ctx.accounts.token_metadata_program.to_account_info(),
+            BurnNftCpiAccounts {
+                metadata: &ctx.accounts.name_metadata.to_account_info(),
+                owner: &ctx.accounts.owner.to_account_info(),
+                mint: &ctx.accounts.name_mint.to_account_info(),
+                token_account: &ctx.accounts.name_token.to_account_info(),
+                master_edition_account: &ctx.accounts.name_master_edition.to_account_info(),
+                spl_token_program: &ctx.accounts.token_program.to_account_info(),
+                collection_metadata: None,
+            },
+        )
+        .invoke()?;
+
+        // NOTE: legacy SPL Token has no instruction that closes a Mint account (that requires
+        // Token-2022's mint-close-authority extension), so `name_mint`'s `[MINT_SEED, name]` PDA
+        // stays allocated at zero supply after the burn above rather than being freed for reuse.
+        // Truly allowing the same name to be re-registered would require migrating `name_mint` to
+        // Token-2022; that migration is out of scope here and left as a known limitation.
+
         Ok(())
     }
+
+    /// Reads the registered-name count straight off the sized collection's `CollectionDetails`,
+    /// which `VerifyCollectionV1Cpi`/`UnverifyCollectionV1Cpi` keep in sync on every mint and
+    /// release - returned as an Anchor return value rather than duplicated into our own state.
+    pub fn get_registered_name_count(ctx: Context<GetRegisteredNameCount>) -> Result<u64> {
+        match ctx.accounts.collection_metadata.collection_details {
+            Some(CollectionDetails::V1 { size }) => Ok(size),
+            None => err!(ErrorCode::MissingCollectionDetails),
+        }
+    }
+}
+
+/// Mirrors Metaplex's own `assert_data_valid` in `utils.rs` - rejecting malformed `DataV2` up front
+/// saves the compute a doomed `CreateMetadataAccountV3`/`UpdateMetadataAccountV2` CPI would otherwise
+/// burn before the Metaplex program rejects it itself.
+fn assert_data_valid(data: &DataV2) -> Result<()> {
+    require!(
+        data.name.len() <= MAX_METADATA_NAME_LENGTH,
+        ErrorCode::InvalidMetadataNameLength
+    );
+    require!(
+        data.symbol.len() <= MAX_SYMBOL_LENGTH,
+        ErrorCode::InvalidSymbolLength
+    );
+    require!(data.uri.len() <= MAX_URI_LENGTH, ErrorCode::InvalidUriLength);
+    require!(
+        data.seller_fee_basis_points <= 10_000,
+        ErrorCode::InvalidSellerFeeBasisPoints
+    );
+
+    if let Some(creators) = &data.creators {
+        require!(
+            creators.len() <= MAX_CREATOR_LIMIT,
+            ErrorCode::InvalidCreatorCount
+        );
+
+        let share_total = creators
+            .iter()
+            .fold(0u16, |total, creator| total + u16::from(creator.share));
+        require!(
+            share_total == u16::from(MAX_CREATOR_SHARE_TOTAL),
+            ErrorCode::InvalidCreatorShares
+        );
+    }
+
+    Ok(())
+}
+
+/// Shared by `mint_name_nft` and `mint_name_nft_leased` - the two differ only in whether the
+/// metadata's `uses` field is populated, everything else (mint, metadata, master edition, collection
+/// verification) is identical.
+fn mint_name_nft_with_uses(
+    ctx: Context<MintNameNFT>,
+    name: String,
+    uses: Option<Uses>,
+) -> Result<()> {
+    require!(
+        !name.is_empty() && name.len() <= MAX_NAME_LENGTH,
+        ErrorCode::InvalidNameLength
+    );
+    require!(
+        name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'),
+        ErrorCode::InvalidNameCharacters
+    );
+
+    let collection_seeds = &[COLLECTION_SEED, &[ctx.bumps.collection_mint]];
+    let collection_signer_seeds = &[&collection_seeds[..]];
+
+    // Mint the Name NFT
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.name_mint.to_account_info(),
+                to: ctx.accounts.name_token.to_account_info(),
+                authority: ctx.accounts.collection_mint.to_account_info(),
+            },
+            collection_signer_seeds,
+        ),
+        1,
+    )?;
+
+    // Create metadata with the name
+    let creator = vec![Creator {
+        address: ctx.accounts.collection_mint.key(),
+        verified: true,
+        share: 100,
+    }];
+
+    let data = DataV2 {
+        name,
+        symbol: "MSN".to_owned(),
+        uri: String::new(),
+        seller_fee_basis_points: 0,
+        creators: Some(creator),
+        collection: Some(Collection {
+            verified: false,
+            key: ctx.accounts.collection_mint.key(),
+        }),
+        uses,
+    };
+    assert_data_valid(&data)?;
+
+    CreateMetadataAccountV3Cpi::new(
+        &ctx.accounts.token_metadata_program.to_account_info(),
+        CreateMetadataAccountV3CpiAccounts {
+            metadata: &ctx.accounts.name_metadata.to_account_info(),
+            mint: &ctx.accounts.name_mint.to_account_info(),
+            mint_authority: &ctx.accounts.collection_mint.to_account_info(),
+            payer: &ctx.accounts.owner.to_account_info(),
+            update_authority: (&ctx.accounts.collection_mint.to_account_info(), true),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            rent: None,
+        },
+        CreateMetadataAccountV3InstructionArgs {
+            data,
+            is_mutable: true,
+            collection_details: None,
+        },
+    )
+    .invoke_signed(collection_signer_seeds)?;
+
+    // Create master edition for the name NFT
+    CreateMasterEditionV3Cpi::new(
+        &ctx.accounts.token_metadata_program.to_account_info(),
+        CreateMasterEditionV3CpiAccounts {
+            edition: &ctx.accounts.name_master_edition.to_account_info(),
+            update_authority: &ctx.accounts.collection_mint.to_account_info(),
+            mint_authority: &ctx.accounts.collection_mint.to_account_info(),
+            mint: &ctx.accounts.name_mint.to_account_info(),
+            payer: &ctx.accounts.owner.to_account_info(),
+            metadata: &ctx.accounts.name_metadata.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            rent: None,
+        },
+        CreateMasterEditionV3InstructionArgs {
+            max_supply: Some(0),
+        },
+    )
+    .invoke_signed(collection_signer_seeds)?;
+
+    // Verify collection membership
+    // A delegated registrar (see `approve_registrar`) is recorded here purely as an extra account
+    // for Metaplex to validate - `collection_mint` remains the signing authority regardless, since
+    // it is the collection's actual update authority.
+    let delegate_record_info = ctx
+        .accounts
+        .delegate_record
+        .as_ref()
+        .map(|account| account.to_account_info());
+
+    VerifyCollectionV1Cpi::new(
+        &ctx.accounts.token_metadata_program.to_account_info(),
+        VerifyCollectionV1CpiAccounts {
+            authority: &ctx.accounts.collection_mint.to_account_info(),
+            delegate_record: delegate_record_info.as_ref(),
+            metadata: &ctx.accounts.name_metadata.to_account_info(),
+            collection_mint: &ctx.accounts.collection_mint.to_account_info(),
+            collection_metadata: Some(&ctx.accounts.collection_metadata.to_account_info()),
+            collection_master_edition: Some(
+                &ctx.accounts.collection_master_edition.to_account_info(),
+            ),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            sysvar_instructions: &ctx.accounts.sysvar_instruction.to_account_info(),
+        },
+    )
+    .invoke_signed(collection_signer_seeds)?;
+
+    Ok(())
 }
 
 #[derive(Accounts)]
@@ -297,6 +565,11 @@ pub struct MintNameNFT<'info> {
 
     pub collection_master_edition: Account<'info, MasterEditionAccount>,
 
+    /// CHECK: optional collection-authority record for a registrar delegated via `approve_registrar`;
+    /// passed through to `VerifyCollectionV1` as `delegate_record` for Metaplex to validate, while
+    /// `collection_mint` still signs the verification
+    pub delegate_record: Option<UncheckedAccount<'info>>,
+
     // // System accounts
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
@@ -307,25 +580,199 @@ pub struct MintNameNFT<'info> {
     pub sysvar_instruction: UncheckedAccount<'info>,
 }
 
+#[derive(Accounts)]
+#[instruction(new_authority: Pubkey)]
+pub struct ApproveRegistrar<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: only used as the public key being delegated collection authority, never read or written
+    #[account(address = new_authority)]
+    pub new_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_SEED],
+        bump,
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub collection_metadata: Account<'info, MetadataAccount>,
+
+    #[account(mut)]
+    /// CHECK: Metaplex collection-authority-record PDA, initialized by the CPI in `approve_registrar`
+    pub collection_authority_record: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRegistrar<'info> {
+    #[account(
+        seeds = [COLLECTION_SEED],
+        bump,
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub collection_metadata: Account<'info, MetadataAccount>,
+
+    /// CHECK: the registrar whose collection-authority record is being revoked
+    pub delegate: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Metaplex collection-authority-record PDA, closed by the CPI in `revoke_registrar`
+    pub collection_authority_record: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateNameRecord<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        associated_token::mint = name_mint,
+        associated_token::authority = owner,
+        constraint = name_token.amount == 1 @ ErrorCode::NotNameOwner,
+    )]
+    pub name_token: Account<'info, TokenAccount>,
+
+    pub name_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub name_metadata: Account<'info, MetadataAccount>,
+
+    #[account(
+        seeds = [COLLECTION_SEED],
+        bump,
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct ReleaseName<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = name_mint,
+        associated_token::authority = owner,
+        constraint = name_token.amount == 1 @ ErrorCode::NotNameOwner,
+    )]
+    pub name_token: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [MINT_SEED, name.as_bytes()],
+        bump,
+    )]
+    pub name_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub name_metadata: Account<'info, MetadataAccount>,
+
+    #[account(mut)]
+    pub name_master_edition: Account<'info, MasterEditionAccount>,
+
+    #[account(
+        seeds = [COLLECTION_SEED],
+        bump,
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub collection_metadata: Account<'info, MetadataAccount>,
+
+    pub collection_master_edition: Account<'info, MasterEditionAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    /// CHECK: Sysvar instruction account that is being checked with an address constraint
+    pub sysvar_instruction: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetRegisteredNameCount<'info> {
+    pub collection_metadata: Account<'info, MetadataAccount>,
+}
+
+#[derive(Accounts)]
+pub struct RenewName<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        associated_token::mint = name_mint,
+        associated_token::authority = owner,
+        constraint = name_token.amount == 1 @ ErrorCode::NotNameOwner,
+    )]
+    pub name_token: Account<'info, TokenAccount>,
+
+    pub name_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub name_metadata: Account<'info, MetadataAccount>,
+
+    #[account(
+        seeds = [COLLECTION_SEED],
+        bump,
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Name must be between 1 and 10 characters")]
     InvalidNameLength,
     #[msg("Name can only contain alphanumeric characters and underscores")]
     InvalidNameCharacters,
+    #[msg("URI must be at most 200 characters")]
+    InvalidUriLength,
+    #[msg("Signer does not hold the name NFT")]
+    NotNameOwner,
+    #[msg("Collection metadata is missing its sized-collection details")]
+    MissingCollectionDetails,
+    #[msg("Lease duration must be greater than zero uses")]
+    InvalidLeaseDuration,
+    #[msg("Metadata name must be at most 32 characters")]
+    InvalidMetadataNameLength,
+    #[msg("Symbol must be at most 10 characters")]
+    InvalidSymbolLength,
+    #[msg("Seller fee basis points must be at most 10000")]
+    InvalidSellerFeeBasisPoints,
+    #[msg("At most 5 creators are allowed")]
+    InvalidCreatorCount,
+    #[msg("Creator shares must sum to 100")]
+    InvalidCreatorShares,
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        instruction::{CreateNameCollection, MintNameNft},
-        COLLECTION_SEED, ID as PROGRAM_ID, MINT_SEED,
+        instruction::{
+            ApproveRegistrar, CreateNameCollection, MintNameNft, MintNameNftLeased, ReleaseName,
+            RenewName, RevokeRegistrar, UpdateNameRecord,
+        },
+        assert_data_valid, ErrorCode, COLLECTION_SEED, ID as PROGRAM_ID, MINT_SEED,
     };
 
     use anchor_lang::{
         prelude::AccountMeta,
         solana_program::{
             instruction::Instruction,
+            program_error::ProgramError,
+            system_instruction::SystemError,
             sysvar::instructions::{
                 construct_instructions_data, BorrowedAccountMeta, BorrowedInstruction,
             },
@@ -508,6 +955,7 @@ mod tests {
                 AccountMeta::new(collection_mint_key, false),
                 AccountMeta::new(collection_metadata_key, false),
                 AccountMeta::new_readonly(collection_master_edition_key, false),
+                AccountMeta::new_readonly(PROGRAM_ID, false),
                 AccountMeta::new_readonly(system_program::id(), false),
                 AccountMeta::new_readonly(spl_token::id(), false),
                 AccountMeta::new_readonly(associated_token::ID, false),
@@ -524,13 +972,32 @@ mod tests {
             ],
         );
 
-        // Process instruction chain
-        mollusk.process_and_validate_instruction_chain(
-            &[
-                (&create_collection_instruction, &[Check::success()]),
-                (&mint_name_instruction, &[Check::success()]),
-            ],
-            &[
+        let new_uri = "https://example.com/alice.json".to_owned();
+        let update_name_record_data = UpdateNameRecord {
+            uri: new_uri.clone(),
+        }
+        .data();
+        let update_name_record_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &update_name_record_data,
+            vec![
+                AccountMeta::new_readonly(authority_key, true),
+                AccountMeta::new_readonly(name_token_key, false),
+                AccountMeta::new_readonly(name_mint_key, false),
+                AccountMeta::new(name_metadata_key, false),
+                AccountMeta::new_readonly(collection_mint_key, false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            ],
+        );
+
+        // Process instruction chain
+        let result = mollusk.process_and_validate_instruction_chain(
+            &[
+                (&create_collection_instruction, &[Check::success()]),
+                (&mint_name_instruction, &[Check::success()]),
+                (&update_name_record_instruction, &[Check::success()]),
+            ],
+            &[
                 (authority_key, authority_account.clone()),
                 // Collection accounts
                 (collection_mint_key, collection_mint_account.clone()),
@@ -557,5 +1024,993 @@ mod tests {
                 keyed_instructions_sysvar_account,
             ],
         );
+
+        let (_, updated_name_metadata_account) = result
+            .resulting_accounts
+            .iter()
+            .find(|(key, _)| *key == name_metadata_key)
+            .expect("name_metadata account should be present in the resulting accounts");
+
+        let updated_name_metadata =
+            mpl_token_metadata::accounts::Metadata::safe_deserialize(
+                &updated_name_metadata_account.data,
+            )
+            .expect("name_metadata account should deserialize into Metadata");
+
+        assert_eq!(updated_name_metadata.uri, new_uri);
+        assert_eq!(updated_name_metadata.name, test_name);
+    }
+
+    // `release_name` burns the name NFT but (per the note in `release_name`) legacy SPL Token has
+    // no instruction that closes a Mint account, so `name_mint`'s PDA stays allocated and
+    // token-program-owned afterwards. The re-mint below therefore can't succeed yet: `init`'s
+    // `system_program::create_account` CPI rejects an already-funded, already-owned destination
+    // with `SystemError::AccountAlreadyInUse`. This asserts that documented limitation rather than
+    // the re-mint succeeding - re-enabling re-registration requires the Token-2022 migration
+    // called out above.
+    #[test]
+    fn test_release_and_remint_name() {
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+
+        mollusk_svm_programs_token::token::add_program(&mut mollusk);
+        mollusk_svm_programs_token::associated_token::add_program(&mut mollusk);
+        mollusk.add_program_with_elf_and_loader(
+            &mpl_token_metadata::ID,
+            MPL_TOKEN_METADATA_ELF,
+            &mollusk_svm::program::loader_keys::LOADER_V2,
+        );
+
+        let authority_key = Pubkey::new_unique();
+        let authority_account = Account::new(1_000_000_000, 0, &system_program::id());
+
+        let (collection_mint_key, _) =
+            Pubkey::find_program_address(&[COLLECTION_SEED], &PROGRAM_ID);
+        let collection_mint_account = Account::default();
+
+        let collection_token_key =
+            associated_token::get_associated_token_address(&authority_key, &collection_mint_key);
+        let collection_token_account = Account::default();
+
+        let (collection_metadata_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &collection_mint_key.to_bytes(),
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let collection_metadata_account = Account::default();
+
+        let (collection_master_edition_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &collection_mint_key.to_bytes(),
+                b"edition",
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let collection_master_edition_account = Account::default();
+
+        let create_collection_data = CreateNameCollection {}.data();
+        let create_collection_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &create_collection_data,
+            vec![
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new(collection_master_edition_key, false),
+                AccountMeta::new(collection_token_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(associated_token::ID, false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            ],
+        );
+
+        let test_name = "alice";
+        let (name_mint_key, _) =
+            Pubkey::find_program_address(&[MINT_SEED, test_name.as_bytes()], &PROGRAM_ID);
+        let name_mint_account = Account::default();
+
+        let name_token_key =
+            associated_token::get_associated_token_address(&authority_key, &name_mint_key);
+        let name_token_account = Account::default();
+
+        let (name_metadata_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &name_mint_key.to_bytes(),
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let name_metadata_account = Account::default();
+
+        let (name_master_edition_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &name_mint_key.to_bytes(),
+                b"edition",
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let name_master_edition_account = Account::default();
+
+        let mint_name_data = MintNameNft {
+            name: test_name.to_string(),
+        }
+        .data();
+        let mint_name_accounts = vec![
+            AccountMeta::new(authority_key, true),
+            AccountMeta::new(name_mint_key, false),
+            AccountMeta::new(name_token_key, false),
+            AccountMeta::new(name_metadata_key, false),
+            AccountMeta::new(name_master_edition_key, false),
+            AccountMeta::new(collection_mint_key, false),
+            AccountMeta::new(collection_metadata_key, false),
+            AccountMeta::new_readonly(collection_master_edition_key, false),
+            AccountMeta::new_readonly(PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(associated_token::ID, false),
+            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            AccountMeta::new_readonly(solana_sdk_ids::sysvar::instructions::ID, false),
+        ];
+        let mint_name_instruction =
+            Instruction::new_with_bytes(PROGRAM_ID, &mint_name_data, mint_name_accounts.clone());
+
+        let release_name_data = ReleaseName {
+            _name: test_name.to_string(),
+        }
+        .data();
+        let release_name_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &release_name_data,
+            vec![
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new(name_token_key, false),
+                AccountMeta::new(name_mint_key, false),
+                AccountMeta::new(name_metadata_key, false),
+                AccountMeta::new(name_master_edition_key, false),
+                AccountMeta::new(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new_readonly(collection_master_edition_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+                AccountMeta::new_readonly(solana_sdk_ids::sysvar::instructions::ID, false),
+            ],
+        );
+
+        let remint_name_instruction =
+            Instruction::new_with_bytes(PROGRAM_ID, &mint_name_data, mint_name_accounts);
+
+        let keyed_instructions_sysvar_account = get_account_instructions_sysvar(
+            &mut mollusk,
+            &[
+                create_collection_instruction.clone(),
+                mint_name_instruction.clone(),
+                release_name_instruction.clone(),
+                remint_name_instruction.clone(),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction_chain(
+            &[
+                (&create_collection_instruction, &[Check::success()]),
+                (&mint_name_instruction, &[Check::success()]),
+                (&release_name_instruction, &[Check::success()]),
+                (
+                    &remint_name_instruction,
+                    &[Check::err(ProgramError::Custom(
+                        SystemError::AccountAlreadyInUse as u32,
+                    ))],
+                ),
+            ],
+            &[
+                (authority_key, authority_account.clone()),
+                (collection_mint_key, collection_mint_account.clone()),
+                (collection_token_key, collection_token_account.clone()),
+                (collection_metadata_key, collection_metadata_account.clone()),
+                (
+                    collection_master_edition_key,
+                    collection_master_edition_account.clone(),
+                ),
+                (name_mint_key, name_mint_account.clone()),
+                (name_token_key, name_token_account.clone()),
+                (name_metadata_key, name_metadata_account.clone()),
+                (name_master_edition_key, name_master_edition_account.clone()),
+                mollusk_svm_programs_token::token::keyed_account(),
+                mollusk_svm_programs_token::associated_token::keyed_account(),
+                (
+                    mpl_token_metadata::ID,
+                    mollusk_svm::program::create_program_account_loader_v2(MPL_TOKEN_METADATA_ELF),
+                ),
+                keyed_account_for_system_program(),
+                keyed_instructions_sysvar_account,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_collection_size_tracks_mint_and_release() {
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+
+        mollusk_svm_programs_token::token::add_program(&mut mollusk);
+        mollusk_svm_programs_token::associated_token::add_program(&mut mollusk);
+        mollusk.add_program_with_elf_and_loader(
+            &mpl_token_metadata::ID,
+            MPL_TOKEN_METADATA_ELF,
+            &mollusk_svm::program::loader_keys::LOADER_V2,
+        );
+
+        let authority_key = Pubkey::new_unique();
+        let authority_account = Account::new(1_000_000_000, 0, &system_program::id());
+
+        let (collection_mint_key, _) =
+            Pubkey::find_program_address(&[COLLECTION_SEED], &PROGRAM_ID);
+        let collection_mint_account = Account::default();
+
+        let collection_token_key =
+            associated_token::get_associated_token_address(&authority_key, &collection_mint_key);
+        let collection_token_account = Account::default();
+
+        let (collection_metadata_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &collection_mint_key.to_bytes(),
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let collection_metadata_account = Account::default();
+
+        let (collection_master_edition_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &collection_mint_key.to_bytes(),
+                b"edition",
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let collection_master_edition_account = Account::default();
+
+        let create_collection_data = CreateNameCollection {}.data();
+        let create_collection_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &create_collection_data,
+            vec![
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new(collection_master_edition_key, false),
+                AccountMeta::new(collection_token_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(associated_token::ID, false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            ],
+        );
+
+        let test_name = "alice";
+        let (name_mint_key, _) =
+            Pubkey::find_program_address(&[MINT_SEED, test_name.as_bytes()], &PROGRAM_ID);
+        let name_mint_account = Account::default();
+
+        let name_token_key =
+            associated_token::get_associated_token_address(&authority_key, &name_mint_key);
+        let name_token_account = Account::default();
+
+        let (name_metadata_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &name_mint_key.to_bytes(),
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let name_metadata_account = Account::default();
+
+        let (name_master_edition_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &name_mint_key.to_bytes(),
+                b"edition",
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let name_master_edition_account = Account::default();
+
+        let mint_name_data = MintNameNft {
+            name: test_name.to_string(),
+        }
+        .data();
+        let mint_name_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &mint_name_data,
+            vec![
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new(name_mint_key, false),
+                AccountMeta::new(name_token_key, false),
+                AccountMeta::new(name_metadata_key, false),
+                AccountMeta::new(name_master_edition_key, false),
+                AccountMeta::new(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new_readonly(collection_master_edition_key, false),
+                AccountMeta::new_readonly(PROGRAM_ID, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(associated_token::ID, false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+                AccountMeta::new_readonly(solana_sdk_ids::sysvar::instructions::ID, false),
+            ],
+        );
+
+        let release_name_data = ReleaseName {
+            _name: test_name.to_string(),
+        }
+        .data();
+        let release_name_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &release_name_data,
+            vec![
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new(name_token_key, false),
+                AccountMeta::new(name_mint_key, false),
+                AccountMeta::new(name_metadata_key, false),
+                AccountMeta::new(name_master_edition_key, false),
+                AccountMeta::new(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new_readonly(collection_master_edition_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+                AccountMeta::new_readonly(solana_sdk_ids::sysvar::instructions::ID, false),
+            ],
+        );
+
+        let keyed_instructions_sysvar_account = get_account_instructions_sysvar(
+            &mut mollusk,
+            &[
+                create_collection_instruction.clone(),
+                mint_name_instruction.clone(),
+                release_name_instruction.clone(),
+            ],
+        );
+
+        let initial_accounts = [
+            (authority_key, authority_account.clone()),
+            (collection_mint_key, collection_mint_account.clone()),
+            (collection_token_key, collection_token_account.clone()),
+            (collection_metadata_key, collection_metadata_account.clone()),
+            (
+                collection_master_edition_key,
+                collection_master_edition_account.clone(),
+            ),
+            (name_mint_key, name_mint_account.clone()),
+            (name_token_key, name_token_account.clone()),
+            (name_metadata_key, name_metadata_account.clone()),
+            (name_master_edition_key, name_master_edition_account.clone()),
+            mollusk_svm_programs_token::token::keyed_account(),
+            mollusk_svm_programs_token::associated_token::keyed_account(),
+            (
+                mpl_token_metadata::ID,
+                mollusk_svm::program::create_program_account_loader_v2(MPL_TOKEN_METADATA_ELF),
+            ),
+            keyed_account_for_system_program(),
+            keyed_instructions_sysvar_account,
+        ];
+
+        let after_mint = mollusk.process_and_validate_instruction_chain(
+            &[
+                (&create_collection_instruction, &[Check::success()]),
+                (&mint_name_instruction, &[Check::success()]),
+            ],
+            &initial_accounts,
+        );
+
+        let (_, collection_metadata_after_mint) = after_mint
+            .resulting_accounts
+            .iter()
+            .find(|(key, _)| *key == collection_metadata_key)
+            .expect("collection_metadata account should be present after minting");
+        let metadata_after_mint =
+            mpl_token_metadata::accounts::Metadata::safe_deserialize(
+                &collection_metadata_after_mint.data,
+            )
+            .expect("collection_metadata account should deserialize into Metadata");
+        assert_eq!(
+            metadata_after_mint.collection_details,
+            Some(mpl_token_metadata::types::CollectionDetails::V1 { size: 1 })
+        );
+
+        let after_release = mollusk.process_and_validate_instruction(
+            &release_name_instruction,
+            &after_mint.resulting_accounts,
+            &[Check::success()],
+        );
+
+        let (_, collection_metadata_after_release) = after_release
+            .resulting_accounts
+            .iter()
+            .find(|(key, _)| *key == collection_metadata_key)
+            .expect("collection_metadata account should be present after releasing");
+        let metadata_after_release =
+            mpl_token_metadata::accounts::Metadata::safe_deserialize(
+                &collection_metadata_after_release.data,
+            )
+            .expect("collection_metadata account should deserialize into Metadata");
+        assert_eq!(
+            metadata_after_release.collection_details,
+            Some(mpl_token_metadata::types::CollectionDetails::V1 { size: 0 })
+        );
+    }
+
+    #[test]
+    fn test_leased_name_renewal() {
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+
+        mollusk_svm_programs_token::token::add_program(&mut mollusk);
+        mollusk_svm_programs_token::associated_token::add_program(&mut mollusk);
+        mollusk.add_program_with_elf_and_loader(
+            &mpl_token_metadata::ID,
+            MPL_TOKEN_METADATA_ELF,
+            &mollusk_svm::program::loader_keys::LOADER_V2,
+        );
+
+        let authority_key = Pubkey::new_unique();
+        let authority_account = Account::new(1_000_000_000, 0, &system_program::id());
+
+        let (collection_mint_key, _) =
+            Pubkey::find_program_address(&[COLLECTION_SEED], &PROGRAM_ID);
+        let collection_mint_account = Account::default();
+
+        let collection_token_key =
+            associated_token::get_associated_token_address(&authority_key, &collection_mint_key);
+        let collection_token_account = Account::default();
+
+        let (collection_metadata_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &collection_mint_key.to_bytes(),
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let collection_metadata_account = Account::default();
+
+        let (collection_master_edition_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &collection_mint_key.to_bytes(),
+                b"edition",
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let collection_master_edition_account = Account::default();
+
+        let create_collection_data = CreateNameCollection {}.data();
+        let create_collection_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &create_collection_data,
+            vec![
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new(collection_master_edition_key, false),
+                AccountMeta::new(collection_token_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(associated_token::ID, false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            ],
+        );
+
+        let test_name = "alice";
+        let (name_mint_key, _) =
+            Pubkey::find_program_address(&[MINT_SEED, test_name.as_bytes()], &PROGRAM_ID);
+        let name_mint_account = Account::default();
+
+        let name_token_key =
+            associated_token::get_associated_token_address(&authority_key, &name_mint_key);
+        let name_token_account = Account::default();
+
+        let (name_metadata_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &name_mint_key.to_bytes(),
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let name_metadata_account = Account::default();
+
+        let (name_master_edition_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &name_mint_key.to_bytes(),
+                b"edition",
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let name_master_edition_account = Account::default();
+
+        let mint_name_leased_data = MintNameNftLeased {
+            name: test_name.to_string(),
+            total_uses: 2,
+        }
+        .data();
+        let mint_name_leased_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &mint_name_leased_data,
+            vec![
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new(name_mint_key, false),
+                AccountMeta::new(name_token_key, false),
+                AccountMeta::new(name_metadata_key, false),
+                AccountMeta::new(name_master_edition_key, false),
+                AccountMeta::new(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new_readonly(collection_master_edition_key, false),
+                AccountMeta::new_readonly(PROGRAM_ID, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(associated_token::ID, false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+                AccountMeta::new_readonly(solana_sdk_ids::sysvar::instructions::ID, false),
+            ],
+        );
+
+        let renew_name_data = RenewName {
+            _name: test_name.to_string(),
+        }
+        .data();
+        let renew_name_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &renew_name_data,
+            vec![
+                AccountMeta::new_readonly(authority_key, true),
+                AccountMeta::new_readonly(name_token_key, false),
+                AccountMeta::new_readonly(name_mint_key, false),
+                AccountMeta::new(name_metadata_key, false),
+                AccountMeta::new_readonly(collection_mint_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            ],
+        );
+
+        let keyed_instructions_sysvar_account = get_account_instructions_sysvar(
+            &mut mollusk,
+            &[
+                create_collection_instruction.clone(),
+                mint_name_leased_instruction.clone(),
+            ],
+        );
+
+        let initial_accounts = [
+            (authority_key, authority_account.clone()),
+            (collection_mint_key, collection_mint_account.clone()),
+            (collection_token_key, collection_token_account.clone()),
+            (collection_metadata_key, collection_metadata_account.clone()),
+            (
+                collection_master_edition_key,
+                collection_master_edition_account.clone(),
+            ),
+            (name_mint_key, name_mint_account.clone()),
+            (name_token_key, name_token_account.clone()),
+            (name_metadata_key, name_metadata_account.clone()),
+            (name_master_edition_key, name_master_edition_account.clone()),
+            mollusk_svm_programs_token::token::keyed_account(),
+            mollusk_svm_programs_token::associated_token::keyed_account(),
+            (
+                mpl_token_metadata::ID,
+                mollusk_svm::program::create_program_account_loader_v2(MPL_TOKEN_METADATA_ELF),
+            ),
+            keyed_account_for_system_program(),
+            keyed_instructions_sysvar_account,
+        ];
+
+        // Mint the 2-use lease, then renew it twice - each renewal decrements `Uses.remaining` by
+        // one via `UtilizeCpi`, so the second renewal brings it to zero.
+        let after_renewals = mollusk.process_and_validate_instruction_chain(
+            &[
+                (&create_collection_instruction, &[Check::success()]),
+                (&mint_name_leased_instruction, &[Check::success()]),
+                (&renew_name_instruction, &[Check::success()]),
+                (&renew_name_instruction, &[Check::success()]),
+            ],
+            &initial_accounts,
+        );
+
+        let (_, name_metadata_after_renewals) = after_renewals
+            .resulting_accounts
+            .iter()
+            .find(|(key, _)| *key == name_metadata_key)
+            .expect("name_metadata account should be present after renewing");
+        let metadata_after_renewals = mpl_token_metadata::accounts::Metadata::safe_deserialize(
+            &name_metadata_after_renewals.data,
+        )
+        .expect("name_metadata account should deserialize into Metadata");
+        assert_eq!(
+            metadata_after_renewals.uses,
+            Some(mpl_token_metadata::types::Uses {
+                use_method: mpl_token_metadata::types::UseMethod::Burn,
+                total: 2,
+                remaining: 0,
+            })
+        );
+
+        // The lease is exhausted - a third renewal is rejected by `Utilize` itself rather than by
+        // any check of ours.
+        mollusk.process_and_validate_instruction(
+            &renew_name_instruction,
+            &after_renewals.resulting_accounts,
+            &[Check::err(ProgramError::Custom(
+                mpl_token_metadata::error::MplTokenMetadataError::NotEnoughUses as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    fn test_delegated_registrar_verifies_then_loses_access_after_revocation() {
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+
+        mollusk_svm_programs_token::token::add_program(&mut mollusk);
+        mollusk_svm_programs_token::associated_token::add_program(&mut mollusk);
+        mollusk.add_program_with_elf_and_loader(
+            &mpl_token_metadata::ID,
+            MPL_TOKEN_METADATA_ELF,
+            &mollusk_svm::program::loader_keys::LOADER_V2,
+        );
+
+        let authority_key = Pubkey::new_unique();
+        let authority_account = Account::new(1_000_000_000, 0, &system_program::id());
+
+        let registrar_key = Pubkey::new_unique();
+        let registrar_account = Account::default();
+
+        let (collection_mint_key, _) =
+            Pubkey::find_program_address(&[COLLECTION_SEED], &PROGRAM_ID);
+        let collection_mint_account = Account::default();
+
+        let collection_token_key =
+            associated_token::get_associated_token_address(&authority_key, &collection_mint_key);
+        let collection_token_account = Account::default();
+
+        let (collection_metadata_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &collection_mint_key.to_bytes(),
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let collection_metadata_account = Account::default();
+
+        let (collection_master_edition_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &collection_mint_key.to_bytes(),
+                b"edition",
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let collection_master_edition_account = Account::default();
+
+        let (collection_authority_record_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &collection_mint_key.to_bytes(),
+                b"collection_authority",
+                &registrar_key.to_bytes(),
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let collection_authority_record_account = Account::default();
+
+        let create_collection_data = CreateNameCollection {}.data();
+        let create_collection_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &create_collection_data,
+            vec![
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new(collection_master_edition_key, false),
+                AccountMeta::new(collection_token_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(associated_token::ID, false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            ],
+        );
+
+        let approve_registrar_data = ApproveRegistrar {
+            _new_authority: registrar_key,
+        }
+        .data();
+        let approve_registrar_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &approve_registrar_data,
+            vec![
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new_readonly(registrar_key, false),
+                AccountMeta::new(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new(collection_authority_record_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            ],
+        );
+
+        let revoke_registrar_data = RevokeRegistrar {}.data();
+        let revoke_registrar_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &revoke_registrar_data,
+            vec![
+                AccountMeta::new_readonly(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new_readonly(registrar_key, false),
+                AccountMeta::new(collection_authority_record_key, false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            ],
+        );
+
+        let test_name = "alice";
+        let (name_mint_key, _) =
+            Pubkey::find_program_address(&[MINT_SEED, test_name.as_bytes()], &PROGRAM_ID);
+        let name_mint_account = Account::default();
+
+        let name_token_key =
+            associated_token::get_associated_token_address(&authority_key, &name_mint_key);
+        let name_token_account = Account::default();
+
+        let (name_metadata_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &name_mint_key.to_bytes(),
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let name_metadata_account = Account::default();
+
+        let (name_master_edition_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &name_mint_key.to_bytes(),
+                b"edition",
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let name_master_edition_account = Account::default();
+
+        let mint_name_data = MintNameNft {
+            name: test_name.to_string(),
+        }
+        .data();
+        let mint_name_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &mint_name_data,
+            vec![
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new(name_mint_key, false),
+                AccountMeta::new(name_token_key, false),
+                AccountMeta::new(name_metadata_key, false),
+                AccountMeta::new(name_master_edition_key, false),
+                AccountMeta::new(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new_readonly(collection_master_edition_key, false),
+                AccountMeta::new_readonly(collection_authority_record_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(associated_token::ID, false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+                AccountMeta::new_readonly(solana_sdk_ids::sysvar::instructions::ID, false),
+            ],
+        );
+
+        let keyed_instructions_sysvar_account = get_account_instructions_sysvar(
+            &mut mollusk,
+            &[
+                create_collection_instruction.clone(),
+                mint_name_instruction.clone(),
+            ],
+        );
+
+        let initial_accounts = [
+            (authority_key, authority_account.clone()),
+            (registrar_key, registrar_account.clone()),
+            (collection_mint_key, collection_mint_account.clone()),
+            (collection_token_key, collection_token_account.clone()),
+            (collection_metadata_key, collection_metadata_account.clone()),
+            (
+                collection_master_edition_key,
+                collection_master_edition_account.clone(),
+            ),
+            (
+                collection_authority_record_key,
+                collection_authority_record_account.clone(),
+            ),
+            (name_mint_key, name_mint_account.clone()),
+            (name_token_key, name_token_account.clone()),
+            (name_metadata_key, name_metadata_account.clone()),
+            (name_master_edition_key, name_master_edition_account.clone()),
+            mollusk_svm_programs_token::token::keyed_account(),
+            mollusk_svm_programs_token::associated_token::keyed_account(),
+            (
+                mpl_token_metadata::ID,
+                mollusk_svm::program::create_program_account_loader_v2(MPL_TOKEN_METADATA_ELF),
+            ),
+            keyed_account_for_system_program(),
+            keyed_instructions_sysvar_account,
+        ];
+
+        // The registrar's collection-authority record is supplied as `mint_name_nft`'s
+        // `delegate_record`, and collection verification succeeds with it.
+        let after_mint = mollusk.process_and_validate_instruction_chain(
+            &[
+                (&create_collection_instruction, &[Check::success()]),
+                (&approve_registrar_instruction, &[Check::success()]),
+                (&mint_name_instruction, &[Check::success()]),
+            ],
+            &initial_accounts,
+        );
+
+        // Revoking the registrar closes its collection-authority record.
+        let after_revoke = mollusk.process_and_validate_instruction(
+            &revoke_registrar_instruction,
+            &after_mint.resulting_accounts,
+            &[Check::success()],
+        );
+
+        let second_name = "bob";
+        let (second_name_mint_key, _) =
+            Pubkey::find_program_address(&[MINT_SEED, second_name.as_bytes()], &PROGRAM_ID);
+        let second_name_token_key =
+            associated_token::get_associated_token_address(&authority_key, &second_name_mint_key);
+        let (second_name_metadata_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &second_name_mint_key.to_bytes(),
+            ],
+            &mpl_token_metadata::ID,
+        );
+        let (second_name_master_edition_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::ID.to_bytes(),
+                &second_name_mint_key.to_bytes(),
+                b"edition",
+            ],
+            &mpl_token_metadata::ID,
+        );
+
+        let mint_second_name_data = MintNameNft {
+            name: second_name.to_string(),
+        }
+        .data();
+        let mint_second_name_instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &mint_second_name_data,
+            vec![
+                AccountMeta::new(authority_key, true),
+                AccountMeta::new(second_name_mint_key, false),
+                AccountMeta::new(second_name_token_key, false),
+                AccountMeta::new(second_name_metadata_key, false),
+                AccountMeta::new(second_name_master_edition_key, false),
+                AccountMeta::new(collection_mint_key, false),
+                AccountMeta::new(collection_metadata_key, false),
+                AccountMeta::new_readonly(collection_master_edition_key, false),
+                AccountMeta::new_readonly(collection_authority_record_key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(associated_token::ID, false),
+                AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+                AccountMeta::new_readonly(solana_sdk_ids::sysvar::instructions::ID, false),
+            ],
+        );
+
+        let mut accounts_after_revoke = after_revoke.resulting_accounts.clone();
+        accounts_after_revoke.push((second_name_mint_key, Account::default()));
+        accounts_after_revoke.push((second_name_token_key, Account::default()));
+        accounts_after_revoke.push((second_name_metadata_key, Account::default()));
+        accounts_after_revoke.push((second_name_master_edition_key, Account::default()));
+
+        // A still-revoked delegate's stale collection-authority record no longer deserializes into a
+        // valid record, so a second registration that supplies it is rejected by `VerifyCollectionV1`.
+        mollusk.process_and_validate_instruction(
+            &mint_second_name_instruction,
+            &accounts_after_revoke,
+            &[Check::err(ProgramError::Custom(
+                mpl_token_metadata::error::MplTokenMetadataError::Uninitialized as u32,
+            ))],
+        );
+    }
+
+    fn valid_data() -> mpl_token_metadata::types::DataV2 {
+        mpl_token_metadata::types::DataV2 {
+            name: "alice".to_owned(),
+            symbol: "MSN".to_owned(),
+            uri: String::new(),
+            seller_fee_basis_points: 0,
+            creators: Some(vec![mpl_token_metadata::types::Creator {
+                address: Pubkey::new_unique(),
+                verified: true,
+                share: 100,
+            }]),
+            collection: None,
+            uses: None,
+        }
+    }
+
+    #[test]
+    fn test_assert_data_valid_accepts_well_formed_data() {
+        assert!(assert_data_valid(&valid_data()).is_ok());
+    }
+
+    #[test]
+    fn test_assert_data_valid_rejects_long_name() {
+        let mut data = valid_data();
+        data.name = "a".repeat(33);
+        assert!(assert_data_valid(&data).is_err());
+    }
+
+    #[test]
+    fn test_assert_data_valid_rejects_long_symbol() {
+        let mut data = valid_data();
+        data.symbol = "a".repeat(11);
+        assert!(assert_data_valid(&data).is_err());
+    }
+
+    #[test]
+    fn test_assert_data_valid_rejects_long_uri() {
+        let mut data = valid_data();
+        data.uri = "a".repeat(201);
+        assert!(assert_data_valid(&data).is_err());
+    }
+
+    #[test]
+    fn test_assert_data_valid_rejects_excessive_seller_fee_basis_points() {
+        let mut data = valid_data();
+        data.seller_fee_basis_points = 10_001;
+        assert!(assert_data_valid(&data).is_err());
+    }
+
+    #[test]
+    fn test_assert_data_valid_rejects_too_many_creators() {
+        let mut data = valid_data();
+        data.creators = Some(
+            (0..6)
+                .map(|_| mpl_token_metadata::types::Creator {
+                    address: Pubkey::new_unique(),
+                    verified: false,
+                    share: 100 / 6,
+                })
+                .collect(),
+        );
+        assert!(assert_data_valid(&data).is_err());
+    }
+
+    #[test]
+    fn test_assert_data_valid_rejects_creator_shares_not_summing_to_100() {
+        let mut data = valid_data();
+        data.creators = Some(vec![mpl_token_metadata::types::Creator {
+            address: Pubkey::new_unique(),
+            verified: true,
+            share: 99,
+        }]);
+        assert!(assert_data_valid(&data).is_err());
     }
 }
@@ -2,18 +2,18 @@
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use mpl_token_metadata::{
-    instructions::{
-        CreateMasterEditionV3Builder, CreateMetadataAccountV3Builder, VerifyCollectionV1Builder,
-    },
-    types::{Collection, CollectionDetails, Creator, DataV2},
+    instructions::{BurnV1Builder, CreateV1Builder, MintV1Builder, VerifyCollectionV1Builder},
+    types::{Collection, CollectionDetails, Creator, PrintSupply, TokenStandard},
 };
 use solana_program::{
     account_info::AccountInfo,
+    clock::Clock,
     declare_id, entrypoint,
     entrypoint::ProgramResult,
     msg,
     program::{invoke, invoke_signed},
     program_error::ProgramError,
+    program_option::COption,
     program_pack::Pack,
     pubkey::Pubkey,
     rent::{self, Rent},
@@ -22,18 +22,88 @@ use solana_program::{
 use solana_sdk_ids::system_program;
 use solana_system_interface::instruction as system_instruction;
 use spl_associated_token_account::instruction as associated_token_instruction;
-use spl_token_2022::{instruction as token_instruction, state::Mint};
+use spl_token_2022::{
+    extension::{metadata_pointer, ExtensionType},
+    instruction as token_instruction,
+    state::{Account as TokenAccount, Mint, Multisig},
+};
+use spl_token_metadata_interface::{instruction as token_metadata_instruction, state::Field};
 
 declare_id!("3EMcczaGi9ivdLxvvFwRbGYeEUEHpGwabXegARw4jLxa");
 
 pub static COLLECTION_SEED: &[u8] = b"collection";
 pub static MINT_SEED: &[u8] = b"mint";
+pub static RECORD_SEED: &[u8] = b"record";
+pub static REVERSE_SEED: &[u8] = b"reverse";
+pub static REGISTRATION_SEED: &[u8] = b"registration";
 pub const MAX_NAME_LENGTH: usize = 10;
+/// A registration period, in seconds - 365 days.
+pub const REGISTRATION_PERIOD_SECONDS: i64 = 365 * 24 * 60 * 60;
+
+// Mirrors the limits Metaplex's own `assert_data_valid` enforces on a `DataV2` before writing it
+// into a metadata account.
+pub const MAX_METADATA_NAME_LENGTH: usize = 32;
+pub const MAX_METADATA_SYMBOL_LENGTH: usize = 10;
+pub const MAX_METADATA_URI_LENGTH: usize = 200;
+pub const MAX_METADATA_SELLER_FEE_BASIS_POINTS: u16 = 10_000;
+pub const MAX_METADATA_CREATORS: usize = 5;
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub enum Instruction {
-    CreateNameCollection,
-    MintNameNft { name: String },
+    /// `multisig`, when set, becomes the collection mint's freeze authority and doubles as the
+    /// program-enforced gate on future `MintNameNft` calls: anyone minting a name must then also
+    /// supply that `spl_token_2022::state::Multisig` account plus enough of its registered signers
+    /// to meet its `m`-of-`n` threshold.
+    CreateNameCollection { multisig: Option<Pubkey> },
+    MintNameNft {
+        name: String,
+        symbol: String,
+        uri: String,
+        /// When set, creates the forward/reverse resolver records for `name` pointing at this
+        /// target in the same instruction, instead of requiring a follow-up `SetRecord`.
+        record_target: Option<Pubkey>,
+    },
+    /// Same as [`Instruction::CreateNameCollection`], but the collection mint carries its metadata
+    /// directly via Token-2022's `metadata_pointer`/`token_metadata` extensions instead of a
+    /// Metaplex `CreateMetadataAccountV3`/`CreateMasterEditionV3` pair.
+    CreateNameCollectionNative,
+    /// Same as [`Instruction::MintNameNft`], but writes Token-2022 native metadata onto the name
+    /// mint rather than creating separate Metaplex metadata/edition accounts.
+    MintNameNftNative { name: String },
+    /// Points `name` at `target`, keeping the reverse record at `target` consistent so it always
+    /// resolves back to `name` as its current primary. Requires the caller to hold the name NFT.
+    SetRecord { name: String, target: Pubkey },
+    /// Clears the forward record for `name` and its matching reverse record. Requires the caller
+    /// to hold the name NFT.
+    ClearRecord { name: String },
+    /// Extends `name`'s registration by [`REGISTRATION_PERIOD_SECONDS`] from whichever is later:
+    /// its current expiry, or now. Requires the caller to hold the name NFT.
+    RenewName { name: String },
+    /// Reclaims a lapsed registration: once `now > expiry`, burns the name NFT and closes its
+    /// mint/metadata/master-edition accounts so the name can be minted again.
+    ReleaseName { name: String },
+}
+
+/// A forward resolver record: `name -> target`, stored at the `[RECORD_SEED, name]` PDA.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct NameRecord {
+    pub target: Pubkey,
+}
+
+/// A reverse resolver record: `target -> name`, stored at the `[REVERSE_SEED, target]` PDA. Only
+/// one name can be the reverse-resolved "primary" for a given address at a time.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReverseRecord {
+    pub name: String,
+}
+
+/// Tracks a name's registration period, stored at the `[REGISTRATION_SEED, name]` PDA alongside the
+/// name mint. `MintNameNft` opens one, `RenewName` extends it, and `ReleaseName` frees it once it
+/// has lapsed.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Registration {
+    pub registered_by: Pubkey,
+    pub expiry_unix_ts: i64,
 }
 
 pub fn process_instruction(
@@ -49,15 +119,130 @@ pub fn process_instruction(
         .map_err(|_| ProgramError::InvalidInstructionData)?;
 
     match instruction {
-        Instruction::CreateNameCollection => process_create_name_collection(program_id, accounts),
-        Instruction::MintNameNft { name } => process_mint_name_nft(program_id, accounts, name),
+        Instruction::CreateNameCollection { multisig } => {
+            process_create_name_collection(program_id, accounts, multisig)
+        }
+        Instruction::MintNameNft {
+            name,
+            symbol,
+            uri,
+            record_target,
+        } => process_mint_name_nft(program_id, accounts, name, symbol, uri, record_target),
+        Instruction::CreateNameCollectionNative => {
+            process_create_name_collection_native(program_id, accounts)
+        }
+        Instruction::MintNameNftNative { name } => {
+            process_mint_name_nft_native(program_id, accounts, name)
+        }
+        Instruction::SetRecord { name, target } => {
+            process_set_record(program_id, accounts, name, target)
+        }
+        Instruction::ClearRecord { name } => process_clear_record(program_id, accounts, name),
+        Instruction::RenewName { name } => process_renew_name(program_id, accounts, name),
+        Instruction::ReleaseName { name } => process_release_name(program_id, accounts, name),
+    }
+}
+
+/// Mirrors the invariants Metaplex's own `assert_data_valid` enforces on a `DataV2` before it is
+/// written into a metadata account, so malformed input is rejected here with a precise
+/// `ProgramError` instead of failing deep inside the `mpl_token_metadata` CPI.
+fn validate_metadata(
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    seller_fee_basis_points: u16,
+    creators: &[Creator],
+) -> Result<(), ProgramError> {
+    if name.len() > MAX_METADATA_NAME_LENGTH
+        || symbol.len() > MAX_METADATA_SYMBOL_LENGTH
+        || uri.len() > MAX_METADATA_URI_LENGTH
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if seller_fee_basis_points > MAX_METADATA_SELLER_FEE_BASIS_POINTS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if creators.len() > MAX_METADATA_CREATORS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    for (i, creator) in creators.iter().enumerate() {
+        if creators[..i]
+            .iter()
+            .any(|other| other.address == creator.address)
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+    }
+
+    if !creators.is_empty() {
+        let total_share: u16 = creators.iter().map(|creator| creator.share as u16).sum();
+
+        if total_share != 100 {
+            return Err(ProgramError::InvalidArgument);
+        }
+    }
+
+    Ok(())
+}
+
+/// Mirrors the `MAX_SIGNERS` bound the SPL Token-2022 processor itself enforces when validating a
+/// multisig owner, so a malformed signer set is rejected here before the gated CPI rather than
+/// deep inside the token program.
+fn is_valid_signer_index(index: usize) -> bool {
+    index < token_instruction::MAX_SIGNERS
+}
+
+/// Checks that `signers` satisfies the M-of-N threshold recorded on `multisig_account`: at least
+/// `multisig.m` of the pubkeys in `multisig.signers[..multisig.n]` must be present among
+/// `signers` and marked as a transaction signer.
+///
+/// Mirrors SPL Token-2022's own `validate_owner`: each registered-signer *position* can only be
+/// consumed once, so listing the same signer account twice (Solana allows duplicate pubkeys
+/// across account metas) can't be used to satisfy an M-of-N threshold with fewer than M distinct
+/// keys.
+fn require_multisig_threshold(
+    multisig_account: &AccountInfo,
+    signers: &[AccountInfo],
+) -> Result<(), ProgramError> {
+    let multisig = Multisig::unpack(&multisig_account.try_borrow_data()?)?;
+    let registered_signers = &multisig.signers[..multisig.n as usize];
+    let mut used_signers = [false; token_instruction::MAX_SIGNERS];
+    let mut valid_signers = 0usize;
+
+    for (index, signer) in signers.iter().enumerate() {
+        if !is_valid_signer_index(index) || !signer.is_signer {
+            continue;
+        }
+
+        if let Some(slot) = registered_signers
+            .iter()
+            .position(|registered_key| registered_key == signer.key)
+        {
+            if !used_signers[slot] {
+                used_signers[slot] = true;
+                valid_signers += 1;
+            }
+        }
+    }
+
+    if valid_signers < multisig.m as usize {
+        return Err(ProgramError::MissingRequiredSignature);
     }
+
+    Ok(())
 }
 
-fn process_create_name_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+fn process_create_name_collection(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    multisig: Option<Pubkey>,
+) -> ProgramResult {
     msg!("Create Name Collection");
 
-    let [authority, collection_mint, collection_metadata, collection_master_edition, collection_token, system_program, token_program, associated_token_program, token_metadata_program, rent_sysvar] =
+    let [authority, collection_mint, collection_metadata, collection_master_edition, collection_token, system_program, token_program, associated_token_program, token_metadata_program, sysvar_instruction, rent_sysvar] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -72,6 +257,7 @@ fn process_create_name_collection(program_id: &Pubkey, accounts: &[AccountInfo])
         || *token_program.key != spl_token_2022::id()
         || *associated_token_program.key != spl_associated_token_account::id()
         || *token_metadata_program.key != mpl_token_metadata::ID
+        || *sysvar_instruction.key != solana_sdk_ids::sysvar::instructions::id()
         || *rent_sysvar.key != rent::sysvar::id()
     {
         return Err(ProgramError::IncorrectProgramId);
@@ -155,13 +341,18 @@ fn process_create_name_collection(program_id: &Pubkey, accounts: &[AccountInfo])
 
     msg!("Created Name Collection Mint Account");
 
-    // Initialize mint with 0 decimals for NFT
+    // Initialize mint with 0 decimals for NFT. `collection_mint` stays its own mint authority so the
+    // program can keep signing future name mints with its own PDA seeds; an optional multisig is
+    // recorded as the freeze authority instead, doubling as the flag `process_mint_name_nft` checks
+    // to decide whether it must additionally require that multisig's signer set.
+    let freeze_authority_key = multisig.unwrap_or(collection_mint_key);
+
     invoke_signed(
         &token_instruction::initialize_mint(
             &spl_token_2022::id(),
             collection_mint.key,
             collection_mint.key,
-            Some(collection_mint.key),
+            Some(&freeze_authority_key),
             0,
         )?,
         &[collection_mint.clone(), rent_sysvar.clone()],
@@ -170,118 +361,92 @@ fn process_create_name_collection(program_id: &Pubkey, accounts: &[AccountInfo])
 
     msg!("Intitialized Name Collection Mint");
 
-    // Create associated token account
-    invoke(
-        &associated_token_instruction::create_associated_token_account(
-            authority.key,
-            authority.key,
-            collection_mint.key,
-            &spl_token_2022::id(),
-        ),
-        &[
-            authority.clone(),
-            collection_token.clone(),
-            authority.clone(),
-            collection_mint.clone(),
-            system_program.clone(),
-            token_program.clone(),
-            associated_token_program.clone(),
-        ],
-    )?;
-
-    msg!("Created Name Collection ATA");
-
-    // Mint 1 token to the collection token account
-    invoke_signed(
-        &token_instruction::mint_to(
-            &spl_token_2022::id(),
-            collection_mint.key,
-            collection_token.key,
-            collection_mint.key,
-            &[],
-            1,
-        )?,
-        &[
-            collection_mint.clone(),
-            collection_token.clone(),
-            collection_mint.clone(),
-        ],
-        &[signer_seeds],
-    )?;
-
-    msg!("Minted Collection to ATA");
-
-    // Create metadata account
+    // Create metadata and master edition in a single CPI. The mint is created above rather than
+    // handed to `CreateV1` as a signer, because `CreateV1` always derives the new mint's freeze
+    // authority from its own authority argument - it has no way to point freeze authority at a
+    // separate multisig the way the manual `initialize_mint` call above does.
     let creators = vec![Creator {
         address: *collection_mint.key,
         verified: true,
         share: 100,
     }];
 
-    let create_metadata_ix = CreateMetadataAccountV3Builder::new()
+    validate_metadata("Mock Name Service", "MNS", "", 0, &creators)?;
+
+    let create_ix = CreateV1Builder::new()
         .metadata(*collection_metadata.key)
-        .mint(*collection_mint.key)
-        .mint_authority(*collection_mint.key)
+        .master_edition(Some(*collection_master_edition.key))
+        .mint(*collection_mint.key, false)
+        .authority(*collection_mint.key)
         .payer(*authority.key)
         .update_authority(*collection_mint.key, true)
         .system_program(*system_program.key)
-        .data(DataV2 {
-            name: "Mock Name Service".to_string(),
-            symbol: "MNS".to_string(),
-            uri: String::new(),
-            seller_fee_basis_points: 0,
-            creators: Some(creators),
-            collection: None,
-            uses: None,
-        })
+        .sysvar_instructions(*sysvar_instruction.key)
+        .spl_token_program(Some(*token_program.key))
+        .name("Mock Name Service".to_string())
+        .symbol("MNS".to_string())
+        .uri(String::new())
+        .seller_fee_basis_points(0)
+        .creators(creators)
         .is_mutable(true)
+        .token_standard(TokenStandard::NonFungible)
         .collection_details(CollectionDetails::V1 { size: 0 })
+        .print_supply(PrintSupply::Zero)
         .instruction();
 
     invoke_signed(
-        &create_metadata_ix,
+        &create_ix,
         &[
             collection_metadata.clone(),
+            collection_master_edition.clone(),
             collection_mint.clone(),
             collection_mint.clone(),
             authority.clone(),
             collection_mint.clone(),
             system_program.clone(),
+            sysvar_instruction.clone(),
+            token_program.clone(),
         ],
         &[signer_seeds],
     )?;
 
-    msg!("Created Name Collection Metadata");
+    msg!("Created Name Collection Metadata and Master Edition");
 
-    // Create master edition
-    let create_edition_ix = CreateMasterEditionV3Builder::new()
-        .edition(*collection_master_edition.key)
-        .update_authority(*collection_mint.key)
-        .mint_authority(*collection_mint.key)
+    // Create the collection token account and mint the single collection token into it in one CPI
+    let mint_ix = MintV1Builder::new()
+        .token(*collection_token.key)
+        .token_owner(Some(*authority.key))
+        .metadata(*collection_metadata.key)
+        .master_edition(Some(*collection_master_edition.key))
         .mint(*collection_mint.key)
+        .authority(*collection_mint.key)
         .payer(*authority.key)
-        .metadata(*collection_metadata.key)
-        .token_program(*token_program.key)
         .system_program(*system_program.key)
-        .max_supply(0)
+        .sysvar_instructions(*sysvar_instruction.key)
+        .spl_token_program(*token_program.key)
+        .spl_ata_program(*associated_token_program.key)
+        .amount(1)
         .instruction();
 
     invoke_signed(
-        &create_edition_ix,
+        &mint_ix,
         &[
+            collection_token.clone(),
+            authority.clone(),
+            collection_metadata.clone(),
             collection_master_edition.clone(),
             collection_mint.clone(),
             collection_mint.clone(),
-            collection_mint.clone(),
             authority.clone(),
-            collection_metadata.clone(),
-            token_program.clone(),
             system_program.clone(),
+            sysvar_instruction.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
         ],
         &[signer_seeds],
     )?;
 
-    msg!("Created Name Collection Master Edition");
+    msg!("Minted Collection to ATA");
 
     Ok(())
 }
@@ -290,8 +455,11 @@ fn process_mint_name_nft(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     name: String,
+    symbol: String,
+    uri: String,
+    record_target: Option<Pubkey>,
 ) -> ProgramResult {
-    let [owner, name_mint, name_token, name_metadata, name_master_edition, collection_mint, collection_metadata, collection_master_edition, system_program, token_program, associated_token_program, token_metadata_program, sysvar_instruction, rent_sysvar] =
+    let [owner, name_mint, name_token, name_metadata, name_master_edition, collection_mint, collection_metadata, collection_master_edition, system_program, token_program, associated_token_program, token_metadata_program, sysvar_instruction, registration, remaining @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -328,11 +496,34 @@ fn process_mint_name_nft(
         return Err(ProgramError::InvalidSeeds);
     }
 
-    // Check if name mint already exists
+    // Check if name mint already exists. A live account always blocks re-creation outright - a
+    // Solana-level invariant, not a policy choice - but once a registration lapses, `ReleaseName`
+    // burns the name NFT and closes the mint/metadata/master-edition accounts, which is what makes
+    // this check pass again for the next owner.
     if !name_mint.data_is_empty() || *name_mint.owner == spl_token_2022::id() {
         return Err(ProgramError::AccountAlreadyInitialized);
     }
 
+    // Derive and verify the registration PDA tracking this name's expiry
+    let (registration_key, registration_bump) =
+        Pubkey::find_program_address(&[REGISTRATION_SEED, name.as_bytes()], program_id);
+
+    if registration_key != *registration.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // A stale-but-not-yet-released registration should never coexist with an empty mint account in
+    // practice (see the comment above), but guard against it rather than silently overwrite a still
+    // -active registration.
+    if !registration.data_is_empty() {
+        let existing = Registration::try_from_slice(&registration.try_borrow_data()?)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if Clock::get()?.unix_timestamp <= existing.expiry_unix_ts {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+    }
+
     // Verify collection mint PDA
     let (collection_mint_key, collection_bump) =
         Pubkey::find_program_address(&[COLLECTION_SEED], program_id);
@@ -381,140 +572,121 @@ fn process_mint_name_nft(
         return Err(ProgramError::InvalidAccountData);
     }
 
-    let collection_signer_seeds = &[COLLECTION_SEED, &[collection_bump]];
+    // `remaining` carries two optional, independently-sized tails: the record/reverse-record pair
+    // (present only when `record_target` is set) comes first, followed by the multisig account and
+    // its signers (present only when the collection's freeze authority names a multisig).
+    let (record_accounts, multisig_accounts) = if record_target.is_some() {
+        if remaining.len() < 2 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        remaining.split_at(2)
+    } else {
+        remaining.split_at(0)
+    };
 
-    // Create name mint account
-    let mint_space = Mint::get_packed_len();
-    let mint_lamports = Rent::get()?.minimum_balance(mint_space);
+    // If the collection was created with a multisig, its pubkey was recorded as the collection
+    // mint's freeze authority; require that multisig's signer threshold before minting into it.
+    let collection_mint_state = Mint::unpack(&collection_mint.try_borrow_data()?)?;
 
-    invoke_signed(
-        &system_instruction::create_account(
-            owner.key,
-            name_mint.key,
-            mint_lamports,
-            mint_space as u64,
-            &spl_token_2022::id(),
-        ),
-        &[owner.clone(), name_mint.clone(), system_program.clone()],
-        &[&[MINT_SEED, &[name_bump]]],
-    )?;
+    if let COption::Some(freeze_authority) = collection_mint_state.freeze_authority {
+        if freeze_authority != collection_mint_key {
+            let [multisig, signers @ ..] = multisig_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
 
-    // Initialize name mint with collection mint as authority
-    invoke_signed(
-        &token_instruction::initialize_mint(
-            &spl_token_2022::id(),
-            name_mint.key,
-            collection_mint.key,
-            Some(collection_mint.key),
-            0,
-        )?,
-        &[name_mint.clone(), rent_sysvar.clone()],
-        &[collection_signer_seeds],
-    )?;
+            if *multisig.key != freeze_authority {
+                return Err(ProgramError::InvalidAccountData);
+            }
 
-    // Create associated token account for name NFT
-    invoke(
-        &associated_token_instruction::create_associated_token_account(
-            owner.key,
-            owner.key,
-            name_mint.key,
-            &spl_token_2022::id(),
-        ),
-        &[
-            owner.clone(),
-            name_token.clone(),
-            owner.clone(),
-            name_mint.clone(),
-            system_program.clone(),
-            token_program.clone(),
-            associated_token_program.clone(),
-        ],
-    )?;
+            require_multisig_threshold(multisig, signers)?;
+        }
+    }
 
-    // Mint 1 token
-    invoke_signed(
-        &token_instruction::mint_to(
-            &spl_token_2022::id(),
-            name_mint.key,
-            name_token.key,
-            collection_mint.key,
-            &[],
-            1,
-        )?,
-        &[
-            name_mint.clone(),
-            name_token.clone(),
-            collection_mint.clone(),
-        ],
-        &[collection_signer_seeds],
-    )?;
+    let collection_signer_seeds = &[COLLECTION_SEED, &[collection_bump]];
+    let name_signer_seeds = &[MINT_SEED, name.as_bytes(), &[name_bump]];
 
-    // Create metadata for name NFT
+    // Create the name mint, its metadata and its master edition in a single CPI. Unlike the
+    // collection mint, the name mint has no custom freeze authority to preserve, so `CreateV1` can
+    // create and initialize it directly (`mint` is passed as a signer) instead of us doing it
+    // manually first.
     let creators = vec![Creator {
         address: *collection_mint.key,
         verified: true,
         share: 100,
     }];
 
-    let create_metadata_ix = CreateMetadataAccountV3Builder::new()
+    validate_metadata(&name, &symbol, &uri, 0, &creators)?;
+
+    let create_ix = CreateV1Builder::new()
         .metadata(*name_metadata.key)
-        .mint(*name_mint.key)
-        .mint_authority(*collection_mint.key)
+        .master_edition(Some(*name_master_edition.key))
+        .mint(*name_mint.key, true)
+        .authority(*collection_mint.key)
         .payer(*owner.key)
         .update_authority(*collection_mint.key, true)
         .system_program(*system_program.key)
-        .data(DataV2 {
-            name: name.clone(),
-            symbol: "MSN".to_owned(),
-            uri: String::new(),
-            seller_fee_basis_points: 0,
-            creators: Some(creators),
-            collection: Some(Collection {
-                verified: false,
-                key: *collection_mint.key,
-            }),
-            uses: None,
-        })
+        .sysvar_instructions(*sysvar_instruction.key)
+        .spl_token_program(Some(*token_program.key))
+        .name(name.clone())
+        .symbol(symbol)
+        .uri(uri)
+        .seller_fee_basis_points(0)
+        .creators(creators)
         .is_mutable(true)
+        .token_standard(TokenStandard::NonFungible)
+        .collection(Collection {
+            verified: false,
+            key: *collection_mint.key,
+        })
         .instruction();
 
     invoke_signed(
-        &create_metadata_ix,
+        &create_ix,
         &[
             name_metadata.clone(),
+            name_master_edition.clone(),
             name_mint.clone(),
             collection_mint.clone(),
             owner.clone(),
             collection_mint.clone(),
             system_program.clone(),
+            sysvar_instruction.clone(),
+            token_program.clone(),
         ],
-        &[collection_signer_seeds],
+        &[collection_signer_seeds, name_signer_seeds],
     )?;
 
-    // Create master edition for name NFT
-    let create_edition_ix = CreateMasterEditionV3Builder::new()
-        .edition(*name_master_edition.key)
-        .update_authority(*collection_mint.key)
-        .mint_authority(*collection_mint.key)
+    // Create the name token account and mint the single name token into it in one CPI
+    let mint_ix = MintV1Builder::new()
+        .token(*name_token.key)
+        .token_owner(Some(*owner.key))
+        .metadata(*name_metadata.key)
+        .master_edition(Some(*name_master_edition.key))
         .mint(*name_mint.key)
+        .authority(*collection_mint.key)
         .payer(*owner.key)
-        .metadata(*name_metadata.key)
-        .token_program(*token_program.key)
         .system_program(*system_program.key)
-        .max_supply(1)
+        .sysvar_instructions(*sysvar_instruction.key)
+        .spl_token_program(*token_program.key)
+        .spl_ata_program(*associated_token_program.key)
+        .amount(1)
         .instruction();
 
     invoke_signed(
-        &create_edition_ix,
+        &mint_ix,
         &[
+            name_token.clone(),
+            owner.clone(),
+            name_metadata.clone(),
             name_master_edition.clone(),
-            collection_mint.clone(),
-            collection_mint.clone(),
             name_mint.clone(),
+            collection_mint.clone(),
             owner.clone(),
-            name_metadata.clone(),
-            token_program.clone(),
             system_program.clone(),
+            sysvar_instruction.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
         ],
         &[collection_signer_seeds],
     )?;
@@ -544,51 +716,907 @@ fn process_mint_name_nft(
         &[collection_signer_seeds],
     )?;
 
-    Ok(())
-}
+    // Open a fresh registration period for the new owner
+    write_record_account(
+        registration,
+        owner,
+        system_program,
+        program_id,
+        &[REGISTRATION_SEED, name.as_bytes(), &[registration_bump]],
+        &Registration {
+            registered_by: *owner.key,
+            expiry_unix_ts: Clock::get()?
+                .unix_timestamp
+                .saturating_add(REGISTRATION_PERIOD_SECONDS),
+        },
+    )?;
 
-entrypoint!(process_instruction);
+    msg!("Registered Name");
 
-#[cfg(test)]
-mod tests {
-    use super::{Instruction, COLLECTION_SEED, ID as PROGRAM_ID, MINT_SEED};
+    if let Some(target) = record_target {
+        let [record, reverse_record] = record_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
 
-    use mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk};
-    use solana_account::Account;
-    use solana_program::instruction::{AccountMeta, Instruction as SolanaInstruction};
-    use solana_pubkey::Pubkey;
-    use solana_sdk_ids::system_program;
+        write_name_record(
+            program_id,
+            owner,
+            system_program,
+            record,
+            reverse_record,
+            &name,
+            target,
+        )?;
 
-    static MPL_TOKEN_METADATA_ELF: &[u8] = include_bytes!("../../elf/mpl-token-metadata.so");
+        msg!("Created Name Record at mint time");
+    }
 
-    // TODO: Fix program so test passes. Directionally correct but account debugging required.
-    #[test]
-    fn test_program() {
-        let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+    Ok(())
+}
 
-        // Add required programs
-        mollusk_svm_programs_token::token2022::add_program(&mut mollusk);
-        mollusk_svm_programs_token::associated_token::add_program(&mut mollusk);
-        mollusk.add_program_with_elf_and_loader(
-            &mpl_token_metadata::ID,
-            MPL_TOKEN_METADATA_ELF,
-            &mollusk_svm::program::loader_keys::LOADER_V2,
-        );
+/// Token-2022 native-metadata counterpart to [`process_create_name_collection`]. Drops the
+/// `mpl_token_metadata` CPI chain (and its bundled program account) entirely: the collection mint
+/// is allocated with room for the `metadata_pointer` extension, the pointer is set to the mint
+/// itself, and `token_metadata_initialize` writes name/symbol/uri directly onto the mint account.
+fn process_create_name_collection_native(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Create Name Collection (Token-2022 native metadata)");
 
-        let authority_key = Pubkey::new_unique();
-        let authority_lamports = 1_000_000_000;
-        let authority_account = Account::new(authority_lamports, 0, &system_program::id());
+    let [authority, collection_mint, collection_token, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
 
-        // Collection setup
-        let (collection_mint_key, _) =
-            Pubkey::find_program_address(&[COLLECTION_SEED], &PROGRAM_ID);
-        let collection_mint_account = Account::default();
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
 
-        let collection_token_key =
-            spl_associated_token_account::get_associated_token_address_with_program_id(
-                &authority_key,
-                &collection_mint_key,
-                &spl_token_2022::id(),
+    // Verify program IDs
+    if *system_program.key != system_program::id()
+        || *token_program.key != spl_token_2022::id()
+        || *associated_token_program.key != spl_associated_token_account::id()
+    {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Derive and verify collection mint PDA
+    let (collection_mint_key, collection_bump) =
+        Pubkey::find_program_address(&[COLLECTION_SEED], program_id);
+
+    if collection_mint_key != *collection_mint.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Check if collection mint already exists
+    if !collection_mint.data_is_empty() || *collection_mint.owner == spl_token_2022::id() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // Verify collection token account
+    let expected_collection_token =
+        spl_associated_token_account::get_associated_token_address_with_program_id(
+            authority.key,
+            &collection_mint_key,
+            &spl_token_2022::id(),
+        );
+
+    if expected_collection_token != *collection_token.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let signer_seeds = &[COLLECTION_SEED, &[collection_bump]];
+
+    // Allocate the mint with room for the fixed-size metadata-pointer extension; the variable-length
+    // `TokenMetadata` TLV that `token_metadata_initialize` writes grows the account again below, once
+    // the extra rent for it has been topped up.
+    let mint_space =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::MetadataPointer])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+    let mint_lamports = Rent::get()?.minimum_balance(mint_space);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            authority.key,
+            collection_mint.key,
+            mint_lamports,
+            mint_space as u64,
+            &spl_token_2022::id(),
+        ),
+        &[
+            authority.clone(),
+            collection_mint.clone(),
+            system_program.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    msg!("Created Name Collection Mint Account");
+
+    // Point the mint's metadata at itself - Token-2022's self-hosted metadata pattern
+    invoke_signed(
+        &metadata_pointer::instruction::initialize(
+            &spl_token_2022::id(),
+            collection_mint.key,
+            Some(collection_mint_key),
+            Some(collection_mint_key),
+        )?,
+        &[collection_mint.clone()],
+        &[signer_seeds],
+    )?;
+
+    // Initialize mint with 0 decimals for NFT
+    invoke_signed(
+        &token_instruction::initialize_mint2(
+            &spl_token_2022::id(),
+            collection_mint.key,
+            collection_mint.key,
+            Some(collection_mint.key),
+            0,
+        )?,
+        &[collection_mint.clone()],
+        &[signer_seeds],
+    )?;
+
+    msg!("Intitialized Name Collection Mint (metadata-pointer extension)");
+
+    // Create associated token account
+    invoke(
+        &associated_token_instruction::create_associated_token_account(
+            authority.key,
+            authority.key,
+            collection_mint.key,
+            &spl_token_2022::id(),
+        ),
+        &[
+            authority.clone(),
+            collection_token.clone(),
+            authority.clone(),
+            collection_mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )?;
+
+    msg!("Created Name Collection ATA");
+
+    // Mint 1 token to the collection token account
+    invoke_signed(
+        &token_instruction::mint_to(
+            &spl_token_2022::id(),
+            collection_mint.key,
+            collection_token.key,
+            collection_mint.key,
+            &[],
+            1,
+        )?,
+        &[
+            collection_mint.clone(),
+            collection_token.clone(),
+            collection_mint.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    msg!("Minted Collection to ATA");
+
+    // Top up the mint's rent before the upcoming metadata write reallocs it to fit the TLV payload
+    let metadata_lamports = Rent::get()?.minimum_balance(mint_space + 256);
+    let additional_lamports = metadata_lamports.saturating_sub(collection_mint.lamports());
+
+    if additional_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(authority.key, collection_mint.key, additional_lamports),
+            &[
+                authority.clone(),
+                collection_mint.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    invoke_signed(
+        &token_metadata_instruction::initialize(
+            &spl_token_2022::id(),
+            collection_mint.key,
+            collection_mint.key,
+            collection_mint.key,
+            collection_mint.key,
+            "Mock Name Service".to_string(),
+            "MNS".to_string(),
+            String::new(),
+        ),
+        &[
+            collection_mint.clone(),
+            collection_mint.clone(),
+            collection_mint.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    msg!("Created Name Collection Token-2022 native metadata");
+
+    Ok(())
+}
+
+/// Token-2022 native-metadata counterpart to [`process_mint_name_nft`]. No separate metadata or
+/// master-edition accounts are derived or created; `token_metadata_initialize` writes the name
+/// straight onto the name mint, and since the metadata extension has no first-class "collection"
+/// field the way Metaplex's `DataV2` does, the parent collection is recorded as an additional
+/// key/value field via `token_metadata_update_field` instead of a `VerifyCollectionV1` step.
+fn process_mint_name_nft_native(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+) -> ProgramResult {
+    msg!("Mint Name NFT (Token-2022 native metadata)");
+
+    let [owner, name_mint, name_token, collection_mint, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Validate name
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Verify program IDs
+    if *system_program.key != system_program::id()
+        || *token_program.key != spl_token_2022::id()
+        || *associated_token_program.key != spl_associated_token_account::id()
+    {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Derive and verify name mint PDA
+    let (name_mint_key, name_bump) =
+        Pubkey::find_program_address(&[MINT_SEED, name.as_bytes()], program_id);
+
+    if name_mint_key != *name_mint.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Check if name mint already exists
+    if !name_mint.data_is_empty() || *name_mint.owner == spl_token_2022::id() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // Verify collection mint PDA
+    let (collection_mint_key, collection_bump) =
+        Pubkey::find_program_address(&[COLLECTION_SEED], program_id);
+
+    if collection_mint_key != *collection_mint.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Verify name token account
+    let expected_name_token =
+        spl_associated_token_account::get_associated_token_address_with_program_id(
+            owner.key,
+            &name_mint_key,
+            &spl_token_2022::id(),
+        );
+
+    if expected_name_token != *name_token.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let collection_signer_seeds = &[COLLECTION_SEED, &[collection_bump]];
+
+    // Create name mint account
+    let mint_space =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::MetadataPointer])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+    let mint_lamports = Rent::get()?.minimum_balance(mint_space);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            owner.key,
+            name_mint.key,
+            mint_lamports,
+            mint_space as u64,
+            &spl_token_2022::id(),
+        ),
+        &[owner.clone(), name_mint.clone(), system_program.clone()],
+        &[&[MINT_SEED, name.as_bytes(), &[name_bump]]],
+    )?;
+
+    // Point the name mint's metadata at itself, with the collection mint as metadata authority
+    invoke_signed(
+        &metadata_pointer::instruction::initialize(
+            &spl_token_2022::id(),
+            name_mint.key,
+            Some(collection_mint_key),
+            Some(name_mint_key),
+        )?,
+        &[name_mint.clone()],
+        &[collection_signer_seeds],
+    )?;
+
+    // Initialize name mint with collection mint as authority
+    invoke_signed(
+        &token_instruction::initialize_mint2(
+            &spl_token_2022::id(),
+            name_mint.key,
+            collection_mint.key,
+            Some(collection_mint.key),
+            0,
+        )?,
+        &[name_mint.clone()],
+        &[collection_signer_seeds],
+    )?;
+
+    // Create associated token account for name NFT
+    invoke(
+        &associated_token_instruction::create_associated_token_account(
+            owner.key,
+            owner.key,
+            name_mint.key,
+            &spl_token_2022::id(),
+        ),
+        &[
+            owner.clone(),
+            name_token.clone(),
+            owner.clone(),
+            name_mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )?;
+
+    // Mint 1 token
+    invoke_signed(
+        &token_instruction::mint_to(
+            &spl_token_2022::id(),
+            name_mint.key,
+            name_token.key,
+            collection_mint.key,
+            &[],
+            1,
+        )?,
+        &[
+            name_mint.clone(),
+            name_token.clone(),
+            collection_mint.clone(),
+        ],
+        &[collection_signer_seeds],
+    )?;
+
+    // Top up the mint's rent before the upcoming metadata write reallocs it to fit the TLV payload
+    let metadata_lamports = Rent::get()?.minimum_balance(mint_space + 256);
+    let additional_lamports = metadata_lamports.saturating_sub(name_mint.lamports());
+
+    if additional_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(owner.key, name_mint.key, additional_lamports),
+            &[owner.clone(), name_mint.clone(), system_program.clone()],
+        )?;
+    }
+
+    invoke_signed(
+        &token_metadata_instruction::initialize(
+            &spl_token_2022::id(),
+            name_mint.key,
+            collection_mint.key,
+            name_mint.key,
+            collection_mint.key,
+            name.clone(),
+            "MSN".to_string(),
+            String::new(),
+        ),
+        &[
+            name_mint.clone(),
+            collection_mint.clone(),
+            name_mint.clone(),
+            collection_mint.clone(),
+        ],
+        &[collection_signer_seeds],
+    )?;
+
+    // Record the parent collection the same way Metaplex's `DataV2.collection` would
+    invoke_signed(
+        &token_metadata_instruction::update_field(
+            &spl_token_2022::id(),
+            name_mint.key,
+            collection_mint.key,
+            Field::Key("collection".to_string()),
+            collection_mint_key.to_string(),
+        ),
+        &[name_mint.clone(), collection_mint.clone()],
+        &[collection_signer_seeds],
+    )?;
+
+    msg!("Created Name NFT Token-2022 native metadata");
+
+    Ok(())
+}
+
+/// Requires that `owner` holds exactly one token of `name_mint` in its associated token account,
+/// i.e. is the current holder of that name's NFT.
+fn require_name_holder(
+    owner: &AccountInfo,
+    name_mint: &AccountInfo,
+    name_token: &AccountInfo,
+) -> Result<(), ProgramError> {
+    let expected_name_token =
+        spl_associated_token_account::get_associated_token_address_with_program_id(
+            owner.key,
+            name_mint.key,
+            &spl_token_2022::id(),
+        );
+
+    if expected_name_token != *name_token.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let token_account = TokenAccount::unpack(&name_token.try_borrow_data()?)?;
+
+    if token_account.mint != *name_mint.key
+        || token_account.owner != *owner.key
+        || token_account.amount != 1
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Creates `account` (funded by `payer`) if empty, or reallocs it and tops up its rent if it
+/// already holds a different-sized record, then writes `value`'s Borsh encoding into it.
+fn write_record_account<T: BorshSerialize>(
+    account: &AccountInfo,
+    payer: &AccountInfo,
+    system_program: &AccountInfo,
+    program_id: &Pubkey,
+    signer_seeds: &[&[u8]],
+    value: &T,
+) -> ProgramResult {
+    let data = borsh::to_vec(value).map_err(|_| ProgramError::InvalidArgument)?;
+    let rent_lamports = Rent::get()?.minimum_balance(data.len());
+
+    if account.data_is_empty() {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                account.key,
+                rent_lamports,
+                data.len() as u64,
+                program_id,
+            ),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+    } else {
+        if *account.owner != *program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let additional_lamports = rent_lamports.saturating_sub(account.lamports());
+
+        if additional_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(payer.key, account.key, additional_lamports),
+                &[payer.clone(), account.clone(), system_program.clone()],
+            )?;
+        }
+
+        account.realloc(data.len(), false)?;
+    }
+
+    account.try_borrow_mut_data()?[..data.len()].copy_from_slice(&data);
+
+    Ok(())
+}
+
+/// Derives, verifies and writes the forward `[RECORD_SEED, name]` and reverse `[REVERSE_SEED,
+/// target]` PDAs so `name` resolves to `target` and `target` reverse-resolves back to `name`.
+fn write_name_record(
+    program_id: &Pubkey,
+    payer: &AccountInfo,
+    system_program: &AccountInfo,
+    record: &AccountInfo,
+    reverse_record: &AccountInfo,
+    name: &str,
+    target: Pubkey,
+) -> ProgramResult {
+    let (record_key, record_bump) =
+        Pubkey::find_program_address(&[RECORD_SEED, name.as_bytes()], program_id);
+
+    if record_key != *record.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let (reverse_record_key, reverse_bump) =
+        Pubkey::find_program_address(&[REVERSE_SEED, target.as_ref()], program_id);
+
+    if reverse_record_key != *reverse_record.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    write_record_account(
+        record,
+        payer,
+        system_program,
+        program_id,
+        &[RECORD_SEED, name.as_bytes(), &[record_bump]],
+        &NameRecord { target },
+    )?;
+
+    write_record_account(
+        reverse_record,
+        payer,
+        system_program,
+        program_id,
+        &[REVERSE_SEED, target.as_ref(), &[reverse_bump]],
+        &ReverseRecord {
+            name: name.to_string(),
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Zeroes, refunds and hands `account` back to the system program - the standard Solana
+/// close-account pattern.
+fn close_record_account(account: &AccountInfo, recipient: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+
+    **account.try_borrow_mut_lamports()? -= lamports;
+    **recipient.try_borrow_mut_lamports()? += lamports;
+
+    account.realloc(0, false)?;
+    account.assign(&system_program::id());
+
+    Ok(())
+}
+
+/// Points `name` at `target`, creating or updating both the forward and reverse records. Requires
+/// `owner` to currently hold the name NFT - resolver records follow the NFT, not a separate
+/// authority.
+fn process_set_record(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    target: Pubkey,
+) -> ProgramResult {
+    msg!("Set Record");
+
+    let [owner, name_mint, name_token, record, reverse_record, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if *system_program.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (name_mint_key, _) =
+        Pubkey::find_program_address(&[MINT_SEED, name.as_bytes()], program_id);
+
+    if name_mint_key != *name_mint.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    require_name_holder(owner, name_mint, name_token)?;
+
+    write_name_record(
+        program_id,
+        owner,
+        system_program,
+        record,
+        reverse_record,
+        &name,
+        target,
+    )?;
+
+    msg!("Updated Name Record");
+
+    Ok(())
+}
+
+/// Clears the forward record for `name` and its matching reverse record. Requires `owner` to
+/// currently hold the name NFT, same as [`process_set_record`].
+///
+/// Note: if `name` was previously pointed at a different target, that stale target's reverse
+/// record is left untouched here - only the reverse record matching the name's *current* target is
+/// cleared alongside it.
+fn process_clear_record(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+) -> ProgramResult {
+    msg!("Clear Record");
+
+    let [owner, name_mint, name_token, record, reverse_record, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (name_mint_key, _) =
+        Pubkey::find_program_address(&[MINT_SEED, name.as_bytes()], program_id);
+
+    if name_mint_key != *name_mint.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    require_name_holder(owner, name_mint, name_token)?;
+
+    let (record_key, _) = Pubkey::find_program_address(&[RECORD_SEED, name.as_bytes()], program_id);
+
+    if record_key != *record.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if record.data_is_empty() {
+        return Ok(());
+    }
+
+    let existing = NameRecord::try_from_slice(&record.try_borrow_data()?)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let (expected_reverse, _) =
+        Pubkey::find_program_address(&[REVERSE_SEED, existing.target.as_ref()], program_id);
+
+    if expected_reverse != *reverse_record.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    close_record_account(record, owner)?;
+
+    if !reverse_record.data_is_empty() {
+        close_record_account(reverse_record, owner)?;
+    }
+
+    msg!("Cleared Name Record");
+
+    Ok(())
+}
+
+/// Extends `name`'s registration by [`REGISTRATION_PERIOD_SECONDS`] from whichever is later: its
+/// current expiry, or now. Requires `owner` to currently hold the name NFT, same as
+/// [`process_set_record`].
+fn process_renew_name(program_id: &Pubkey, accounts: &[AccountInfo], name: String) -> ProgramResult {
+    msg!("Renew Name");
+
+    let [owner, name_mint, name_token, registration, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if *system_program.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (name_mint_key, _) =
+        Pubkey::find_program_address(&[MINT_SEED, name.as_bytes()], program_id);
+
+    if name_mint_key != *name_mint.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    require_name_holder(owner, name_mint, name_token)?;
+
+    let (registration_key, registration_bump) =
+        Pubkey::find_program_address(&[REGISTRATION_SEED, name.as_bytes()], program_id);
+
+    if registration_key != *registration.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let current_expiry = if registration.data_is_empty() {
+        now
+    } else {
+        Registration::try_from_slice(&registration.try_borrow_data()?)
+            .map_err(|_| ProgramError::InvalidAccountData)?
+            .expiry_unix_ts
+    };
+
+    write_record_account(
+        registration,
+        owner,
+        system_program,
+        program_id,
+        &[REGISTRATION_SEED, name.as_bytes(), &[registration_bump]],
+        &Registration {
+            registered_by: *owner.key,
+            expiry_unix_ts: current_expiry.max(now).saturating_add(REGISTRATION_PERIOD_SECONDS),
+        },
+    )?;
+
+    msg!("Renewed Name");
+
+    Ok(())
+}
+
+/// Reclaims a lapsed registration: once `now > expiry`, burns the name NFT and closes its
+/// mint/metadata/master-edition accounts via a single `BurnV1` CPI (Metaplex's `BurnV1` is the only
+/// way to close those accounts, since they're owned by `spl_token_2022`/`mpl_token_metadata`, not
+/// this program), then frees the `registration` PDA, which this program does own.
+///
+/// This still requires `owner` - the current holder of the (expired) name NFT - to sign, since
+/// `BurnV1` requires the token account's owner or delegate to authorize the burn. It is therefore a
+/// voluntary relinquishment of a lapsed name rather than a permissionless reclaim by a third party;
+/// a fully trustless reclaim would need a different mechanism than burning the incumbent's token.
+fn process_release_name(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+) -> ProgramResult {
+    msg!("Release Name");
+
+    let [owner, name_mint, name_token, name_metadata, name_master_edition, registration, token_program, system_program, sysvar_instruction] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if *token_program.key != spl_token_2022::id()
+        || *system_program.key != system_program::id()
+        || *sysvar_instruction.key != solana_sdk_ids::sysvar::instructions::id()
+    {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (name_mint_key, _) =
+        Pubkey::find_program_address(&[MINT_SEED, name.as_bytes()], program_id);
+
+    if name_mint_key != *name_mint.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    require_name_holder(owner, name_mint, name_token)?;
+
+    let (registration_key, _) =
+        Pubkey::find_program_address(&[REGISTRATION_SEED, name.as_bytes()], program_id);
+
+    if registration_key != *registration.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if registration.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let existing = Registration::try_from_slice(&registration.try_borrow_data()?)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    if Clock::get()?.unix_timestamp <= existing.expiry_unix_ts {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (expected_name_metadata, _) = Pubkey::find_program_address(
+        &[
+            b"metadata",
+            &mpl_token_metadata::ID.to_bytes(),
+            &name_mint_key.to_bytes(),
+        ],
+        &mpl_token_metadata::ID,
+    );
+
+    if expected_name_metadata != *name_metadata.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (expected_name_edition, _) = Pubkey::find_program_address(
+        &[
+            b"metadata",
+            &mpl_token_metadata::ID.to_bytes(),
+            &name_mint_key.to_bytes(),
+            b"edition",
+        ],
+        &mpl_token_metadata::ID,
+    );
+
+    if expected_name_edition != *name_master_edition.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let burn_ix = BurnV1Builder::new()
+        .authority(*owner.key)
+        .metadata(*name_metadata.key)
+        .edition(Some(*name_master_edition.key))
+        .mint(*name_mint.key)
+        .token(*name_token.key)
+        .token_owner(Some(*owner.key))
+        .system_program(*system_program.key)
+        .sysvar_instructions(*sysvar_instruction.key)
+        .spl_token_program(*token_program.key)
+        .amount(1)
+        .instruction();
+
+    invoke(
+        &burn_ix,
+        &[
+            owner.clone(),
+            name_metadata.clone(),
+            name_master_edition.clone(),
+            name_mint.clone(),
+            name_token.clone(),
+            owner.clone(),
+            system_program.clone(),
+            sysvar_instruction.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    close_record_account(registration, owner)?;
+
+    msg!("Released Name");
+
+    Ok(())
+}
+
+entrypoint!(process_instruction);
+
+#[cfg(test)]
+mod tests {
+    use super::{Instruction, COLLECTION_SEED, ID as PROGRAM_ID, MINT_SEED};
+
+    use mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk};
+    use solana_account::Account;
+    use solana_program::instruction::{AccountMeta, Instruction as SolanaInstruction};
+    use solana_pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    static MPL_TOKEN_METADATA_ELF: &[u8] = include_bytes!("../../elf/mpl-token-metadata.so");
+
+    // TODO: Fix program so test passes. Directionally correct but account debugging required.
+    #[test]
+    fn test_program() {
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+
+        // Add required programs
+        mollusk_svm_programs_token::token2022::add_program(&mut mollusk);
+        mollusk_svm_programs_token::associated_token::add_program(&mut mollusk);
+        mollusk.add_program_with_elf_and_loader(
+            &mpl_token_metadata::ID,
+            MPL_TOKEN_METADATA_ELF,
+            &mollusk_svm::program::loader_keys::LOADER_V2,
+        );
+
+        let authority_key = Pubkey::new_unique();
+        let authority_lamports = 1_000_000_000;
+        let authority_account = Account::new(authority_lamports, 0, &system_program::id());
+
+        // Collection setup
+        let (collection_mint_key, _) =
+            Pubkey::find_program_address(&[COLLECTION_SEED], &PROGRAM_ID);
+        let collection_mint_account = Account::default();
+
+        let collection_token_key =
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                &authority_key,
+                &collection_mint_key,
+                &spl_token_2022::id(),
             );
         let collection_token_account = Account::default();
 
@@ -614,7 +1642,8 @@ mod tests {
         let collection_master_edition_account = Account::default();
 
         // Create collection instruction
-        let create_collection_data = borsh::to_vec(&Instruction::CreateNameCollection).unwrap();
+        let create_collection_data =
+            borsh::to_vec(&Instruction::CreateNameCollection { multisig: None }).unwrap();
         let create_collection_instruction = SolanaInstruction::new_with_bytes(
             PROGRAM_ID,
             &create_collection_data,
@@ -628,6 +1657,7 @@ mod tests {
                 AccountMeta::new_readonly(spl_token_2022::id(), false),
                 AccountMeta::new_readonly(spl_associated_token_account::id(), false),
                 AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+                AccountMeta::new_readonly(solana_sdk_ids::sysvar::instructions::id(), false),
                 AccountMeta::new_readonly(solana_sdk_ids::sysvar::rent::id(), false),
             ],
         );
@@ -667,8 +1697,17 @@ mod tests {
         );
         let name_master_edition_account = Account::default();
 
+        let (registration_key, _) = Pubkey::find_program_address(
+            &[super::REGISTRATION_SEED, test_name.as_bytes()],
+            &PROGRAM_ID,
+        );
+        let registration_account = Account::default();
+
         let mint_name_data = borsh::to_vec(&Instruction::MintNameNft {
             name: test_name.to_string(),
+            symbol: "MSN".to_string(),
+            uri: String::new(),
+            record_target: None,
         })
         .unwrap();
         let mint_name_instruction = SolanaInstruction::new_with_bytes(
@@ -688,7 +1727,7 @@ mod tests {
                 AccountMeta::new_readonly(spl_associated_token_account::id(), false),
                 AccountMeta::new_readonly(mpl_token_metadata::ID, false),
                 AccountMeta::new_readonly(solana_sdk_ids::sysvar::instructions::id(), false),
-                AccountMeta::new_readonly(solana_sdk_ids::sysvar::rent::id(), false),
+                AccountMeta::new(registration_key, false),
             ],
         );
 
@@ -713,6 +1752,7 @@ mod tests {
                 (name_token_key, name_token_account.clone()),
                 (name_metadata_key, name_metadata_account.clone()),
                 (name_master_edition_key, name_master_edition_account.clone()),
+                (registration_key, registration_account.clone()),
                 // Programs
                 mollusk_svm_programs_token::token2022::keyed_account(),
                 mollusk_svm_programs_token::associated_token::keyed_account(),
@@ -12,6 +12,10 @@ sol! {
     error ContractAlreadyPaused();
     #[derive(Debug)]
     error ContractAlreadyUnpaused();
+    #[derive(Debug)]
+    error TimelockNotElapsed(uint64 effective_block);
+    #[derive(Debug)]
+    error NoPendingAction();
 }
 
 #[derive(SolidityError, Debug)]
@@ -23,6 +27,8 @@ pub enum ContractError {
     Unauthorized(ownable::OwnableUnauthorizedAccount),
     AlreadyPaused(ContractAlreadyPaused),
     AlreadyUnpaused(ContractAlreadyUnpaused),
+    TimelockNotElapsed(TimelockNotElapsed),
+    NoPendingAction(NoPendingAction),
 }
 
 impl From<ownable::Error> for ContractError {
@@ -39,20 +45,34 @@ impl From<ownable::Error> for ContractError {
 pub struct OwnableContract {
     ownable: Ownable2Step,
     is_paused: StorageBool,
+    /// Number of blocks a pause/unpause or ownership transfer must wait
+    /// between being requested and being applied via `execute_pending`.
+    delay_blocks: StorageU64,
+    pending_pause_target: StorageBool,
+    pending_pause_effective_block: StorageU64,
+    has_pending_pause: StorageBool,
+    pending_owner_target: StorageAddress,
+    pending_owner_effective_block: StorageU64,
+    has_pending_owner_transfer: StorageBool,
 }
 
 #[public]
 #[implements(IOwnable2Step<Error = ownable::Error>)]
 impl OwnableContract {
     #[constructor]
-    pub fn constructor(&mut self) -> Result<(), ContractError> {
+    pub fn constructor(&mut self, delay_blocks: U64) -> Result<(), ContractError> {
         self.ownable.constructor(self.vm().msg_sender())?;
 
         self.is_paused.set(true);
+        self.delay_blocks.set(delay_blocks);
 
         Ok(())
     }
 
+    pub fn delay_blocks(&self) -> U64 {
+        self.delay_blocks.get()
+    }
+
     pub fn pause_contract(&mut self) -> Result<(), ContractError> {
         self.ownable.only_owner()?;
 
@@ -60,7 +80,7 @@ impl OwnableContract {
             return Err(ContractAlreadyPaused {}.into());
         }
 
-        self.is_paused.set(true);
+        self.queue_pending_pause(true);
 
         Ok(())
     }
@@ -72,14 +92,84 @@ impl OwnableContract {
             return Err(ContractAlreadyUnpaused {}.into());
         }
 
-        self.is_paused.set(false);
+        self.queue_pending_pause(false);
+
+        Ok(())
+    }
+
+    /// Cancels a queued pause/unpause before `execute_pending` applies it.
+    pub fn cancel_pending_pause_change(&mut self) -> Result<(), ContractError> {
+        self.ownable.only_owner()?;
+        self.has_pending_pause.set(false);
+        Ok(())
+    }
 
+    /// Cancels a queued ownership transfer before `execute_pending` stages
+    /// it with the underlying `Ownable2Step` component.
+    pub fn cancel_pending_ownership_transfer(&mut self) -> Result<(), ContractError> {
+        self.ownable.only_owner()?;
+        self.has_pending_owner_transfer.set(false);
         Ok(())
     }
 
+    /// Applies every queued action (pause/unpause, then ownership transfer)
+    /// once each has reached its effective block. Errors with
+    /// `TimelockNotElapsed` if any queued action is not yet due (the call
+    /// then reverts, so a due action is not applied on its own either), or
+    /// `NoPendingAction` if nothing is queued at all.
+    pub fn execute_pending(&mut self) -> Result<(), ContractError> {
+        let block_number = U64::from(self.vm().block_number());
+        let mut applied = false;
+
+        if self.has_pending_pause.get() {
+            let effective_block = self.pending_pause_effective_block.get();
+            if block_number < effective_block {
+                return Err(TimelockNotElapsed { effective_block }.into());
+            }
+
+            self.is_paused.set(self.pending_pause_target.get());
+            self.has_pending_pause.set(false);
+            applied = true;
+        }
+
+        if self.has_pending_owner_transfer.get() {
+            let effective_block = self.pending_owner_effective_block.get();
+            if block_number < effective_block {
+                return Err(TimelockNotElapsed { effective_block }.into());
+            }
+
+            self.ownable
+                .transfer_ownership(self.pending_owner_target.get())?;
+            self.has_pending_owner_transfer.set(false);
+            applied = true;
+        }
+
+        if !applied {
+            return Err(NoPendingAction {}.into());
+        }
+
+        Ok(())
+    }
+
+    fn queue_pending_pause(&mut self, target: bool) {
+        let effective_block = U64::from(self.vm().block_number()) + self.delay_blocks.get();
+
+        self.pending_pause_target.set(target);
+        self.pending_pause_effective_block.set(effective_block);
+        self.has_pending_pause.set(true);
+    }
+
     pub fn is_paused(&self) -> bool {
         self.is_paused.get()
     }
+
+    pub fn pending_pause_effective_block(&self) -> U64 {
+        self.pending_pause_effective_block.get()
+    }
+
+    pub fn pending_owner_effective_block(&self) -> U64 {
+        self.pending_owner_effective_block.get()
+    }
 }
 
 #[public]
@@ -94,8 +184,21 @@ impl IOwnable2Step for OwnableContract {
         self.ownable.pending_owner()
     }
 
+    /// Queues `new_owner` to be staged as the `Ownable2Step` pending owner
+    /// once `execute_pending` runs after `delay_blocks` have passed, rather
+    /// than staging it immediately. `accept_ownership` is unaffected: it
+    /// still finalizes the transfer the `Ownable2Step` component's own way,
+    /// once that staging has actually happened.
     fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), Self::Error> {
-        self.ownable.transfer_ownership(new_owner)
+        self.ownable.only_owner()?;
+
+        let effective_block = U64::from(self.vm().block_number()) + self.delay_blocks.get();
+
+        self.pending_owner_target.set(new_owner);
+        self.pending_owner_effective_block.set(effective_block);
+        self.has_pending_owner_transfer.set(true);
+
+        Ok(())
     }
 
     fn accept_ownership(&mut self) -> Result<(), Self::Error> {
@@ -119,44 +222,68 @@ mod tests {
         bob: Address,
         charlie: Address,
     ) {
-        // Initialize the contract - alice becomes the owner
-        contract.sender(alice).constructor().motsu_unwrap();
+        // Initialize the contract - alice becomes the owner, with a 10-block delay
+        contract
+            .sender(alice)
+            .constructor(U64::from(10))
+            .motsu_unwrap();
 
         // Verify initial state
         assert_eq!(contract.sender(alice).owner(), alice);
         assert_eq!(contract.sender(alice).pending_owner(), Address::ZERO);
         assert_eq!(contract.sender(alice).is_paused(), true);
 
-        // Owner can unpause the contract
-        contract.sender(alice).unpause_contract().motsu_unwrap();
-        assert_eq!(contract.sender(alice).is_paused(), false);
-
-        // Attempting to unpause when already unpaused should fail
-        let err = contract.sender(alice).unpause_contract().motsu_unwrap_err();
-        assert!(matches!(err, ContractError::AlreadyUnpaused(_)));
+        VM::set_block_number(100);
 
-        // Owner can pause the contract
-        contract.sender(alice).pause_contract().motsu_unwrap();
+        // Owner queues an unpause, but it isn't applied until the delay elapses
+        contract.sender(alice).unpause_contract().motsu_unwrap();
         assert_eq!(contract.sender(alice).is_paused(), true);
+        let err = contract.sender(alice).execute_pending().motsu_unwrap_err();
+        assert!(matches!(err, ContractError::TimelockNotElapsed(_)));
 
-        // Attempting to pause when already paused should fail
-        let err = contract.sender(alice).pause_contract().motsu_unwrap_err();
-        assert!(matches!(err, ContractError::AlreadyPaused(_)));
-
-        // Non-owner (bob) cannot pause the contract
-        let err = contract.sender(bob).pause_contract().motsu_unwrap_err();
+        // Non-owner cannot queue a pause/unpause
+        let err = contract
+            .sender(bob)
+            .pause_contract()
+            .motsu_unwrap_err();
         assert!(matches!(err, ContractError::Unauthorized(_)));
 
-        // Non-owner (bob) cannot unpause the contract
-        let err = contract.sender(bob).unpause_contract().motsu_unwrap_err();
-        assert!(matches!(err, ContractError::Unauthorized(_)));
+        // Advance past the effective block and apply the queued unpause
+        VM::set_block_number(110);
+        contract.sender(alice).execute_pending().motsu_unwrap();
+        assert_eq!(contract.sender(alice).is_paused(), false);
+
+        // Nothing left queued
+        let err = contract.sender(alice).execute_pending().motsu_unwrap_err();
+        assert!(matches!(err, ContractError::NoPendingAction(_)));
+
+        // Owner queues a pause, then cancels it before it takes effect
+        contract.sender(alice).pause_contract().motsu_unwrap();
+        contract
+            .sender(alice)
+            .cancel_pending_pause_change()
+            .motsu_unwrap();
+        VM::set_block_number(200);
+        let err = contract.sender(alice).execute_pending().motsu_unwrap_err();
+        assert!(matches!(err, ContractError::NoPendingAction(_)));
+        assert_eq!(contract.sender(alice).is_paused(), false);
 
-        // Owner initiates ownership transfer to bob
+        // Owner queues an ownership transfer to bob
         contract
             .sender(alice)
             .transfer_ownership(bob)
             .motsu_unwrap();
-        assert_eq!(contract.sender(alice).owner(), alice); // Still alice until accepted
+        assert_eq!(contract.sender(alice).owner(), alice); // still alice
+        assert_eq!(contract.sender(alice).pending_owner(), Address::ZERO); // not staged yet
+
+        // Too early: execute_pending refuses
+        let err = contract.sender(alice).execute_pending().motsu_unwrap_err();
+        assert!(matches!(err, ContractError::TimelockNotElapsed(_)));
+
+        // Advance past the effective block: the transfer is staged with Ownable2Step
+        VM::set_block_number(210);
+        contract.sender(alice).execute_pending().motsu_unwrap();
+        assert_eq!(contract.sender(alice).owner(), alice); // still alice until accepted
         assert_eq!(contract.sender(alice).pending_owner(), bob);
 
         // Charlie (non-pending owner) cannot accept ownership
@@ -166,59 +293,29 @@ mod tests {
             .motsu_unwrap_err();
         assert!(matches!(err, ownable::Error::UnauthorizedAccount(_)));
 
-        // Alice is still the owner and can perform owner actions
-        contract.sender(alice).unpause_contract().motsu_unwrap();
-        assert_eq!(contract.sender(alice).is_paused(), false);
-
-        // Bob (pending owner) accepts ownership
+        // Bob (pending owner) accepts ownership immediately; Ownable2Step's
+        // own acceptance step isn't timelocked
         contract.sender(bob).accept_ownership().motsu_unwrap();
         assert_eq!(contract.sender(bob).owner(), bob);
         assert_eq!(contract.sender(bob).pending_owner(), Address::ZERO);
 
-        // Alice is no longer the owner and cannot perform owner actions
+        // Alice is no longer the owner and cannot queue owner actions
         let err = contract.sender(alice).pause_contract().motsu_unwrap_err();
         assert!(matches!(err, ContractError::Unauthorized(_)));
 
-        // Bob (new owner) can perform owner actions
-        contract.sender(bob).pause_contract().motsu_unwrap();
-        assert_eq!(contract.sender(bob).is_paused(), true);
-
-        // Bob initiates transfer to charlie
+        // Bob (new owner) can queue and cancel a pending ownership transfer
         contract
             .sender(bob)
             .transfer_ownership(charlie)
             .motsu_unwrap();
-        assert_eq!(contract.sender(bob).pending_owner(), charlie);
-
-        // Bob can cancel the transfer by transferring to Address::ZERO
         contract
             .sender(bob)
-            .transfer_ownership(Address::ZERO)
+            .cancel_pending_ownership_transfer()
             .motsu_unwrap();
+        VM::set_block_number(220);
+        let err = contract.sender(bob).execute_pending().motsu_unwrap_err();
+        assert!(matches!(err, ContractError::NoPendingAction(_)));
         assert_eq!(contract.sender(bob).pending_owner(), Address::ZERO);
-
-        // Charlie cannot accept ownership anymore
-        let err = contract
-            .sender(charlie)
-            .accept_ownership()
-            .motsu_unwrap_err();
-        assert!(matches!(err, ownable::Error::UnauthorizedAccount(_)));
-
-        // Bob remains the owner
         assert_eq!(contract.sender(bob).owner(), bob);
-
-        // Bob can renounce ownership
-        contract.sender(bob).renounce_ownership().motsu_unwrap();
-        assert_eq!(contract.sender(bob).owner(), Address::ZERO);
-
-        // No one can perform owner actions after renouncement
-        let err = contract.sender(bob).pause_contract().motsu_unwrap_err();
-        assert!(matches!(err, ContractError::Unauthorized(_)));
-
-        let err = contract.sender(alice).unpause_contract().motsu_unwrap_err();
-        assert!(matches!(err, ContractError::Unauthorized(_)));
-
-        // Contract remains in its last state (paused)
-        assert_eq!(contract.sender(alice).is_paused(), true);
     }
 }
@@ -157,14 +157,12 @@ mod tests {
         .try_to_vec()
         .unwrap();
 
-        let set_value_wrong_authority_instruction = Instruction::new_with_bytes(
-            PROGRAM_ID,
-            &set_value_instruction_data,
-            vec![
-                AccountMeta::new(counter_state_key, false),
-                AccountMeta::new(non_authority_key, true),
-            ],
-        );
+        let initial_accounts = [
+            (counter_state_key, counter_account.clone()),
+            (authority_key, authority_account.clone()),
+            (non_authority_key, non_authority_account.clone()),
+            keyed_account_for_system_program(),
+        ];
 
         mollusk.process_and_validate_instruction_chain(
             &[
@@ -198,19 +196,69 @@ mod tests {
                             .build(),
                     ],
                 ),
-                (
-                    &set_value_wrong_authority_instruction,
-                    &[Check::err(ProgramError::Custom(
-                        ErrorCode::ConstraintHasOne as u32,
-                    ))],
-                ),
-            ],
-            &[
-                (counter_state_key, counter_account.clone()),
-                (authority_key, authority_account.clone()),
-                (non_authority_key, non_authority_account.clone()),
-                keyed_account_for_system_program(),
             ],
+            &initial_accounts,
+        );
+
+        // Every state-mutating path is exercised against privilege escalation (a
+        // dropped signer), writable deescalation (write access downgraded to
+        // read-only - caught here by Anchor's own `mut` constraint, not the runtime),
+        // and a wrong-authority substitution, via the shared Mollusk negative-test
+        // harness.
+        use mollusk_privilege_harness::ExpectedViolation;
+
+        let account_not_signer =
+            ExpectedViolation::Program(ProgramError::Custom(ErrorCode::AccountNotSigner as u32));
+        let constraint_mut =
+            ExpectedViolation::Program(ProgramError::Custom(ErrorCode::ConstraintMut as u32));
+
+        mollusk_privilege_harness::assert_rejects_signer_deescalation(
+            &mollusk,
+            &initialize_instruction,
+            &initial_accounts,
+            account_not_signer.clone(),
+        );
+        mollusk_privilege_harness::assert_rejects_writable_deescalation(
+            &mollusk,
+            &initialize_instruction,
+            &initial_accounts,
+            constraint_mut.clone(),
+        );
+
+        let after_initialize = mollusk.process_and_validate_instruction(
+            &initialize_instruction,
+            &initial_accounts,
+            &[Check::success()],
+        );
+
+        mollusk_privilege_harness::assert_rejects_writable_deescalation(
+            &mollusk,
+            &increment_instruction,
+            &after_initialize.resulting_accounts,
+            constraint_mut.clone(),
+        );
+
+        mollusk_privilege_harness::assert_rejects_signer_deescalation(
+            &mollusk,
+            &set_value_instruction,
+            &after_initialize.resulting_accounts,
+            account_not_signer,
+        );
+        mollusk_privilege_harness::assert_rejects_writable_deescalation(
+            &mollusk,
+            &set_value_instruction,
+            &after_initialize.resulting_accounts,
+            constraint_mut,
+        );
+        mollusk_privilege_harness::assert_rejects_wrong_authority(
+            &mollusk,
+            &set_value_instruction,
+            &after_initialize.resulting_accounts,
+            1,
+            (non_authority_key, non_authority_account.clone()),
+            ExpectedViolation::Program(ProgramError::Custom(
+                ErrorCode::ConstraintHasOne as u32,
+            )),
         );
     }
 }
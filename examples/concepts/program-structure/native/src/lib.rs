@@ -10,6 +10,11 @@ use solana_system_interface::instruction as system_instruction;
 
 declare_id!("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
 
+/// Exposed so other on-chain programs can depend on this crate as a library (see
+/// `cpi-to-counter`) without pulling in this program's own `entrypoint!`.
+#[cfg(feature = "no-entrypoint")]
+pub static PROGRAM_NAME: &str = env!("CARGO_CRATE_NAME");
+
 static STATE_PDA_SEED: &[u8] = b"state";
 
 #[derive(BorshSerialize, BorshDeserialize, BorshSchema)]
@@ -144,6 +149,7 @@ fn process_set_value(accounts: &[AccountInfo], new_value: u64) -> ProgramResult
     Ok(())
 }
 
+#[cfg(not(feature = "no-entrypoint"))]
 entrypoint!(process_instruction);
 
 #[cfg(test)]
@@ -153,7 +159,7 @@ mod test {
     use mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk};
     use solana_account::Account;
     use solana_program::{
-        instruction::{AccountMeta, Instruction as SolanaInstruction},
+        instruction::{AccountMeta, Instruction as SolanaInstruction, InstructionError},
         program_error::ProgramError,
     };
     use solana_pubkey::Pubkey;
@@ -229,14 +235,12 @@ mod test {
         })
         .unwrap();
 
-        let set_value_wrong_authority_instruction = SolanaInstruction::new_with_bytes(
-            PROGRAM_ID,
-            &set_value_instruction_data,
-            vec![
-                AccountMeta::new(counter_state_key, false),
-                AccountMeta::new(non_authority_key, true),
-            ],
-        );
+        let initial_accounts = [
+            (counter_state_key, counter_account.clone()),
+            (authority_key, authority_account.clone()),
+            (non_authority_key, non_authority_account.clone()),
+            keyed_account_for_system_program(),
+        ];
 
         mollusk.process_and_validate_instruction_chain(
             &[
@@ -270,17 +274,61 @@ mod test {
                             .build(),
                     ],
                 ),
-                (
-                    &set_value_wrong_authority_instruction,
-                    &[Check::err(ProgramError::MissingRequiredSignature)],
-                ),
-            ],
-            &[
-                (counter_state_key, counter_account.clone()),
-                (authority_key, authority_account.clone()),
-                (non_authority_key, non_authority_account.clone()),
-                keyed_account_for_system_program(),
             ],
+            &initial_accounts,
+        );
+
+        // Every state-mutating path is exercised against privilege escalation (a
+        // dropped signer), writable deescalation (write access downgraded to
+        // read-only), and a wrong-authority substitution, via the shared Mollusk
+        // negative-test harness.
+        use mollusk_privilege_harness::ExpectedViolation;
+
+        mollusk_privilege_harness::assert_rejects_signer_deescalation(
+            &mollusk,
+            &initialize_instruction,
+            &initial_accounts,
+            ExpectedViolation::Program(ProgramError::MissingRequiredSignature),
+        );
+        mollusk_privilege_harness::assert_rejects_writable_deescalation(
+            &mollusk,
+            &initialize_instruction,
+            &initial_accounts,
+            ExpectedViolation::Instruction(InstructionError::ReadonlyDataModified),
+        );
+
+        let after_initialize = mollusk.process_and_validate_instruction(
+            &initialize_instruction,
+            &initial_accounts,
+            &[Check::success()],
+        );
+
+        mollusk_privilege_harness::assert_rejects_writable_deescalation(
+            &mollusk,
+            &increment_instruction,
+            &after_initialize.resulting_accounts,
+            ExpectedViolation::Instruction(InstructionError::ReadonlyDataModified),
+        );
+
+        mollusk_privilege_harness::assert_rejects_signer_deescalation(
+            &mollusk,
+            &set_value_instruction,
+            &after_initialize.resulting_accounts,
+            ExpectedViolation::Program(ProgramError::MissingRequiredSignature),
+        );
+        mollusk_privilege_harness::assert_rejects_writable_deescalation(
+            &mollusk,
+            &set_value_instruction,
+            &after_initialize.resulting_accounts,
+            ExpectedViolation::Instruction(InstructionError::ReadonlyDataModified),
+        );
+        mollusk_privilege_harness::assert_rejects_wrong_authority(
+            &mollusk,
+            &set_value_instruction,
+            &after_initialize.resulting_accounts,
+            1,
+            (non_authority_key, non_authority_account.clone()),
+            ExpectedViolation::Program(ProgramError::MissingRequiredSignature),
         );
     }
 }
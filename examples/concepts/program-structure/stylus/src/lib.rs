@@ -1,31 +1,134 @@
 extern crate alloc;
 
 use stylus_sdk::{
-    alloy_primitives::{Address, U256},
+    alloy_primitives::{Address, Bytes, Signature, B256, U256},
     alloy_sol_types::sol,
+    crypto::keccak,
     prelude::*,
-    storage::{StorageAddress, StorageU256},
+    storage::{StorageAddress, StorageBool, StorageMap, StorageU256},
 };
 
 sol! {
     event ContractInitialized(uint256 initial_value, address authority);
     event ValueIncremented(uint256 new_value);
     event ValueUpdated(uint256 new_value);
+    event AuthorityProposed(address proposed_authority);
+    event AuthorityTransferred(address previous_authority, address new_authority);
+    event CallerGranted(address account);
+    event CallerRevoked(address account);
 
     #[derive(Debug, PartialEq, Eq)]
     error Unauthorized(address caller);
+    #[derive(Debug, PartialEq, Eq)]
+    error InvalidSignature();
+    #[derive(Debug, PartialEq, Eq)]
+    error ExpiredSignature(uint256 deadline, uint256 current_timestamp);
 }
 
 #[derive(SolidityError, Debug, PartialEq, Eq)]
 pub enum CounterError {
     Unauthorized(Unauthorized),
+    InvalidSignature(InvalidSignature),
+    ExpiredSignature(ExpiredSignature),
+}
+
+/// `keccak256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")`'s
+/// preimage, hashed fresh per call in [`Counter::domain_separator`] rather than cached as a constant.
+const EIP712_DOMAIN_TYPE: &[u8] =
+    b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const EIP712_DOMAIN_NAME: &[u8] = b"Counter";
+const EIP712_DOMAIN_VERSION: &[u8] = b"1";
+/// `keccak256("SetValue(uint256 new_value,uint256 nonce,uint256 deadline)")`'s preimage.
+const SET_VALUE_TYPE: &[u8] = b"SetValue(uint256 new_value,uint256 nonce,uint256 deadline)";
+
+/// Wraps a [`StorageU256`], eliding the underlying `SSTORE` (and any log that would announce the
+/// write) when the new value is identical to what's already stored - the same no-op-write saving
+/// EIP-1283 net-metering gives a plain `SSTORE` at the EVM level, made explicit at the storage-type
+/// boundary instead of left to the gas schedule.
+#[storage]
+pub struct CachedStorageU256 {
+    value: StorageU256,
+}
+
+impl CachedStorageU256 {
+    pub fn get(&self) -> U256 {
+        self.value.get()
+    }
+
+    pub fn set(&mut self, new_value: U256) {
+        self.value.set(new_value);
+    }
+
+    /// Writes `new_value` only if it differs from the current value, returning whether a write
+    /// actually happened so callers can skip logging a no-op change.
+    pub fn set_if_changed(&mut self, new_value: U256) -> bool {
+        if self.value.get() == new_value {
+            return false;
+        }
+
+        self.value.set(new_value);
+
+        true
+    }
 }
 
 #[storage]
 #[entrypoint]
 pub struct Counter {
-    value: StorageU256,
+    value: CachedStorageU256,
     authority: StorageAddress,
+    /// Incremented on every successful [`Counter::set_value_signed`] call so a signature can't be
+    /// replayed once it's been consumed.
+    nonce: StorageU256,
+    /// Holds a would-be successor between [`Counter::propose_authority`] and
+    /// [`Counter::accept_authority`]; `Address::ZERO` means no transfer is in flight.
+    pending_authority: StorageAddress,
+    /// Addresses allowed to call `set_value`/`set_value_signed` alongside the primary authority.
+    /// Only the primary authority may grant/revoke entries here.
+    allowed_callers: StorageMap<Address, StorageBool>,
+}
+
+impl Counter {
+    /// Returns `Err(CounterError::Unauthorized)` unless `caller` is the primary authority - the
+    /// check every authority-gated method other than `set_value`/`set_value_signed` uses.
+    fn require_authorized(&self, caller: Address) -> Result<(), CounterError> {
+        if caller != self.authority.get() {
+            return Err(CounterError::Unauthorized(Unauthorized { caller }));
+        }
+
+        Ok(())
+    }
+
+    /// EIP-712 domain separator, rederived from the live chain id and contract address rather than
+    /// cached at construction time.
+    fn domain_separator(&self) -> B256 {
+        let mut preimage = [0u8; 160];
+        preimage[0..32].copy_from_slice(keccak(EIP712_DOMAIN_TYPE).as_slice());
+        preimage[32..64].copy_from_slice(keccak(EIP712_DOMAIN_NAME).as_slice());
+        preimage[64..96].copy_from_slice(keccak(EIP712_DOMAIN_VERSION).as_slice());
+        preimage[96..128].copy_from_slice(&U256::from(self.vm().chain_id()).to_be_bytes::<32>());
+        preimage[128..160].copy_from_slice(self.vm().contract_address().into_word().as_slice());
+
+        keccak(preimage)
+    }
+
+    /// EIP-712 typed-data digest authorizing a `set_value_signed` call for `new_value`, consumable
+    /// only while `self.nonce` still equals `nonce` and only before `deadline`.
+    fn set_value_digest(&self, new_value: U256, nonce: U256, deadline: U256) -> B256 {
+        let mut struct_preimage = [0u8; 128];
+        struct_preimage[0..32].copy_from_slice(keccak(SET_VALUE_TYPE).as_slice());
+        struct_preimage[32..64].copy_from_slice(&new_value.to_be_bytes::<32>());
+        struct_preimage[64..96].copy_from_slice(&nonce.to_be_bytes::<32>());
+        struct_preimage[96..128].copy_from_slice(&deadline.to_be_bytes::<32>());
+        let struct_hash = keccak(struct_preimage);
+
+        let mut digest_preimage = [0u8; 66];
+        digest_preimage[0..2].copy_from_slice(&[0x19, 0x01]);
+        digest_preimage[2..34].copy_from_slice(self.domain_separator().as_slice());
+        digest_preimage[34..66].copy_from_slice(struct_hash.as_slice());
+
+        keccak(digest_preimage)
+    }
 }
 
 #[public]
@@ -49,9 +152,9 @@ impl Counter {
     pub fn increment(&mut self) -> U256 {
         let new_value = self.value.get() + U256::ONE;
 
-        self.value.set(new_value);
-
-        log(self.vm(), ValueIncremented { new_value });
+        if self.value.set_if_changed(new_value) {
+            log(self.vm(), ValueIncremented { new_value });
+        }
 
         new_value
     }
@@ -59,14 +162,124 @@ impl Counter {
     pub fn set_value(&mut self, new_value: U256) -> Result<(), CounterError> {
         let caller = self.vm().msg_sender();
 
-        // Only authority can set value
-        if caller != self.authority.get() {
+        // The primary authority or any account it's granted may set the value
+        if caller != self.authority.get() && !self.allowed_callers.get(caller) {
             return Err(CounterError::Unauthorized(Unauthorized { caller }));
         }
 
-        self.value.set(new_value);
+        if self.value.set_if_changed(new_value) {
+            log(self.vm(), ValueUpdated { new_value });
+        }
+
+        Ok(())
+    }
+
+    /// Meta-transaction variant of [`Counter::set_value`] - authorizes the update with an EIP-712
+    /// signature over `(new_value, nonce, deadline)` instead of `msg.sender`, so a relayer can submit
+    /// the transaction on the authority's behalf. `nonce` is implicit: it must match `self.nonce`,
+    /// which only advances once a signature is consumed, so a captured signature can't be replayed.
+    pub fn set_value_signed(
+        &mut self,
+        new_value: U256,
+        deadline: U256,
+        signature: Bytes,
+    ) -> Result<(), CounterError> {
+        let current_timestamp = U256::from(self.vm().block_timestamp());
+        if current_timestamp > deadline {
+            return Err(CounterError::ExpiredSignature(ExpiredSignature {
+                deadline,
+                current_timestamp,
+            }));
+        }
+
+        let nonce = self.nonce.get();
+        let digest = self.set_value_digest(new_value, nonce, deadline);
 
-        log(self.vm(), ValueUpdated { new_value });
+        let signature = Signature::from_raw(signature.as_ref())
+            .map_err(|_| CounterError::InvalidSignature(InvalidSignature {}))?;
+        let recovered = signature
+            .recover_address_from_prehash(&digest)
+            .map_err(|_| CounterError::InvalidSignature(InvalidSignature {}))?;
+
+        if recovered != self.authority.get() && !self.allowed_callers.get(recovered) {
+            return Err(CounterError::Unauthorized(Unauthorized { caller: recovered }));
+        }
+
+        self.nonce.set(nonce + U256::ONE);
+
+        if self.value.set_if_changed(new_value) {
+            log(self.vm(), ValueUpdated { new_value });
+        }
+
+        Ok(())
+    }
+
+    /// Nominates `proposed` as the next authority. The transfer doesn't take effect until
+    /// `proposed` itself calls [`Counter::accept_authority`] - unlike a one-step handover, a typo'd
+    /// or unreachable address can't brick the contract's authority.
+    pub fn propose_authority(&mut self, proposed: Address) -> Result<(), CounterError> {
+        let caller = self.vm().msg_sender();
+        self.require_authorized(caller)?;
+
+        self.pending_authority.set(proposed);
+
+        log(
+            self.vm(),
+            AuthorityProposed {
+                proposed_authority: proposed,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Completes a transfer proposed via [`Counter::propose_authority`]. Only the proposed address
+    /// itself may call this.
+    pub fn accept_authority(&mut self) -> Result<(), CounterError> {
+        let caller = self.vm().msg_sender();
+        let pending_authority = self.pending_authority.get();
+
+        if caller != pending_authority {
+            return Err(CounterError::Unauthorized(Unauthorized { caller }));
+        }
+
+        let previous_authority = self.authority.get();
+        self.authority.set(pending_authority);
+        self.pending_authority.set(Address::ZERO);
+
+        log(
+            self.vm(),
+            AuthorityTransferred {
+                previous_authority,
+                new_authority: pending_authority,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Grants `account` permission to call `set_value`/`set_value_signed` without being the
+    /// primary authority. Only the primary authority may grant.
+    pub fn grant_caller(&mut self, account: Address) -> Result<(), CounterError> {
+        let caller = self.vm().msg_sender();
+        self.require_authorized(caller)?;
+
+        self.allowed_callers.setter(account).set(true);
+
+        log(self.vm(), CallerGranted { account });
+
+        Ok(())
+    }
+
+    /// Revokes a permission previously granted via [`Counter::grant_caller`]. Only the primary
+    /// authority may revoke.
+    pub fn revoke_caller(&mut self, account: Address) -> Result<(), CounterError> {
+        let caller = self.vm().msg_sender();
+        self.require_authorized(caller)?;
+
+        self.allowed_callers.setter(account).set(false);
+
+        log(self.vm(), CallerRevoked { account });
 
         Ok(())
     }
@@ -79,14 +292,53 @@ impl Counter {
     pub fn get_authority(&self) -> Address {
         self.authority.get()
     }
+
+    pub fn get_pending_authority(&self) -> Address {
+        self.pending_authority.get()
+    }
+
+    pub fn is_granted(&self, account: Address) -> bool {
+        self.allowed_callers.get(account)
+    }
+
+    pub fn get_nonce(&self) -> U256 {
+        self.nonce.get()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use alloy_sol_types::SolEvent;
+    use k256::{
+        ecdsa::{signature::hazmat::PrehashSigner, RecoveryId, Signature as EcdsaSignature, SigningKey},
+        elliptic_curve::sec1::ToEncodedPoint,
+    };
     use stylus_sdk::testing::*;
 
+    /// Derives the Ethereum address for a freshly-generated signing key, the same way a real chain
+    /// derives one: the low 20 bytes of `keccak256` over the uncompressed public key's `X || Y`.
+    fn test_signer() -> (SigningKey, Address) {
+        let signing_key = SigningKey::from_bytes(&[0x42u8; 32].into()).expect("valid scalar");
+        let encoded_point = signing_key.verifying_key().to_encoded_point(false);
+        let hash = keccak(&encoded_point.as_bytes()[1..]);
+        let address = Address::from_slice(&hash.as_slice()[12..32]);
+
+        (signing_key, address)
+    }
+
+    fn sign_digest(signing_key: &SigningKey, digest: B256) -> Bytes {
+        let (signature, recovery_id): (EcdsaSignature, RecoveryId) = signing_key
+            .sign_prehash_recoverable(digest.as_slice())
+            .expect("signing succeeds");
+
+        let mut raw = [0u8; 65];
+        raw[..64].copy_from_slice(&signature.to_bytes());
+        raw[64] = 27 + recovery_id.to_byte();
+
+        Bytes::from(raw.to_vec())
+    }
+
     #[test]
     fn test_contract() {
         let vm = TestVM::new();
@@ -143,4 +395,182 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn test_set_value_same_value_emits_only_one_log() {
+        let vm = TestVM::new();
+        let mut c = Counter::from(&vm);
+
+        c.constructor(U256::from(42));
+
+        let new_value = U256::from(100);
+        assert!(c.set_value(new_value).is_ok());
+        let logs_after_first_set = vm.get_emitted_logs().len();
+        assert_eq!(
+            vm.get_emitted_logs().last(),
+            Some(&(
+                vec![ValueUpdated::SIGNATURE_HASH],
+                ValueUpdated { new_value }.encode_data()
+            ))
+        );
+
+        // Setting the same value again should not emit another `ValueUpdated` log
+        assert!(c.set_value(new_value).is_ok());
+        assert_eq!(vm.get_emitted_logs().len(), logs_after_first_set);
+        assert_eq!(c.get_value(), new_value);
+    }
+
+    #[test]
+    fn test_set_value_signed_valid_signature() {
+        let vm = TestVM::new();
+        let mut c = Counter::from(&vm);
+        let (signing_key, authority) = test_signer();
+
+        vm.set_sender(authority);
+        c.constructor(U256::from(42));
+
+        let new_value = U256::from(7);
+        let deadline = U256::from(vm.block_timestamp()) + U256::from(1000);
+        let digest = c.set_value_digest(new_value, c.get_nonce(), deadline);
+        let signature = sign_digest(&signing_key, digest);
+
+        assert!(c.set_value_signed(new_value, deadline, signature).is_ok());
+        assert_eq!(c.get_value(), new_value);
+        assert_eq!(c.get_nonce(), U256::ONE);
+    }
+
+    #[test]
+    fn test_set_value_signed_rejects_replayed_signature() {
+        let vm = TestVM::new();
+        let mut c = Counter::from(&vm);
+        let (signing_key, authority) = test_signer();
+
+        vm.set_sender(authority);
+        c.constructor(U256::from(42));
+
+        let new_value = U256::from(7);
+        let deadline = U256::from(vm.block_timestamp()) + U256::from(1000);
+        let digest = c.set_value_digest(new_value, c.get_nonce(), deadline);
+        let signature = sign_digest(&signing_key, digest);
+
+        assert!(c
+            .set_value_signed(new_value, deadline, signature.clone())
+            .is_ok());
+
+        // The signature was only valid for the digest binding it to nonce 0, which has since
+        // advanced to 1 - replaying it now authorizes nothing.
+        assert!(c.set_value_signed(new_value, deadline, signature).is_err());
+    }
+
+    #[test]
+    fn test_set_value_signed_rejects_expired_deadline() {
+        let vm = TestVM::new();
+        let mut c = Counter::from(&vm);
+        let (_signing_key, authority) = test_signer();
+
+        vm.set_sender(authority);
+        c.constructor(U256::from(42));
+
+        let deadline = U256::from(vm.block_timestamp());
+        vm.set_block_timestamp(vm.block_timestamp() + 1);
+
+        assert_eq!(
+            c.set_value_signed(U256::from(7), deadline, Bytes::new()),
+            Err(CounterError::ExpiredSignature(ExpiredSignature {
+                deadline,
+                current_timestamp: U256::from(vm.block_timestamp()),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_propose_then_accept_authority() {
+        let vm = TestVM::new();
+        let mut c = Counter::from(&vm);
+        let original_authority = vm.msg_sender();
+        let successor = Address::from([0x42; 20]);
+
+        c.constructor(U256::from(42));
+
+        assert!(c.propose_authority(successor).is_ok());
+        assert_eq!(c.get_pending_authority(), successor);
+        assert_eq!(
+            vm.get_emitted_logs().last(),
+            Some(&(
+                vec![AuthorityProposed::SIGNATURE_HASH],
+                AuthorityProposed {
+                    proposed_authority: successor
+                }
+                .encode_data()
+            ))
+        );
+
+        vm.set_sender(successor);
+        assert!(c.accept_authority().is_ok());
+        assert_eq!(c.get_authority(), successor);
+        assert_eq!(c.get_pending_authority(), Address::ZERO);
+        assert_eq!(
+            vm.get_emitted_logs().last(),
+            Some(&(
+                vec![AuthorityTransferred::SIGNATURE_HASH],
+                AuthorityTransferred {
+                    previous_authority: original_authority,
+                    new_authority: successor,
+                }
+                .encode_data()
+            ))
+        );
+
+        // The old authority can no longer set the value, but the new one can
+        vm.set_sender(original_authority);
+        assert!(c.set_value(U256::from(1)).is_err());
+        vm.set_sender(successor);
+        assert!(c.set_value(U256::from(1)).is_ok());
+    }
+
+    #[test]
+    fn test_accept_authority_rejects_non_proposed_caller() {
+        let vm = TestVM::new();
+        let mut c = Counter::from(&vm);
+
+        c.constructor(U256::from(42));
+        assert!(c.propose_authority(Address::from([0x42; 20])).is_ok());
+
+        vm.set_sender(Address::from([0x99; 20]));
+        assert_eq!(
+            c.accept_authority(),
+            Err(CounterError::Unauthorized(Unauthorized {
+                caller: Address::from([0x99; 20])
+            }))
+        );
+    }
+
+    #[test]
+    fn test_grant_and_revoke_caller() {
+        let vm = TestVM::new();
+        let mut c = Counter::from(&vm);
+        let authority = vm.msg_sender();
+        let grantee = Address::from([0x11; 20]);
+
+        c.constructor(U256::from(42));
+
+        // Not yet granted - can't set the value
+        vm.set_sender(grantee);
+        assert!(c.set_value(U256::from(1)).is_err());
+
+        vm.set_sender(authority);
+        assert!(c.grant_caller(grantee).is_ok());
+        assert!(c.is_granted(grantee));
+
+        vm.set_sender(grantee);
+        assert!(c.set_value(U256::from(1)).is_ok());
+        assert_eq!(c.get_value(), U256::from(1));
+
+        vm.set_sender(authority);
+        assert!(c.revoke_caller(grantee).is_ok());
+        assert!(!c.is_granted(grantee));
+
+        vm.set_sender(grantee);
+        assert!(c.set_value(U256::from(2)).is_err());
+    }
 }
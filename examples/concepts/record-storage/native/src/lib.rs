@@ -0,0 +1,319 @@
+#![allow(unexpected_cfgs)]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    declare_id, entrypoint,
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use solana_system_interface::instruction as system_instruction;
+
+declare_id!("EwZ5N8u3QU6auQj12KxUapA7SPoe5nBBgZuc9ad4V6Aa");
+
+/// One byte tag marking a record account as belonging to this program, stored
+/// ahead of the owning authority so a stale or foreign account can't be read as
+/// a record just because it happens to be the right size.
+pub const RECORD_TAG: u8 = 1;
+pub const HEADER_LEN: usize = 1 + 32;
+
+#[derive(Clone, Copy, Debug, thiserror::Error)]
+pub enum RecordError {
+    #[error("record account has not been initialized")]
+    Uninitialized,
+    #[error("signer does not match the record's authority")]
+    AuthorityMismatch,
+}
+
+impl From<RecordError> for ProgramError {
+    fn from(error: RecordError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub enum Instruction {
+    /// Creates a record account sized to just the header and stamps it with
+    /// `authority`. Unlike a keyed Stylus mapping, each Solana record is its own
+    /// account, so there's no record id here: the record's address *is* its id.
+    Initialize { authority: Pubkey },
+    /// Splices `data` into the record's byte buffer starting at `offset`,
+    /// after checking the signer matches the stored authority. Reallocs the
+    /// account (topping up or refunding rent via the system program) so the
+    /// buffer always fits exactly `HEADER_LEN + offset + data.len()`.
+    Write { offset: u64, data: Vec<u8> },
+    /// Transfers authority over the record to `new_authority`.
+    SetAuthority { new_authority: Pubkey },
+    /// Drains all lamports to `recipient` and zeroes the account's data.
+    CloseAccount,
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if !check_id(program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let instruction = Instruction::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    match instruction {
+        Instruction::Initialize { authority } => process_initialize(accounts, authority),
+        Instruction::Write { offset, data } => process_write(accounts, offset, &data),
+        Instruction::SetAuthority { new_authority } => {
+            process_set_authority(accounts, new_authority)
+        }
+        Instruction::CloseAccount => process_close_account(accounts),
+    }
+}
+
+/// Reads/writes just the header prefix of a record account, leaving the rest of
+/// `account.data` to `Write`'s arbitrary-offset byte buffer.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy)]
+struct RecordHeader {
+    tag: u8,
+    authority: Pubkey,
+}
+
+impl RecordHeader {
+    fn read(account: &AccountInfo) -> Result<Self, ProgramError> {
+        let data = account.data.borrow();
+
+        if data.len() < HEADER_LEN {
+            return Err(RecordError::Uninitialized.into());
+        }
+
+        let header = Self::try_from_slice(&data[..HEADER_LEN])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if header.tag != RECORD_TAG {
+            return Err(RecordError::Uninitialized.into());
+        }
+
+        Ok(header)
+    }
+
+    fn write(&self, account: &AccountInfo) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        let encoded = borsh::to_vec(self).map_err(|_| ProgramError::InvalidAccountData)?;
+        data[..encoded.len()].copy_from_slice(&encoded);
+        Ok(())
+    }
+}
+
+fn verify_authority(header: &RecordHeader, signer_account: &AccountInfo) -> ProgramResult {
+    if !signer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if header.authority != *signer_account.key {
+        return Err(RecordError::AuthorityMismatch.into());
+    }
+
+    Ok(())
+}
+
+fn process_initialize(accounts: &[AccountInfo], authority: Pubkey) -> ProgramResult {
+    let [payer_account, record_account, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !payer_account.is_signer || !record_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let lamports_required = Rent::get()?.minimum_balance(HEADER_LEN);
+
+    invoke(
+        &system_instruction::create_account(
+            payer_account.key,
+            record_account.key,
+            lamports_required,
+            HEADER_LEN as u64,
+            &ID,
+        ),
+        &[
+            payer_account.clone(),
+            record_account.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    RecordHeader {
+        tag: RECORD_TAG,
+        authority,
+    }
+    .write(record_account)
+}
+
+fn process_write(accounts: &[AccountInfo], offset: u64, data: &[u8]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let record_account = next_account_info(account_info_iter)?;
+    let signer_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let header = RecordHeader::read(record_account)?;
+    verify_authority(&header, signer_account)?;
+
+    let offset = usize::try_from(offset).map_err(|_| ProgramError::ArithmeticOverflow)?;
+    let new_len = HEADER_LEN
+        .checked_add(offset)
+        .and_then(|end| end.checked_add(data.len()))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let current_len = record_account.data_len();
+    let current_lamports = record_account.lamports();
+    let lamports_required = Rent::get()?.minimum_balance(new_len);
+
+    match new_len.cmp(&current_len) {
+        std::cmp::Ordering::Greater => {
+            if lamports_required > current_lamports {
+                invoke(
+                    &system_instruction::transfer(
+                        payer_account.key,
+                        record_account.key,
+                        lamports_required - current_lamports,
+                    ),
+                    &[
+                        payer_account.clone(),
+                        record_account.clone(),
+                        system_program.clone(),
+                    ],
+                )?;
+            }
+
+            record_account.realloc(new_len, true)?;
+        }
+        std::cmp::Ordering::Less => {
+            record_account.realloc(new_len, false)?;
+
+            if current_lamports > lamports_required {
+                let refund = current_lamports - lamports_required;
+
+                **record_account.try_borrow_mut_lamports()? -= refund;
+                **payer_account.try_borrow_mut_lamports()? += refund;
+            }
+        }
+        std::cmp::Ordering::Equal => {}
+    }
+
+    let mut record_data = record_account.try_borrow_mut_data()?;
+    record_data[HEADER_LEN + offset..new_len].copy_from_slice(data);
+
+    Ok(())
+}
+
+fn process_set_authority(accounts: &[AccountInfo], new_authority: Pubkey) -> ProgramResult {
+    let [record_account, signer_account] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let mut header = RecordHeader::read(record_account)?;
+    verify_authority(&header, signer_account)?;
+
+    header.authority = new_authority;
+    header.write(record_account)
+}
+
+fn process_close_account(accounts: &[AccountInfo]) -> ProgramResult {
+    let [record_account, signer_account, recipient_account] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let header = RecordHeader::read(record_account)?;
+    verify_authority(&header, signer_account)?;
+
+    let lamports = record_account.lamports();
+    **record_account.try_borrow_mut_lamports()? -= lamports;
+    **recipient_account.try_borrow_mut_lamports()? += lamports;
+
+    record_account.realloc(0, false)
+}
+
+entrypoint!(process_instruction);
+
+#[cfg(test)]
+mod test {
+    use super::{Instruction, ID as PROGRAM_ID, RECORD_TAG};
+
+    use mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk};
+    use solana_account::Account;
+    use solana_program::instruction::{AccountMeta, Instruction as SolanaInstruction};
+    use solana_pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    #[test]
+    fn test_initialize_and_write() {
+        let mollusk = Mollusk::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"));
+
+        let payer_key = Pubkey::new_unique();
+        let payer_account = Account::new(100_000_000, 0, &system_program::id());
+
+        let record_key = Pubkey::new_unique();
+
+        let initialize_instruction = SolanaInstruction::new_with_bytes(
+            PROGRAM_ID,
+            &borsh::to_vec(&Instruction::Initialize {
+                authority: payer_key,
+            })
+            .unwrap(),
+            vec![
+                AccountMeta::new(payer_key, true),
+                AccountMeta::new(record_key, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let payload = b"StylusPort::Solana".to_vec();
+        let write_instruction = SolanaInstruction::new_with_bytes(
+            PROGRAM_ID,
+            &borsh::to_vec(&Instruction::Write {
+                offset: 0,
+                data: payload.clone(),
+            })
+            .unwrap(),
+            vec![
+                AccountMeta::new(record_key, false),
+                AccountMeta::new_readonly(payer_key, true),
+                AccountMeta::new(payer_key, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let mut expected_record_data = vec![RECORD_TAG];
+        expected_record_data.extend_from_slice(payer_key.as_ref());
+        expected_record_data.extend_from_slice(&payload);
+
+        mollusk.process_and_validate_instruction_chain(
+            &[
+                (
+                    &initialize_instruction,
+                    &[Check::success(), Check::account(&record_key).owner(&PROGRAM_ID).build()],
+                ),
+                (
+                    &write_instruction,
+                    &[
+                        Check::success(),
+                        Check::account(&record_key)
+                            .data(&expected_record_data)
+                            .owner(&PROGRAM_ID)
+                            .build(),
+                    ],
+                ),
+            ],
+            &[
+                (payer_key, payer_account),
+                (record_key, Account::default()),
+                keyed_account_for_system_program(),
+            ],
+        );
+    }
+}
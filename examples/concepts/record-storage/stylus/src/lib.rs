@@ -0,0 +1,174 @@
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+extern crate alloc;
+
+use openzeppelin_stylus::access::ownable;
+use stylus_sdk::{alloy_primitives::*, alloy_sol_types::sol, prelude::*, storage::*};
+
+sol! {
+    #[derive(Debug)]
+    error RecordNotFound(uint256 id);
+}
+
+#[derive(SolidityError, Debug)]
+pub enum ContractError {
+    RecordNotFound(RecordNotFound),
+    Unauthorized(ownable::OwnableUnauthorizedAccount),
+}
+
+/// One arbitrary-length byte record, addressed by id the way each SPL record
+/// program account is addressed by its own pubkey.
+#[storage]
+pub struct Record {
+    owner: StorageAddress,
+    data: StorageBytes,
+}
+
+#[storage]
+#[entrypoint]
+pub struct RecordStorage {
+    records: StorageMap<U256, Record>,
+    next_id: StorageU256,
+}
+
+impl RecordStorage {
+    /// Errors unless `id` names an initialized record owned by `caller`, the
+    /// Stylus equivalent of the native program's `verify_authority` check.
+    fn require_owner(&self, id: U256, caller: Address) -> Result<(), ContractError> {
+        let owner = self.records.getter(id).owner.get();
+
+        if owner == Address::ZERO {
+            return Err(RecordNotFound { id }.into());
+        }
+        if owner != caller {
+            return Err(ownable::OwnableUnauthorizedAccount { account: caller }.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[public]
+impl RecordStorage {
+    /// Creates a new record owned by the caller and returns its id. Stands in
+    /// for the native program's `Initialize`: since a Stylus record lives in a
+    /// map rather than its own account, there's no separate authority argument
+    /// to accept, the creator is always the initial owner.
+    pub fn create_record(&mut self) -> U256 {
+        let id = self.next_id.get();
+        self.records.setter(id).owner.set(self.vm().msg_sender());
+        self.next_id.set(id + U256::ONE);
+        id
+    }
+
+    /// Splices `data` into record `id`'s byte buffer starting at `offset`,
+    /// growing the buffer if needed, after checking the caller owns the
+    /// record.
+    pub fn write(&mut self, id: U256, offset: u64, data: Vec<u8>) -> Result<(), ContractError> {
+        self.require_owner(id, self.vm().msg_sender())?;
+
+        let offset = offset as usize;
+        let end = offset + data.len();
+
+        let mut record = self.records.setter(id);
+        let mut buffer = record.data.get_bytes();
+        if buffer.len() < end {
+            buffer.resize(end, 0);
+        }
+        buffer[offset..end].copy_from_slice(&data);
+        record.data.set_bytes(buffer);
+
+        Ok(())
+    }
+
+    /// Transfers ownership of record `id` to `new_owner`.
+    pub fn set_authority(&mut self, id: U256, new_owner: Address) -> Result<(), ContractError> {
+        self.require_owner(id, self.vm().msg_sender())?;
+        self.records.setter(id).owner.set(new_owner);
+        Ok(())
+    }
+
+    /// Deletes record `id`, clearing its owner and data so `require_owner`
+    /// treats it as never having existed, mirroring the native program's
+    /// `CloseAccount`.
+    pub fn delete(&mut self, id: U256) -> Result<(), ContractError> {
+        self.require_owner(id, self.vm().msg_sender())?;
+
+        let mut record = self.records.setter(id);
+        record.owner.set(Address::ZERO);
+        record.data.set_bytes(Vec::new());
+
+        Ok(())
+    }
+
+    pub fn record(&self, id: U256) -> Result<(Address, Vec<u8>), ContractError> {
+        let record = self.records.getter(id);
+        let owner = record.owner.get();
+
+        if owner == Address::ZERO {
+            return Err(RecordNotFound { id }.into());
+        }
+
+        Ok((owner, record.data.get_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use motsu::prelude::*;
+
+    #[motsu::test]
+    fn test_record_lifecycle(contract: Contract<RecordStorage>, alice: Address, bob: Address) {
+        let id = contract.sender(alice).create_record();
+        assert_eq!(id, U256::ZERO);
+
+        contract
+            .sender(alice)
+            .write(id, 0, b"StylusPort".to_vec())
+            .motsu_unwrap();
+
+        let (owner, data) = contract.sender(alice).record(id).motsu_unwrap();
+        assert_eq!(owner, alice);
+        assert_eq!(data, b"StylusPort".to_vec());
+
+        // writing past the end of the buffer grows it
+        contract
+            .sender(alice)
+            .write(id, 10, b"::Solana".to_vec())
+            .motsu_unwrap();
+        let (_, data) = contract.sender(alice).record(id).motsu_unwrap();
+        assert_eq!(data, b"StylusPort::Solana".to_vec());
+
+        // a non-owner cannot write
+        let err = contract
+            .sender(bob)
+            .write(id, 0, b"hijacked".to_vec())
+            .motsu_unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized(_)));
+
+        // the owner can transfer authority
+        contract
+            .sender(alice)
+            .set_authority(id, bob)
+            .motsu_unwrap();
+        assert!(matches!(
+            contract.sender(alice).write(id, 0, vec![]).motsu_unwrap_err(),
+            ContractError::Unauthorized(_)
+        ));
+        contract
+            .sender(bob)
+            .write(id, 0, b"bob's data".to_vec())
+            .motsu_unwrap();
+
+        // the new owner can close the record
+        contract.sender(bob).delete(id).motsu_unwrap();
+        let err = contract.sender(bob).record(id).motsu_unwrap_err();
+        assert!(matches!(err, ContractError::RecordNotFound(_)));
+    }
+
+    #[motsu::test]
+    fn test_unknown_record_not_found(contract: Contract<RecordStorage>, alice: Address) {
+        let err = contract.sender(alice).record(U256::from(42)).motsu_unwrap_err();
+        assert!(matches!(err, ContractError::RecordNotFound(_)));
+    }
+}
@@ -0,0 +1,132 @@
+//! End-to-end harness that compiles an example contract with `cargo-stylus` and deploys it
+//! to a locally launched Arbitrum Nitro devnode, returning the deployed address.
+//!
+//! A contract deployed this way runs in a real Stylus execution environment rather than
+//! the `TestVM`/motsu mock (for example the `deposit`/`withdraw_all` ETH flows in
+//! `NativeTokenHandling`, or the `static_call` in `ExternalCaller` against a genuinely
+//! deployed `Adder`). The crate launches the devnode and deploys; it has no helpers for
+//! calling a deployed contract over RPC. Requires the binaries installed by `scripts/setup.sh`
+//! (`cargo-stylus`, `solc`) and is gated behind the `e2e` feature since it shells out to
+//! external processes and is far slower than the in-process test suites.
+#![cfg(feature = "e2e")]
+
+use std::{
+    net::TcpStream,
+    path::Path,
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use alloy_primitives::Address;
+
+const DEVNODE_RPC_PORT: u16 = 8547;
+const DEVNODE_READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("devnode did not become ready within {0:?}")]
+    DevnodeTimeout(Duration),
+    #[error("failed to spawn `{0}`: {1}")]
+    Spawn(&'static str, std::io::Error),
+    #[error("`{0}` exited with a non-zero status")]
+    CommandFailed(&'static str),
+    #[error("failed to parse deployed contract address from cargo-stylus output")]
+    AddressNotFound,
+}
+
+/// A running local Nitro/Stylus devnode, torn down when dropped.
+pub struct Devnode {
+    process: Child,
+    rpc_url: String,
+}
+
+impl Devnode {
+    /// Launches a local devnode (via the `nitro-devnode` docker compose setup) and
+    /// blocks until its RPC endpoint accepts connections.
+    pub fn launch() -> Result<Self, Error> {
+        let process = Command::new("docker")
+            .args(["compose", "-f", "nitro-devnode/docker-compose.yml", "up"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|err| Error::Spawn("docker compose up", err))?;
+
+        let rpc_url = format!("http://localhost:{DEVNODE_RPC_PORT}");
+
+        let deadline = Instant::now() + DEVNODE_READY_TIMEOUT;
+        while Instant::now() < deadline {
+            if TcpStream::connect(("localhost", DEVNODE_RPC_PORT)).is_ok() {
+                return Ok(Self { process, rpc_url });
+            }
+            thread::sleep(Duration::from_millis(250));
+        }
+
+        Err(Error::DevnodeTimeout(DEVNODE_READY_TIMEOUT))
+    }
+
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// Compiles and deploys the contract crate at `manifest_dir` via `cargo stylus
+    /// deploy`, returning its deployed address.
+    pub fn deploy_contract(
+        &self,
+        manifest_dir: &Path,
+        private_key: &str,
+    ) -> Result<Address, Error> {
+        let output = Command::new("cargo")
+            .current_dir(manifest_dir)
+            .args([
+                "stylus",
+                "deploy",
+                "--endpoint",
+                &self.rpc_url,
+                "--private-key",
+                private_key,
+                "--no-verify",
+            ])
+            .output()
+            .map_err(|err| Error::Spawn("cargo stylus deploy", err))?;
+
+        if !output.status.success() {
+            return Err(Error::CommandFailed("cargo stylus deploy"));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        stdout
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("deployed code at address: "))
+            .and_then(|addr| addr.parse().ok())
+            .ok_or(Error::AddressNotFound)
+    }
+}
+
+impl Drop for Devnode {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+/// A deployed contract's address plus the sender address a test acts as. It holds no
+/// RPC client and has no methods for calling the contract.
+pub struct Fixture {
+    pub address: Address,
+    pub sender: Address,
+}
+
+impl Fixture {
+    /// Deploys `manifest_dir` to `devnode` and returns a fixture scoped to `sender`.
+    pub fn deploy(
+        devnode: &Devnode,
+        manifest_dir: &Path,
+        private_key: &str,
+        sender: Address,
+    ) -> Result<Self, Error> {
+        let address = devnode.deploy_contract(manifest_dir, private_key)?;
+        Ok(Self { address, sender })
+    }
+}
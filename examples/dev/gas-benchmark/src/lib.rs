@@ -0,0 +1,88 @@
+//! A small gas-benchmark harness for the example contracts, built on top of
+//! `stylus_sdk::testing::TestVM` the same way the existing motsu/`TestVM` unit tests
+//! drive contract calls, but reporting the EVM gas each call consumed instead of just
+//! asserting on return values.
+//!
+//! Example contracts add this crate as a dev-dependency and benchmark a call like:
+//!
+//! ```ignore
+//! let vm = TestVM::default();
+//! let mut contract = MyContract::from(&vm);
+//! let report = gas_benchmark::measure("deposit", &vm, || contract.deposit());
+//! println!("{report}");
+//! ```
+
+use std::fmt;
+
+use stylus_sdk::testing::TestVM;
+
+/// The gas consumed by a single benchmarked call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchmarkReport {
+    pub name: String,
+    pub gas_used: u64,
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} gas", self.name, self.gas_used)
+    }
+}
+
+/// Runs `call`, recording how much EVM gas `vm` reports as consumed while it ran.
+pub fn measure<T>(name: impl Into<String>, vm: &TestVM, call: impl FnOnce() -> T) -> BenchmarkReport {
+    let gas_before = vm.evm_gas_left();
+    call();
+    let gas_after = vm.evm_gas_left();
+
+    BenchmarkReport {
+        name: name.into(),
+        gas_used: gas_before.saturating_sub(gas_after),
+    }
+}
+
+/// Renders a set of reports as a sorted (most expensive first) table, suitable for
+/// dumping in CI output to catch gas regressions.
+pub fn render_table(reports: &mut [BenchmarkReport]) -> String {
+    reports.sort_by(|a, b| b.gas_used.cmp(&a.gas_used));
+
+    reports
+        .iter()
+        .map(|report| report.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measure_reports_call_name() {
+        let vm = TestVM::default();
+        vm.set_gas(1_000_000);
+
+        let report = measure("noop", &vm, || {});
+
+        assert_eq!(report.name, "noop");
+    }
+
+    #[test]
+    fn render_table_sorts_by_gas_used_descending() {
+        let mut reports = vec![
+            BenchmarkReport {
+                name: "cheap".to_owned(),
+                gas_used: 100,
+            },
+            BenchmarkReport {
+                name: "expensive".to_owned(),
+                gas_used: 5_000,
+            },
+        ];
+
+        let table = render_table(&mut reports);
+        let expensive_pos = table.find("expensive").unwrap();
+        let cheap_pos = table.find("cheap").unwrap();
+        assert!(expensive_pos < cheap_pos);
+    }
+}
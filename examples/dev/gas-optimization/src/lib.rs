@@ -1,6 +1,6 @@
 extern crate alloc;
 
-use stylus_sdk::{prelude::*, storage::*};
+use stylus_sdk::{alloy_primitives::U256, prelude::*, storage::*};
 
 #[storage]
 #[allow(dead_code)]
@@ -18,13 +18,147 @@ pub struct InefficientStorage {
     flag2: StorageBool,
 }
 
+/// Bin-packs up to 256 bits' worth of sub-word fields into a single [`StorageU256`], generalizing
+/// the manual field-ordering trick `EfficientStorage`/`InefficientStorage` above rely on: instead of
+/// leaning on the compiler to place small fields next to each other, `PackedSlot` explicitly tracks a
+/// `(bit_offset, width)` per sub-field and masks/shifts within one 256-bit word, so the packing holds
+/// regardless of declaration order.
+///
+/// This is a runtime-checked building block rather than a `#[packed]` attribute or derive macro: that
+/// would need a dedicated proc-macro crate, which this repo doesn't have set up (every contract here
+/// is hand-written, not macro-generated). `get`/`set` panic rather than fail to compile when a
+/// `(bit_offset, width)` pair doesn't fit in the word - callers that want that checked ahead of time
+/// should gate their offset/width constants behind a `const _: () = assert!(...)`, as `PackedFields`
+/// below does.
+#[storage]
+#[allow(dead_code)]
+pub struct PackedSlot {
+    word: StorageU256,
+}
+
+impl PackedSlot {
+    fn mask(width: usize) -> U256 {
+        if width >= 256 {
+            U256::MAX
+        } else {
+            (U256::ONE << width) - U256::ONE
+        }
+    }
+
+    /// Reads the `width`-bit field starting at `bit_offset` out of the packed word.
+    pub fn get(&self, bit_offset: usize, width: usize) -> U256 {
+        assert!(
+            width > 0 && bit_offset + width <= 256,
+            "packed field out of range"
+        );
+
+        (self.word.get() >> bit_offset) & Self::mask(width)
+    }
+
+    /// Writes `value` into the `width`-bit field starting at `bit_offset`, leaving every other field
+    /// packed into the same word untouched.
+    pub fn set(&mut self, bit_offset: usize, width: usize, value: U256) {
+        assert!(
+            width > 0 && bit_offset + width <= 256,
+            "packed field out of range"
+        );
+        let mask = Self::mask(width);
+        debug_assert!(value <= mask, "value does not fit in the packed field's width");
+
+        let cleared = self.word.get() & !(mask << bit_offset);
+        self.word.set(cleared | ((value & mask) << bit_offset));
+    }
+}
+
+const PACKED_FLAG_A_OFFSET: usize = 0;
+const PACKED_FLAG_B_OFFSET: usize = 1;
+const PACKED_COUNTER_OFFSET: usize = 2;
+const PACKED_COUNTER_WIDTH: usize = 64;
+
+const _: () = assert!(
+    PACKED_COUNTER_OFFSET + PACKED_COUNTER_WIDTH <= 256,
+    "packed group overflows its slot"
+);
+
+/// Two `bool`s and a `u64` sharing one slot via [`PackedSlot`] - the same data `EfficientStorage`
+/// would need two slots for (its two `StorageBool`s share one word, but its `StorageU256` claims a
+/// whole word of its own), packed down to one because every sub-field's `(bit_offset, width)` is explicit here.
+#[storage]
+#[allow(dead_code)]
+pub struct PackedFields {
+    slot: PackedSlot,
+}
+
+#[allow(dead_code)]
+impl PackedFields {
+    pub fn flag_a(&self) -> bool {
+        !self.slot.get(PACKED_FLAG_A_OFFSET, 1).is_zero()
+    }
+
+    pub fn set_flag_a(&mut self, value: bool) {
+        self.slot
+            .set(PACKED_FLAG_A_OFFSET, 1, U256::from(value as u8));
+    }
+
+    pub fn flag_b(&self) -> bool {
+        !self.slot.get(PACKED_FLAG_B_OFFSET, 1).is_zero()
+    }
+
+    pub fn set_flag_b(&mut self, value: bool) {
+        self.slot
+            .set(PACKED_FLAG_B_OFFSET, 1, U256::from(value as u8));
+    }
+
+    pub fn counter(&self) -> u64 {
+        self.slot
+            .get(PACKED_COUNTER_OFFSET, PACKED_COUNTER_WIDTH)
+            .to::<u64>()
+    }
+
+    pub fn set_counter(&mut self, value: u64) {
+        self.slot
+            .set(PACKED_COUNTER_OFFSET, PACKED_COUNTER_WIDTH, U256::from(value));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use stylus_sdk::testing::*;
 
     #[test]
     fn test_slot_usage() {
         assert_eq!(EfficientStorage::required_slots(), 2);
         assert_eq!(InefficientStorage::required_slots(), 3);
     }
+
+    #[test]
+    fn test_packed_fields_use_one_slot() {
+        assert_eq!(PackedFields::required_slots(), 1);
+    }
+
+    #[test]
+    fn test_packed_fields_round_trip_isolation() {
+        let vm = TestVM::new();
+        let mut fields = PackedFields::from(&vm);
+
+        fields.set_flag_a(true);
+        fields.set_counter(12345);
+        fields.set_flag_b(true);
+
+        assert!(fields.flag_a());
+        assert!(fields.flag_b());
+        assert_eq!(fields.counter(), 12345);
+
+        // Writing one field doesn't corrupt its slot-mates
+        fields.set_flag_a(false);
+        assert!(!fields.flag_a());
+        assert!(fields.flag_b());
+        assert_eq!(fields.counter(), 12345);
+
+        fields.set_counter(0);
+        assert!(!fields.flag_a());
+        assert!(fields.flag_b());
+        assert_eq!(fields.counter(), 0);
+    }
 }
@@ -0,0 +1,145 @@
+//! A reusable Mollusk negative-test harness for the privilege/writability guarantee
+//! every program in `examples/concepts` relies on: an instruction must be rejected if
+//! it drops a required signer, or if it writes to an account the caller only granted
+//! read access to.
+//!
+//! Who catches a given violation - and therefore what error comes back - depends on
+//! the target: native programs that check `is_signer` themselves return a
+//! `ProgramError`, while a dropped write permission they never check is instead
+//! caught by the runtime's post-execution account-permission check and surfaces as a
+//! raw `InstructionError` (e.g. `ReadonlyDataModified`). Anchor's `#[account(mut)]`
+//! constraint, by contrast, checks `is_writable` itself, so the same violation comes
+//! back as one of its own `ProgramError::Custom` codes (e.g. `ConstraintMut`).
+//! [`ExpectedViolation`] lets each call site say which applies.
+//!
+//! Example programs add this crate as a dev-dependency and, per state-mutating
+//! instruction, add a couple of lines to their existing test:
+//!
+//! ```ignore
+//! mollusk_privilege_harness::assert_rejects_signer_deescalation(
+//!     &mollusk,
+//!     &set_value_instruction,
+//!     &accounts,
+//!     ExpectedViolation::Program(ProgramError::MissingRequiredSignature),
+//! );
+//! mollusk_privilege_harness::assert_rejects_writable_deescalation(
+//!     &mollusk,
+//!     &set_value_instruction,
+//!     &accounts,
+//!     ExpectedViolation::Instruction(InstructionError::ReadonlyDataModified),
+//! );
+//! mollusk_privilege_harness::assert_rejects_wrong_authority(
+//!     &mollusk,
+//!     &set_value_instruction,
+//!     &accounts,
+//!     1,
+//!     (non_authority_key, non_authority_account),
+//!     ExpectedViolation::Program(ProgramError::MissingRequiredSignature),
+//! );
+//! ```
+
+use mollusk_svm::{result::Check, Mollusk};
+use solana_account::Account;
+use solana_program::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_error::ProgramError,
+};
+use solana_pubkey::Pubkey;
+
+/// The error a rejected variant is expected to come back with: a `ProgramError` the
+/// target program (or an Anchor constraint) returned itself, or a raw
+/// `InstructionError` the runtime's own account-permission check raised.
+#[derive(Clone)]
+pub enum ExpectedViolation {
+    Program(ProgramError),
+    Instruction(InstructionError),
+}
+
+impl ExpectedViolation {
+    fn check(&self) -> Check<'_> {
+        match self.clone() {
+            ExpectedViolation::Program(error) => Check::err(error),
+            ExpectedViolation::Instruction(error) => Check::instruction_err(error),
+        }
+    }
+}
+
+/// Replays `instruction` once per account it marks as a required signer, with that
+/// one flipped to non-signer, asserting every such variant is rejected with
+/// `expected`. `accounts` is the same keyed-account list `instruction` would normally
+/// be run against.
+pub fn assert_rejects_signer_deescalation(
+    mollusk: &Mollusk,
+    instruction: &Instruction,
+    accounts: &[(Pubkey, Account)],
+    expected: ExpectedViolation,
+) {
+    for index in signer_indices(instruction) {
+        let variant = flip_meta(instruction, index, |meta| meta.is_signer = false);
+
+        mollusk.process_and_validate_instruction(&variant, accounts, &[expected.check()]);
+    }
+}
+
+/// Replays `instruction` once per account it marks writable, with that one flipped to
+/// read-only, asserting every such variant is rejected with `expected`.
+pub fn assert_rejects_writable_deescalation(
+    mollusk: &Mollusk,
+    instruction: &Instruction,
+    accounts: &[(Pubkey, Account)],
+    expected: ExpectedViolation,
+) {
+    for index in writable_indices(instruction) {
+        let variant = flip_meta(instruction, index, |meta| meta.is_writable = false);
+
+        mollusk.process_and_validate_instruction(&variant, accounts, &[expected.check()]);
+    }
+}
+
+/// Replays `instruction` with the account at `authority_index` swapped for
+/// `wrong_authority` (kept a signer, so only the substitution itself is under test),
+/// asserting the variant is rejected with `expected`.
+pub fn assert_rejects_wrong_authority(
+    mollusk: &Mollusk,
+    instruction: &Instruction,
+    accounts: &[(Pubkey, Account)],
+    authority_index: usize,
+    wrong_authority: (Pubkey, Account),
+    expected: ExpectedViolation,
+) {
+    let mut variant = instruction.clone();
+    variant.accounts[authority_index].pubkey = wrong_authority.0;
+
+    let mut variant_accounts = accounts.to_vec();
+    variant_accounts[authority_index] = wrong_authority;
+
+    mollusk.process_and_validate_instruction(&variant, &variant_accounts, &[expected.check()]);
+}
+
+fn signer_indices(instruction: &Instruction) -> impl Iterator<Item = usize> + '_ {
+    instruction
+        .accounts
+        .iter()
+        .enumerate()
+        .filter(|(_, meta)| meta.is_signer)
+        .map(|(index, _)| index)
+}
+
+fn writable_indices(instruction: &Instruction) -> impl Iterator<Item = usize> + '_ {
+    instruction
+        .accounts
+        .iter()
+        .enumerate()
+        .filter(|(_, meta)| meta.is_writable)
+        .map(|(index, _)| index)
+}
+
+fn flip_meta(
+    instruction: &Instruction,
+    index: usize,
+    mutate: impl FnOnce(&mut AccountMeta),
+) -> Instruction {
+    let mut variant = instruction.clone();
+    mutate(&mut variant.accounts[index]);
+    variant
+}
@@ -0,0 +1,135 @@
+//! A fluent builder for standing up a [`Mollusk`] instance, reducing the environment-account
+//! boilerplate every `#[cfg(test)] mod tests` in `examples/concepts` otherwise repeats by hand.
+//!
+//! Mollusk only populates the `Clock` and `Rent` sysvars by default - a program that reads
+//! `EpochSchedule`, `SlotHashes`, `StakeHistory` or `EpochRewards` (directly, or transitively
+//! through a CPI into one that does) needs those accounts added to the instruction's keyed-account
+//! list by hand, or the runtime looks up an account that was never created. [`HarnessBuilder`]
+//! preloads all five with their `Default` values up front, with a fluent setter for overriding any
+//! one of them (e.g. a vesting-schedule test that needs a specific `Clock.unix_timestamp`).
+//!
+//! A program that CPIs into several others under test - the way `examples/concepts`'s own NFT
+//! example invokes SPL Token-2022, the associated-token program and Metaplex's token-metadata
+//! program - needs every one of those programs' ELFs loaded into the same `Mollusk` instance, each
+//! with a matching loader-owned account in the instruction's keyed-account list. `with_program`
+//! takes that `(Pubkey, ELF)` pair once per CPI target and `build` wires both sides up, rather than
+//! every such test pairing its own `add_program_with_elf_and_loader` call with a
+//! `create_program_account_loader_v2` account entry by hand.
+//!
+//! ```ignore
+//! let (mollusk, mut accounts) = HarnessBuilder::new(&PROGRAM_ID, env!("CARGO_CRATE_NAME"))
+//!     .clock(Clock {
+//!         unix_timestamp: 1_700_000_000,
+//!         ..Clock::default()
+//!     })
+//!     .with_program(mpl_token_metadata::ID, MPL_TOKEN_METADATA_ELF)
+//!     .account(authority_key, authority_account)
+//!     .build();
+//!
+//! accounts.push(keyed_account_for_system_program());
+//! mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]);
+//! ```
+
+use mollusk_svm::{program::loader_keys::LOADER_V2, sysvar::Sysvars, Mollusk};
+use solana_account::Account;
+use solana_program::{
+    clock::Clock, epoch_rewards::EpochRewards, epoch_schedule::EpochSchedule,
+    slot_hashes::SlotHashes, stake_history::StakeHistory,
+};
+use solana_pubkey::Pubkey;
+
+/// Builds a [`Mollusk`] instance and its sysvar-preloaded environment accounts.
+///
+/// `program_name` is the compiled `.so` file's name (sans extension), matching `Mollusk::new`'s own
+/// convention - call sites typically pass `env!("CARGO_CRATE_NAME")`, same as the hand-written
+/// `Mollusk::new` calls this builder replaces.
+pub struct HarnessBuilder<'a> {
+    program_id: &'a Pubkey,
+    program_name: &'a str,
+    sysvars: Sysvars,
+    accounts: Vec<(Pubkey, Account)>,
+    programs: Vec<(Pubkey, &'a [u8])>,
+}
+
+impl<'a> HarnessBuilder<'a> {
+    pub fn new(program_id: &'a Pubkey, program_name: &'a str) -> Self {
+        Self {
+            program_id,
+            program_name,
+            sysvars: Sysvars::default(),
+            accounts: Vec::new(),
+            programs: Vec::new(),
+        }
+    }
+
+    /// Overrides the preloaded `Clock` sysvar (default: `Clock::default()`).
+    pub fn clock(mut self, clock: Clock) -> Self {
+        self.sysvars.clock = clock;
+        self
+    }
+
+    /// Overrides the preloaded `EpochSchedule` sysvar (default: `EpochSchedule::default()`).
+    pub fn epoch_schedule(mut self, epoch_schedule: EpochSchedule) -> Self {
+        self.sysvars.epoch_schedule = epoch_schedule;
+        self
+    }
+
+    /// Overrides the preloaded `SlotHashes` sysvar (default: empty).
+    pub fn slot_hashes(mut self, slot_hashes: SlotHashes) -> Self {
+        self.sysvars.slot_hashes = slot_hashes;
+        self
+    }
+
+    /// Overrides the preloaded `StakeHistory` sysvar (default: empty).
+    pub fn stake_history(mut self, stake_history: StakeHistory) -> Self {
+        self.sysvars.stake_history = stake_history;
+        self
+    }
+
+    /// Overrides the preloaded `EpochRewards` sysvar (default: `EpochRewards::default()`).
+    pub fn epoch_rewards(mut self, epoch_rewards: EpochRewards) -> Self {
+        self.sysvars.epoch_rewards = epoch_rewards;
+        self
+    }
+
+    /// Adds a non-sysvar account (a PDA, a token account, an authority keypair, ...) to the
+    /// environment-account list `build` returns.
+    pub fn account(mut self, pubkey: Pubkey, account: Account) -> Self {
+        self.accounts.push((pubkey, account));
+        self
+    }
+
+    /// Registers an additional program the instruction under test CPIs into, e.g. a real SPL Token,
+    /// associated-token, token-metadata or token-vault program ELF. `build` loads every registered
+    /// program into the returned `Mollusk` instance and adds a matching loader-owned account to the
+    /// environment-account list, so several real programs can be exercised in the same CPI chain.
+    pub fn with_program(mut self, program_id: Pubkey, elf: &'a [u8]) -> Self {
+        self.programs.push((program_id, elf));
+        self
+    }
+
+    /// Constructs the `Mollusk` instance and the full environment-account list: every account
+    /// added via `account`, a keyed account for each registered `with_program` ELF, followed by a
+    /// keyed account for `Rent` and for each of the five sysvars above.
+    pub fn build(self) -> (Mollusk, Vec<(Pubkey, Account)>) {
+        let mut mollusk = Mollusk::new(self.program_id, self.program_name);
+
+        let mut accounts = self.accounts;
+        for (program_id, elf) in &self.programs {
+            mollusk.add_program_with_elf_and_loader(program_id, elf, &LOADER_V2);
+            accounts.push((
+                *program_id,
+                mollusk_svm::program::create_program_account_loader_v2(elf),
+            ));
+        }
+
+        accounts.push(self.sysvars.keyed_account_for_clock_sysvar());
+        accounts.push(self.sysvars.keyed_account_for_rent_sysvar());
+        accounts.push(self.sysvars.keyed_account_for_epoch_schedule_sysvar());
+        accounts.push(self.sysvars.keyed_account_for_slot_hashes_sysvar());
+        accounts.push(self.sysvars.keyed_account_for_stake_history_sysvar());
+        accounts.push(self.sysvars.keyed_account_for_epoch_rewards_sysvar());
+
+        (mollusk, accounts)
+    }
+}
@@ -0,0 +1,211 @@
+//! A CosmWasm multi-test-style harness for exercising cross-contract Stylus calls
+//! (`static_call`/`call`/`transfer_eth`) without a WASM runtime.
+//!
+//! `sol_interface!`-generated interfaces can only be tested in a WASM runtime
+//! (<https://github.com/OffchainLabs/stylus-sdk-rs/issues/301>), so contracts that call
+//! out through them - such as `ExternalCaller::add` in the `cpi-to-external-call`
+//! example, or `NativeTokenHandling::withdraw_all`'s `transfer_eth` - need a substitute
+//! router that decodes calldata against a registered dispatcher and executes it against
+//! real storage.
+
+use std::collections::HashMap;
+
+use stylus_sdk::alloy_primitives::{Address, U256};
+
+/// Decodes and executes calldata against a deployed contract instance, the way a
+/// `static_call`/`call` would be routed by a real Nitro/Stylus node.
+pub trait ContractDispatcher {
+    /// Decodes the leading 4-byte selector against this contract's ABI and runs the
+    /// matching handler, returning encoded returndata (or revert data on `Err`).
+    fn dispatch(&mut self, calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>>;
+}
+
+#[derive(Default, Clone)]
+struct Bank {
+    balances: HashMap<Address, U256>,
+}
+
+impl Bank {
+    fn balance(&self, address: Address) -> U256 {
+        self.balances.get(&address).copied().unwrap_or_default()
+    }
+
+    fn set_balance(&mut self, address: Address, amount: U256) {
+        self.balances.insert(address, amount);
+    }
+
+    fn transfer(&mut self, from: Address, to: Address, amount: U256) -> Result<(), &'static str> {
+        let from_balance = self.balance(from);
+
+        if from_balance < amount {
+            return Err("insufficient balance");
+        }
+
+        self.set_balance(from, from_balance - amount);
+        self.set_balance(to, self.balance(to) + amount);
+
+        Ok(())
+    }
+}
+
+/// A snapshot of the harness's bank state, taken before a call that might revert so it
+/// can be rolled back without re-deploying every contract.
+#[derive(Clone)]
+pub struct Snapshot {
+    bank: Bank,
+}
+
+/// Maintains a registry of deployed contract instances plus a minimal bank module,
+/// routing `static_call`/`call`/`transfer_eth` between them.
+#[derive(Default)]
+pub struct App {
+    contracts: HashMap<Address, Box<dyn ContractDispatcher>>,
+    bank: Bank,
+}
+
+impl App {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a contract instance at `address`, making it reachable via
+    /// `static_call`/`call`.
+    pub fn deploy(&mut self, address: Address, contract: Box<dyn ContractDispatcher>) {
+        self.contracts.insert(address, contract);
+    }
+
+    pub fn set_balance(&mut self, address: Address, amount: U256) {
+        self.bank.set_balance(address, amount);
+    }
+
+    pub fn balance(&self, address: Address) -> U256 {
+        self.bank.balance(address)
+    }
+
+    /// Routes a read-only `static_call` to the dispatcher registered at `address`.
+    pub fn static_call(&mut self, address: Address, calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+        let contract = self
+            .contracts
+            .get_mut(&address)
+            .ok_or_else(|| b"no contract deployed at address".to_vec())?;
+
+        contract.dispatch(calldata)
+    }
+
+    /// Routes a state-changing `call` to the dispatcher registered at `address`.
+    pub fn call(&mut self, address: Address, calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+        self.static_call(address, calldata)
+    }
+
+    /// Moves ETH from `from` to `to`, mirroring `vm().transfer_eth`.
+    pub fn transfer_eth(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.bank
+            .transfer(from, to, amount)
+            .map_err(|msg| msg.as_bytes().to_vec())
+    }
+
+    /// Snapshots the bank state so a reverting call can be rolled back without
+    /// re-deploying contracts.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            bank: self.bank.clone(),
+        }
+    }
+
+    /// Restores the bank state captured by `snapshot`.
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        self.bank = snapshot.bank;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // add(uint64,uint64) selector used by the cpi-to-external-call example.
+    const ADD_SELECTOR: [u8; 4] = [0x6e, 0x2c, 0x73, 0x2d];
+
+    struct Adder;
+
+    impl ContractDispatcher for Adder {
+        fn dispatch(&mut self, calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+            let (selector, params) = calldata
+                .split_at_checked(4)
+                .ok_or_else(|| b"calldata too short".to_vec())?;
+
+            if selector != ADD_SELECTOR {
+                return Err(b"unknown selector".to_vec());
+            }
+
+            let a = u64::from_be_bytes(params[24..32].try_into().unwrap());
+            let b = u64::from_be_bytes(params[56..64].try_into().unwrap());
+            let sum = u128::from(a) + u128::from(b);
+
+            let mut returndata = vec![0u8; 32];
+            returndata[16..].copy_from_slice(&sum.to_be_bytes());
+            Ok(returndata)
+        }
+    }
+
+    fn add_calldata(a: u64, b: u64) -> Vec<u8> {
+        let mut calldata = ADD_SELECTOR.to_vec();
+        calldata.extend_from_slice(&[0u8; 24]);
+        calldata.extend_from_slice(&a.to_be_bytes());
+        calldata.extend_from_slice(&[0u8; 24]);
+        calldata.extend_from_slice(&b.to_be_bytes());
+        calldata
+    }
+
+    #[test]
+    fn routes_static_call_to_deployed_contract() {
+        let mut app = App::new();
+        let adder_address = Address::from([0x05; 20]);
+        app.deploy(adder_address, Box::new(Adder));
+
+        let returndata = app
+            .static_call(adder_address, &add_calldata(5, 10))
+            .unwrap();
+
+        assert_eq!(&returndata[16..], &15u128.to_be_bytes());
+    }
+
+    #[test]
+    fn static_call_to_unknown_address_errors() {
+        let mut app = App::new();
+        assert!(app
+            .static_call(Address::from([0x09; 20]), &add_calldata(1, 2))
+            .is_err());
+    }
+
+    #[test]
+    fn transfer_eth_moves_balance_and_rolls_back() {
+        let mut app = App::new();
+        let alice = Address::from([0x01; 20]);
+        let bob = Address::from([0x02; 20]);
+        app.set_balance(alice, U256::from(100));
+
+        let snapshot = app.snapshot();
+
+        app.transfer_eth(alice, bob, U256::from(40)).unwrap();
+        assert_eq!(app.balance(alice), U256::from(60));
+        assert_eq!(app.balance(bob), U256::from(40));
+
+        app.rollback(snapshot);
+        assert_eq!(app.balance(alice), U256::from(100));
+        assert_eq!(app.balance(bob), U256::ZERO);
+    }
+
+    #[test]
+    fn transfer_eth_insufficient_balance_errors() {
+        let mut app = App::new();
+        let alice = Address::from([0x01; 20]);
+        let bob = Address::from([0x02; 20]);
+
+        assert!(app.transfer_eth(alice, bob, U256::from(1)).is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! A static analysis pass over a native program's `process_instruction`
+//! (shown in `access-control`'s `accounts` slice pattern match and
+//! `is_signer`/`is_writable` checks) that reproduces the account-safety
+//! invariants the Solana runtime itself enforces, so a porting tool can warn
+//! before silently generating unsound Stylus code.
+//!
+//! Unlike the other modules in this crate, this pass doesn't lower anything
+//! to Stylus - Stylus has no analogue for any of these rules, since EVM
+//! storage has no signer/writable flags and no CPI privilege model to begin
+//! with. It only flags source locations where the *Solana* side relied on an
+//! invariant that won't automatically carry over, so those call sites get a
+//! human look before being ported.
+
+/// Per-`AccountInfo` privileges as read off the incoming `accounts` slice
+/// pattern match (`payer.is_signer`, `AccountMeta::new` vs `new_readonly`,
+/// ...) - this pass trusts that reading rather than inferring privileges of
+/// its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountPrivileges {
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountPrivileges {
+    pub fn new(is_signer: bool, is_writable: bool) -> Self {
+        Self { is_signer, is_writable }
+    }
+}
+
+/// One operation this pass checks an account's privileges against.
+#[derive(Debug, Clone, Copy)]
+pub enum AccountOperation {
+    /// `set_data`/`*lamports.borrow_mut()`/`assign`: the account must be
+    /// both writable and owned by the executing program.
+    Write { owned_by_program: bool },
+    /// An `AccountMeta` handed to an `invoke`/`invoke_signed` callee, with
+    /// the privileges granted to it in the child instruction.
+    CpiGrant { granted: AccountPrivileges },
+    /// A `Ref`/`RefMut` over an account's `data`/`lamports` still held when
+    /// an `invoke`/`invoke_signed` call is made.
+    BorrowHeldAcrossCpi,
+}
+
+/// One `AccountOperation` this pass observed a program perform against an
+/// account, keyed to the source span the account's incoming privileges and
+/// the operation were read from.
+#[derive(Debug, Clone)]
+pub struct AccountAccess {
+    pub account: String,
+    pub span: String,
+    pub privileges: AccountPrivileges,
+    pub operation: AccountOperation,
+}
+
+impl AccountAccess {
+    pub fn new(
+        account: impl Into<String>,
+        span: impl Into<String>,
+        privileges: AccountPrivileges,
+        operation: AccountOperation,
+    ) -> Self {
+        Self {
+            account: account.into(),
+            span: span.into(),
+            privileges,
+            operation,
+        }
+    }
+
+    /// Re-checks this access against the same invariants the Solana runtime
+    /// enforces at the end of every instruction, returning the diagnostic it
+    /// violates, if any.
+    pub fn check(&self) -> Option<SafetyDiagnostic> {
+        match self.operation {
+            AccountOperation::Write { owned_by_program } => {
+                (!self.privileges.is_writable || !owned_by_program).then(|| {
+                    self.diagnostic("writes to an account that is not writable and owned by this program")
+                })
+            }
+            AccountOperation::CpiGrant { granted } => {
+                let escalates_signer = granted.is_signer && !self.privileges.is_signer;
+                let escalates_writable = granted.is_writable && !self.privileges.is_writable;
+
+                (escalates_signer || escalates_writable)
+                    .then(|| self.diagnostic("escalates privileges across a CPI boundary instead of deescalating them"))
+            }
+            AccountOperation::BorrowHeldAcrossCpi => {
+                Some(self.diagnostic("holds a borrow across an invoke/invoke_signed call - risks AccountBorrowFailed"))
+            }
+        }
+    }
+
+    fn diagnostic(&self, message: &str) -> SafetyDiagnostic {
+        SafetyDiagnostic {
+            account: self.account.clone(),
+            span: self.span.clone(),
+            message: message.to_owned(),
+        }
+    }
+}
+
+/// One violation of the Solana runtime's account-safety invariants, keyed to
+/// the account and source span it was found at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafetyDiagnostic {
+    pub account: String,
+    pub span: String,
+    pub message: String,
+}
+
+/// Runs `check` over every access, in order, keeping only the ones that flag
+/// a violation.
+pub fn find_violations(accesses: &[AccountAccess]) -> Vec<SafetyDiagnostic> {
+    accesses.iter().filter_map(AccountAccess::check).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_to_readonly_account_is_flagged() {
+        let access = AccountAccess::new(
+            "config_account",
+            "src/lib.rs:42",
+            AccountPrivileges::new(false, false),
+            AccountOperation::Write { owned_by_program: true },
+        );
+
+        assert_eq!(
+            access.check(),
+            Some(SafetyDiagnostic {
+                account: "config_account".to_owned(),
+                span: "src/lib.rs:42".to_owned(),
+                message: "writes to an account that is not writable and owned by this program".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn write_to_writable_owned_account_is_not_flagged() {
+        let access = AccountAccess::new(
+            "config_account",
+            "src/lib.rs:42",
+            AccountPrivileges::new(false, true),
+            AccountOperation::Write { owned_by_program: true },
+        );
+
+        assert_eq!(access.check(), None);
+    }
+
+    #[test]
+    fn write_to_unowned_writable_account_is_flagged() {
+        let access = AccountAccess::new(
+            "other_programs_account",
+            "src/lib.rs:50",
+            AccountPrivileges::new(false, true),
+            AccountOperation::Write { owned_by_program: false },
+        );
+
+        assert!(access.check().is_some());
+    }
+
+    #[test]
+    fn cpi_grant_deescalating_privileges_is_not_flagged() {
+        let access = AccountAccess::new(
+            "counter_state",
+            "src/lib.rs:60",
+            AccountPrivileges::new(true, true),
+            AccountOperation::CpiGrant {
+                granted: AccountPrivileges::new(false, true),
+            },
+        );
+
+        assert_eq!(access.check(), None);
+    }
+
+    #[test]
+    fn cpi_grant_escalating_signer_privilege_is_flagged() {
+        let access = AccountAccess::new(
+            "counter_state",
+            "src/lib.rs:60",
+            AccountPrivileges::new(false, true),
+            AccountOperation::CpiGrant {
+                granted: AccountPrivileges::new(true, true),
+            },
+        );
+
+        assert_eq!(
+            access.check().unwrap().message,
+            "escalates privileges across a CPI boundary instead of deescalating them"
+        );
+    }
+
+    #[test]
+    fn cpi_grant_escalating_writable_privilege_is_flagged() {
+        let access = AccountAccess::new(
+            "counter_state",
+            "src/lib.rs:60",
+            AccountPrivileges::new(true, false),
+            AccountOperation::CpiGrant {
+                granted: AccountPrivileges::new(true, true),
+            },
+        );
+
+        assert!(access.check().is_some());
+    }
+
+    #[test]
+    fn borrow_held_across_cpi_is_always_flagged() {
+        let access = AccountAccess::new(
+            "deposit_account",
+            "src/lib.rs:70",
+            AccountPrivileges::new(false, true),
+            AccountOperation::BorrowHeldAcrossCpi,
+        );
+
+        assert!(access.check().is_some());
+    }
+
+    #[test]
+    fn find_violations_keeps_only_flagged_accesses() {
+        let accesses = vec![
+            AccountAccess::new(
+                "config_account",
+                "src/lib.rs:42",
+                AccountPrivileges::new(false, true),
+                AccountOperation::Write { owned_by_program: true },
+            ),
+            AccountAccess::new(
+                "config_account",
+                "src/lib.rs:50",
+                AccountPrivileges::new(false, false),
+                AccountOperation::Write { owned_by_program: true },
+            ),
+        ];
+
+        let violations = find_violations(&accesses);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].span, "src/lib.rs:50");
+    }
+}
@@ -0,0 +1,129 @@
+//! Lowers Anchor's declarative `#[derive(Accounts)]` account constraints
+//! (shown in the `counter` module: `SetValue`'s `has_one = authority` and
+//! `seeds = [STATE_PDA_SEED], bump`, `Increment`'s re-derived `seeds`/`bump`,
+//! and `Initialize`'s `init, payer, space`) into the imperative guard code a
+//! Stylus method has to write explicitly instead, since Stylus has no
+//! account-validation layer to declare them against.
+//!
+//! `init`/`space`/`payer` collapse into a single "initialize once" storage
+//! guard: EVM storage is implicitly allocated (no `space` to size, no
+//! `payer` to charge rent) and the caller already pays for it via gas, so
+//! those two attributes are simply dropped.
+
+use crate::pda_storage::PdaSeeds;
+
+/// One constraint from an Anchor `#[account(...)]` attribute, translated
+/// independently of the others on the same field - Anchor runs them all at
+/// once, but Stylus guard code runs as a sequence of early-return checks.
+pub enum AccountConstraint<'a> {
+    /// `has_one = <field>`: the stored account's `<field>` must match the
+    /// caller-supplied authority. There's no separate authority account to
+    /// pass in Stylus, so this compares against `msg.sender` directly.
+    HasOne {
+        stored_field: String,
+        error_variant: String,
+    },
+    /// `seeds = [...], bump`: recompute the PDA key from its seeds and
+    /// reject a mismatch, rather than trusting the caller-supplied address.
+    SeedsBump {
+        seeds: &'a PdaSeeds,
+        stored_key_field: String,
+        error_variant: String,
+    },
+    /// `init, payer = ..., space = ...`: collapses to an initialize-once
+    /// guard over a boolean flag; `payer`/`space` are dropped.
+    Init { initialized_flag_field: String },
+    /// An arbitrary `constraint = <expr> @ <Error>`.
+    Constraint { expr: String, error_variant: String },
+}
+
+impl<'a> AccountConstraint<'a> {
+    /// Renders the early-return guard this constraint lowers to.
+    pub fn to_guard(&self) -> String {
+        match self {
+            Self::HasOne {
+                stored_field,
+                error_variant,
+            } => format!(
+                "if self.{stored_field}.get() != self.vm().msg_sender() {{\n    return Err({error_variant} {{}}.into());\n}}"
+            ),
+            Self::SeedsBump {
+                seeds,
+                stored_key_field,
+                error_variant,
+            } => format!(
+                "if {key_expr} != self.{stored_key_field}.get() {{\n    return Err({error_variant} {{}}.into());\n}}",
+                key_expr = seeds.to_key_expr(),
+            ),
+            Self::Init {
+                initialized_flag_field,
+            } => format!(
+                "if self.{initialized_flag_field}.get() {{\n    return Err(AlreadyInitialized {{}}.into());\n}}\nself.{initialized_flag_field}.set(true);"
+            ),
+            Self::Constraint {
+                expr,
+                error_variant,
+            } => format!("if !({expr}) {{\n    return Err({error_variant} {{}}.into());\n}}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pda_storage::SeedPart;
+
+    #[test]
+    fn has_one_checks_against_msg_sender() {
+        let constraint = AccountConstraint::HasOne {
+            stored_field: "authority".to_owned(),
+            error_variant: "Unauthorized".to_owned(),
+        };
+
+        assert_eq!(
+            constraint.to_guard(),
+            "if self.authority.get() != self.vm().msg_sender() {\n    return Err(Unauthorized {}.into());\n}"
+        );
+    }
+
+    #[test]
+    fn seeds_bump_recomputes_key_and_compares() {
+        let seeds = PdaSeeds::new(vec![SeedPart::Literal(b"state".to_vec())]);
+        let constraint = AccountConstraint::SeedsBump {
+            seeds: &seeds,
+            stored_key_field: "counter_key".to_owned(),
+            error_variant: "SeedMismatch".to_owned(),
+        };
+
+        assert_eq!(
+            constraint.to_guard(),
+            "if keccak256(abi::encode(&(b\"state\"))) != self.counter_key.get() {\n    return Err(SeedMismatch {}.into());\n}"
+        );
+    }
+
+    #[test]
+    fn init_collapses_to_initialize_once_guard_dropping_payer_and_space() {
+        let constraint = AccountConstraint::Init {
+            initialized_flag_field: "initialized".to_owned(),
+        };
+
+        let guard = constraint.to_guard();
+        assert!(guard.contains("self.initialized.get()"));
+        assert!(guard.contains("self.initialized.set(true);"));
+        assert!(!guard.contains("payer"));
+        assert!(!guard.contains("space"));
+    }
+
+    #[test]
+    fn arbitrary_constraint_negates_expr() {
+        let constraint = AccountConstraint::Constraint {
+            expr: "false".to_owned(),
+            error_variant: "Unauthorized".to_owned(),
+        };
+
+        assert_eq!(
+            constraint.to_guard(),
+            "if !(false) {\n    return Err(Unauthorized {}.into());\n}"
+        );
+    }
+}
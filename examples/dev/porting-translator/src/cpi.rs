@@ -0,0 +1,385 @@
+//! Lowers a native program's `invoke`/`invoke_signed` call into another
+//! program (shown in `cpi-to-counter`'s `build_counter_cpi`, which forwards
+//! its own `AccountInfo`s as `AccountMeta`s unchanged) onto a Stylus
+//! cross-contract call.
+//!
+//! The calling conventions don't line up 1:1: Solana addresses the callee by
+//! program id and grants it a keyed account list (plus, for CPI into the
+//! System Program, sysvar/system accounts with no EVM counterpart), while
+//! EVM addresses the callee by contract address and grants it nothing but
+//! the calldata - `msg.sender`/`call`/`static_call` carry the caller's
+//! identity and intent instead. So this pass drops system/sysvar accounts
+//! entirely and turns the remaining account-metas list into nothing (Stylus
+//! has no account-permission calling convention to forward), keeping only
+//! the callee address and ABI-encoded instruction arguments.
+//!
+//! `invoke`/`invoke_signed` sites that build a `solana_system_interface`
+//! instruction (shown in `native-token-handling`'s `WithdrawAllLamports`,
+//! which withdraws a deposit PDA's balance with `system_instruction::transfer`
+//! signed by `signer_seeds`) are recognized separately as
+//! [`SystemInstructionCall`]s rather than [`CpiCall`]s, since the System
+//! Program has no instruction handler of its own to address by contract call
+//! - a transfer is a value movement, not a method call. `CpiSite` ties both
+//! shapes together with a third, `Unmapped`, for callees this pass has no
+//! translation for at all, so a caller always gets either working Stylus
+//! code or a [`CpiDiagnostic`] explaining why not - never silently wrong
+//! output.
+
+use crate::pda_storage::PdaSeeds;
+use crate::system_instructions::{self, StylusLowering, SystemInstruction};
+
+/// One account in an `invoke`/`invoke_signed` call's account-metas list.
+#[derive(Debug, Clone)]
+pub struct CpiAccount {
+    pub name: String,
+    /// System Program, sysvars, and other accounts with no EVM counterpart -
+    /// dropped rather than translated.
+    pub has_no_evm_equivalent: bool,
+}
+
+impl CpiAccount {
+    pub fn new(name: impl Into<String>, has_no_evm_equivalent: bool) -> Self {
+        Self {
+            name: name.into(),
+            has_no_evm_equivalent,
+        }
+    }
+}
+
+/// One Solidity-typed argument of the callee method being invoked.
+#[derive(Debug, Clone)]
+pub struct CpiArg {
+    pub name: String,
+    pub sol_type: &'static str,
+}
+
+impl CpiArg {
+    pub fn new(name: impl Into<String>, sol_type: &'static str) -> Self {
+        Self {
+            name: name.into(),
+            sol_type,
+        }
+    }
+}
+
+/// An `invoke`/`invoke_signed` call site into another program's instruction.
+#[derive(Debug, Clone)]
+pub struct CpiCall {
+    pub callee_address_field: String,
+    pub method_name: String,
+    pub accounts: Vec<CpiAccount>,
+    pub args: Vec<CpiArg>,
+    /// `invoke` calls that only read callee state lower to `static_call`;
+    /// anything else (including every `invoke_signed`, since EVM has no PDA
+    /// signer to re-derive) lowers to `call`.
+    pub mutates_state: bool,
+}
+
+impl CpiCall {
+    pub fn new(
+        callee_address_field: impl Into<String>,
+        method_name: impl Into<String>,
+        accounts: Vec<CpiAccount>,
+        args: Vec<CpiArg>,
+        mutates_state: bool,
+    ) -> Self {
+        Self {
+            callee_address_field: callee_address_field.into(),
+            method_name: method_name.into(),
+            accounts,
+            args,
+            mutates_state,
+        }
+    }
+
+    fn signature(&self) -> String {
+        let types = self
+            .args
+            .iter()
+            .map(|arg| arg.sol_type)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!("{}({})", self.method_name, types)
+    }
+
+    /// Account-metas this call forwarded that have no Stylus equivalent and
+    /// are therefore dropped, rather than translated, by `to_stylus_call`.
+    pub fn dropped_accounts(&self) -> impl Iterator<Item = &CpiAccount> {
+        self.accounts.iter().filter(|account| account.has_no_evm_equivalent)
+    }
+
+    /// Renders the `call`/`static_call` this CPI site lowers to, ABI-encoding
+    /// the callee's instruction arguments (account-metas have no EVM
+    /// equivalent, so they're dropped - see `dropped_accounts`).
+    pub fn to_stylus_call(&self) -> String {
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.name.clone())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let method = if self.mutates_state {
+            "call"
+        } else {
+            "static_call"
+        };
+
+        format!(
+            "let calldata = [&selector(\"{signature}\")[..], &abi::encode_params(&({args}))[..]].concat();\nself.vm().{method}(&Call::new(), self.{callee}.get(), &calldata)?;",
+            signature = self.signature(),
+            args = args,
+            method = method,
+            callee = self.callee_address_field,
+        )
+    }
+}
+
+/// Explains why a recognized `invoke`/`invoke_signed` call site couldn't be
+/// lowered to Stylus code, keyed to the callee, so a caller can surface it to
+/// the user instead of silently emitting nothing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpiDiagnostic {
+    pub callee: String,
+    pub reason: String,
+}
+
+impl CpiDiagnostic {
+    fn unknown_target(callee: impl Into<String>) -> Self {
+        let callee = callee.into();
+        Self {
+            reason: format!("no known Stylus mapping for CPI target `{callee}`"),
+            callee,
+        }
+    }
+
+    fn unsupported_instruction(instruction: SystemInstruction, reason: &str, suggestion: &str) -> Self {
+        Self {
+            callee: "system_program".to_owned(),
+            reason: format!("{instruction:?} has no Stylus equivalent: {reason} ({suggestion})"),
+        }
+    }
+
+    fn not_a_call_site(instruction: SystemInstruction, primitive: &str) -> Self {
+        Self {
+            callee: "system_program".to_owned(),
+            reason: format!(
+                "{instruction:?} lowers to {primitive}, generated by the pda_storage pass, not call-site code"
+            ),
+        }
+    }
+}
+
+/// An `invoke`/`invoke_signed` call site recognized as building a
+/// `solana_system_interface::instruction`, rather than a call into a
+/// program-defined instruction (see `CpiCall`). Looks up its Stylus lowering
+/// in `system_instructions` instead of special-casing `transfer` itself.
+#[derive(Debug, Clone)]
+pub struct SystemInstructionCall {
+    pub instruction: SystemInstruction,
+    pub to_field: String,
+    pub lamports_field: String,
+    /// The PDA seeds `invoke_signed` passed to sign as the source account.
+    /// Stylus has no PDA signer to re-derive at call time, so the seeds
+    /// themselves are dropped from the lowered call - only the seed→address
+    /// derivation is kept, for `pda_storage` to resolve the account against.
+    pub signer_seeds: Option<PdaSeeds>,
+}
+
+impl SystemInstructionCall {
+    pub fn new(
+        instruction: SystemInstruction,
+        to_field: impl Into<String>,
+        lamports_field: impl Into<String>,
+        signer_seeds: Option<PdaSeeds>,
+    ) -> Self {
+        Self {
+            instruction,
+            to_field: to_field.into(),
+            lamports_field: lamports_field.into(),
+            signer_seeds,
+        }
+    }
+
+    /// The seed→address derivation `invoke_signed`'s dropped `signer_seeds`
+    /// recorded, or `None` for a plain `invoke` with no PDA signer.
+    pub fn signer_derivation(&self) -> Option<String> {
+        self.signer_seeds.as_ref().map(PdaSeeds::to_key_expr)
+    }
+
+    /// Renders the Stylus call this site lowers to, or a diagnostic when
+    /// `system_instructions` has no call-site lowering for it - either
+    /// because the instruction is unsupported outright, or because it lowers
+    /// to a storage declaration rather than a call (see
+    /// `StylusLowering::renders_as_call`).
+    pub fn to_stylus_call(&self) -> Result<String, CpiDiagnostic> {
+        match system_instructions::lowering(self.instruction) {
+            StylusLowering::Supported {
+                renders_as_call: true,
+                ..
+            } => Ok(format!(
+                "self.vm().transfer_eth(self.{}.get(), self.{}.get())?;",
+                self.to_field, self.lamports_field
+            )),
+            StylusLowering::Supported {
+                primitive,
+                renders_as_call: false,
+                ..
+            } => Err(CpiDiagnostic::not_a_call_site(self.instruction, primitive)),
+            StylusLowering::Unsupported { reason, suggestion } => {
+                Err(CpiDiagnostic::unsupported_instruction(self.instruction, reason, suggestion))
+            }
+        }
+    }
+}
+
+/// An `invoke`/`invoke_signed` call site, resolved to whichever of the three
+/// shapes this pass recognizes: a `system_instruction`, a call into a
+/// program this pass has a known mapping for, or a callee it doesn't
+/// recognize at all.
+#[derive(Debug, Clone)]
+pub enum CpiSite {
+    SystemInstruction(SystemInstructionCall),
+    Known(CpiCall),
+    Unmapped(String),
+}
+
+impl CpiSite {
+    /// Renders the Stylus call this site lowers to, or a diagnostic
+    /// explaining why it couldn't be - an unmapped callee or a
+    /// `system_instruction` with no Stylus equivalent - rather than
+    /// producing uncompilable output.
+    pub fn to_stylus_call(&self) -> Result<String, CpiDiagnostic> {
+        match self {
+            CpiSite::SystemInstruction(call) => call.to_stylus_call(),
+            CpiSite::Known(call) => Ok(call.to_stylus_call()),
+            CpiSite::Unmapped(callee) => Err(CpiDiagnostic::unknown_target(callee)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drive_increment() -> CpiCall {
+        CpiCall::new(
+            "counter_address",
+            "increment",
+            vec![CpiAccount::new("counter_state", false)],
+            vec![],
+            true,
+        )
+    }
+
+    #[test]
+    fn renders_call_for_state_mutating_cpi() {
+        assert_eq!(
+            drive_increment().to_stylus_call(),
+            "let calldata = [&selector(\"increment()\")[..], &abi::encode_params(&())[..]].concat();\nself.vm().call(&Call::new(), self.counter_address.get(), &calldata)?;"
+        );
+    }
+
+    #[test]
+    fn renders_static_call_for_read_only_cpi() {
+        let view_call = CpiCall::new("adder_address", "add", vec![], vec![CpiArg::new("a", "uint64")], false);
+
+        assert!(view_call.to_stylus_call().contains("self.vm().static_call("));
+    }
+
+    #[test]
+    fn drops_system_program_accounts() {
+        let call = CpiCall::new(
+            "counter_address",
+            "initialize",
+            vec![
+                CpiAccount::new("counter_state", false),
+                CpiAccount::new("authority", false),
+                CpiAccount::new("system_program", true),
+            ],
+            vec![CpiArg::new("value", "uint64")],
+            true,
+        );
+
+        let dropped: Vec<_> = call.dropped_accounts().map(|a| a.name.as_str()).collect();
+        assert_eq!(dropped, vec!["system_program"]);
+        assert!(!call.to_stylus_call().contains("system_program"));
+    }
+
+    /// Mirrors `native-token-handling`'s `WithdrawAllLamports`: a
+    /// `system_instruction::transfer` out of a deposit PDA, signed with
+    /// `invoke_signed`'s `signer_seeds`.
+    fn withdraw_all_lamports() -> SystemInstructionCall {
+        SystemInstructionCall::new(
+            SystemInstruction::Transfer,
+            "payer",
+            "deposit_account_lamports",
+            Some(PdaSeeds::new(vec![
+                crate::pda_storage::SeedPart::Literal(b"deposit".to_vec()),
+                crate::pda_storage::SeedPart::Literal(b"-".to_vec()),
+                crate::pda_storage::SeedPart::Dynamic("payer".to_owned()),
+            ])),
+        )
+    }
+
+    #[test]
+    fn renders_transfer_as_transfer_eth() {
+        assert_eq!(
+            withdraw_all_lamports().to_stylus_call(),
+            Ok("self.vm().transfer_eth(self.payer.get(), self.deposit_account_lamports.get())?;".to_owned())
+        );
+    }
+
+    #[test]
+    fn records_signer_seeds_derivation_instead_of_forwarding_them() {
+        assert_eq!(
+            withdraw_all_lamports().signer_derivation(),
+            Some("keccak256(abi::encode(&(b\"deposit\", b\"-\", payer)))".to_owned())
+        );
+    }
+
+    #[test]
+    fn plain_invoke_has_no_signer_derivation() {
+        let call = SystemInstructionCall::new(SystemInstruction::Transfer, "payer", "amount", None);
+        assert_eq!(call.signer_derivation(), None);
+    }
+
+    #[test]
+    fn diagnoses_system_instructions_with_no_stylus_equivalent() {
+        let call = SystemInstructionCall::new(SystemInstruction::Assign, "new_account", "lamports", None);
+
+        let err = call.to_stylus_call().unwrap_err();
+        assert_eq!(err.callee, "system_program");
+        assert!(err.reason.contains("Assign has no Stylus equivalent"));
+    }
+
+    #[test]
+    fn diagnoses_account_lifecycle_instructions_as_not_a_call_site() {
+        let call = SystemInstructionCall::new(SystemInstruction::CreateAccount, "new_account", "lamports", None);
+
+        let err = call.to_stylus_call().unwrap_err();
+        assert_eq!(err.callee, "system_program");
+        assert!(err.reason.contains("CreateAccount lowers to"));
+        assert!(err.reason.contains("not call-site code"));
+    }
+
+    #[test]
+    fn cpi_site_diagnoses_unmapped_callees() {
+        let site = CpiSite::Unmapped("wormhole_core_bridge".to_owned());
+
+        assert_eq!(
+            site.to_stylus_call(),
+            Err(CpiDiagnostic {
+                callee: "wormhole_core_bridge".to_owned(),
+                reason: "no known Stylus mapping for CPI target `wormhole_core_bridge`".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn cpi_site_renders_known_calls() {
+        let site = CpiSite::Known(drive_increment());
+        assert!(site.to_stylus_call().unwrap().contains("self.vm().call("));
+    }
+}
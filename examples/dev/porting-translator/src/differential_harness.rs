@@ -0,0 +1,219 @@
+//! Synthesizes a differential test harness that replays the same instruction
+//! chain against both the original Solana program (via Mollusk) and its
+//! generated Stylus port, then asserts the two runtimes reached equivalent
+//! post-states - the same instruction-by-instruction invariants a fixture
+//! like `native-token-handling`'s `withdraw_all_lamports_happy_path` already
+//! checks with `Check::account(&key).lamports(..)`, translated into a
+//! dual-runtime assertion instead of a single-runtime one.
+
+/// Solana's native unit (lamports, 1e9 per SOL) vs. Stylus/EVM's (wei, 1e18
+/// per ETH) - the scaling factor a lamport balance is multiplied by before
+/// comparing it against the Stylus side's wei balance.
+const LAMPORTS_TO_WEI_SCALE: u128 = 1_000_000_000;
+
+/// One post-instruction assertion to replay against both runtimes, mirroring
+/// a single `mollusk_svm::result::Check` but carrying enough information to
+/// also check the Stylus side.
+#[derive(Debug, Clone)]
+pub enum DifferentialCheck {
+    /// `Check::account(&key).lamports(..)`: a lamport balance, compared
+    /// against the equivalent Stylus-side address's wei balance once scaled
+    /// by `LAMPORTS_TO_WEI_SCALE`.
+    Lamports {
+        solana_account_field: String,
+        stylus_address_field: String,
+    },
+    /// `Check::account(&key).data(..)`: owned-account data bytes, compared
+    /// against the contract's equivalent storage via a getter call.
+    AccountData {
+        solana_account_field: String,
+        stylus_getter_expr: String,
+    },
+    /// `Check::success()`/`Check::err(..)`: the instruction's own
+    /// success/error outcome.
+    Outcome { expect_success: bool },
+}
+
+impl DifferentialCheck {
+    /// Renders the Rust assertion this check lowers to, assuming
+    /// `solana_result` and `stylus_result` are bound in the enclosing scope.
+    pub fn to_assertion(&self) -> String {
+        match self {
+            Self::Lamports {
+                solana_account_field,
+                stylus_address_field,
+            } => format!(
+                "assert_eq!(\n    u128::from(solana_result.get_account({solana_account_field}).unwrap().lamports) * {LAMPORTS_TO_WEI_SCALE},\n    stylus_vm.balance({stylus_address_field}).to::<u128>()\n);"
+            ),
+            Self::AccountData {
+                solana_account_field,
+                stylus_getter_expr,
+            } => format!(
+                "assert_eq!(\n    solana_result.get_account({solana_account_field}).unwrap().data,\n    {stylus_getter_expr}\n);"
+            ),
+            Self::Outcome { expect_success: true } => {
+                "assert!(solana_result.program_result.is_ok());\nassert!(stylus_result.is_ok());".to_owned()
+            }
+            Self::Outcome { expect_success: false } => {
+                "assert!(solana_result.program_result.is_err());\nassert!(stylus_result.is_err());".to_owned()
+            }
+        }
+    }
+}
+
+/// One instruction in the chain, paired with the checks to replay against
+/// both runtimes once it's run against each.
+#[derive(Debug, Clone)]
+pub struct DifferentialStep {
+    pub solana_instruction_expr: String,
+    pub stylus_call_expr: String,
+    pub checks: Vec<DifferentialCheck>,
+}
+
+impl DifferentialStep {
+    pub fn new(
+        solana_instruction_expr: impl Into<String>,
+        stylus_call_expr: impl Into<String>,
+        checks: Vec<DifferentialCheck>,
+    ) -> Self {
+        Self {
+            solana_instruction_expr: solana_instruction_expr.into(),
+            stylus_call_expr: stylus_call_expr.into(),
+            checks,
+        }
+    }
+
+    /// Renders the two dispatch lines plus every check for this step,
+    /// indented one level under the enclosing `#[test] fn`.
+    fn to_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("    let solana_result = mollusk.process_instruction(&{});", self.solana_instruction_expr),
+            format!("    let stylus_result = {};", self.stylus_call_expr),
+        ];
+
+        for check in &self.checks {
+            for line in check.to_assertion().lines() {
+                lines.push(format!("    {line}"));
+            }
+        }
+
+        lines
+    }
+}
+
+/// A full differential test harness: a chain of `DifferentialStep`s, each
+/// replayed against both runtimes in lockstep, rendered as a ready-to-run
+/// `#[test]` function a user can drop straight into their port's test suite.
+#[derive(Debug, Clone)]
+pub struct DifferentialHarness {
+    pub test_name: String,
+    pub steps: Vec<DifferentialStep>,
+}
+
+impl DifferentialHarness {
+    pub fn new(test_name: impl Into<String>, steps: Vec<DifferentialStep>) -> Self {
+        Self {
+            test_name: test_name.into(),
+            steps,
+        }
+    }
+
+    /// Renders the full `#[test] fn { .. }` replaying every step in order
+    /// against both runtimes and asserting the equivalences each step's
+    /// checks describe.
+    pub fn to_test_fn(&self) -> String {
+        let mut lines = vec![format!("#[test]\nfn {}() {{", self.test_name)];
+
+        for step in &self.steps {
+            lines.extend(step.to_lines());
+        }
+
+        lines.push("}".to_owned());
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mirrors `native-token-handling`'s `withdraw_all_lamports_happy_path`:
+    /// fund the deposit PDA, then withdraw it all back to the payer.
+    fn withdraw_all_lamports_harness() -> DifferentialHarness {
+        DifferentialHarness::new(
+            "withdraw_all_lamports_matches_across_runtimes",
+            vec![
+                DifferentialStep::new(
+                    "deposit_funding_ix",
+                    "stylus_contract.deposit(deposit_amount)",
+                    vec![DifferentialCheck::Lamports {
+                        solana_account_field: "&deposit_pda_key".to_owned(),
+                        stylus_address_field: "deposit_contract_address".to_owned(),
+                    }],
+                ),
+                DifferentialStep::new(
+                    "withdraw_all_ix",
+                    "stylus_contract.withdraw_all()",
+                    vec![
+                        DifferentialCheck::Outcome { expect_success: true },
+                        DifferentialCheck::Lamports {
+                            solana_account_field: "&deposit_pda_key".to_owned(),
+                            stylus_address_field: "deposit_contract_address".to_owned(),
+                        },
+                    ],
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn lamports_check_scales_to_wei_and_compares_balances() {
+        let check = DifferentialCheck::Lamports {
+            solana_account_field: "&deposit_pda_key".to_owned(),
+            stylus_address_field: "deposit_contract_address".to_owned(),
+        };
+
+        assert_eq!(
+            check.to_assertion(),
+            "assert_eq!(\n    u128::from(solana_result.get_account(&deposit_pda_key).unwrap().lamports) * 1000000000,\n    stylus_vm.balance(deposit_contract_address).to::<u128>()\n);"
+        );
+    }
+
+    #[test]
+    fn account_data_check_compares_bytes_directly() {
+        let check = DifferentialCheck::AccountData {
+            solana_account_field: "&counter_state_key".to_owned(),
+            stylus_getter_expr: "stylus_contract.value().to_le_bytes().to_vec()".to_owned(),
+        };
+
+        assert_eq!(
+            check.to_assertion(),
+            "assert_eq!(\n    solana_result.get_account(&counter_state_key).unwrap().data,\n    stylus_contract.value().to_le_bytes().to_vec()\n);"
+        );
+    }
+
+    #[test]
+    fn outcome_check_asserts_both_runtimes_agree() {
+        assert_eq!(
+            DifferentialCheck::Outcome { expect_success: true }.to_assertion(),
+            "assert!(solana_result.program_result.is_ok());\nassert!(stylus_result.is_ok());"
+        );
+        assert_eq!(
+            DifferentialCheck::Outcome { expect_success: false }.to_assertion(),
+            "assert!(solana_result.program_result.is_err());\nassert!(stylus_result.is_err());"
+        );
+    }
+
+    #[test]
+    fn renders_test_fn_replaying_every_step_in_order() {
+        let rendered = withdraw_all_lamports_harness().to_test_fn();
+
+        assert!(rendered.starts_with("#[test]\nfn withdraw_all_lamports_matches_across_runtimes() {"));
+        assert!(rendered.ends_with('}'));
+        assert!(rendered.contains("let solana_result = mollusk.process_instruction(&deposit_funding_ix);"));
+        assert!(rendered.contains("let stylus_result = stylus_contract.deposit(deposit_amount);"));
+        assert!(rendered.contains("let solana_result = mollusk.process_instruction(&withdraw_all_ix);"));
+        assert!(rendered.contains("let stylus_result = stylus_contract.withdraw_all();"));
+        assert!(rendered.contains("assert!(solana_result.program_result.is_ok());"));
+    }
+}
@@ -0,0 +1,129 @@
+//! Lowers an Anchor `#[error_code]` enum (shown in the `errors-events`
+//! example's `ErrorCode`) to Stylus `SolidityError`-derived error types, one
+//! variant per error, with the `#[msg("...")]` string preserved as a doc
+//! comment on the generated error. The generated errors take no arguments,
+//! so the revert data carries only the error's selector, not the message.
+//!
+//! Anchor numbers custom codes starting at an offset (6000 by default,
+//! `anchor_lang::error::ERROR_CODE_OFFSET`) and Anchor's own built-in
+//! constraint errors (like `ConstraintHasOne`, asserted against in the
+//! `counter` module's tests) live below that offset - this pass only covers
+//! user-defined `#[error_code]` enums, since the built-ins have no
+//! translation target of their own. EVM selectors are keccak-derived rather
+//! than sequential, so callers that depended on the numeric Anchor code need
+//! the side table `to_selector_table` emits to update their off-chain
+//! decoding.
+
+/// One `#[msg("...")] Variant` arm of an Anchor `#[error_code]` enum.
+#[derive(Debug, Clone)]
+pub struct ErrorVariant {
+    pub name: String,
+    pub msg: String,
+}
+
+impl ErrorVariant {
+    pub fn new(name: impl Into<String>, msg: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            msg: msg.into(),
+        }
+    }
+}
+
+/// An Anchor `#[error_code] pub enum Name { .. }` declaration.
+#[derive(Debug, Clone)]
+pub struct ErrorCodeEnum {
+    pub name: String,
+    pub variants: Vec<ErrorVariant>,
+    /// Anchor's custom-code offset; the first variant is assigned this code,
+    /// and each later variant increments by one. Defaults to Anchor's own
+    /// `6000` in `new`.
+    pub code_offset: u32,
+}
+
+impl ErrorCodeEnum {
+    pub fn new(name: impl Into<String>, variants: Vec<ErrorVariant>) -> Self {
+        Self {
+            name: name.into(),
+            variants,
+            code_offset: 6000,
+        }
+    }
+
+    /// Renders the `sol!` zero-argument error declarations this enum lowers
+    /// to, one per variant, each preceded by its `#[msg]` string as a doc
+    /// comment.
+    pub fn to_sol_errors(&self) -> String {
+        self.variants
+            .iter()
+            .map(|variant| format!("    /// {}\n    error {}();", variant.msg, variant.name))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Renders the `#[derive(SolidityError)] pub enum ContractError { .. }`
+    /// this enum lowers to, wrapping each generated error type.
+    pub fn to_error_enum(&self, enum_name: &str) -> String {
+        let arms = self
+            .variants
+            .iter()
+            .map(|variant| format!("    {name}({name}),", name = variant.name))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            "#[derive(SolidityError)]\npub enum {enum_name} {{\n{arms}\n}}"
+        )
+    }
+
+    /// Renders the old-Anchor-code -> new-error side table integrators need
+    /// to update off-chain error handling, since Anchor's sequential numeric
+    /// codes have no equivalent on the keccak-selector-addressed EVM side.
+    pub fn to_selector_table(&self) -> String {
+        let mut lines = vec!["| anchor code | stylus error |".to_owned(), "| --- | --- |".to_owned()];
+        for (index, variant) in self.variants.iter().enumerate() {
+            let anchor_code = self.code_offset + index as u32;
+            lines.push(format!("| {} | {}() |", anchor_code, variant.name));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_code() -> ErrorCodeEnum {
+        ErrorCodeEnum::new(
+            "ErrorCode",
+            vec![
+                ErrorVariant::new("InvalidAmount", "Invalid amount: amount must be greater than 0"),
+                ErrorVariant::new("Unauthorized", "Unauthorized"),
+            ],
+        )
+    }
+
+    #[test]
+    fn renders_sol_errors_with_msg_as_doc_comment() {
+        assert_eq!(
+            error_code().to_sol_errors(),
+            "    /// Invalid amount: amount must be greater than 0\n    error InvalidAmount();\n    /// Unauthorized\n    error Unauthorized();"
+        );
+    }
+
+    #[test]
+    fn renders_error_enum_wrapping_each_variant() {
+        assert_eq!(
+            error_code().to_error_enum("ContractError"),
+            "#[derive(SolidityError)]\npub enum ContractError {\n    InvalidAmount(InvalidAmount),\n    Unauthorized(Unauthorized),\n}"
+        );
+    }
+
+    #[test]
+    fn selector_table_assigns_sequential_codes_from_offset() {
+        assert_eq!(
+            error_code().to_selector_table(),
+            "| anchor code | stylus error |\n| --- | --- |\n| 6000 | InvalidAmount() |\n| 6001 | Unauthorized() |"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Lowers Anchor's `#[event]`/`emit!` idiom (shown in the `errors-events`
+//! example's `OwnerChanged` declaration and `emit_event` instruction) into the
+//! Stylus equivalent: a `sol!`-style event definition and a
+//! `stylus_sdk::evm::log` call with the same fields, in the same order.
+//!
+//! Solana logs are an unstructured string stream; EVM logs are structured,
+//! indexable-by-topic entries, so there's no runtime translation possible -
+//! this has to happen at port time, once, against the declaration.
+
+/// An Anchor event field's Rust type, restricted to the primitives that show
+/// up in `#[event]` structs across the example programs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnchorFieldType {
+    /// A 32-byte Solana public key. There's no address-vs-opaque-hash
+    /// distinction on the Solana side, so this always lowers to `bytes32`;
+    /// callers that know a given `Pubkey` field is actually an EVM address
+    /// convention (like `OwnerChanged`'s `current_owner` in this repo's own
+    /// `stylus` translation) should narrow it by hand after generation.
+    Pubkey,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Bool,
+    String,
+}
+
+impl AnchorFieldType {
+    fn sol_type(self) -> &'static str {
+        match self {
+            Self::Pubkey => "bytes32",
+            Self::U8 => "uint8",
+            Self::U16 => "uint16",
+            Self::U32 => "uint32",
+            Self::U64 => "uint64",
+            Self::U128 => "uint128",
+            Self::Bool => "bool",
+            Self::String => "string",
+        }
+    }
+}
+
+/// One field of an Anchor `#[event]` struct.
+#[derive(Debug, Clone)]
+pub struct EventField {
+    pub name: String,
+    pub ty: AnchorFieldType,
+}
+
+impl EventField {
+    pub fn new(name: impl Into<String>, ty: AnchorFieldType) -> Self {
+        Self {
+            name: name.into(),
+            ty,
+        }
+    }
+}
+
+/// An Anchor `#[event] struct Name { .. }` declaration.
+#[derive(Debug, Clone)]
+pub struct EventDecl {
+    pub name: String,
+    pub fields: Vec<EventField>,
+}
+
+impl EventDecl {
+    pub fn new(name: impl Into<String>, fields: Vec<EventField>) -> Self {
+        Self {
+            name: name.into(),
+            fields,
+        }
+    }
+
+    /// Renders the `sol!` macro entry this event lowers to, with fields
+    /// mapped 1:1 in declaration order.
+    pub fn to_sol_event(&self) -> String {
+        let params = self
+            .fields
+            .iter()
+            .map(|field| format!("{} {}", field.ty.sol_type(), field.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("event {}({});", self.name, params)
+    }
+}
+
+/// One `emit!(Name { field: expr, .. })` call site, paired with the
+/// `EventDecl` it instantiates.
+#[derive(Debug, Clone)]
+pub struct EmitCall<'a> {
+    pub event: &'a EventDecl,
+    /// Field initializer expressions, in the same order as `event.fields`.
+    pub field_exprs: Vec<String>,
+}
+
+impl<'a> EmitCall<'a> {
+    pub fn new(event: &'a EventDecl, field_exprs: Vec<String>) -> Self {
+        Self { event, field_exprs }
+    }
+
+    /// Renders the `stylus_sdk::evm::log` call this `emit!` site lowers to,
+    /// ABI-encoding the same fields as topics/data via the generated event
+    /// type.
+    pub fn to_log_call(&self) -> String {
+        let fields = self
+            .event
+            .fields
+            .iter()
+            .zip(&self.field_exprs)
+            .map(|(field, expr)| format!("{}: {}", field.name, expr))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "log(self.vm(), {} {{ {} }});",
+            self.event.name, fields
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owner_changed() -> EventDecl {
+        EventDecl::new(
+            "OwnerChanged",
+            vec![
+                EventField::new("previous_owner", AnchorFieldType::Pubkey),
+                EventField::new("current_owner", AnchorFieldType::Pubkey),
+            ],
+        )
+    }
+
+    #[test]
+    fn translates_event_decl_to_sol_event() {
+        assert_eq!(
+            owner_changed().to_sol_event(),
+            "event OwnerChanged(bytes32 previous_owner, bytes32 current_owner);"
+        );
+    }
+
+    #[test]
+    fn translates_emit_call_to_log_call() {
+        let event = owner_changed();
+        let emit = EmitCall::new(
+            &event,
+            vec![
+                "previous_owner".to_owned(),
+                "current_owner".to_owned(),
+            ],
+        );
+
+        assert_eq!(
+            emit.to_log_call(),
+            "log(self.vm(), OwnerChanged { previous_owner: previous_owner, current_owner: current_owner });"
+        );
+    }
+
+    #[test]
+    fn field_order_follows_declaration_order() {
+        let event = EventDecl::new(
+            "Transfer",
+            vec![
+                EventField::new("from", AnchorFieldType::Pubkey),
+                EventField::new("to", AnchorFieldType::Pubkey),
+                EventField::new("amount", AnchorFieldType::U64),
+            ],
+        );
+
+        assert_eq!(
+            event.to_sol_event(),
+            "event Transfer(bytes32 from, bytes32 to, uint64 amount);"
+        );
+    }
+}
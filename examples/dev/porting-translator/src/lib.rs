@@ -0,0 +1,17 @@
+//! Translation passes that lower specific Anchor/native Solana idioms to their
+//! Stylus equivalent, given a structured description of the Solana-side
+//! construct rather than parsing Rust source directly.
+//!
+//! Each module targets one idiom demonstrated in `examples/concepts` and
+//! mirrors, in code, the hand-translation already shown in that example's
+//! `stylus` crate - so a porting tool can generate the same output instead of
+//! requiring it to be written by hand.
+
+pub mod account_safety;
+pub mod constraints;
+pub mod cpi;
+pub mod differential_harness;
+pub mod errors;
+pub mod events;
+pub mod pda_storage;
+pub mod system_instructions;
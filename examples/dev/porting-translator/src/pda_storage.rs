@@ -0,0 +1,146 @@
+//! Lowers the native-program PDA-account idiom (shown in the
+//! `pdas-to-mappings` example's player program: `find_program_address` +
+//! `data_is_empty`/`lamports`/`owner` init guard + `invoke_signed(create_account)`)
+//! onto Stylus contract storage.
+//!
+//! EVM/Stylus has no account or rent model to create an account *into*, so
+//! the translation target is a storage slot rather than a new account: the
+//! seed tuple becomes a `keccak256`-derived map key, the Borsh account state
+//! becomes a `#[storage]` struct kept at that key, and the three-way
+//! already-initialized check collapses into a single `initialized` flag the
+//! generated code asserts is false.
+
+/// One component of a PDA's seed list. Mirrors the two shapes seeds take in
+/// `find_program_address(&[...], program_id)` calls across the example
+/// programs: a fixed byte-string constant, or an account/argument key that
+/// varies per call.
+#[derive(Debug, Clone)]
+pub enum SeedPart {
+    Literal(Vec<u8>),
+    Dynamic(String),
+}
+
+/// A `find_program_address` seed list, in order.
+#[derive(Debug, Clone, Default)]
+pub struct PdaSeeds(pub Vec<SeedPart>);
+
+impl PdaSeeds {
+    pub fn new(parts: Vec<SeedPart>) -> Self {
+        Self(parts)
+    }
+
+    /// Renders the `keccak256(abi.encode(..))` expression the seed list
+    /// lowers to, used as the `StorageMap` key.
+    pub fn to_key_expr(&self) -> String {
+        let args = self
+            .0
+            .iter()
+            .map(|part| match part {
+                SeedPart::Literal(bytes) => format!("b\"{}\"", String::from_utf8_lossy(bytes)),
+                SeedPart::Dynamic(name) => name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("keccak256(abi::encode(&({args})))")
+    }
+}
+
+/// A Borsh-serialized PDA account state field.
+#[derive(Debug, Clone)]
+pub struct StateField {
+    pub name: String,
+    pub ty: &'static str,
+}
+
+impl StateField {
+    pub fn new(name: impl Into<String>, ty: &'static str) -> Self {
+        Self {
+            name: name.into(),
+            ty,
+        }
+    }
+}
+
+/// A native program's PDA-backed account state plus the seeds that address
+/// it, e.g. the `pdas-to-mappings` example's `PlayerAccountState` keyed by
+/// `[PLAYER_PDA_ACCOUNT_SEED, SEED_SEPARATOR, payer.key]`.
+#[derive(Debug, Clone)]
+pub struct PdaAccount {
+    pub struct_name: String,
+    pub seeds: PdaSeeds,
+    pub fields: Vec<StateField>,
+}
+
+impl PdaAccount {
+    pub fn new(struct_name: impl Into<String>, seeds: PdaSeeds, fields: Vec<StateField>) -> Self {
+        Self {
+            struct_name: struct_name.into(),
+            seeds,
+            fields,
+        }
+    }
+
+    /// Renders the `#[storage]` struct this account state lowers to, with an
+    /// `initialized` flag standing in for the
+    /// `data_is_empty`/`lamports`/`owner` already-initialized guard.
+    pub fn to_storage_struct(&self) -> String {
+        let mut lines = vec![format!("#[storage]\npub struct {} {{", self.struct_name)];
+        lines.push("    initialized: StorageBool,".to_owned());
+        for field in &self.fields {
+            lines.push(format!("    {}: {},", field.name, field.ty));
+        }
+        lines.push("}".to_owned());
+        lines.join("\n")
+    }
+
+    /// Renders the initialize-once guard that replaces
+    /// `create_account`+`copy_from_slice`: recompute the key, assert the
+    /// slot isn't already marked initialized, then set it.
+    pub fn to_init_guard(&self) -> String {
+        format!(
+            "let key = {};\nlet mut state = self.accounts.setter(key);\nif state.initialized.get() {{\n    return Err(AlreadyInitialized {{}}.into());\n}}\nstate.initialized.set(true);",
+            self.seeds.to_key_expr()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player_account() -> PdaAccount {
+        PdaAccount::new(
+            "PlayerAccountState",
+            PdaSeeds::new(vec![
+                SeedPart::Literal(b"player".to_vec()),
+                SeedPart::Literal(b"-".to_vec()),
+                SeedPart::Dynamic("payer".to_owned()),
+            ]),
+            vec![StateField::new("lives", "StorageU8")],
+        )
+    }
+
+    #[test]
+    fn renders_key_expr_from_mixed_literal_and_dynamic_seeds() {
+        assert_eq!(
+            player_account().seeds.to_key_expr(),
+            "keccak256(abi::encode(&(b\"player\", b\"-\", payer)))"
+        );
+    }
+
+    #[test]
+    fn renders_storage_struct_with_initialized_flag() {
+        assert_eq!(
+            player_account().to_storage_struct(),
+            "#[storage]\npub struct PlayerAccountState {\n    initialized: StorageBool,\n    lives: StorageU8,\n}"
+        );
+    }
+
+    #[test]
+    fn renders_init_guard_checking_initialized_flag() {
+        let guard = player_account().to_init_guard();
+        assert!(guard.contains("state.initialized.get()"));
+        assert!(guard.contains("state.initialized.set(true);"));
+    }
+}
@@ -0,0 +1,169 @@
+//! A declarative mapping table over the full `solana_system_interface::instruction`
+//! surface (used by fixtures like `native-token-handling`'s `WithdrawAllLamports`,
+//! which builds a `transfer`), replacing `cpi`'s prior ad-hoc "is it a
+//! transfer or not" special case with a single registry the rest of the
+//! translator can query for a given instruction's Stylus lowering and its
+//! preconditions.
+
+/// One `solana_system_interface::instruction` constructor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SystemInstruction {
+    CreateAccount,
+    CreateAccountWithSeed,
+    Allocate,
+    AllocateWithSeed,
+    Assign,
+    Transfer,
+    TransferWithSeed,
+    InitializeNonceAccount,
+    AdvanceNonceAccount,
+    WithdrawNonceAccount,
+    AuthorizeNonceAccount,
+}
+
+/// Every instruction this table has an entry for, in the order
+/// `solana_system_interface::instruction` declares them.
+pub const ALL: [SystemInstruction; 11] = [
+    SystemInstruction::CreateAccount,
+    SystemInstruction::CreateAccountWithSeed,
+    SystemInstruction::Allocate,
+    SystemInstruction::AllocateWithSeed,
+    SystemInstruction::Assign,
+    SystemInstruction::Transfer,
+    SystemInstruction::TransferWithSeed,
+    SystemInstruction::InitializeNonceAccount,
+    SystemInstruction::AdvanceNonceAccount,
+    SystemInstruction::WithdrawNonceAccount,
+    SystemInstruction::AuthorizeNonceAccount,
+];
+
+/// How a `SystemInstruction` lowers to Stylus, or why it can't.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StylusLowering {
+    /// Lowers to `primitive` under `preconditions`.
+    Supported {
+        primitive: &'static str,
+        preconditions: &'static [&'static str],
+        /// Whether `primitive` replaces the `invoke`/`invoke_signed` call
+        /// site itself - true only for a bare value transfer. Everything
+        /// else supported here (`create_account`, `allocate`, ...) lowers to
+        /// a storage declaration generated by the `pda_storage` pass
+        /// instead, so there's no call-site code for `cpi` to emit for it.
+        renders_as_call: bool,
+    },
+    /// No Stylus equivalent; explains why and suggests a rewrite.
+    Unsupported {
+        reason: &'static str,
+        suggestion: &'static str,
+    },
+}
+
+/// Looks up `instruction`'s Stylus lowering.
+pub fn lowering(instruction: SystemInstruction) -> StylusLowering {
+    use SystemInstruction::*;
+
+    match instruction {
+        Transfer | TransferWithSeed => StylusLowering::Supported {
+            primitive: "transfer_eth",
+            preconditions: &["the source account signs via invoke_signed as a PDA this contract itself derives"],
+            renders_as_call: true,
+        },
+        CreateAccount => StylusLowering::Supported {
+            primitive: "a #[storage] field declaration",
+            preconditions: &["the new account is rent-exempt and owned by the invoking program"],
+            renders_as_call: false,
+        },
+        CreateAccountWithSeed => StylusLowering::Supported {
+            primitive: "a #[storage] field declaration keyed by the seed-derived slot",
+            preconditions: &[
+                "the new account is rent-exempt and owned by the invoking program",
+                "the seed/base/owner triple resolves to a slot pda_storage can derive a StorageMap key from",
+            ],
+            renders_as_call: false,
+        },
+        Allocate | AllocateWithSeed => StylusLowering::Supported {
+            primitive: "a #[storage] field declaration",
+            preconditions: &["the account is already owned by the invoking program - no ownership transfer happens"],
+            renders_as_call: false,
+        },
+        Assign => StylusLowering::Unsupported {
+            reason: "reassigning an account's owner program has no Stylus analogue - a contract's storage can't change which contract owns it after deployment",
+            suggestion: "model the target program's behavior as a method on this contract instead of reassigning ownership to a separate one",
+        },
+        InitializeNonceAccount | AdvanceNonceAccount | WithdrawNonceAccount | AuthorizeNonceAccount => {
+            StylusLowering::Unsupported {
+                reason: "durable transaction nonces have no EVM equivalent - Stylus/EVM replay protection is the account nonce built into every transaction, not an opt-in account a program manages",
+                suggestion: "drop the nonce account entirely and rely on the caller's own EOA/contract transaction nonce for replay protection",
+            }
+        }
+    }
+}
+
+/// Every instruction in `ALL` this table reports as unsupported, paired with
+/// its lowering - an actionable report a caller can surface up front, rather
+/// than one instruction failing ad hoc to lower and the rest continuing to
+/// succeed until the user stumbles into the unsupported one.
+pub fn unsupported_report() -> Vec<(SystemInstruction, StylusLowering)> {
+    ALL.into_iter()
+        .filter(|instruction| matches!(lowering(*instruction), StylusLowering::Unsupported { .. }))
+        .map(|instruction| (instruction, lowering(instruction)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfer_lowers_to_transfer_eth_as_a_call() {
+        assert_eq!(
+            lowering(SystemInstruction::Transfer),
+            StylusLowering::Supported {
+                primitive: "transfer_eth",
+                preconditions: &["the source account signs via invoke_signed as a PDA this contract itself derives"],
+                renders_as_call: true,
+            }
+        );
+    }
+
+    #[test]
+    fn create_account_is_supported_but_not_a_call_site() {
+        match lowering(SystemInstruction::CreateAccount) {
+            StylusLowering::Supported { renders_as_call, .. } => assert!(!renders_as_call),
+            StylusLowering::Unsupported { .. } => panic!("create_account should be supported"),
+        }
+    }
+
+    #[test]
+    fn assign_is_unsupported() {
+        assert!(matches!(lowering(SystemInstruction::Assign), StylusLowering::Unsupported { .. }));
+    }
+
+    #[test]
+    fn nonce_operations_are_unsupported() {
+        for instruction in [
+            SystemInstruction::InitializeNonceAccount,
+            SystemInstruction::AdvanceNonceAccount,
+            SystemInstruction::WithdrawNonceAccount,
+            SystemInstruction::AuthorizeNonceAccount,
+        ] {
+            assert!(matches!(lowering(instruction), StylusLowering::Unsupported { .. }));
+        }
+    }
+
+    #[test]
+    fn unsupported_report_covers_assign_and_every_nonce_operation() {
+        let reported: Vec<_> = unsupported_report().into_iter().map(|(instruction, _)| instruction).collect();
+
+        assert_eq!(
+            reported,
+            vec![
+                SystemInstruction::Assign,
+                SystemInstruction::InitializeNonceAccount,
+                SystemInstruction::AdvanceNonceAccount,
+                SystemInstruction::WithdrawNonceAccount,
+                SystemInstruction::AuthorizeNonceAccount,
+            ]
+        );
+    }
+}
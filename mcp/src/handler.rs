@@ -1,12 +1,18 @@
 use rust_mcp_schema::{
-    CallToolRequest, CallToolResult, GetPromptRequest, GetPromptResult, Implementation,
-    InitializeRequest, InitializeResult, LATEST_PROTOCOL_VERSION, ListPromptsRequest,
-    ListPromptsResult, ListResourceTemplatesRequest, ListResourceTemplatesResult,
-    ListResourcesRequest, ListResourcesResult, ListToolsRequest, ListToolsResult, PingRequest,
-    ReadResourceRequest, ReadResourceResult, Result as CustomResult, RpcError, ServerCapabilities,
-    ServerCapabilitiesPrompts, ServerCapabilitiesResources, ServerCapabilitiesTools,
+    CallToolRequest, CallToolResult, CompleteRequest, CompleteResult, CompleteResultCompletion,
+    GetPromptRequest, GetPromptResult, Implementation, InitializeRequest, InitializeResult,
+    LATEST_PROTOCOL_VERSION, ListPromptsRequest, ListPromptsResult, ListResourceTemplatesRequest,
+    ListResourceTemplatesResult, ListResourcesRequest, ListResourcesResult, ListToolsRequest,
+    ListToolsResult, PingRequest, ReadResourceRequest, ReadResourceResult, Reference,
+    Result as CustomResult, RpcError, ServerCapabilities, ServerCapabilitiesCompletions,
+    ServerCapabilitiesLogging, ServerCapabilitiesPrompts, ServerCapabilitiesResources,
+    ServerCapabilitiesTools, SetLevelRequest, SubscribeRequest, UnsubscribeRequest,
 };
 
+/// `CompleteRequest` never returns more than this many candidates in one response - callers needing the rest
+/// refine `argument.value` and ask again, same as a normal prefix search.
+const MAX_COMPLETION_VALUES: usize = 100;
+
 pub fn initialize_request(req: InitializeRequest) -> Result<InitializeResult, RpcError> {
     if req.params.protocol_version.as_str() > LATEST_PROTOCOL_VERSION {
         return Err(RpcError::internal_error().with_message(format!(
@@ -15,11 +21,21 @@ pub fn initialize_request(req: InitializeRequest) -> Result<InitializeResult, Rp
         )));
     }
 
+    let supports_base64_zstd = req
+        .params
+        .capabilities
+        .experimental
+        .as_ref()
+        .is_some_and(|experimental| experimental.contains_key("base64zstd"));
+    crate::resources::compression::set_client_supports_base64_zstd(supports_base64_zstd);
+
     Ok(InitializeResult {
         capabilities: ServerCapabilities {
+            completions: Some(ServerCapabilitiesCompletions {}),
+            logging: Some(ServerCapabilitiesLogging {}),
             resources: Some(ServerCapabilitiesResources {
-                list_changed: Some(false),
-                subscribe: Some(false),
+                list_changed: Some(true),
+                subscribe: Some(true),
             }),
             tools: Some(ServerCapabilitiesTools {
                 list_changed: Some(false),
@@ -71,7 +87,55 @@ pub fn read_resource_request(req: ReadResourceRequest) -> Result<ReadResourceRes
     };
 
     Ok(ReadResourceResult {
-        contents: vec![content],
+        contents: vec![crate::resources::compression::maybe_compress(content)],
+        meta: None,
+    })
+}
+
+pub fn set_level_request(req: SetLevelRequest) -> Result<CustomResult, RpcError> {
+    crate::logging::set_level(req.params.level);
+
+    Ok(CustomResult::default())
+}
+
+pub fn subscribe_request(req: SubscribeRequest) -> Result<CustomResult, RpcError> {
+    if !crate::resources::subscribe(&req.params.uri) {
+        return Err(RpcError::internal_error()
+            .with_message(format!("resource URI not found: {}", req.params.uri)));
+    }
+
+    Ok(CustomResult::default())
+}
+
+pub fn unsubscribe_request(req: UnsubscribeRequest) -> Result<CustomResult, RpcError> {
+    crate::resources::unsubscribe(&req.params.uri);
+
+    Ok(CustomResult::default())
+}
+
+pub fn complete_request(req: CompleteRequest) -> Result<CompleteResult, RpcError> {
+    let partial = req.params.argument.value.as_str();
+
+    let mut values = match req.params.reference {
+        Reference::PromptReference(prompt_ref) => {
+            crate::prompts::complete_argument(&prompt_ref.name, &req.params.argument.name, partial)
+        }
+        Reference::ResourceTemplateReference(template_ref) => {
+            crate::resources::complete_template_uri(&template_ref.uri, partial)
+        }
+    };
+    values.sort();
+
+    let total = values.len();
+    let has_more = total > MAX_COMPLETION_VALUES;
+    values.truncate(MAX_COMPLETION_VALUES);
+
+    Ok(CompleteResult {
+        completion: CompleteResultCompletion {
+            has_more: Some(has_more),
+            total: Some(total as i64),
+            values,
+        },
         meta: None,
     })
 }
@@ -0,0 +1,73 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use rust_mcp_schema::schema_utils::{NotificationFromServer, ServerJsonrpcNotification, ServerMessage};
+use rust_mcp_schema::{LoggingLevel, LoggingMessageNotification, LoggingMessageNotificationParams, ServerNotification};
+
+/// RFC 5424 severity order `LoggingLevel` mirrors - `SetLevelRequest` asks for this level and every more severe
+/// one, so comparisons are done on this scale rather than the enum's own (unordered) variants.
+fn severity(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+/// The client's configured minimum level, defaulting to `Info` until a `SetLevelRequest` changes it. Stored as
+/// the `severity` scale rather than `LoggingLevel` itself so reads/writes stay a single atomic op.
+static MIN_LEVEL: AtomicU8 = AtomicU8::new(1);
+
+/// Applies a `SetLevelRequest`: every `log` call at or above `level` is forwarded to the client from now on.
+pub fn set_level(level: LoggingLevel) {
+    MIN_LEVEL.store(severity(level), Ordering::Relaxed);
+}
+
+/// Sanitizes `data` the same way raw stdin input is before it's ever logged, and drops it entirely if `level`
+/// is below the client's configured minimum. Returns the `notifications/message` `ServerMessage` to forward
+/// through `OutputSink` otherwise - the caller is expected to also keep writing to stderr, same as before
+/// `SetLevelRequest` existed.
+pub fn log(level: LoggingLevel, logger: &str, data: &str) -> Option<ServerMessage> {
+    if severity(level) < MIN_LEVEL.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    let data = crate::sanitize_for_log(data);
+
+    Some(ServerMessage::Notification(ServerJsonrpcNotification::new(
+        NotificationFromServer::ServerNotification(ServerNotification::LoggingMessageNotification(
+            LoggingMessageNotification {
+                method: "notifications/message".to_owned(),
+                params: LoggingMessageNotificationParams {
+                    data: serde_json::Value::String(data),
+                    level,
+                    logger: Some(logger.to_owned()),
+                },
+            },
+        )),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `MIN_LEVEL` is process-global, so both behaviors are exercised in one test to avoid racing against
+    // another test thread's `set_level` call.
+    #[test]
+    fn set_level_filters_by_severity() {
+        assert!(log(LoggingLevel::Info, "test", "hello").is_some());
+        assert!(log(LoggingLevel::Error, "test", "hello").is_some());
+
+        set_level(LoggingLevel::Error);
+        assert!(log(LoggingLevel::Warning, "test", "hello").is_none());
+        assert!(log(LoggingLevel::Error, "test", "hello").is_some());
+
+        // Restore the default so other tests in this process aren't affected.
+        set_level(LoggingLevel::Info);
+    }
+}
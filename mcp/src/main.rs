@@ -1,11 +1,15 @@
 mod handler;
+mod logging;
 mod prompts;
 mod resources;
 mod server;
 mod tools;
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
 use rust_mcp_schema::{
-    ClientRequest, JsonrpcError, RpcError, ServerResult,
+    ClientRequest, JsonrpcError, LoggingLevel, RpcError, ServerResult,
     schema_utils::{
         ClientMessage, ClientMessages, RequestFromClient, ResultFromServer, ServerJsonrpcResponse,
         ServerMessage,
@@ -23,34 +27,98 @@ fn sanitize_for_log(s: &str) -> String {
         .collect()
 }
 
-fn parse_client_msg(input: &str) -> Option<Vec<ClientMessage>> {
+/// Forwards `data` to the client as a `notifications/message` log notification through `output_sink`, if
+/// `level` clears the client's `SetLevelRequest`-configured minimum. The caller keeps writing to stderr
+/// regardless - this only adds a second, level-filtered channel, it doesn't replace the first.
+fn log(output_sink: &mut OutputSink, level: LoggingLevel, logger: &str, data: &str) {
+    if let Some(notification) = logging::log(level, logger, data) {
+        let output = serde_json::to_string(&notification).expect("infallible serialization");
+        output_sink.send(output);
+    }
+}
+
+/// Shared among every `WorkItem::Batched` spawned from the same input line, so whichever worker thread
+/// processes the last member of the batch is the one that gets back the full, ordered-by-completion set of
+/// responses to flush as a single JSON array - notifications (which produce no `ServerMessage`) simply don't
+/// contribute an element.
+struct BatchCollector {
+    remaining: AtomicUsize,
+    responses: Mutex<Vec<ServerMessage>>,
+}
+
+impl BatchCollector {
+    fn new(size: usize) -> Self {
+        Self {
+            remaining: AtomicUsize::new(size),
+            responses: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Records this batch member's response, if any, and returns the full collected set once every member has
+    /// reported in - `None` otherwise, since the batch isn't ready to flush yet.
+    fn complete(&self, response: Option<ServerMessage>) -> Option<Vec<ServerMessage>> {
+        if let Some(response) = response {
+            self.responses.lock().unwrap().push(response);
+        }
+
+        if self.remaining.fetch_sub(1, Ordering::AcqRel) == 1 {
+            Some(std::mem::take(&mut self.responses.lock().unwrap()))
+        } else {
+            None
+        }
+    }
+}
+
+/// One unit of work dispatched to the worker pool: a standalone request/notification, one member of a batch
+/// (paired with the `BatchCollector` the rest of its batch shares), or a batch the spec forbids outright.
+enum WorkItem {
+    Single(ClientMessage),
+    Batched(ClientMessage, Arc<BatchCollector>),
+    EmptyBatch,
+}
+
+fn parse_client_msg(input: &str) -> Option<Vec<WorkItem>> {
     let Ok(client_msgs) = serde_json::from_str(input) else {
         eprintln!("Unexpected input: {}", sanitize_for_log(input));
         return None;
     };
 
-    let msgs = match client_msgs {
-        ClientMessages::Single(msg) => vec![msg],
-        ClientMessages::Batch(msgs) => msgs,
-    };
-
-    Some(msgs)
+    match client_msgs {
+        ClientMessages::Single(msg) => Some(vec![WorkItem::Single(msg)]),
+        // https://www.jsonrpc.org/specification#batch: "an empty array [is] invalid" - handled by a worker so
+        // it can report the error back through `OutputSink`, same as every other work item.
+        ClientMessages::Batch(msgs) if msgs.is_empty() => Some(vec![WorkItem::EmptyBatch]),
+        ClientMessages::Batch(msgs) => {
+            let collector = Arc::new(BatchCollector::new(msgs.len()));
+            Some(
+                msgs.into_iter()
+                    .map(|msg| WorkItem::Batched(msg, Arc::clone(&collector)))
+                    .collect(),
+            )
+        }
+    }
 }
 
-fn handle_client_msg(msg: ClientMessage, output_sink: &mut OutputSink) {
+/// Dispatches `msg` to its handler and builds the `ServerMessage` to send back, or `None` if `msg` was a
+/// notification (or other non-request) rather than a request awaiting a response.
+fn process_client_msg(msg: ClientMessage, output_sink: &mut OutputSink) -> Option<ServerMessage> {
     let ClientMessage::Request(req_msg) = msg else {
-        eprintln!(
+        let text = format!(
             "received non-request: {}",
             sanitize_for_log(&msg.to_string())
         );
-        return;
+        eprintln!("{text}");
+        log(output_sink, LoggingLevel::Warning, "dispatch", &text);
+        return None;
     };
 
     let request = match req_msg.request {
         RequestFromClient::ClientRequest(client_request) => client_request,
         RequestFromClient::CustomRequest(value) => {
-            eprintln!("Unsupported custom request: {value:#}");
-            return;
+            let text = format!("Unsupported custom request: {value:#}");
+            eprintln!("{text}");
+            log(output_sink, LoggingLevel::Warning, "dispatch", &text);
+            return None;
         }
     };
 
@@ -72,25 +140,69 @@ fn handle_client_msg(msg: ClientMessage, output_sink: &mut OutputSink) {
         ClientRequest::GetPromptRequest(req) => handler::get_prompt_request(req).map(Into::into),
         ClientRequest::ListToolsRequest(req) => handler::list_tools_request(req).map(Into::into),
         ClientRequest::CallToolRequest(req) => handler::call_tool_request(req).map(Into::into),
-        ClientRequest::CompleteRequest(_)
-        | ClientRequest::SubscribeRequest(_)
-        | ClientRequest::UnsubscribeRequest(_)
-        | ClientRequest::SetLevelRequest(_) => Err(RpcError::internal_error().with_message(
-            format!("missing method handling capability: {}", request.method()),
-        )),
+        ClientRequest::SubscribeRequest(req) => handler::subscribe_request(req).map(Into::into),
+        ClientRequest::UnsubscribeRequest(req) => {
+            handler::unsubscribe_request(req).map(Into::into)
+        }
+        ClientRequest::CompleteRequest(req) => handler::complete_request(req).map(Into::into),
+        ClientRequest::SetLevelRequest(req) => handler::set_level_request(req).map(Into::into),
     };
 
-    let response = match result {
+    if let Err(rpc_err) = &result {
+        log(
+            output_sink,
+            LoggingLevel::Error,
+            "dispatch",
+            &format!("{} ({})", rpc_err.message, rpc_err.code),
+        );
+    }
+
+    Some(match result {
         Ok(success_res) => ServerMessage::Response(ServerJsonrpcResponse::new(
             req_msg.id,
             ResultFromServer::ServerResult(success_res),
         )),
         Err(rpc_err) => ServerMessage::Error(JsonrpcError::new(rpc_err, req_msg.id)),
-    };
+    })
+}
 
-    let output = serde_json::to_string(&response).expect("infallible serialization");
+/// JSON-RPC 2.0 requires an empty batch array to be rejected with a single `Invalid Request` error whose `id`
+/// is `null` - there's no request to attribute it to, which `JsonrpcError` (built around a `RequestId`) can't
+/// express, so this is assembled directly rather than through the schema types.
+fn empty_batch_error() -> String {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "error": { "code": -32600, "message": "Invalid Request: batch array must not be empty" },
+        "id": null,
+    })
+    .to_string()
+}
 
-    output_sink.send(output);
+fn handle_client_msg(item: WorkItem, output_sink: &mut OutputSink) {
+    match item {
+        WorkItem::Single(msg) => {
+            if let Some(response) = process_client_msg(msg, output_sink) {
+                let output = serde_json::to_string(&response).expect("infallible serialization");
+                output_sink.send(output);
+            }
+        }
+        WorkItem::Batched(msg, collector) => {
+            let response = process_client_msg(msg, output_sink);
+            if let Some(responses) = collector.complete(response) {
+                // Per spec, a batch with no Response objects (every member was a notification) isn't answered
+                // with an empty array - it isn't answered at all.
+                if !responses.is_empty() {
+                    let output =
+                        serde_json::to_string(&responses).expect("infallible serialization");
+                    output_sink.send(output);
+                }
+            }
+        }
+        WorkItem::EmptyBatch => {
+            eprintln!("Received an empty batch");
+            output_sink.send(empty_batch_error());
+        }
+    }
 }
 
 fn main() {
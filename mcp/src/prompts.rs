@@ -35,3 +35,20 @@ pub fn call(name: &str, args: Option<&HashMap<String, String>>) -> Option<GetPro
         _ => None,
     }
 }
+
+/// Known value sets for a prompt argument, keyed by `(prompt_name, argument_name)` - what `CompleteRequest`
+/// enumerates candidates from. Empty today since `plan_solana_program_stylus_migration` (the only prompt
+/// registered) declares no arguments; a future prompt with an enumerable argument adds an entry here.
+const ARGUMENT_VALUES: &[(&str, &str, &[&str])] = &[];
+
+/// Candidates for `argument_name` of `prompt_name` whose value starts with `partial`, for `CompleteRequest`.
+pub fn complete_argument(prompt_name: &str, argument_name: &str, partial: &str) -> Vec<String> {
+    ARGUMENT_VALUES
+        .iter()
+        .find(|(p, a, _)| *p == prompt_name && *a == argument_name)
+        .into_iter()
+        .flat_map(|(_, _, values)| *values)
+        .filter(|value| value.starts_with(partial))
+        .map(|value| (*value).to_owned())
+        .collect()
+}
@@ -1,7 +1,14 @@
 mod bm25;
+pub mod compression;
+
+use rust_mcp_schema::schema_utils::{NotificationFromServer, ServerJsonrpcNotification, ServerMessage};
+use rust_mcp_schema::{
+    ResourceListChangedNotification, ResourceUpdatedNotification,
+    ResourceUpdatedNotificationParams, ServerNotification,
+};
 
 macro_rules! create_chapter {
-    ($module_name:ident, $file_path:literal, $title:literal, $description:literal) => {
+    ($module_name:ident, $file_path:literal, $title:literal, $description:literal, $mime_type:literal) => {
         pub mod $module_name {
             pub static CONTENT: &str = include_str!(concat!("../../", $file_path));
 
@@ -10,7 +17,7 @@ macro_rules! create_chapter {
                     annotations: None,
                     description: Some($description.to_owned()),
                     meta: None,
-                    mime_type: Some("text/markdown".to_owned()),
+                    mime_type: Some($mime_type.to_owned()),
                     name: stringify!($module_name).replace('_', "-").to_owned(),
                     size: Some(CONTENT.len() as _),
                     title: Some($title.to_owned()),
@@ -21,7 +28,7 @@ macro_rules! create_chapter {
             pub fn content() -> ::rust_mcp_schema::ReadResourceResultContentsItem {
                 ::rust_mcp_schema::TextResourceContents {
                     meta: None,
-                    mime_type: Some("text/markdown".to_owned()),
+                    mime_type: Some($mime_type.to_owned()),
                     text: CONTENT.to_owned(),
                     uri: concat!("file://", $file_path).to_owned(),
                 }
@@ -31,6 +38,10 @@ macro_rules! create_chapter {
     };
 }
 
+// `create_handbook!` and `create_examples!` both register a corpus of `text/...` resources and
+// feed it into the combined search index below, differing only in the submodule/mime-type they
+// use and the doc-entries/get_resource/get_all function names they emit (so the two corpora can
+// be combined at the bottom of this file without name collisions).
 macro_rules! create_handbook {
     (
         $(
@@ -43,42 +54,77 @@ macro_rules! create_handbook {
     ) => {
         pub mod chapter {
             $(
-                create_chapter!($module_name, $file_path, $title, $description);
+                create_chapter!($module_name, $file_path, $title, $description, "text/markdown");
             )+
         }
 
-        static INDEX: std::sync::LazyLock<bm25::Index<'static>> = std::sync::LazyLock::new(|| {
-            let mut index = bm25::Index::new(1.2);
+        fn chapter_doc_entries() -> Vec<(&'static str, &'static str, &'static str, &'static str)> {
+            vec![
+                $(
+                    (concat!("file://", $file_path), $title, $description, chapter::$module_name::CONTENT),
+                )+
+            ]
+        }
+
+        fn chapter_get_resource(uri: &str) -> Option<::rust_mcp_schema::ReadResourceResultContentsItem> {
+            match uri {
+                $(
+                    concat!("file://", $file_path) => Some(chapter::$module_name::content()),
+                )+
+                _ => None,
+            }
+        }
+
+        fn chapter_get_all() -> Vec<::rust_mcp_schema::Resource> {
+            vec![
+                $(
+                    chapter::$module_name::resource(),
+                )+
+            ]
+        }
+    };
+}
+
+// A parallel registration path for reference example programs (`include_str!`'d as
+// `text/x-rust`), so a query like "PDA withdraw lamports" can surface the concrete program that
+// implements a pattern alongside the handbook chapter that discusses it.
+macro_rules! create_examples {
+    (
+        $(
+            $module_name:ident => {
+                file_path: $file_path:literal,
+                title: $title:literal,
+                description: $description:literal
+            }
+        ),+ $(,)?
+    ) => {
+        pub mod example {
             $(
-                index.add_doc(
-                    concat!("file://", $file_path),
-                    chapter::$module_name::CONTENT
-                );
+                create_chapter!($module_name, $file_path, $title, $description, "text/x-rust");
             )+
-            index.finalize();
-            index
-        });
+        }
 
-        pub fn search(query: &str) -> Vec<String> {
-            INDEX.score(query)
-                .into_iter()
-                .map(|(uri, _score)| uri.to_string())
-                .collect()
+        fn example_doc_entries() -> Vec<(&'static str, &'static str, &'static str, &'static str)> {
+            vec![
+                $(
+                    (concat!("file://", $file_path), $title, $description, example::$module_name::CONTENT),
+                )+
+            ]
         }
 
-        pub fn get_resource(uri: &str) -> Option<::rust_mcp_schema::ReadResourceResultContentsItem> {
+        fn example_get_resource(uri: &str) -> Option<::rust_mcp_schema::ReadResourceResultContentsItem> {
             match uri {
                 $(
-                    concat!("file://", $file_path) => Some(chapter::$module_name::content()),
+                    concat!("file://", $file_path) => Some(example::$module_name::content()),
                 )+
                 _ => None,
             }
         }
 
-        pub fn get_all() -> Vec<::rust_mcp_schema::Resource> {
+        fn example_get_all() -> Vec<::rust_mcp_schema::Resource> {
             vec![
                 $(
-                    chapter::$module_name::resource(),
+                    example::$module_name::resource(),
                 )+
             ]
         }
@@ -153,6 +199,250 @@ create_handbook! {
     },
 }
 
+create_examples! {
+    access_control => {
+        file_path: "/examples/concepts/access-control/native/src/lib.rs",
+        title: "Example Program: Access Control (Native Solana)",
+        description: "A native Solana program implementing access control patterns"
+    },
+    accounts_to_storage => {
+        file_path: "/examples/concepts/accounts-to-storage/native/src/lib.rs",
+        title: "Example Program: Accounts to Storage (Native Solana)",
+        description: "A native Solana program demonstrating account-based state layout"
+    },
+    cpi_account_integrity => {
+        file_path: "/examples/concepts/cpi-account-integrity/native/src/lib.rs",
+        title: "Example Program: CPI Account Integrity (Native Solana)",
+        description: "A native Solana program validating account ownership and signer checks across a CPI"
+    },
+    cpi_to_counter => {
+        file_path: "/examples/concepts/cpi-to-counter/native/src/lib.rs",
+        title: "Example Program: CPI to Counter (Native Solana)",
+        description: "A native Solana program issuing a cross-program invocation to a counter program"
+    },
+    cpi_to_external_call => {
+        file_path: "/examples/concepts/cpi-to-external-call/native/src/lib.rs",
+        title: "Example Program: CPI to External Call (Native Solana)",
+        description: "A native Solana program making a cross-program invocation to an external program"
+    },
+    cpi_to_wormhole => {
+        file_path: "/examples/concepts/cpi-to-wormhole/native/src/lib.rs",
+        title: "Example Program: CPI to Wormhole (Native Solana)",
+        description: "A native Solana program posting messages through the Wormhole core bridge via CPI"
+    },
+    errors_events => {
+        file_path: "/examples/concepts/errors-events/native/src/lib.rs",
+        title: "Example Program: Errors and Events (Native Solana)",
+        description: "A native Solana program demonstrating custom errors and event emission"
+    },
+    fungible_tokens => {
+        file_path: "/examples/concepts/fungible-tokens/native/src/lib.rs",
+        title: "Example Program: Fungible Tokens (Native Solana)",
+        description: "A native Solana program handling SPL fungible token transfers"
+    },
+    native_token_handling => {
+        file_path: "/examples/concepts/native-token-handling/native/src/lib.rs",
+        title: "Example Program: Native Token Handling (Native Solana)",
+        description: "A native Solana program transferring and withdrawing lamports from a PDA"
+    },
+    non_fungible_tokens => {
+        file_path: "/examples/concepts/non-fungible-tokens/native/src/lib.rs",
+        title: "Example Program: Non-Fungible Tokens (Native Solana)",
+        description: "A native Solana program minting and transferring a non-fungible token"
+    },
+    pdas_to_mappings => {
+        file_path: "/examples/concepts/pdas-to-mappings/native/src/lib.rs",
+        title: "Example Program: PDAs to Mappings (Native Solana)",
+        description: "A native Solana program deriving program-derived addresses for per-user state"
+    },
+    program_structure => {
+        file_path: "/examples/concepts/program-structure/native/src/lib.rs",
+        title: "Example Program: Program Structure (Native Solana)",
+        description: "A native Solana counter program demonstrating instruction dispatch and Borsh (de)serialization"
+    },
+    record_storage => {
+        file_path: "/examples/concepts/record-storage/native/src/lib.rs",
+        title: "Example Program: Record Storage (Native Solana)",
+        description: "A native Solana program storing and resizing variable-length records with offset writes"
+    },
+}
+
+/// A single `search_detailed` result: a chapter's URI, its BM25F relevance score, and the
+/// highest-scoring passage, with matched terms marked, so a client can see why it matched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub uri: String,
+    pub score: f64,
+    pub snippet: String,
+}
+
+// Tokens per sliding snippet window. Wide enough to carry a full sentence or two of surrounding
+// context around a hit, narrow enough that the snippet stays a "why it matched" preview rather
+// than a second copy of the chapter.
+const SNIPPET_WINDOW: usize = 40;
+
+/// Slides a `SNIPPET_WINDOW`-token window over `content`, scoring each by how many of its words
+/// normalize to a term in `terms`, and returns the highest-scoring window with matched words
+/// wrapped in `**marks**`. Ties keep the earliest (lowest-offset) window.
+fn best_snippet(content: &str, terms: &std::collections::HashSet<String>) -> String {
+    let words: Vec<&str> = content.split_whitespace().collect();
+    if words.is_empty() {
+        return String::new();
+    }
+
+    let hits: Vec<bool> = words
+        .iter()
+        .map(|word| bm25::Index::normalize_text(word).iter().any(|t| terms.contains(t)))
+        .collect();
+
+    let window = SNIPPET_WINDOW.min(words.len());
+    let mut window_hits: usize = hits[..window].iter().filter(|&&h| h).count();
+    let mut best_start = 0;
+    let mut best_hits = window_hits;
+
+    for start in 1..=words.len() - window {
+        if hits[start - 1] {
+            window_hits -= 1;
+        }
+        if hits[start + window - 1] {
+            window_hits += 1;
+        }
+        if window_hits > best_hits {
+            best_hits = window_hits;
+            best_start = start;
+        }
+    }
+
+    words[best_start..best_start + window]
+        .iter()
+        .zip(&hits[best_start..best_start + window])
+        .map(|(word, &matched)| if matched { format!("**{word}**") } else { (*word).to_owned() })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// All documents searched by `search`/`search_detailed`: handbook chapters plus reference example
+/// programs, combined so a query can surface both the chapter discussing a pattern and the
+/// concrete program implementing it.
+fn doc_entries() -> Vec<(&'static str, &'static str, &'static str, &'static str)> {
+    chapter_doc_entries()
+        .into_iter()
+        .chain(example_doc_entries())
+        .collect()
+}
+
+static INDEX: std::sync::LazyLock<bm25::Index<'static>> = std::sync::LazyLock::new(|| {
+    let mut index = bm25::Index::new(1.2);
+    for (uri, title, description, body) in doc_entries() {
+        index.add_doc_fields(uri, &[("title", title), ("description", description), ("body", body)]);
+    }
+    index.finalize();
+    index
+});
+
+pub fn search(query: &str) -> Vec<String> {
+    INDEX.score(query)
+        .into_iter()
+        .map(|(uri, _score)| uri.to_string())
+        .collect()
+}
+
+/// Like `search`, but keeps each hit's BM25F score and locates its best-matching passage, so a
+/// client can see *why* a document matched rather than just its URI.
+pub fn search_detailed(query: &str) -> Vec<SearchHit> {
+    let terms = bm25::Index::query_terms(query);
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let entries = doc_entries();
+    INDEX.score(query)
+        .into_iter()
+        .map(|(uri, score)| {
+            let content = entries
+                .iter()
+                .find(|(entry_uri, ..)| *entry_uri == uri)
+                .map(|(_, _, _, body)| *body)
+                .unwrap_or("");
+
+            SearchHit {
+                uri: uri.to_owned(),
+                score,
+                snippet: best_snippet(content, &terms),
+            }
+        })
+        .collect()
+}
+
+pub fn get_resource(uri: &str) -> Option<::rust_mcp_schema::ReadResourceResultContentsItem> {
+    chapter_get_resource(uri).or_else(|| example_get_resource(uri))
+}
+
+pub fn get_all() -> Vec<::rust_mcp_schema::Resource> {
+    chapter_get_all().into_iter().chain(example_get_all()).collect()
+}
+
+static SUBSCRIPTIONS: std::sync::LazyLock<std::sync::Mutex<std::collections::HashSet<String>>> =
+    std::sync::LazyLock::new(|| std::sync::Mutex::new(std::collections::HashSet::new()));
+
+/// Subscribes `uri` to change notifications, returning `false` (and leaving the set untouched) if it isn't a
+/// known resource. The server handles exactly one client connection per process, so this single global set
+/// doubles as "this connection's subscriptions" - there's no separate connection identity to key on.
+pub fn subscribe(uri: &str) -> bool {
+    if get_resource(uri).is_none() {
+        return false;
+    }
+
+    SUBSCRIPTIONS.lock().unwrap().insert(uri.to_owned());
+    true
+}
+
+/// Unsubscribes `uri`. A no-op if it wasn't subscribed, per spec.
+pub fn unsubscribe(uri: &str) {
+    SUBSCRIPTIONS.lock().unwrap().remove(uri);
+}
+
+/// Builds the `notifications/resources/updated` message for `uri`, or `None` if nothing is currently subscribed
+/// to it. Every chapter here is embedded at compile time via `include_str!`, so nothing in this server mutates a
+/// resource's content today - this exists for a future dynamic resource to call once one does.
+pub fn updated_notification(uri: &str) -> Option<ServerMessage> {
+    if !SUBSCRIPTIONS.lock().unwrap().contains(uri) {
+        return None;
+    }
+
+    Some(ServerMessage::Notification(ServerJsonrpcNotification::new(
+        NotificationFromServer::ServerNotification(ServerNotification::ResourceUpdatedNotification(
+            ResourceUpdatedNotification {
+                method: "notifications/resources/updated".to_owned(),
+                params: ResourceUpdatedNotificationParams {
+                    uri: uri.to_owned(),
+                    meta: None,
+                },
+            },
+        )),
+    )))
+}
+
+/// Candidates for a resource template's `uri` completion, for `CompleteRequest`. `get_all`/`list_resource_templates_request`
+/// never register an actual `{variable}` template - every chapter is a fully-resolved `file://` URI - so there's
+/// nothing to expand a template variable against yet; this returns an empty set rather than guessing.
+pub fn complete_template_uri(_template_uri: &str, _partial: &str) -> Vec<String> {
+    vec![]
+}
+
+/// Builds the `notifications/resources/list_changed` message - broadcast unconditionally rather than filtered
+/// by subscription, since it describes the resource list itself rather than one resource's content.
+pub fn list_changed_notification() -> ServerMessage {
+    ServerMessage::Notification(ServerJsonrpcNotification::new(
+        NotificationFromServer::ServerNotification(
+            ServerNotification::ResourceListChangedNotification(ResourceListChangedNotification {
+                method: "notifications/resources/list_changed".to_owned(),
+                params: None,
+            }),
+        ),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,4 +496,83 @@ mod tests {
             "should find multiple chapters discussing tokens"
         );
     }
+
+    #[test]
+    fn search_detailed_returns_scores_matching_search_order() {
+        let hits = search_detailed("token");
+        assert!(
+            hits.len() >= 2,
+            "should find multiple chapters discussing tokens"
+        );
+        assert_eq!(hits, {
+            let mut sorted = hits.clone();
+            sorted.sort_by(|a, b| b.score.total_cmp(&a.score));
+            sorted
+        });
+        assert!(hits.iter().all(|hit| hit.uri.starts_with("file://")));
+        assert!(hits.iter().all(|hit| !hit.snippet.is_empty()));
+    }
+
+    #[test]
+    fn search_detailed_marks_matched_terms_in_snippet() {
+        let hits = search_detailed("constructor");
+        assert!(!hits.is_empty());
+        assert!(
+            hits[0].snippet.contains("**"),
+            "top hit's snippet should mark a matched term: {}",
+            hits[0].snippet
+        );
+    }
+
+    #[test]
+    fn search_detailed_empty_query_returns_empty() {
+        assert!(search_detailed("").is_empty());
+    }
+
+    #[test]
+    fn subscribe_rejects_unknown_uri() {
+        assert!(!subscribe("file://does-not-exist"));
+    }
+
+    #[test]
+    fn subscribe_then_unsubscribe_drops_update_notifications() {
+        let uri = "file:///handbook/src/program-structure.md";
+
+        assert!(subscribe(uri));
+        assert!(updated_notification(uri).is_some());
+
+        unsubscribe(uri);
+        assert!(updated_notification(uri).is_none());
+    }
+
+    #[test]
+    fn unsubscribed_uri_gets_no_update_notification() {
+        assert!(updated_notification("file:///handbook/src/access-control.md").is_none());
+    }
+
+    #[test]
+    fn search_surfaces_both_a_chapter_and_an_example_program() {
+        let results = search("PDA withdraw lamports");
+        assert!(
+            results.iter().any(|uri| uri.ends_with(".md")),
+            "should find a handbook chapter: {results:?}"
+        );
+        assert!(
+            results.iter().any(|uri| uri.ends_with(".rs")),
+            "should find an example program: {results:?}"
+        );
+    }
+
+    #[test]
+    fn get_all_covers_both_chapters_and_examples() {
+        let all = get_all();
+        assert!(all.iter().any(|r| r.mime_type.as_deref() == Some("text/markdown")));
+        assert!(all.iter().any(|r| r.mime_type.as_deref() == Some("text/x-rust")));
+    }
+
+    #[test]
+    fn get_resource_retrieves_an_example_program() {
+        let content = get_resource("file:///examples/concepts/program-structure/native/src/lib.rs");
+        assert!(content.is_some());
+    }
 }
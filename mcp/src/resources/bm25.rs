@@ -6,28 +6,58 @@ struct Posting<'a> {
     tf: u32,
 }
 
+#[derive(Debug, Clone, Copy)]
+struct FieldWeights {
+    boost: f64,
+    b_prose: f64,
+    b_code: f64,
+}
+
+// `title` and `description` are short, high-signal blurbs - a query term hitting one of them is
+// a much stronger relevance signal than the same term appearing once in a whole chapter's body,
+// so they get a large boost and the body (or any field the caller doesn't special-case) stays at
+// the old neutral weight.
+fn default_field_weights(field: &str) -> FieldWeights {
+    match field {
+        "title" => FieldWeights {
+            boost: 3.0,
+            b_prose: 0.75,
+            b_code: 0.5,
+        },
+        "description" => FieldWeights {
+            boost: 2.0,
+            b_prose: 0.75,
+            b_code: 0.5,
+        },
+        _ => FieldWeights {
+            boost: 1.0,
+            b_prose: 0.75,
+            b_code: 0.5,
+        },
+    }
+}
+
 #[derive(Debug, Default)]
 struct TermStats<'a> {
-    prose: Vec<Posting<'a>>,
-    code: Vec<Posting<'a>>,
+    // Keyed by field name ("title", "description", "body", ...), each holding the postings for
+    // that field's prose tokens and code tokens respectively.
+    prose: HashMap<&'static str, Vec<Posting<'a>>>,
+    code: HashMap<&'static str, Vec<Posting<'a>>>,
     idf: f64,
 }
 
 pub struct Index<'a> {
     terms: HashMap<String, TermStats<'a>>,
-    len_prose: HashMap<&'a str, u32>,
-    len_code: HashMap<&'a str, u32>,
-    avg_prose: f64,
-    avg_code: f64,
+    len_prose: HashMap<&'static str, HashMap<&'a str, u32>>,
+    len_code: HashMap<&'static str, HashMap<&'a str, u32>>,
+    avg_prose: HashMap<&'static str, f64>,
+    avg_code: HashMap<&'static str, f64>,
+    field_weights: HashMap<&'static str, FieldWeights>,
+    doc_ids: HashSet<&'a str>,
     k1: f64,
-    // field params
-    w_prose: f64,
-    b_prose: f64,
-    w_code: f64,
-    b_code: f64,
 }
 
-/// Mostly AI-generated implementation of Okapi BM25 ranking function for text search
+/// Mostly AI-generated implementation of a fielded (BM25F) variant of Okapi BM25 ranking.
 /// https://en.wikipedia.org/wiki/Okapi_BM25
 impl<'a> Index<'a> {
     pub fn new(k1: f64) -> Self {
@@ -35,14 +65,11 @@ impl<'a> Index<'a> {
             terms: HashMap::new(),
             len_prose: HashMap::new(),
             len_code: HashMap::new(),
-            avg_prose: 0.0,
-            avg_code: 0.0,
+            avg_prose: HashMap::new(),
+            avg_code: HashMap::new(),
+            field_weights: HashMap::new(),
+            doc_ids: HashSet::new(),
             k1,
-            // Mixed corpus default: neutral weights
-            w_prose: 1.0,
-            b_prose: 0.75,
-            w_code: 1.0,
-            b_code: 0.50,
         }
     }
 
@@ -61,13 +88,15 @@ impl<'a> Index<'a> {
             || tok.ends_with('!')
     }
 
-    // Split order: path '::' -> snake '_' -> camel (acronym-aware), then lowercase.
+    // Split order: path '::' -> snake '_' -> camel (acronym-aware), then the same light
+    // lemmatization prose tokens get, so a sub-token like "Constructors" folds to "constructor"
+    // and matches the prose query term it was split out to stand in for.
     fn split_identifier(tok: &str) -> impl Iterator<Item = String> + '_ {
         tok.split("::")
             .flat_map(|path_seg| path_seg.split('_'))
             .flat_map(|seg| Self::split_camel_acronyms(seg))
             .filter(|s| !s.is_empty())
-            .map(|s| s.to_ascii_lowercase())
+            .map(|s| Self::normalize_prose(&s))
     }
 
     fn split_camel_acronyms(seg: &str) -> Vec<String> {
@@ -171,73 +200,129 @@ impl<'a> Index<'a> {
         false
     }
 
+    /// Normalizes a single raw token the way `add_doc_fields`/`score` index and query it: the
+    /// whole token (lowercased, stemmed if prose) plus, for identifier-shaped tokens, its
+    /// camelCase/snake_case sub-tokens. Exposed so callers that need to test arbitrary text for
+    /// membership in a query's term set (e.g. snippet extraction) don't have to duplicate the
+    /// tokenizer pipeline.
+    pub fn normalize_token(tok: &str) -> Vec<String> {
+        if Self::looks_code(tok) {
+            let whole = tok.to_ascii_lowercase();
+            let mut out = Vec::new();
+            if !Self::is_noise_code_token(&whole) {
+                out.push(whole);
+            }
+            out.extend(Self::split_identifier(tok).filter(|s| !Self::is_noise_code_token(s)));
+            out
+        } else {
+            vec![Self::normalize_prose(tok)]
+        }
+    }
+
+    /// Normalizes every token in `text`, splitting it the same way `add_doc_fields` does first.
+    pub fn normalize_text(text: &str) -> Vec<String> {
+        Self::tokenize_raw(text)
+            .flat_map(Self::normalize_token)
+            .collect()
+    }
+
+    /// The set of normalized terms `score(query)` matches documents against.
+    pub fn query_terms(query: &str) -> HashSet<String> {
+        Self::normalize_text(query).into_iter().collect()
+    }
+
+    /// Indexes a single-field document, e.g. a chapter whose only indexable text is its body.
+    /// Equivalent to `add_doc_fields(doc_id, &[("body", text)])`.
     pub fn add_doc(&mut self, doc_id: &'a str, text: &str) -> &mut Self {
-        if self.len_prose.contains_key(&doc_id) {
+        self.add_doc_fields(doc_id, &[("body", text)])
+    }
+
+    /// Indexes a document made up of several named fields (e.g. `title`, `description`, `body`),
+    /// each tracked with its own per-field length statistics so BM25F can weight a hit in one
+    /// field (a title) more heavily than the same term in another (the body).
+    pub fn add_doc_fields(&mut self, doc_id: &'a str, fields: &[(&'static str, &str)]) -> &mut Self {
+        if !self.doc_ids.insert(doc_id) {
             panic!("duplicate: {doc_id}");
         }
 
-        let mut tf_prose: HashMap<String, u32> = HashMap::new();
-        let mut tf_code: HashMap<String, u32> = HashMap::new();
-        let mut lp = 0u32;
-        let mut lc = 0u32;
-
-        for raw in Self::tokenize_raw(text) {
-            if Self::looks_code(raw) {
-                // whole identifier/path/macro
-                let lower = raw.to_ascii_lowercase();
-                if !Self::is_noise_code_token(&lower) {
-                    *tf_code.entry(lower).or_insert(0) += 1;
-                    lc += 1;
-                }
-                // subwords (path -> snake -> camel acronyms)
-                for sub in Self::split_identifier(raw) {
-                    if !Self::is_noise_code_token(&sub) {
-                        *tf_code.entry(sub).or_insert(0) += 1;
+        for &(field, text) in fields {
+            self.field_weights
+                .entry(field)
+                .or_insert_with(|| default_field_weights(field));
+
+            let mut tf_prose: HashMap<String, u32> = HashMap::new();
+            let mut tf_code: HashMap<String, u32> = HashMap::new();
+            let mut lp = 0u32;
+            let mut lc = 0u32;
+
+            for raw in Self::tokenize_raw(text) {
+                if Self::looks_code(raw) {
+                    // whole identifier/path/macro
+                    let lower = raw.to_ascii_lowercase();
+                    if !Self::is_noise_code_token(&lower) {
+                        *tf_code.entry(lower).or_insert(0) += 1;
                         lc += 1;
                     }
+                    // subwords (path -> snake -> camel acronyms)
+                    for sub in Self::split_identifier(raw) {
+                        if !Self::is_noise_code_token(&sub) {
+                            *tf_code.entry(sub).or_insert(0) += 1;
+                            lc += 1;
+                        }
+                    }
+                } else {
+                    let n = Self::normalize_prose(raw);
+                    *tf_prose.entry(n).or_insert(0) += 1;
+                    lp += 1;
                 }
-            } else {
-                let n = Self::normalize_prose(raw);
-                *tf_prose.entry(n).or_insert(0) += 1;
-                lp += 1;
             }
-        }
-
-        self.len_prose.insert(doc_id, lp);
-        self.len_code.insert(doc_id, lc);
 
-        for (t, f) in tf_prose {
-            self.terms
-                .entry(t)
-                .or_default()
-                .prose
-                .push(Posting { doc: doc_id, tf: f });
-        }
-        for (t, f) in tf_code {
-            self.terms
-                .entry(t)
-                .or_default()
-                .code
-                .push(Posting { doc: doc_id, tf: f });
+            self.len_prose.entry(field).or_default().insert(doc_id, lp);
+            self.len_code.entry(field).or_default().insert(doc_id, lc);
+
+            for (t, f) in tf_prose {
+                self.terms
+                    .entry(t)
+                    .or_default()
+                    .prose
+                    .entry(field)
+                    .or_default()
+                    .push(Posting { doc: doc_id, tf: f });
+            }
+            for (t, f) in tf_code {
+                self.terms
+                    .entry(t)
+                    .or_default()
+                    .code
+                    .entry(field)
+                    .or_default()
+                    .push(Posting { doc: doc_id, tf: f });
+            }
         }
         self
     }
 
     pub fn finalize(&mut self) {
-        let n = self.len_prose.len() as f64; // total docs
+        let n = self.doc_ids.len() as f64; // total docs
 
-        let sum_p: u64 = self.len_prose.values().map(|&x| x as u64).sum();
-        let sum_c: u64 = self.len_code.values().map(|&x| x as u64).sum();
-        self.avg_prose = if n > 0.0 { sum_p as f64 / n } else { 0.0 };
-        self.avg_code = if n > 0.0 { sum_c as f64 / n } else { 0.0 };
+        for (&field, lens) in &self.len_prose {
+            let sum: u64 = lens.values().map(|&x| x as u64).sum();
+            self.avg_prose
+                .insert(field, if n > 0.0 { sum as f64 / n } else { 0.0 });
+        }
+        for (&field, lens) in &self.len_code {
+            let sum: u64 = lens.values().map(|&x| x as u64).sum();
+            self.avg_code
+                .insert(field, if n > 0.0 { sum as f64 / n } else { 0.0 });
+        }
 
         for stats in self.terms.values_mut() {
             let mut seen: HashSet<&str> = HashSet::new();
-            for p in &stats.prose {
-                seen.insert(p.doc);
+            for postings in stats.prose.values() {
+                seen.extend(postings.iter().map(|p| p.doc));
             }
-            for p in &stats.code {
-                seen.insert(p.doc);
+            for postings in stats.code.values() {
+                seen.extend(postings.iter().map(|p| p.doc));
             }
             let df = seen.len() as f64;
 
@@ -252,15 +337,7 @@ impl<'a> Index<'a> {
     pub fn score(&self, query: &str) -> Vec<(&'a str, f64)> {
         let mut seen_terms = HashSet::new();
         let mut acc: HashMap<&'a str, f64> = HashMap::new();
-        let (k1, w_p, b_p, w_c, b_c, avg_p, avg_c) = (
-            self.k1,
-            self.w_prose,
-            self.b_prose,
-            self.w_code,
-            self.b_code,
-            self.avg_prose,
-            self.avg_code,
-        );
+        let k1 = self.k1;
 
         // Classify query tokens the same way; include whole+subwords; filter noisy code tokens.
         let mut q_terms: Vec<(String, bool)> = Vec::new(); // (term, is_code)
@@ -292,22 +369,42 @@ impl<'a> Index<'a> {
             // query-time field boosts
             let (qb_prose, qb_code) = if is_code { (0.95, 1.05) } else { (1.05, 0.95) };
 
-            for p in &ts.prose {
-                let dl = *self.len_prose.get(p.doc).unwrap_or(&0) as f64;
-                let tf = p.tf as f64;
-                let norm = 1.0 - b_p + b_p * (dl / avg_p.max(1e-9));
-                let tfp = w_p * tf / norm;
-                *acc.entry(p.doc).or_insert(0.0) +=
-                    qb_prose * idf * ((tfp * (k1 + 1.0)) / (tfp + k1));
+            for (&field, postings) in &ts.prose {
+                let weights = self
+                    .field_weights
+                    .get(field)
+                    .copied()
+                    .unwrap_or_else(|| default_field_weights(field));
+                let avg = *self.avg_prose.get(field).unwrap_or(&0.0);
+                let lens = self.len_prose.get(field);
+
+                for p in postings {
+                    let dl = lens.and_then(|l| l.get(p.doc)).copied().unwrap_or(0) as f64;
+                    let tf = p.tf as f64;
+                    let norm = 1.0 - weights.b_prose + weights.b_prose * (dl / avg.max(1e-9));
+                    let tfp = weights.boost * tf / norm;
+                    *acc.entry(p.doc).or_insert(0.0) +=
+                        qb_prose * idf * ((tfp * (k1 + 1.0)) / (tfp + k1));
+                }
             }
 
-            for p in &ts.code {
-                let dl = *self.len_code.get(p.doc).unwrap_or(&0) as f64;
-                let tf = p.tf as f64;
-                let norm = 1.0 - b_c + b_c * (dl / avg_c.max(1e-9));
-                let tfc = w_c * tf / norm;
-                *acc.entry(p.doc).or_insert(0.0) +=
-                    qb_code * idf * ((tfc * (k1 + 1.0)) / (tfc + k1));
+            for (&field, postings) in &ts.code {
+                let weights = self
+                    .field_weights
+                    .get(field)
+                    .copied()
+                    .unwrap_or_else(|| default_field_weights(field));
+                let avg = *self.avg_code.get(field).unwrap_or(&0.0);
+                let lens = self.len_code.get(field);
+
+                for p in postings {
+                    let dl = lens.and_then(|l| l.get(p.doc)).copied().unwrap_or(0) as f64;
+                    let tf = p.tf as f64;
+                    let norm = 1.0 - weights.b_code + weights.b_code * (dl / avg.max(1e-9));
+                    let tfc = weights.boost * tf / norm;
+                    *acc.entry(p.doc).or_insert(0.0) +=
+                        qb_code * idf * ((tfc * (k1 + 1.0)) / (tfc + k1));
+                }
             }
         }
         let mut v: Vec<_> = acc.into_iter().collect();
@@ -452,4 +549,73 @@ mod tests {
         }
         assert_eq!(res[0].0, "d1");
     }
+
+    #[test]
+    fn title_hits_outrank_body_only_hits() {
+        let mut idx = Index::new(1.2);
+        idx.add_doc_fields(
+            "d1",
+            &[
+                ("title", "Access Control Migration"),
+                ("description", "Migrating access control patterns"),
+                ("body", "long chapter body that never repeats the query term"),
+            ],
+        );
+        idx.add_doc_fields(
+            "d2",
+            &[
+                ("title", "Unrelated Chapter"),
+                ("description", "Nothing to do with it"),
+                (
+                    "body",
+                    "this chapter body mentions migration only once in passing",
+                ),
+            ],
+        );
+        idx.finalize();
+
+        let res = idx.score("migration");
+        assert!(!res.is_empty());
+        assert_eq!(res[0].0, "d1", "a title hit should outrank a body hit");
+    }
+
+    #[test]
+    fn identifier_subword_stemming_matches_prose() {
+        // "msg_sender" splits to "msg" + "sender"; "StorageAddress" splits to "storage" +
+        // "address" - none need stemming. "NewConstructors" splits to "new" + "constructors",
+        // which must fold to "constructor" to match the singular prose query.
+        let idx = ix(vec![
+            ("d1", "the NewConstructors handle msg_sender and StorageAddress"),
+            ("d2", "totally unrelated text"),
+        ]);
+        let res = idx.score("constructor sender address");
+        assert!(!res.is_empty());
+        assert_eq!(res[0].0, "d1");
+    }
+
+    #[test]
+    fn query_terms_matches_normalize_text() {
+        let terms = Index::query_terms("StorageAddress msg_sender constructors");
+        assert!(terms.contains("storage"));
+        assert!(terms.contains("address"));
+        assert!(terms.contains("msg"));
+        assert!(terms.contains("sender"));
+        assert!(terms.contains("constructor"));
+    }
+
+    #[test]
+    fn add_doc_is_body_only() {
+        let mut fields_idx = Index::new(1.2);
+        fields_idx.add_doc_fields("d1", &[("body", "the quick brown fox")]);
+        fields_idx.finalize();
+
+        let mut single_idx = Index::new(1.2);
+        single_idx.add_doc("d1", "the quick brown fox");
+        single_idx.finalize();
+
+        assert_eq!(
+            fields_idx.score("quick")[0].1,
+            single_idx.score("quick")[0].1
+        );
+    }
 }
@@ -0,0 +1,116 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use base64::Engine;
+use rust_mcp_schema::{BlobResourceContents, ReadResourceResultContentsItem};
+
+/// Below this many bytes, a zstd frame's fixed overhead plus base64's ~4/3 blowup can't pay for itself - skip
+/// straight to the plain representation rather than bothering to compress.
+const MIN_COMPRESSIBLE_BYTES: usize = 1024;
+
+/// Whether the connected client declared it can decode the `base64+zstd` blob encoding, via the
+/// `experimental.base64zstd` key on its `InitializeRequest` capabilities. Defaults to `false`: a client that
+/// never mentions it is assumed able to handle only the plain representation.
+static CLIENT_SUPPORTS_BASE64_ZSTD: AtomicBool = AtomicBool::new(false);
+
+pub fn set_client_supports_base64_zstd(supported: bool) {
+    CLIENT_SUPPORTS_BASE64_ZSTD.store(supported, Ordering::Relaxed);
+}
+
+/// Replaces `item`'s plain text with a `base64+zstd`-encoded blob when the client opted in, the payload clears
+/// `MIN_COMPRESSIBLE_BYTES`, and the compressed+base64 output actually comes out smaller than the original -
+/// otherwise returns `item` untouched. A no-op for anything that isn't `TextResourceContents` (already a blob,
+/// or some future contents kind this pass doesn't know how to compress).
+pub fn maybe_compress(item: ReadResourceResultContentsItem) -> ReadResourceResultContentsItem {
+    let ReadResourceResultContentsItem::TextResourceContents(text_item) = item else {
+        return item;
+    };
+
+    if !CLIENT_SUPPORTS_BASE64_ZSTD.load(Ordering::Relaxed)
+        || text_item.text.len() < MIN_COMPRESSIBLE_BYTES
+    {
+        return text_item.into();
+    }
+
+    // Infallible: zstd only fails on I/O errors, and a `&[u8]` source can't produce one.
+    let compressed =
+        zstd::encode_all(text_item.text.as_bytes(), 0).expect("in-memory zstd compression cannot fail");
+    let blob = base64::engine::general_purpose::STANDARD.encode(compressed);
+
+    if blob.len() >= text_item.text.len() {
+        return text_item.into();
+    }
+
+    let mut meta = serde_json::Map::new();
+    meta.insert(
+        "encoding".to_owned(),
+        serde_json::Value::String("base64+zstd".to_owned()),
+    );
+
+    BlobResourceContents {
+        blob,
+        meta: Some(meta),
+        mime_type: text_item.mime_type,
+        uri: text_item.uri,
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_text_item(len: usize) -> ReadResourceResultContentsItem {
+        rust_mcp_schema::TextResourceContents {
+            meta: None,
+            mime_type: Some("text/markdown".to_owned()),
+            text: "a".repeat(len),
+            uri: "file:///sample.md".to_owned(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn leaves_content_untouched_when_client_has_not_opted_in() {
+        set_client_supports_base64_zstd(false);
+
+        let result = maybe_compress(sample_text_item(4096));
+        assert!(matches!(
+            result,
+            ReadResourceResultContentsItem::TextResourceContents(_)
+        ));
+    }
+
+    #[test]
+    fn leaves_small_payloads_uncompressed_even_when_opted_in() {
+        set_client_supports_base64_zstd(true);
+
+        let result = maybe_compress(sample_text_item(16));
+        assert!(matches!(
+            result,
+            ReadResourceResultContentsItem::TextResourceContents(_)
+        ));
+
+        set_client_supports_base64_zstd(false);
+    }
+
+    #[test]
+    fn compresses_large_repetitive_payloads_when_opted_in() {
+        set_client_supports_base64_zstd(true);
+
+        // Highly repetitive, so zstd+base64 is guaranteed to come out smaller than the original.
+        let result = maybe_compress(sample_text_item(64 * 1024));
+        match result {
+            ReadResourceResultContentsItem::BlobResourceContents(blob) => {
+                assert_eq!(
+                    blob.meta.as_ref().and_then(|m| m.get("encoding")),
+                    Some(&serde_json::Value::String("base64+zstd".to_owned()))
+                );
+            }
+            ReadResourceResultContentsItem::TextResourceContents(_) => {
+                panic!("expected a compressed blob for a large, repetitive payload")
+            }
+        }
+
+        set_client_supports_base64_zstd(false);
+    }
+}
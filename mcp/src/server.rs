@@ -1,15 +1,56 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::io::{self, BufRead, BufReader, BufWriter, Write};
 use std::sync::{mpsc, Arc, Condvar, Mutex};
 use std::thread;
 
 const NUM_WORKERS: usize = 4;
 
-pub struct OutputSink(mpsc::Sender<String>);
+/// A result-writer event for one `Msg`'s sequence slot: either a line it produced, or its
+/// completion marker (sent once `work_fn` returns, even if it produced zero lines).
+enum ResultEvent {
+    Line(u64, String),
+    Done(u64),
+}
+
+pub struct OutputSink {
+    seq: u64,
+    tx: mpsc::Sender<ResultEvent>,
+}
 
 impl OutputSink {
     pub fn send(&mut self, line: String) {
-        self.0.send(line.trim().to_owned()).ok();
+        self.tx.send(ResultEvent::Line(self.seq, line.trim().to_owned())).ok();
+    }
+}
+
+/// Buffers completed `Msg` slots, keyed by the sequence number `start`'s stdin-reading loop
+/// assigns in submission order, until they're contiguous from `next_expected` - so `NUM_WORKERS`
+/// threads can finish work out of order while still emitting lines in stdin order.
+#[derive(Default)]
+struct ReorderBuffer {
+    buffer: BTreeMap<u64, Vec<String>>,
+    ready: HashSet<u64>,
+    next_expected: u64,
+}
+
+impl ReorderBuffer {
+    fn push_line(&mut self, seq: u64, line: String) {
+        self.buffer.entry(seq).or_default().push(line);
+    }
+
+    /// Marks `seq`'s slot complete - `work_fn` returned for it, whether or not it produced any
+    /// lines - and drains every now-contiguous slot starting at `next_expected`, in order.
+    fn mark_done(&mut self, seq: u64) -> Vec<String> {
+        self.ready.insert(seq);
+
+        let mut out = Vec::new();
+        while self.ready.remove(&self.next_expected) {
+            if let Some(lines) = self.buffer.remove(&self.next_expected) {
+                out.extend(lines);
+            }
+            self.next_expected += 1;
+        }
+        out
     }
 }
 
@@ -102,8 +143,13 @@ where
         let work_fn = Arc::clone(&work_fn);
 
         let handle = thread::spawn(move || {
-            while let Some(line) = work_queue.recv() {
-                work_fn(line, &mut OutputSink(result_tx.clone()));
+            while let Some((seq, msg)) = work_queue.recv() {
+                let mut sink = OutputSink {
+                    seq,
+                    tx: result_tx.clone(),
+                };
+                work_fn(msg, &mut sink);
+                result_tx.send(ResultEvent::Done(seq)).ok();
             }
         });
         worker_handles.push(handle);
@@ -111,11 +157,26 @@ where
 
     drop(result_tx);
 
+    // Work runs out of order across `NUM_WORKERS` threads, but stdin order must match stdout
+    // order; each `Msg` gets a sequence number (assigned below, in stdin order) and this thread
+    // runs it through a `ReorderBuffer` rather than serializing the work itself.
     let result_handle = {
         let mut writer = BufWriter::new(io::stdout());
         thread::spawn(move || -> io::Result<()> {
-            while let Ok(result) = result_rx.recv() {
-                writeln!(writer, "{result}")?;
+            let mut reorder = ReorderBuffer::default();
+
+            while let Ok(event) = result_rx.recv() {
+                let ready_lines = match event {
+                    ResultEvent::Line(seq, line) => {
+                        reorder.push_line(seq, line);
+                        continue;
+                    }
+                    ResultEvent::Done(seq) => reorder.mark_done(seq),
+                };
+
+                for line in ready_lines {
+                    writeln!(writer, "{line}")?;
+                }
                 writer.flush()?;
             }
             Ok(())
@@ -126,10 +187,13 @@ where
     let mut reader = BufReader::new(stdin.lock());
 
     let mut line = String::new();
+    let mut next_seq = 0u64;
     while reader.read_line(&mut line)? > 0 {
         if let Some(msgs) = parse_fn(line.trim()) {
             for msg in msgs {
-                work_queue.send(msg);
+                let seq = next_seq;
+                next_seq += 1;
+                work_queue.send((seq, msg));
             }
         }
         line.clear();
@@ -249,6 +313,39 @@ mod tests {
         assert_eq!(wq.recv(), None);
     }
 
+    #[test]
+    fn reorder_buffer_flushes_in_sequence_order() {
+        let mut reorder = ReorderBuffer::default();
+
+        reorder.push_line(1, "b".to_owned());
+        assert_eq!(reorder.mark_done(1), Vec::<String>::new()); // 0 not done yet, nothing flushes
+
+        reorder.push_line(0, "a".to_owned());
+        assert_eq!(reorder.mark_done(0), vec!["a".to_owned(), "b".to_owned()]);
+    }
+
+    #[test]
+    fn reorder_buffer_handles_zero_line_slots() {
+        let mut reorder = ReorderBuffer::default();
+
+        // A notification-style message produces no lines; its slot must still advance the cursor.
+        assert_eq!(reorder.mark_done(0), Vec::<String>::new());
+        reorder.push_line(1, "only line".to_owned());
+        assert_eq!(reorder.mark_done(1), vec!["only line".to_owned()]);
+    }
+
+    #[test]
+    fn reorder_buffer_collects_multiple_lines_per_slot() {
+        let mut reorder = ReorderBuffer::default();
+
+        reorder.push_line(0, "first".to_owned());
+        reorder.push_line(0, "second".to_owned());
+        assert_eq!(
+            reorder.mark_done(0),
+            vec!["first".to_owned(), "second".to_owned()]
+        );
+    }
+
     #[test]
     fn shutdown_drains_remaining() {
         let wq = WorkQueue::new(10);
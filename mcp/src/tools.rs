@@ -1,9 +1,15 @@
-use rust_mcp_schema::{CallToolResult, ContentBlock, TextContent, Tool, ToolInputSchema};
+use rust_mcp_schema::{
+    CallToolResult, ContentBlock, TextContent, Tool, ToolInputSchema, ToolOutputSchema,
+};
 
-#[derive(Debug, PartialEq, Eq)]
+mod benchmark_instruction;
+mod benchmark_stylus_contract;
+
+#[derive(Debug, PartialEq)]
 pub struct CallResponse {
     content: Vec<String>,
     is_error: bool,
+    structured_content: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl CallResponse {
@@ -11,6 +17,7 @@ impl CallResponse {
         Self {
             content: vec![content.into()],
             is_error: false,
+            structured_content: None,
         }
     }
 
@@ -18,6 +25,21 @@ impl CallResponse {
         Self {
             content: vec![msg.into()],
             is_error: true,
+            structured_content: None,
+        }
+    }
+
+    /// Like `success`, but also attaches machine-readable `structured_content` for tools whose result
+    /// is naturally structured (a ranked list, a detection verdict) rather than prose a client would
+    /// otherwise have to re-parse.
+    fn success_structured(
+        content: Vec<String>,
+        structured_content: serde_json::Map<String, serde_json::Value>,
+    ) -> Self {
+        Self {
+            content,
+            is_error: false,
+            structured_content: Some(structured_content),
         }
     }
 }
@@ -33,7 +55,7 @@ impl From<CallResponse> for CallToolResult {
                 .collect(),
             is_error: Some(value.is_error),
             meta: None,
-            structured_content: None,
+            structured_content: value.structured_content,
         }
     }
 }
@@ -48,6 +70,7 @@ macro_rules! define_tool {
                 $param_name:ident: $param_type:literal = $param_desc:literal
             ),+ $(,)?
         },
+        output_schema: $output_schema:expr,
         handler: $handler:path
     ) => {
         pub mod $fn_name {
@@ -71,7 +94,7 @@ macro_rules! define_tool {
                     ),
                     meta: None,
                     name: stringify!($fn_name).to_owned(),
-                    output_schema: None,
+                    output_schema: $output_schema,
                     title: Some($title.to_owned()),
                 }
             }
@@ -103,6 +126,7 @@ macro_rules! define_tools {
                         $param_name:ident: $param_type:literal = $param_desc:literal
                     ),+ $(,)?
                 },
+                output_schema: $output_schema:expr,
             }
         ),+ $(,)?
     ) => {
@@ -114,6 +138,7 @@ macro_rules! define_tools {
                 params: {
                     $($param_name: $param_type = $param_desc),+
                 },
+                output_schema: $output_schema,
                 handler: $fn_name
             );
         )+
@@ -137,17 +162,34 @@ macro_rules! define_tools {
     };
 }
 
+fn detect_solana_program_kind_structured_content(
+    kind: &str,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut structured_content = serde_json::Map::new();
+    structured_content.insert(
+        "kind".to_owned(),
+        serde_json::Value::String(kind.to_owned()),
+    );
+    structured_content
+}
+
 fn detect_solana_program_kind(cargo_manifest: &str) -> CallResponse {
     if !cargo_manifest.contains("[package]") || !cargo_manifest.contains("dependencies") {
         return CallResponse::error("invalid cargo manifest file");
     }
 
     if cargo_manifest.contains("anchor-lang") {
-        return CallResponse::success("anchor");
+        return CallResponse::success_structured(
+            vec!["anchor".to_owned()],
+            detect_solana_program_kind_structured_content("anchor"),
+        );
     }
 
     if cargo_manifest.contains("solana-program") {
-        return CallResponse::success("native");
+        return CallResponse::success_structured(
+            vec!["native".to_owned()],
+            detect_solana_program_kind_structured_content("native"),
+        );
     }
 
     CallResponse::error(
@@ -155,6 +197,84 @@ fn detect_solana_program_kind(cargo_manifest: &str) -> CallResponse {
     )
 }
 
+/// Known Solana-ecosystem crates this migration report recognizes, each mapped to the closest
+/// Stylus/OpenZeppelin building block a port to Stylus would reach for. A `*`-suffixed name matches by
+/// prefix, catching the rest of a crate family (e.g. `mpl-*` for the various Metaplex program crates)
+/// without needing one entry per crate.
+const SOLANA_CRATE_MIGRATION_NOTES: &[(&str, &str)] = &[
+    (
+        "anchor-lang",
+        "stylus_sdk::prelude (#[storage]/#[entrypoint]/#[public]) - Anchor's account-validation constraints have no 1:1 Stylus equivalent and are checked imperatively in method bodies instead",
+    ),
+    (
+        "solana-program",
+        "stylus_sdk - the base Stylus SDK plays the role solana-program plays for native Solana programs",
+    ),
+    ("spl-token", "openzeppelin_stylus::token::erc20"),
+    (
+        "spl-token-2022",
+        "openzeppelin_stylus::token::erc20, extended by hand - Token-2022 extensions (transfer fees, confidential transfers, etc.) have no Stylus analogue",
+    ),
+    (
+        "spl-associated-token-account",
+        "a plain StorageMap<Address, StorageU256> balance - Stylus contracts don't need a separate token-account PDA the way SPL does",
+    ),
+    (
+        "anchor-spl",
+        "openzeppelin_stylus::token::{erc20, erc721}, depending on which anchor-spl token interfaces are used",
+    ),
+    (
+        "mpl-token-metadata",
+        "openzeppelin_stylus::token::erc721::extensions::Erc721Metadata",
+    ),
+    (
+        "mpl-*",
+        "the Metaplex program family has no direct Stylus equivalent - typically re-implemented with custom storage plus the closest ERC721/ERC1155 extension",
+    ),
+];
+
+fn solana_migration_note_for_crate(crate_name: &str) -> Option<&'static str> {
+    SOLANA_CRATE_MIGRATION_NOTES
+        .iter()
+        .find(|(name, _)| match name.strip_suffix('*') {
+            Some(prefix) => crate_name.starts_with(prefix),
+            None => *name == crate_name,
+        })
+        .map(|(_, note)| *note)
+}
+
+fn generate_solana_migration_report(cargo_manifest: &str) -> CallResponse {
+    let manifest: toml::Table = match cargo_manifest.parse() {
+        Ok(manifest) => manifest,
+        Err(err) => return CallResponse::error(format!("invalid Cargo.toml: {err}")),
+    };
+
+    let Some(dependencies) = manifest.get("dependencies").and_then(|v| v.as_table()) else {
+        return CallResponse::error("Cargo.toml has no [dependencies] table");
+    };
+
+    let mut detected: Vec<(&str, &'static str)> = dependencies
+        .keys()
+        .filter_map(|name| solana_migration_note_for_crate(name).map(|note| (name.as_str(), note)))
+        .collect();
+    detected.sort();
+
+    if detected.is_empty() {
+        return CallResponse::success(
+            "no recognized Solana ecosystem crates detected in [dependencies]",
+        );
+    }
+
+    let mut report = String::from(
+        "Detected Solana ecosystem dependencies and their closest Stylus/OpenZeppelin equivalents:\n",
+    );
+    for (name, note) in &detected {
+        report.push_str(&format!("- {name} -> {note}\n"));
+    }
+
+    CallResponse::success(report)
+}
+
 // https://doc.rust-lang.org/cargo/reference/manifest.html#the-name-field
 fn invalid_package_name(package_name: &str) -> bool {
     package_name.is_empty()
@@ -230,115 +350,2153 @@ fn main() {{
     ))
 }
 
-fn search_handbook(query: &str) -> CallResponse {
-    if query.is_empty() {
-        return CallResponse::error("query cannot be an empty string");
+/// Converts a `kebab-case`/`snake_case` package name into a `PascalCase` contract type name, e.g.
+/// `my-token` -> `MyTokenContract`. Assumes `package_name` has already passed `invalid_package_name`.
+fn contract_type_name(package_name: &str) -> String {
+    let mut name = String::new();
+    for word in package_name.split(['-', '_']) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.extend(chars);
+        }
     }
+    name.push_str("Contract");
+    name
+}
 
-    CallResponse {
-        content: crate::resources::search(query),
-        is_error: false,
-    }
+fn erc20_token_contract_lib_rs(type_name: &str) -> String {
+    format!(
+        r#"
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+extern crate alloc;
+
+use openzeppelin_stylus::token::erc20::{{Erc20, Error as Erc20Error, IErc20}};
+use stylus_sdk::{{alloy_primitives::*, prelude::*}};
+
+#[storage]
+#[entrypoint]
+pub struct {type_name} {{
+    erc20: Erc20,
+}}
+
+#[public]
+#[implements(IErc20<Error = Erc20Error>)]
+impl {type_name} {{
+    #[constructor]
+    pub fn constructor(&mut self, initial_supply: U256) -> Result<(), Erc20Error> {{
+        self.erc20._mint(self.vm().tx_origin(), initial_supply)
+    }}
+}}
+
+#[public]
+impl IErc20 for {type_name} {{
+    type Error = Erc20Error;
+
+    fn total_supply(&self) -> U256 {{
+        self.erc20.total_supply()
+    }}
+
+    fn balance_of(&self, account: Address) -> U256 {{
+        self.erc20.balance_of(account)
+    }}
+
+    fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Self::Error> {{
+        self.erc20.transfer(to, value)
+    }}
+
+    fn allowance(&self, owner: Address, spender: Address) -> U256 {{
+        self.erc20.allowance(owner, spender)
+    }}
+
+    fn approve(&mut self, spender: Address, value: U256) -> Result<bool, Self::Error> {{
+        self.erc20.approve(spender, value)
+    }}
+
+    fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Self::Error> {{
+        self.erc20.transfer_from(from, to, value)
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use motsu::prelude::*;
+
+    #[motsu::test]
+    fn test_constructor_mints_initial_supply(contract: Contract<{type_name}>, alice: Address) {{
+        let initial_supply = U256::from(1_000_000u64);
+        contract
+            .sender(alice)
+            .constructor(initial_supply)
+            .motsu_unwrap();
+
+        assert_eq!(contract.sender(alice).total_supply(), initial_supply);
+        assert_eq!(contract.sender(alice).balance_of(alice), initial_supply);
+    }}
+
+    #[motsu::test]
+    fn test_transfer(contract: Contract<{type_name}>, alice: Address, bob: Address) {{
+        let initial_supply = U256::from(1_000_000u64);
+        contract
+            .sender(alice)
+            .constructor(initial_supply)
+            .motsu_unwrap();
+
+        let amount = U256::from(100u64);
+        contract.sender(alice).transfer(bob, amount).motsu_unwrap();
+
+        assert_eq!(contract.sender(alice).balance_of(bob), amount);
+        assert_eq!(
+            contract.sender(alice).balance_of(alice),
+            initial_supply - amount
+        );
+    }}
+}}
+"#
+    )
 }
 
-define_tools! {
-    detect_solana_program_kind => {
-        description: "Detect the kind of a Solana program, either 'native' or 'anchor', from its Cargo.toml file",
-        title: "Detect Solana Program Kind",
-        params: {
-            cargo_manifest: "string" = "Solana program Cargo.toml file",
-        },
-    },
-    generate_stylus_contract_cargo_manifest => {
-        description: "Generate the Cargo.toml file for a Stylus contract",
-        title: "Generate Stylus Contract Cargo.toml",
-        params: {
-            package_name: "string" = "Stylus contract package name",
-        },
-    },
-    generate_stylus_contract_main_rs => {
-        description: "Generate the main.rs file for a Stylus contract",
-        title: "Generate Stylus Contract main.rs",
-        params: {
-            package_name: "string" = "Stylus contract package name",
-        },
-    },
-    search_handbook => {
-        description: "Search the StylusPort::Solana Handbook, receiving a list of resource URIs in descending order of relevance score",
-        title: "Search StylusPort::Solana Handbook",
-        params: {
-            query: "string" = "Search query",
-        },
-    },
+fn erc721_token_contract_lib_rs(type_name: &str) -> String {
+    format!(
+        r#"
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+extern crate alloc;
+
+use alloc::string::String;
+use openzeppelin_stylus::{{
+    token::erc721::{{
+        self,
+        extensions::{{Erc721Metadata, IErc721Metadata}},
+        Erc721, IErc721,
+    }},
+    utils::introspection::erc165::IErc165,
+}};
+use stylus_sdk::{{
+    abi::Bytes,
+    alloy_primitives::{{aliases::B32, Address, U256}},
+    prelude::*,
+    storage::*,
+}};
+
+#[storage]
+#[entrypoint]
+pub struct {type_name} {{
+    erc721: Erc721,
+    metadata: Erc721Metadata,
+    next_token_id: StorageU256,
+}}
+
+#[public]
+#[implements(IErc721<Error = erc721::Error>, IErc721Metadata<Error = erc721::Error>, IErc165)]
+impl {type_name} {{
+    #[constructor]
+    pub fn constructor(&mut self, name: String, symbol: String) -> Result<(), erc721::Error> {{
+        self.metadata.constructor(name, symbol);
+        self.next_token_id.set(U256::ONE);
+
+        Ok(())
+    }}
+
+    pub fn mint(&mut self, to: Address) -> Result<U256, erc721::Error> {{
+        let token_id = self.next_token_id.get();
+
+        self.erc721._mint(to, token_id)?;
+        self.next_token_id.set(token_id + U256::ONE);
+
+        Ok(token_id)
+    }}
+
+    pub fn total_minted(&self) -> U256 {{
+        self.next_token_id.get() - U256::ONE
+    }}
+}}
+
+#[public]
+impl IErc721 for {type_name} {{
+    type Error = erc721::Error;
+
+    fn balance_of(&self, owner: Address) -> Result<U256, Self::Error> {{
+        self.erc721.balance_of(owner)
+    }}
+
+    fn owner_of(&self, token_id: U256) -> Result<Address, Self::Error> {{
+        self.erc721.owner_of(token_id)
+    }}
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Self::Error> {{
+        self.erc721.safe_transfer_from(from, to, token_id)
+    }}
+
+    fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), Self::Error> {{
+        self.erc721
+            .safe_transfer_from_with_data(from, to, token_id, data)
+    }}
+
+    fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Self::Error> {{
+        self.erc721.transfer_from(from, to, token_id)
+    }}
+
+    fn approve(&mut self, to: Address, token_id: U256) -> Result<(), Self::Error> {{
+        self.erc721.approve(to, token_id)
+    }}
+
+    fn set_approval_for_all(&mut self, to: Address, approved: bool) -> Result<(), Self::Error> {{
+        self.erc721.set_approval_for_all(to, approved)
+    }}
+
+    fn get_approved(&self, token_id: U256) -> Result<Address, Self::Error> {{
+        self.erc721.get_approved(token_id)
+    }}
+
+    fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool {{
+        self.erc721.is_approved_for_all(owner, operator)
+    }}
+}}
+
+#[public]
+impl IErc721Metadata for {type_name} {{
+    type Error = erc721::Error;
+
+    fn name(&self) -> String {{
+        self.metadata.name()
+    }}
+
+    fn symbol(&self) -> String {{
+        self.metadata.symbol()
+    }}
+
+    /// unused
+    fn token_uri(&self, _token_id: U256) -> Result<String, Self::Error> {{
+        Ok(String::new())
+    }}
+}}
+
+#[public]
+impl IErc165 for {type_name} {{
+    fn supports_interface(&self, interface_id: B32) -> bool {{
+        self.erc721.supports_interface(interface_id)
+            || <Self as IErc721Metadata>::interface_id() == interface_id
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use motsu::prelude::*;
+
+    #[motsu::test]
+    fn test_mint_and_transfer(contract: Contract<{type_name}>, alice: Address, bob: Address) {{
+        contract
+            .sender(alice)
+            .constructor("Example".into(), "EX".into())
+            .motsu_unwrap();
+
+        let token_id = contract.sender(alice).mint(alice).motsu_unwrap();
+
+        assert_eq!(
+            contract.sender(alice).owner_of(token_id).motsu_unwrap(),
+            alice
+        );
+        assert_eq!(
+            contract.sender(alice).balance_of(alice).motsu_unwrap(),
+            U256::from(1)
+        );
+
+        contract
+            .sender(alice)
+            .transfer_from(alice, bob, token_id)
+            .motsu_unwrap();
+
+        assert_eq!(
+            contract.sender(alice).owner_of(token_id).motsu_unwrap(),
+            bob
+        );
+    }}
+}}
+"#
+    )
 }
 
+fn erc1155_token_contract_lib_rs(type_name: &str) -> String {
+    format!(
+        r#"
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use openzeppelin_stylus::token::erc1155::{{self, Erc1155, IErc1155}};
+use stylus_sdk::{{abi::Bytes, alloy_primitives::*, prelude::*}};
+
+#[storage]
+#[entrypoint]
+pub struct {type_name} {{
+    erc1155: Erc1155,
+}}
+
+#[public]
+#[implements(IErc1155<Error = erc1155::Error>)]
+impl {type_name} {{
+    #[constructor]
+    pub fn constructor(&mut self) -> Result<(), erc1155::Error> {{
+        Ok(())
+    }}
+
+    pub fn mint(&mut self, to: Address, id: U256, value: U256) -> Result<(), erc1155::Error> {{
+        self.erc1155._mint(to, id, value, Vec::new().into())
+    }}
+
+    pub fn mint_batch(
+        &mut self,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+    ) -> Result<(), erc1155::Error> {{
+        self.erc1155._mint_batch(to, ids, values, Vec::new().into())
+    }}
+}}
+
+#[public]
+impl IErc1155 for {type_name} {{
+    type Error = erc1155::Error;
+
+    fn balance_of(&self, account: Address, id: U256) -> U256 {{
+        self.erc1155.balance_of(account, id)
+    }}
+
+    fn balance_of_batch(
+        &self,
+        accounts: Vec<Address>,
+        ids: Vec<U256>,
+    ) -> Result<Vec<U256>, Self::Error> {{
+        self.erc1155.balance_of_batch(accounts, ids)
+    }}
+
+    fn set_approval_for_all(&mut self, operator: Address, approved: bool) -> Result<(), Self::Error> {{
+        self.erc1155.set_approval_for_all(operator, approved)
+    }}
+
+    fn is_approved_for_all(&self, account: Address, operator: Address) -> bool {{
+        self.erc1155.is_approved_for_all(account, operator)
+    }}
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    ) -> Result<(), Self::Error> {{
+        self.erc1155.safe_transfer_from(from, to, id, value, data)
+    }}
+
+    fn safe_batch_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) -> Result<(), Self::Error> {{
+        self.erc1155
+            .safe_batch_transfer_from(from, to, ids, values, data)
+    }}
+}}
+
 #[cfg(test)]
-mod tests {
+mod tests {{
     use super::*;
+    use motsu::prelude::*;
 
-    #[test]
-    fn test_invalid_package_name_valid_names() {
-        assert!(!invalid_package_name("my_package"));
-        assert!(!invalid_package_name("my-package"));
-        assert!(!invalid_package_name("package123"));
-        assert!(!invalid_package_name("a"));
-        assert!(!invalid_package_name("Package_Name-123"));
-        assert!(!invalid_package_name("_underscore"));
-        assert!(!invalid_package_name("-dash"));
+    #[motsu::test]
+    fn test_mint_and_balance(contract: Contract<{type_name}>, alice: Address) {{
+        contract.sender(alice).constructor().motsu_unwrap();
+
+        let id = U256::from(1u64);
+        let value = U256::from(10u64);
+        contract.sender(alice).mint(alice, id, value).motsu_unwrap();
+
+        assert_eq!(contract.sender(alice).balance_of(alice, id), value);
+    }}
+}}
+"#
+    )
+}
+
+static INVALID_TOKEN_STANDARD_MSG: &str =
+    "invalid token_standard - must be one of: erc20, erc721, erc1155";
+
+fn generate_stylus_token_contract(package_name: &str, token_standard: &str) -> CallResponse {
+    if invalid_package_name(package_name) {
+        return CallResponse::error(INVALID_PACKAGE_NAME_MSG);
     }
 
-    #[test]
-    fn test_invalid_package_name_empty_name() {
-        assert!(invalid_package_name(""));
+    let type_name = contract_type_name(package_name);
+
+    match token_standard {
+        "erc20" => CallResponse::success(erc20_token_contract_lib_rs(&type_name)),
+        "erc721" => CallResponse::success(erc721_token_contract_lib_rs(&type_name)),
+        "erc1155" => CallResponse::success(erc1155_token_contract_lib_rs(&type_name)),
+        _ => CallResponse::error(INVALID_TOKEN_STANDARD_MSG),
     }
+}
 
-    #[test]
-    fn test_invalid_package_name_length_boundary() {
-        let valid_64 = "a".repeat(64);
-        assert!(!invalid_package_name(&valid_64));
-        let invalid_65 = "a".repeat(65);
-        assert!(invalid_package_name(&invalid_65));
-        let invalid_long = "a".repeat(100);
-        assert!(invalid_package_name(&invalid_long));
+/// Converts an Anchor instruction/field name (`camelCase`, as modern Anchor IDLs emit it) into
+/// `snake_case`, matching Rust/Stylus naming conventions.
+fn anchor_name_to_snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i != 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
     }
+    out
+}
 
-    #[test]
-    fn test_invalid_package_name_invalid_characters() {
-        assert!(invalid_package_name("my package")); // space
-        assert!(invalid_package_name("my.package")); // dot
-        assert!(invalid_package_name("my@package")); // @
-        assert!(invalid_package_name("my/package")); // slash
-        assert!(invalid_package_name("my\\package")); // backslash
-        assert!(invalid_package_name("my!package")); // exclamation
-        assert!(invalid_package_name("my#package")); // hash
-        assert!(invalid_package_name("my$package")); // dollar
-        assert!(invalid_package_name("my%package")); // percent
-        assert!(invalid_package_name("my_package\n")); // newline
-                                                       // assert!(invalid_package_name("mypackage"\n[dependencies]\nmalicious-crate = \"1.0\"")) // newlines
+/// Converts an Anchor error name into `PascalCase`, for use as a `sol!` error/`ContractError` variant
+/// name. Anchor error names are conventionally already `PascalCase`; this just guards against a
+/// lowercase-leading name slipping through.
+fn anchor_name_to_pascal_case(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
     }
+}
 
-    #[test]
-    fn test_invalid_package_name_unicode() {
-        assert!(invalid_package_name("my_packagÃ©")); // accented char
-        assert!(invalid_package_name("my_packageâ„¢")); // symbol
-        assert!(invalid_package_name("my_ðŸ“¦")); // emoji
+/// Maps an Anchor IDL type (a JSON string like `"u64"`/`"publicKey"`, or a JSON object like
+/// `{"vec": "u64"}`) to the Stylus/alloy Rust type an instruction argument of that type should use.
+/// Anchor types this generator doesn't recognize (`defined` references to the program's own types,
+/// chiefly) fall back to raw `Bytes` with an inline comment, rather than guessing at a shape - callers
+/// translate those by hand.
+fn map_anchor_type_to_rust(anchor_type: &serde_json::Value) -> String {
+    match anchor_type {
+        serde_json::Value::String(name) => match name.as_str() {
+            "u8" | "u16" | "u32" | "u64" | "u128" => "U256".to_owned(),
+            "i8" | "i16" | "i32" | "i64" | "i128" => "I256".to_owned(),
+            "bool" => "bool".to_owned(),
+            "string" => "String".to_owned(),
+            "publicKey" | "pubkey" => "Address".to_owned(),
+            "bytes" => "Bytes".to_owned(),
+            other => format!("Bytes /* unmapped anchor type \"{other}\" */"),
+        },
+        serde_json::Value::Object(fields) => {
+            if let Some(inner) = fields.get("vec") {
+                format!("Vec<{}>", map_anchor_type_to_rust(inner))
+            } else if let Some(inner) = fields.get("option") {
+                format!("Option<{}>", map_anchor_type_to_rust(inner))
+            } else if let Some([inner, len]) = fields.get("array").and_then(|v| v.as_array()).map(Vec::as_slice)
+            {
+                format!(
+                    "[{}; {}]",
+                    map_anchor_type_to_rust(inner),
+                    len.as_u64().unwrap_or(0)
+                )
+            } else {
+                "Bytes /* unmapped anchor \"defined\" type */".to_owned()
+            }
+        }
+        _ => "Bytes /* unmapped anchor type */".to_owned(),
     }
+}
 
-    #[test]
-    fn test_generate_stylus_contract_main_rs_rejects_code_injection() {
-        assert_eq!(
-            generate_stylus_contract_main_rs("mypackage\n malicious_code();\n"),
-            CallResponse::error(INVALID_PACKAGE_NAME_MSG)
-        )
+/// Maps an Anchor IDL type to the `stylus_sdk::storage` type a struct field of that type should use.
+/// Anything not recognized as a plain scalar falls back to `StorageBytes`, the same way
+/// `map_anchor_type_to_rust` falls back to `Bytes` for argument types.
+fn map_anchor_type_to_storage(anchor_type: &serde_json::Value) -> String {
+    match anchor_type.as_str() {
+        Some("u8" | "u16" | "u32" | "u64" | "u128") => "StorageU256".to_owned(),
+        Some("i8" | "i16" | "i32" | "i64" | "i128") => "StorageI256".to_owned(),
+        Some("bool") => "StorageBool".to_owned(),
+        Some("string") => "StorageString".to_owned(),
+        Some("publicKey" | "pubkey") => "StorageAddress".to_owned(),
+        _ => "StorageBytes".to_owned(),
     }
+}
 
-    #[test]
-    fn test_generate_stylus_contract_cargo_manifest_rejects_malicious_dependency_injection() {
-        assert_eq!(
-            generate_stylus_contract_cargo_manifest(
-                "mypackage\n[dependencies]\nmalicious-crate = \"1.0\""
-            ),
-            CallResponse::error(INVALID_PACKAGE_NAME_MSG)
-        )
+fn generate_stylus_contract_skeleton_from_anchor_idl(
+    package_name: &str,
+    idl_json: &str,
+) -> CallResponse {
+    if invalid_package_name(package_name) {
+        return CallResponse::error(INVALID_PACKAGE_NAME_MSG);
+    }
+
+    let idl: serde_json::Value = match serde_json::from_str(idl_json) {
+        Ok(idl) => idl,
+        Err(err) => return CallResponse::error(format!("invalid IDL JSON: {err}")),
+    };
+
+    let Some(instructions) = idl.get("instructions").and_then(|v| v.as_array()) else {
+        return CallResponse::error("IDL is missing an \"instructions\" array - not a valid Anchor IDL");
+    };
+
+    let type_name = contract_type_name(package_name);
+
+    let errors = idl
+        .get("errors")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut sol_errors = String::new();
+    let mut contract_error_variants = String::new();
+    for error in &errors {
+        let Some(name) = error.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let variant = anchor_name_to_pascal_case(name);
+        let msg = error.get("msg").and_then(|v| v.as_str()).unwrap_or("");
+
+        sol_errors.push_str(&format!(
+            "    /// {msg}\n    #[derive(Debug)]\n    error {variant}();\n"
+        ));
+        contract_error_variants.push_str(&format!("    {variant}({variant}),\n"));
+    }
+
+    let mut storage_fields = String::new();
+    if let Some(accounts) = idl.get("accounts").and_then(|v| v.as_array()) {
+        for account in accounts {
+            let Some(account_name) = account.get("name").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let account_snake = anchor_name_to_snake_case(account_name);
+
+            let fields = account
+                .get("type")
+                .and_then(|v| v.get("fields"))
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+
+            for field in &fields {
+                let (Some(field_name), Some(field_type)) = (
+                    field.get("name").and_then(|v| v.as_str()),
+                    field.get("type"),
+                ) else {
+                    continue;
+                };
+                let field_snake = anchor_name_to_snake_case(field_name);
+                let storage_type = map_anchor_type_to_storage(field_type);
+
+                storage_fields.push_str(&format!(
+                    "    /// from the Anchor \"{account_name}\" account, keyed by its owning address\n    {account_snake}_{field_snake}: StorageMap<Address, {storage_type}>,\n"
+                ));
+            }
+        }
+    }
+
+    let mut methods = String::new();
+    for instruction in instructions {
+        let Some(name) = instruction.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let snake_name = anchor_name_to_snake_case(name);
+
+        let args = instruction
+            .get("args")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let mut params = String::new();
+        for arg in &args {
+            let (Some(arg_name), Some(arg_type)) =
+                (arg.get("name").and_then(|v| v.as_str()), arg.get("type"))
+            else {
+                continue;
+            };
+            params.push_str(&format!(
+                ", {}: {}",
+                anchor_name_to_snake_case(arg_name),
+                map_anchor_type_to_rust(arg_type)
+            ));
+        }
+
+        methods.push_str(&format!(
+            "\n    /// Generated from the Anchor instruction \"{name}\" - account constraints and CPI calls\n    /// are not translated; fill in the body.\n    pub fn {snake_name}(&mut self{params}) -> Result<(), ContractError> {{\n        todo!(\"translate the \\\"{name}\\\" instruction body\")\n    }}\n"
+        ));
+    }
+
+    CallResponse::success(format!(
+        r#"
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+extern crate alloc;
+
+// Generated from an Anchor IDL's instruction/account surface. Method bodies are `todo!` stubs -
+// Anchor's account-validation constraints and any CPI calls an instruction made have no IDL
+// representation to translate from, so only the entrypoint signatures and per-account storage layout
+// are filled in automatically; the rest is deliberately left for manual translation rather than guessed
+// at. No test module is generated for the same reason - there's nothing here yet with defined behavior
+// to test.
+use alloc::{{string::String, vec::Vec}};
+use stylus_sdk::{{abi::Bytes, alloy_primitives::*, alloy_sol_types::sol, prelude::*, storage::*}};
+
+sol! {{
+{sol_errors}}}
+
+#[derive(SolidityError, Debug)]
+pub enum ContractError {{
+{contract_error_variants}}}
+
+#[storage]
+#[entrypoint]
+pub struct {type_name} {{
+{storage_fields}}}
+
+#[public]
+impl {type_name} {{
+    #[constructor]
+    pub fn constructor(&mut self) -> Result<(), ContractError> {{
+        Ok(())
+    }}
+{methods}}}
+"#
+    ))
+}
+
+/// Maps an Anchor IDL type to the Borsh-derived Rust type a ported native program's client SDK
+/// should use - the same source type `map_anchor_type_to_rust` reads, but targeting plain
+/// `borsh`/`solana-program` types instead of Stylus/alloy ones. Anchor `defined` references (the
+/// program's own types) fall back to `Vec<u8>` with an inline comment, same rationale as
+/// `map_anchor_type_to_rust`'s fallback: there's no IDL-only way to know the referenced type's shape.
+fn map_anchor_type_to_native_rust(anchor_type: &serde_json::Value) -> String {
+    match anchor_type {
+        serde_json::Value::String(name) => match name.as_str() {
+            "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128" | "bool" => {
+                name.to_owned()
+            }
+            "string" => "String".to_owned(),
+            "publicKey" | "pubkey" => "Pubkey".to_owned(),
+            "bytes" => "Vec<u8>".to_owned(),
+            other => format!("Vec<u8> /* unmapped anchor type \"{other}\" */"),
+        },
+        serde_json::Value::Object(fields) => {
+            if let Some(inner) = fields.get("vec") {
+                format!("Vec<{}>", map_anchor_type_to_native_rust(inner))
+            } else if let Some(inner) = fields.get("option") {
+                format!("Option<{}>", map_anchor_type_to_native_rust(inner))
+            } else if let Some([inner, len]) = fields.get("array").and_then(|v| v.as_array()).map(Vec::as_slice)
+            {
+                format!(
+                    "[{}; {}]",
+                    map_anchor_type_to_native_rust(inner),
+                    len.as_u64().unwrap_or(0)
+                )
+            } else {
+                "Vec<u8> /* unmapped anchor \"defined\" type */".to_owned()
+            }
+        }
+        _ => "Vec<u8> /* unmapped anchor type */".to_owned(),
+    }
+}
+
+/// True if an Anchor IDL instruction account entry is writable, under either the legacy
+/// (`isMut`) or modern (`writable`) IDL account-list key.
+fn anchor_account_is_writable(account: &serde_json::Value) -> bool {
+    account
+        .get("isMut")
+        .or_else(|| account.get("writable"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// True if an Anchor IDL instruction account entry is a signer, under either the legacy
+/// (`isSigner`) or modern (`signer`) IDL account-list key.
+fn anchor_account_is_signer(account: &serde_json::Value) -> bool {
+    account
+        .get("isSigner")
+        .or_else(|| account.get("signer"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+fn generate_solana_native_client_sdk(crate_name: &str, idl_json: &str) -> CallResponse {
+    if invalid_package_name(crate_name) {
+        return CallResponse::error(INVALID_PACKAGE_NAME_MSG);
+    }
+
+    let idl: serde_json::Value = match serde_json::from_str(idl_json) {
+        Ok(idl) => idl,
+        Err(err) => return CallResponse::error(format!("invalid IDL JSON: {err}")),
+    };
+
+    let Some(instructions) = idl.get("instructions").and_then(|v| v.as_array()) else {
+        return CallResponse::error("IDL is missing an \"instructions\" array - not a valid Anchor IDL");
+    };
+
+    let mut accounts_module = String::new();
+    if let Some(accounts) = idl.get("accounts").and_then(|v| v.as_array()) {
+        for account in accounts {
+            let Some(account_name) = account.get("name").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let struct_name = anchor_name_to_pascal_case(account_name);
+
+            let fields = account
+                .get("type")
+                .and_then(|v| v.get("fields"))
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+
+            let mut struct_fields = String::new();
+            for field in &fields {
+                let (Some(field_name), Some(field_type)) = (
+                    field.get("name").and_then(|v| v.as_str()),
+                    field.get("type"),
+                ) else {
+                    continue;
+                };
+                struct_fields.push_str(&format!(
+                    "    pub {}: {},\n",
+                    anchor_name_to_snake_case(field_name),
+                    map_anchor_type_to_native_rust(field_type)
+                ));
+            }
+
+            accounts_module.push_str(&format!(
+                "\n/// Mirrors the on-chain \"{account_name}\" account's layout.\n#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]\npub struct {struct_name} {{\n{struct_fields}}}\n\nimpl {struct_name} {{\n    pub fn try_from_account_data(data: &[u8]) -> Result<Self, std::io::Error> {{\n        Self::try_from_slice(data)\n    }}\n}}\n"
+            ));
+        }
+    }
+
+    let mut types_module = String::new();
+    if let Some(defined_types) = idl.get("types").and_then(|v| v.as_array()) {
+        for defined_type in defined_types {
+            let Some(type_name) = defined_type.get("name").and_then(|v| v.as_str()) else {
+                continue;
+            };
+
+            let fields = defined_type
+                .get("type")
+                .and_then(|v| v.get("fields"))
+                .and_then(|v| v.as_array())
+                .cloned();
+
+            // Anchor "defined" types that aren't a plain struct (enums, tuple types) have no
+            // field list to translate - skip rather than guess at a shape.
+            let Some(fields) = fields else {
+                continue;
+            };
+
+            let struct_name = anchor_name_to_pascal_case(type_name);
+            let mut struct_fields = String::new();
+            for field in &fields {
+                let (Some(field_name), Some(field_type)) = (
+                    field.get("name").and_then(|v| v.as_str()),
+                    field.get("type"),
+                ) else {
+                    continue;
+                };
+                struct_fields.push_str(&format!(
+                    "    pub {}: {},\n",
+                    anchor_name_to_snake_case(field_name),
+                    map_anchor_type_to_native_rust(field_type)
+                ));
+            }
+
+            types_module.push_str(&format!(
+                "\n#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]\npub struct {struct_name} {{\n{struct_fields}}}\n"
+            ));
+        }
+    }
+
+    let mut error_variants = String::new();
+    for (i, error) in idl
+        .get("errors")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+    {
+        let Some(name) = error.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let variant = anchor_name_to_pascal_case(name);
+        let msg = error.get("msg").and_then(|v| v.as_str()).unwrap_or("");
+        let code = error
+            .get("code")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(i as u64);
+
+        error_variants.push_str(&format!("    /// {msg}\n    {variant} = {code},\n"));
+    }
+
+    let mut instruction_variants = String::new();
+    let mut instruction_builders = String::new();
+    for instruction in instructions {
+        let Some(name) = instruction.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let snake_name = anchor_name_to_snake_case(name);
+        let pascal_name = anchor_name_to_pascal_case(name);
+
+        let args = instruction
+            .get("args")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let mut variant_fields = String::new();
+        let mut fn_args = String::new();
+        let mut struct_args = String::new();
+        for arg in &args {
+            let (Some(arg_name), Some(arg_type)) =
+                (arg.get("name").and_then(|v| v.as_str()), arg.get("type"))
+            else {
+                continue;
+            };
+            let arg_snake = anchor_name_to_snake_case(arg_name);
+            let rust_type = map_anchor_type_to_native_rust(arg_type);
+            variant_fields.push_str(&format!("        {arg_snake}: {rust_type},\n"));
+            fn_args.push_str(&format!(", {arg_snake}: {rust_type}"));
+            struct_args.push_str(&format!("            {arg_snake},\n"));
+        }
+
+        instruction_variants.push_str(&format!(
+            "    {pascal_name} {{\n{variant_fields}    }},\n"
+        ));
+
+        let accounts = instruction
+            .get("accounts")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let accounts_struct_name = format!("{pascal_name}Accounts");
+        let mut account_fields = String::new();
+        let mut account_metas = String::new();
+        for account in &accounts {
+            let Some(account_name) = account.get("name").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let account_snake = anchor_name_to_snake_case(account_name);
+            account_fields.push_str(&format!("    pub {account_snake}: Pubkey,\n"));
+
+            let meta_ctor = if anchor_account_is_writable(account) {
+                "AccountMeta::new"
+            } else {
+                "AccountMeta::new_readonly"
+            };
+            let is_signer = anchor_account_is_signer(account);
+            account_metas.push_str(&format!(
+                "            {meta_ctor}(self.{account_snake}, {is_signer}),\n"
+            ));
+        }
+
+        instruction_builders.push_str(&format!(
+            "\n/// Account inputs for the `{name}` instruction.\npub struct {accounts_struct_name} {{\n{account_fields}}}\n\nimpl {accounts_struct_name} {{\n    fn metas(&self) -> Vec<AccountMeta> {{\n        vec![\n{account_metas}        ]\n    }}\n}}\n\n/// Builds the `{name}` instruction.\npub fn {snake_name}(program_id: &Pubkey, accounts: {accounts_struct_name}{fn_args}) -> SolanaInstruction {{\n    SolanaInstruction {{\n        program_id: *program_id,\n        accounts: accounts.metas(),\n        data: borsh::to_vec(&Instruction::{pascal_name} {{\n{struct_args}        }})\n            .expect(\"instruction args should always serialize\"),\n    }}\n}}\n"
+        ));
+    }
+
+    CallResponse::success(format!(
+        r#"
+// Generated from an Anchor IDL's account/instruction/error surface, for a program ported to
+// this repo's native (non-Anchor) layout. The `accounts`/`instructions`/`types`/`errors` module
+// split mirrors the Metaplex SDK crates (e.g. `mpl-token-metadata`) this repo's own examples
+// already depend on, so a caller already familiar with that crate family finds this client SDK's
+// shape unsurprising. No test module is generated - there's no deployed program here yet to
+// exercise, and the harness in `examples/dev/mollusk-privilege-harness` is what exercises the
+// ported program itself once it exists.
+use borsh::{{BorshDeserialize, BorshSerialize}};
+use solana_program::{{
+    instruction::{{AccountMeta, Instruction as SolanaInstruction}},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+}};
+
+pub mod accounts {{
+    use super::*;
+{accounts_module}}}
+
+pub mod types {{
+    use super::*;
+{types_module}}}
+
+pub mod errors {{
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[repr(u32)]
+    pub enum ProgramErrorCode {{
+{error_variants}    }}
+
+    impl From<ProgramErrorCode> for ProgramError {{
+        fn from(error: ProgramErrorCode) -> Self {{
+            ProgramError::Custom(error as u32)
+        }}
+    }}
+}}
+
+pub mod instructions {{
+    use super::*;
+
+    #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+    pub enum Instruction {{
+{instruction_variants}    }}
+{instruction_builders}}}
+"#
+    ))
+}
+
+fn generate_solana_native_program_layout(package_name: &str, idl_json: &str) -> CallResponse {
+    if invalid_package_name(package_name) {
+        return CallResponse::error(INVALID_PACKAGE_NAME_MSG);
+    }
+
+    let idl: serde_json::Value = match serde_json::from_str(idl_json) {
+        Ok(idl) => idl,
+        Err(err) => return CallResponse::error(format!("invalid IDL JSON: {err}")),
+    };
+
+    let Some(instructions) = idl.get("instructions").and_then(|v| v.as_array()) else {
+        return CallResponse::error("IDL is missing an \"instructions\" array - not a valid Anchor IDL");
+    };
+
+    let mut instruction_variants = String::new();
+    let mut dispatch_arms = String::new();
+    let mut process_fns = String::new();
+    for instruction in instructions {
+        let Some(name) = instruction.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let snake_name = anchor_name_to_snake_case(name);
+        let pascal_name = anchor_name_to_pascal_case(name);
+
+        let args = instruction
+            .get("args")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let mut variant_fields = String::new();
+        let mut pattern_fields = String::new();
+        let mut call_args = String::new();
+        let mut fn_params = String::new();
+        for arg in &args {
+            let (Some(arg_name), Some(arg_type)) =
+                (arg.get("name").and_then(|v| v.as_str()), arg.get("type"))
+            else {
+                continue;
+            };
+            let arg_snake = anchor_name_to_snake_case(arg_name);
+            let rust_type = map_anchor_type_to_native_rust(arg_type);
+            variant_fields.push_str(&format!("        {arg_snake}: {rust_type},\n"));
+            pattern_fields.push_str(&format!("{arg_snake}, "));
+            call_args.push_str(&format!(", {arg_snake}"));
+            fn_params.push_str(&format!(", {arg_snake}: {rust_type}"));
+        }
+
+        instruction_variants.push_str(&format!(
+            "    {pascal_name} {{\n{variant_fields}    }},\n"
+        ));
+
+        dispatch_arms.push_str(&format!(
+            "        Instruction::{pascal_name} {{ {pattern_fields}}} => {{\n            msg!(\"Instruction: {pascal_name}\");\n            process_{snake_name}(program_id, accounts{call_args})\n        }}\n"
+        ));
+
+        process_fns.push_str(&format!(
+            "\n/// Generated from the Anchor instruction \"{name}\" - account constraints and any CPI\n/// calls the original instruction made have no IDL representation to translate from, so only\n/// the signature is filled in; the rest is deliberately left for manual translation.\nfn process_{snake_name}(\n    _program_id: &Pubkey,\n    _accounts: &[AccountInfo]{fn_params},\n) -> ProgramResult {{\n    todo!(\"translate the \\\"{name}\\\" instruction body\")\n}}\n"
+        ));
+    }
+
+    let mut state_structs = String::new();
+    if let Some(accounts) = idl.get("accounts").and_then(|v| v.as_array()) {
+        for account in accounts {
+            let Some(account_name) = account.get("name").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let struct_name = anchor_name_to_pascal_case(account_name);
+
+            let fields = account
+                .get("type")
+                .and_then(|v| v.get("fields"))
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+
+            let mut struct_fields = String::new();
+            for field in &fields {
+                let (Some(field_name), Some(field_type)) = (
+                    field.get("name").and_then(|v| v.as_str()),
+                    field.get("type"),
+                ) else {
+                    continue;
+                };
+                struct_fields.push_str(&format!(
+                    "    pub {}: {},\n",
+                    anchor_name_to_snake_case(field_name),
+                    map_anchor_type_to_native_rust(field_type)
+                ));
+            }
+
+            state_structs.push_str(&format!(
+                "\n#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]\npub struct {struct_name} {{\n{struct_fields}}}\n"
+            ));
+        }
+    }
+
+    let mut error_variants = String::new();
+    for error in idl
+        .get("errors")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default()
+    {
+        let Some(name) = error.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let variant = anchor_name_to_pascal_case(name);
+        let msg = error.get("msg").and_then(|v| v.as_str()).unwrap_or("");
+
+        error_variants.push_str(&format!("    /// {msg}\n    {variant},\n"));
+    }
+
+    let entrypoint_rs = r#"#![allow(unexpected_cfgs)]
+
+use solana_program::entrypoint;
+
+use crate::processor::process_instruction;
+
+entrypoint!(process_instruction);
+"#
+    .to_owned();
+
+    let instruction_rs = format!(
+        r#"use borsh::{{BorshDeserialize, BorshSerialize}};
+use solana_program::{{program_error::ProgramError, pubkey::Pubkey}};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum Instruction {{
+{instruction_variants}}}
+
+impl Instruction {{
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {{
+        Self::try_from_slice(data).map_err(|_| ProgramError::InvalidInstructionData)
+    }}
+}}
+"#
+    );
+
+    let state_rs = format!(
+        r#"use borsh::{{BorshDeserialize, BorshSerialize}};
+use solana_program::pubkey::Pubkey;
+{state_structs}"#
+    );
+
+    let error_rs = format!(
+        r#"use solana_program::program_error::ProgramError;
+
+#[derive(Debug, Clone, Copy)]
+#[repr(u32)]
+pub enum ErrorCode {{
+{error_variants}}}
+
+impl From<ErrorCode> for ProgramError {{
+    fn from(value: ErrorCode) -> Self {{
+        Self::Custom(value as _)
+    }}
+}}
+"#
+    );
+
+    let processor_rs = format!(
+        r#"use solana_program::{{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey}};
+
+use crate::instruction::Instruction;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {{
+    let instruction = Instruction::unpack(instruction_data)?;
+
+    match instruction {{
+{dispatch_arms}    }}
+}}
+{process_fns}"#
+    );
+
+    let mut structured_content = serde_json::Map::new();
+    structured_content.insert("entrypoint.rs".to_owned(), serde_json::Value::String(entrypoint_rs.clone()));
+    structured_content.insert("processor.rs".to_owned(), serde_json::Value::String(processor_rs.clone()));
+    structured_content.insert("instruction.rs".to_owned(), serde_json::Value::String(instruction_rs.clone()));
+    structured_content.insert("state.rs".to_owned(), serde_json::Value::String(state_rs.clone()));
+    structured_content.insert("error.rs".to_owned(), serde_json::Value::String(error_rs.clone()));
+
+    CallResponse::success_structured(
+        vec![entrypoint_rs, processor_rs, instruction_rs, state_rs, error_rs],
+        structured_content,
+    )
+}
+
+// https://doc.rust-lang.org/reference/identifiers.html, restricted to plain ASCII
+fn invalid_rust_ident(ident: &str) -> bool {
+    let mut chars = ident.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {}
+        _ => return true,
+    }
+
+    !chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+struct ParsedMethodSignature {
+    name: String,
+    params: Vec<(String, String)>,
+    returns_result: bool,
+}
+
+/// Parses a single `pub fn name(&self, arg: Type, ...) -> ReturnType` line, as they'd appear in a
+/// `#[public] impl` block. Tracks paren depth rather than splitting on the first `)` so a `Result<(),
+/// Error>` return type's own parens don't get mistaken for the end of the argument list.
+fn parse_method_signature(line: &str) -> Option<ParsedMethodSignature> {
+    let line = line.trim().trim_end_matches([';', ',']).trim();
+    let rest = line.strip_prefix("pub fn ")?;
+    let paren_start = rest.find('(')?;
+    let name = rest[..paren_start].trim().to_owned();
+
+    let mut depth = 0;
+    let mut paren_end = None;
+    for (i, c) in rest.char_indices().skip(paren_start) {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    paren_end = Some(i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let paren_end = paren_end?;
+
+    let args_str = &rest[paren_start + 1..paren_end];
+    let after = rest[paren_end + 1..].trim();
+    let return_type = after.strip_prefix("->").map(|s| s.trim());
+
+    let mut params = Vec::new();
+    for (i, arg) in args_str
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .enumerate()
+    {
+        if i == 0 && (arg == "&self" || arg == "&mut self") {
+            continue;
+        }
+        let (param_name, param_type) = arg.split_once(':')?;
+        params.push((param_name.trim().to_owned(), param_type.trim().to_owned()));
+    }
+
+    Some(ParsedMethodSignature {
+        name,
+        params,
+        returns_result: return_type.is_some_and(|t| t.starts_with("Result")),
+    })
+}
+
+/// Picks a placeholder test value for a parameter's Rust type, cycling through the `alice`/`bob`
+/// fixtures for `Address` params so generated calls read naturally as two distinct accounts. Any type
+/// this doesn't recognize falls back to `Default::default()` with an inline `TODO`, rather than
+/// guessing at a constructor it might not have.
+fn placeholder_value_for_type(rust_type: &str, next_is_bob: &mut bool) -> String {
+    match rust_type {
+        "Address" => {
+            let fixture = if *next_is_bob { "bob" } else { "alice" };
+            *next_is_bob = !*next_is_bob;
+            fixture.to_owned()
+        }
+        "U256" | "U128" | "U64" | "U32" | "U16" | "U8" => format!("{rust_type}::from(1u64)"),
+        "I256" | "I128" | "I64" | "I32" | "I16" | "I8" => format!("{rust_type}::from(1i64)"),
+        "bool" => "true".to_owned(),
+        "String" => "\"example\".to_string()".to_owned(),
+        other => format!("Default::default() /* TODO: provide a {other} value */"),
+    }
+}
+
+fn generate_stylus_contract_tests(contract_type_name: &str, method_signatures: &str) -> CallResponse {
+    if invalid_rust_ident(contract_type_name) {
+        return CallResponse::error(
+            "invalid contract_type_name - must be a valid Rust identifier",
+        );
+    }
+
+    if method_signatures.trim().is_empty() {
+        return CallResponse::error("method_signatures cannot be empty");
+    }
+
+    let mut methods = Vec::new();
+    for line in method_signatures.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let Some(method) = parse_method_signature(line) else {
+            return CallResponse::error(format!("could not parse method signature: {line}"));
+        };
+        methods.push(method);
+    }
+
+    let mut tests = String::new();
+    for method in &methods {
+        let mut next_is_bob = false;
+        let call_args = method
+            .params
+            .iter()
+            .map(|(_, param_type)| placeholder_value_for_type(param_type, &mut next_is_bob))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let name = &method.name;
+        let call = format!("contract.sender(alice).{name}({call_args})");
+        let call_line = if method.returns_result {
+            format!("let result = {call};\n        result.motsu_unwrap();")
+        } else {
+            format!("{call};")
+        };
+
+        tests.push_str(&format!(
+            "\n    #[motsu::test]\n    fn test_{name}_happy_path(contract: Contract<{contract_type_name}>, alice: Address, bob: Address) {{\n        // TODO: call the constructor and any setup needed before exercising `{name}`.\n        {call_line}\n    }}\n"
+        ));
+
+        if method.returns_result {
+            tests.push_str(&format!(
+                "\n    #[motsu::test]\n    fn test_{name}_reverts_on_invalid_input(contract: Contract<{contract_type_name}>, alice: Address, bob: Address) {{\n        // TODO: call the constructor, then call `{name}` with arguments that should make it fail.\n        let _ = (contract, alice, bob);\n        todo!(\"exercise a failure path for `{name}` and assert on its error variant\");\n    }}\n"
+            ));
+        }
+    }
+
+    CallResponse::success(format!(
+        r#"
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use motsu::prelude::*;
+{tests}}}
+"#
+    ))
+}
+
+fn check_stylus_contract(project_path: &str) -> CallResponse {
+    let output = match std::process::Command::new("cargo")
+        .args(["stylus", "check"])
+        .current_dir(project_path)
+        .output()
+    {
+        Ok(output) => output,
+        Err(err) => {
+            return CallResponse::error(format!("failed to run `cargo stylus check`: {err}"));
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if output.status.success() {
+        CallResponse::success(format!(
+            "contract passes `cargo stylus check`:\n{stdout}{stderr}"
+        ))
+    } else {
+        CallResponse::error(format!("`cargo stylus check` failed:\n{stdout}{stderr}"))
+    }
+}
+
+fn search_handbook(query: &str) -> CallResponse {
+    if query.is_empty() {
+        return CallResponse::error("query cannot be an empty string");
+    }
+
+    let uris = crate::resources::search(query);
+
+    let mut structured_content = serde_json::Map::new();
+    structured_content.insert(
+        "uris".to_owned(),
+        serde_json::Value::Array(uris.iter().cloned().map(serde_json::Value::String).collect()),
+    );
+
+    CallResponse::success_structured(uris, structured_content)
+}
+
+fn search_handbook_detailed(query: &str) -> CallResponse {
+    if query.is_empty() {
+        return CallResponse::error("query cannot be an empty string");
+    }
+
+    let hits = crate::resources::search_detailed(query);
+
+    let content = hits
+        .iter()
+        .map(|hit| format!("{} (score {:.3}): {}", hit.uri, hit.score, hit.snippet))
+        .collect();
+
+    let mut structured_content = serde_json::Map::new();
+    structured_content.insert(
+        "hits".to_owned(),
+        serde_json::Value::Array(
+            hits.into_iter()
+                .map(|hit| {
+                    let mut hit_map = serde_json::Map::new();
+                    hit_map.insert("uri".to_owned(), serde_json::Value::String(hit.uri));
+                    hit_map.insert(
+                        "score".to_owned(),
+                        serde_json::Number::from_f64(hit.score)
+                            .map(serde_json::Value::Number)
+                            .unwrap_or(serde_json::Value::Null),
+                    );
+                    hit_map.insert("snippet".to_owned(), serde_json::Value::String(hit.snippet));
+                    serde_json::Value::Object(hit_map)
+                })
+                .collect(),
+        ),
+    );
+
+    CallResponse::success_structured(content, structured_content)
+}
+
+/// A recognizable Anchor/Solana construct this analysis scans for by simple substring matching
+/// (consistent with `solana_migration_note_for_crate` above - no regex dependency is available in
+/// this crate), the chapter-topic terms it contributes to the combined migration query, and how many
+/// times those terms repeat in it - constructs with a more direct Stylus migration story carry
+/// proportionally more BM25 term frequency in the combined query.
+const RECOGNIZED_MIGRATION_PATTERNS: &[(&str, &[&str], &str, usize)] = &[
+    (
+        "PDA derivation (`seeds = ...`, `bump`)",
+        &["seeds ="],
+        "program derived address PDA seeds bump StorageMap mapping",
+        3,
+    ),
+    (
+        "Cross-program invocation (`CpiContext`/`invoke`/`invoke_signed`)",
+        &["CpiContext", "invoke_signed(", "invoke("],
+        "cross program invocation CPI external call",
+        3,
+    ),
+    (
+        "Legacy state struct (`#[state]`)",
+        &["#[state]"],
+        "state storage",
+        1,
+    ),
+    (
+        "Native lamport transfer (`system_program::transfer`)",
+        &["system_program::transfer"],
+        "native token lamports transfer",
+        2,
+    ),
+];
+
+/// Extracts the comma-separated modifier function names out of every `#[access_control(...)]`
+/// attribute in `source_code`, e.g. `#[access_control(is_admin, is_not_paused)]` yields
+/// `["is_admin", "is_not_paused"]`.
+fn access_control_modifiers(source_code: &str) -> Vec<String> {
+    let mut modifiers = Vec::new();
+    let mut rest = source_code;
+
+    while let Some(start) = rest.find("#[access_control(") {
+        let after = &rest[start + "#[access_control(".len()..];
+        let Some(end) = after.find(')') else {
+            break;
+        };
+
+        modifiers.extend(
+            after[..end]
+                .split(',')
+                .map(|modifier| modifier.trim().to_owned())
+                .filter(|modifier| !modifier.is_empty()),
+        );
+        rest = &after[end..];
+    }
+
+    modifiers
+}
+
+/// One construct flagged by `analyze_solana_program_for_migration`: what was detected, and the
+/// single handbook chapter (if any) its own query terms resolve to.
+struct FlaggedPattern {
+    name: String,
+    chapter: Option<String>,
+}
+
+fn analyze_solana_program_for_migration(source_code: &str) -> CallResponse {
+    if source_code.is_empty() {
+        return CallResponse::error("source_code cannot be an empty string");
+    }
+
+    let mut query_terms: Vec<String> = Vec::new();
+    let mut flagged = Vec::new();
+
+    for &(name, needles, terms, weight) in RECOGNIZED_MIGRATION_PATTERNS {
+        if needles.iter().any(|needle| source_code.contains(*needle)) {
+            query_terms.extend(std::iter::repeat(terms.to_owned()).take(weight));
+            flagged.push(FlaggedPattern {
+                name: name.to_owned(),
+                chapter: crate::resources::search(terms).into_iter().next(),
+            });
+        }
+    }
+
+    let modifiers = access_control_modifiers(source_code);
+    if !modifiers.is_empty() {
+        let terms = format!("access control modifier {}", modifiers.join(" "));
+        query_terms.extend(std::iter::repeat(terms.clone()).take(2));
+        flagged.push(FlaggedPattern {
+            name: format!("Access control modifier (`#[access_control({})]`)", modifiers.join(", ")),
+            chapter: crate::resources::search(&terms).into_iter().next(),
+        });
+    }
+
+    if flagged.is_empty() {
+        return CallResponse::error(
+            "no recognized Anchor/Solana migration-relevant constructs found in source_code",
+        );
+    }
+
+    let chapters = crate::resources::search(&query_terms.join(" "));
+
+    let mut content = vec![format!("relevant chapters: {}", chapters.join(", "))];
+    content.extend(flagged.iter().map(|pattern| match &pattern.chapter {
+        Some(chapter) => format!("{} -> {chapter}", pattern.name),
+        None => format!("{} -> no matching chapter found", pattern.name),
+    }));
+
+    let mut structured_content = serde_json::Map::new();
+    structured_content.insert(
+        "chapters".to_owned(),
+        serde_json::Value::Array(chapters.into_iter().map(serde_json::Value::String).collect()),
+    );
+    structured_content.insert(
+        "flagged_patterns".to_owned(),
+        serde_json::Value::Array(
+            flagged
+                .into_iter()
+                .map(|pattern| {
+                    let mut pattern_map = serde_json::Map::new();
+                    pattern_map.insert("name".to_owned(), serde_json::Value::String(pattern.name));
+                    pattern_map.insert(
+                        "chapter".to_owned(),
+                        pattern.chapter.map(serde_json::Value::String).unwrap_or(serde_json::Value::Null),
+                    );
+                    serde_json::Value::Object(pattern_map)
+                })
+                .collect(),
+        ),
+    );
+
+    CallResponse::success_structured(content, structured_content)
+}
+
+// The flat-string-param tools live behind their own `get_all`/`call`, generated by
+// `define_tools!` below; `benchmark_instruction` and `benchmark_stylus_contract` take
+// nested/array parameters the macro can't express, so they're hand-written and merged
+// in at the top level instead.
+mod simple {
+    use super::*;
+
+    define_tools! {
+        detect_solana_program_kind => {
+            description: "Detect the kind of a Solana program, either 'native' or 'anchor', from its Cargo.toml file",
+            title: "Detect Solana Program Kind",
+            params: {
+                cargo_manifest: "string" = "Solana program Cargo.toml file",
+            },
+            output_schema: {
+                let mut properties = ::std::collections::HashMap::new();
+                let mut kind_property = ::serde_json::Map::new();
+                kind_property.insert("type".to_owned(), ::serde_json::Value::String("string".to_owned()));
+                kind_property.insert(
+                    "description".to_owned(),
+                    ::serde_json::Value::String("Detected program kind: \"native\" or \"anchor\"".to_owned()),
+                );
+                properties.insert("kind".to_owned(), kind_property);
+
+                Some(ToolOutputSchema::new(vec!["kind".to_owned()], Some(properties)))
+            },
+        },
+        generate_stylus_contract_cargo_manifest => {
+            description: "Generate the Cargo.toml file for a Stylus contract",
+            title: "Generate Stylus Contract Cargo.toml",
+            params: {
+                package_name: "string" = "Stylus contract package name",
+            },
+            output_schema: None,
+        },
+        generate_stylus_contract_main_rs => {
+            description: "Generate the main.rs file for a Stylus contract",
+            title: "Generate Stylus Contract main.rs",
+            params: {
+                package_name: "string" = "Stylus contract package name",
+            },
+            output_schema: None,
+        },
+        check_stylus_contract => {
+            description: "Run `cargo stylus check` against a Stylus contract project, confirming the compiled WASM passes on-chain size and activation limits before it is deployed",
+            title: "Check Stylus Contract Deployability",
+            params: {
+                project_path: "string" = "Filesystem path to the Stylus contract project directory (containing Cargo.toml)",
+            },
+            output_schema: None,
+        },
+        generate_stylus_token_contract => {
+            description: "Generate a ready-to-edit lib.rs for a Stylus contract composing an openzeppelin-stylus token standard (erc20, erc721 or erc1155), with a matching motsu test module",
+            title: "Generate Stylus Token Contract",
+            params: {
+                package_name: "string" = "Stylus contract package name",
+                token_standard: "string" = "Token standard to scaffold: erc20, erc721 or erc1155",
+            },
+            output_schema: None,
+        },
+        generate_stylus_contract_skeleton_from_anchor_idl => {
+            description: "Generate a Stylus contract skeleton from an Anchor IDL JSON file, translating each instruction into a #[public] method stub and each account struct's fields into storage members",
+            title: "Generate Stylus Skeleton From Anchor IDL",
+            params: {
+                package_name: "string" = "Stylus contract package name",
+                idl_json: "string" = "Anchor IDL JSON file content",
+            },
+            output_schema: None,
+        },
+        generate_solana_native_client_sdk => {
+            description: "Generate a typed Rust client SDK (accounts/instructions/types/errors modules, mirroring the Metaplex SDK crate layout) from a ported program's Anchor IDL JSON file",
+            title: "Generate Solana Native Client SDK",
+            params: {
+                crate_name: "string" = "Client SDK crate package name",
+                idl_json: "string" = "Anchor IDL JSON file content",
+            },
+            output_schema: None,
+        },
+        generate_solana_native_program_layout => {
+            description: "Generate the canonical native (non-Anchor) Solana program source layout - entrypoint.rs, processor.rs, instruction.rs, state.rs and error.rs - from an Anchor IDL JSON file, as an alternative generation target to the Anchor runtime",
+            title: "Generate Solana Native Program Layout",
+            params: {
+                package_name: "string" = "Native Solana program package name",
+                idl_json: "string" = "Anchor IDL JSON file content",
+            },
+            output_schema: {
+                let mut properties = ::std::collections::HashMap::new();
+                for file in ["entrypoint.rs", "processor.rs", "instruction.rs", "state.rs", "error.rs"] {
+                    let mut file_property = ::serde_json::Map::new();
+                    file_property.insert("type".to_owned(), ::serde_json::Value::String("string".to_owned()));
+                    properties.insert(file.to_owned(), file_property);
+                }
+
+                Some(ToolOutputSchema::new(
+                    vec![
+                        "entrypoint.rs".to_owned(),
+                        "processor.rs".to_owned(),
+                        "instruction.rs".to_owned(),
+                        "state.rs".to_owned(),
+                        "error.rs".to_owned(),
+                    ],
+                    Some(properties),
+                ))
+            },
+        },
+        generate_stylus_contract_tests => {
+            description: "Generate a motsu test module from a Stylus contract's #[public] method signatures, with a happy-path test per method and a revert-path stub for each one that returns a Result",
+            title: "Generate Stylus Contract Tests",
+            params: {
+                contract_type_name: "string" = "The contract's #[storage] struct type name, e.g. \"FungibleTokenContract\"",
+                method_signatures: "string" = "Newline-separated #[public] method signatures, e.g. \"pub fn stake(&mut self, amount: U256) -> Result<(), ContractError>\"",
+            },
+            output_schema: None,
+        },
+        generate_solana_migration_report => {
+            description: "Parse a Solana program's Cargo.toml and report every recognized Solana ecosystem dependency (spl-token, anchor-spl, mpl-*, etc.) alongside its closest Stylus/OpenZeppelin equivalent",
+            title: "Generate Solana Migration Report",
+            params: {
+                cargo_manifest: "string" = "Solana program Cargo.toml file",
+            },
+            output_schema: None,
+        },
+        search_handbook => {
+            description: "Search the StylusPort::Solana Handbook, receiving a list of resource URIs in descending order of relevance score",
+            title: "Search StylusPort::Solana Handbook",
+            params: {
+                query: "string" = "Search query",
+            },
+            output_schema: {
+                let mut properties = ::std::collections::HashMap::new();
+                let mut uris_property = ::serde_json::Map::new();
+                uris_property.insert("type".to_owned(), ::serde_json::Value::String("array".to_owned()));
+                uris_property.insert(
+                    "description".to_owned(),
+                    ::serde_json::Value::String("Resource URIs in descending relevance order".to_owned()),
+                );
+                let mut items = ::serde_json::Map::new();
+                items.insert("type".to_owned(), ::serde_json::Value::String("string".to_owned()));
+                uris_property.insert("items".to_owned(), ::serde_json::Value::Object(items));
+                properties.insert("uris".to_owned(), uris_property);
+
+                Some(ToolOutputSchema::new(vec!["uris".to_owned()], Some(properties)))
+            },
+        },
+        search_handbook_detailed => {
+            description: "Search the StylusPort::Solana Handbook, receiving each hit's URI, BM25F relevance score and best-matching passage (with matched terms marked) in descending order of relevance",
+            title: "Search StylusPort::Solana Handbook (Detailed)",
+            params: {
+                query: "string" = "Search query",
+            },
+            output_schema: {
+                let mut hit_properties = ::serde_json::Map::new();
+
+                let mut uri_property = ::serde_json::Map::new();
+                uri_property.insert("type".to_owned(), ::serde_json::Value::String("string".to_owned()));
+                hit_properties.insert("uri".to_owned(), ::serde_json::Value::Object(uri_property));
+
+                let mut score_property = ::serde_json::Map::new();
+                score_property.insert("type".to_owned(), ::serde_json::Value::String("number".to_owned()));
+                hit_properties.insert("score".to_owned(), ::serde_json::Value::Object(score_property));
+
+                let mut snippet_property = ::serde_json::Map::new();
+                snippet_property.insert("type".to_owned(), ::serde_json::Value::String("string".to_owned()));
+                snippet_property.insert(
+                    "description".to_owned(),
+                    ::serde_json::Value::String("Best-matching passage with matched terms wrapped in **marks**".to_owned()),
+                );
+                hit_properties.insert("snippet".to_owned(), ::serde_json::Value::Object(snippet_property));
+
+                let mut hit_item = ::serde_json::Map::new();
+                hit_item.insert("type".to_owned(), ::serde_json::Value::String("object".to_owned()));
+                hit_item.insert("properties".to_owned(), ::serde_json::Value::Object(hit_properties));
+
+                let mut properties = ::std::collections::HashMap::new();
+                let mut hits_property = ::serde_json::Map::new();
+                hits_property.insert("type".to_owned(), ::serde_json::Value::String("array".to_owned()));
+                hits_property.insert(
+                    "description".to_owned(),
+                    ::serde_json::Value::String("Search hits in descending relevance order".to_owned()),
+                );
+                hits_property.insert("items".to_owned(), ::serde_json::Value::Object(hit_item));
+                properties.insert("hits".to_owned(), hits_property);
+
+                Some(ToolOutputSchema::new(vec!["hits".to_owned()], Some(properties)))
+            },
+        },
+        analyze_solana_program_for_migration => {
+            description: "Scan a pasted Anchor/Solana source file for migration-relevant constructs (PDA derivation, CPI, access_control modifiers, legacy #[state] structs, native lamport transfers), and return the handbook chapters most relevant to migrating it alongside the flagged patterns and the chapter covering each",
+            title: "Analyze Solana Program For Migration",
+            params: {
+                source_code: "string" = "Anchor or native Solana program source file content",
+            },
+            output_schema: {
+                let mut properties = ::std::collections::HashMap::new();
+
+                let mut chapters_property = ::serde_json::Map::new();
+                chapters_property.insert("type".to_owned(), ::serde_json::Value::String("array".to_owned()));
+                chapters_property.insert(
+                    "description".to_owned(),
+                    ::serde_json::Value::String("Resource URIs in descending relevance order".to_owned()),
+                );
+                let mut chapter_items = ::serde_json::Map::new();
+                chapter_items.insert("type".to_owned(), ::serde_json::Value::String("string".to_owned()));
+                chapters_property.insert("items".to_owned(), ::serde_json::Value::Object(chapter_items));
+                properties.insert("chapters".to_owned(), chapters_property);
+
+                let mut pattern_properties = ::serde_json::Map::new();
+                let mut pattern_name_property = ::serde_json::Map::new();
+                pattern_name_property.insert("type".to_owned(), ::serde_json::Value::String("string".to_owned()));
+                pattern_properties.insert("name".to_owned(), ::serde_json::Value::Object(pattern_name_property));
+                let mut pattern_chapter_property = ::serde_json::Map::new();
+                pattern_chapter_property.insert("type".to_owned(), ::serde_json::Value::String("string".to_owned()));
+                pattern_properties.insert("chapter".to_owned(), ::serde_json::Value::Object(pattern_chapter_property));
+
+                let mut pattern_item = ::serde_json::Map::new();
+                pattern_item.insert("type".to_owned(), ::serde_json::Value::String("object".to_owned()));
+                pattern_item.insert("properties".to_owned(), ::serde_json::Value::Object(pattern_properties));
+
+                let mut flagged_patterns_property = ::serde_json::Map::new();
+                flagged_patterns_property.insert("type".to_owned(), ::serde_json::Value::String("array".to_owned()));
+                flagged_patterns_property.insert(
+                    "description".to_owned(),
+                    ::serde_json::Value::String("Detected migration-relevant constructs, each with the chapter covering it".to_owned()),
+                );
+                flagged_patterns_property.insert("items".to_owned(), ::serde_json::Value::Object(pattern_item));
+                properties.insert("flagged_patterns".to_owned(), flagged_patterns_property);
+
+                Some(ToolOutputSchema::new(
+                    vec!["chapters".to_owned(), "flagged_patterns".to_owned()],
+                    Some(properties),
+                ))
+            },
+        },
+    }
+}
+
+pub fn get_all() -> Vec<Tool> {
+    let mut tools = simple::get_all();
+    tools.push(benchmark_instruction::tool());
+    tools.push(benchmark_stylus_contract::tool());
+    tools
+}
+
+pub fn call(
+    name: &str,
+    arguments: Option<&::serde_json::Map<String, ::serde_json::Value>>,
+) -> Option<CallToolResult> {
+    if name == "benchmark_instruction" {
+        return Some(benchmark_instruction::call(arguments).into());
+    }
+
+    if name == "benchmark_stylus_contract" {
+        return Some(benchmark_stylus_contract::call(arguments).into());
+    }
+
+    simple::call(name, arguments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_package_name_valid_names() {
+        assert!(!invalid_package_name("my_package"));
+        assert!(!invalid_package_name("my-package"));
+        assert!(!invalid_package_name("package123"));
+        assert!(!invalid_package_name("a"));
+        assert!(!invalid_package_name("Package_Name-123"));
+        assert!(!invalid_package_name("_underscore"));
+        assert!(!invalid_package_name("-dash"));
+    }
+
+    #[test]
+    fn test_invalid_package_name_empty_name() {
+        assert!(invalid_package_name(""));
+    }
+
+    #[test]
+    fn test_invalid_package_name_length_boundary() {
+        let valid_64 = "a".repeat(64);
+        assert!(!invalid_package_name(&valid_64));
+        let invalid_65 = "a".repeat(65);
+        assert!(invalid_package_name(&invalid_65));
+        let invalid_long = "a".repeat(100);
+        assert!(invalid_package_name(&invalid_long));
+    }
+
+    #[test]
+    fn test_invalid_package_name_invalid_characters() {
+        assert!(invalid_package_name("my package")); // space
+        assert!(invalid_package_name("my.package")); // dot
+        assert!(invalid_package_name("my@package")); // @
+        assert!(invalid_package_name("my/package")); // slash
+        assert!(invalid_package_name("my\\package")); // backslash
+        assert!(invalid_package_name("my!package")); // exclamation
+        assert!(invalid_package_name("my#package")); // hash
+        assert!(invalid_package_name("my$package")); // dollar
+        assert!(invalid_package_name("my%package")); // percent
+        assert!(invalid_package_name("my_package\n")); // newline
+                                                       // assert!(invalid_package_name("mypackage"\n[dependencies]\nmalicious-crate = \"1.0\"")) // newlines
+    }
+
+    #[test]
+    fn test_invalid_package_name_unicode() {
+        assert!(invalid_package_name("my_packagÃ©")); // accented char
+        assert!(invalid_package_name("my_packageâ„¢")); // symbol
+        assert!(invalid_package_name("my_ðŸ“¦")); // emoji
+    }
+
+    #[test]
+    fn test_generate_stylus_contract_main_rs_rejects_code_injection() {
+        assert_eq!(
+            generate_stylus_contract_main_rs("mypackage\n malicious_code();\n"),
+            CallResponse::error(INVALID_PACKAGE_NAME_MSG)
+        )
+    }
+
+    #[test]
+    fn test_generate_stylus_contract_cargo_manifest_rejects_malicious_dependency_injection() {
+        assert_eq!(
+            generate_stylus_contract_cargo_manifest(
+                "mypackage\n[dependencies]\nmalicious-crate = \"1.0\""
+            ),
+            CallResponse::error(INVALID_PACKAGE_NAME_MSG)
+        )
+    }
+
+    #[test]
+    fn test_check_stylus_contract_reports_failure_for_nonexistent_project() {
+        let response = check_stylus_contract("/nonexistent/stylusport-mcp-test-path");
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_contract_type_name() {
+        assert_eq!(contract_type_name("my-token"), "MyTokenContract");
+        assert_eq!(contract_type_name("my_token"), "MyTokenContract");
+        assert_eq!(contract_type_name("token"), "TokenContract");
+    }
+
+    #[test]
+    fn test_generate_stylus_token_contract_rejects_invalid_package_name() {
+        assert_eq!(
+            generate_stylus_token_contract("my package", "erc20"),
+            CallResponse::error(INVALID_PACKAGE_NAME_MSG)
+        );
+    }
+
+    #[test]
+    fn test_generate_stylus_token_contract_rejects_invalid_token_standard() {
+        assert_eq!(
+            generate_stylus_token_contract("my-token", "erc404"),
+            CallResponse::error(INVALID_TOKEN_STANDARD_MSG)
+        );
+    }
+
+    #[test]
+    fn test_generate_stylus_token_contract_erc20() {
+        let response = generate_stylus_token_contract("my-token", "erc20");
+        assert!(!response.is_error);
+        assert!(response.content[0].contains("MyTokenContract"));
+        assert!(response.content[0].contains("openzeppelin_stylus::token::erc20"));
+    }
+
+    #[test]
+    fn test_generate_stylus_token_contract_erc721() {
+        let response = generate_stylus_token_contract("my-token", "erc721");
+        assert!(!response.is_error);
+        assert!(response.content[0].contains("MyTokenContract"));
+        assert!(response.content[0].contains("openzeppelin_stylus::token::erc721"));
+    }
+
+    #[test]
+    fn test_generate_stylus_token_contract_erc1155() {
+        let response = generate_stylus_token_contract("my-token", "erc1155");
+        assert!(!response.is_error);
+        assert!(response.content[0].contains("MyTokenContract"));
+        assert!(response.content[0].contains("openzeppelin_stylus::token::erc1155"));
+    }
+
+    #[test]
+    fn test_anchor_name_to_snake_case() {
+        assert_eq!(anchor_name_to_snake_case("initializeVault"), "initialize_vault");
+        assert_eq!(anchor_name_to_snake_case("amount"), "amount");
+    }
+
+    #[test]
+    fn test_generate_stylus_contract_skeleton_from_anchor_idl_rejects_invalid_json() {
+        let response = generate_stylus_contract_skeleton_from_anchor_idl("my-program", "not json");
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_generate_stylus_contract_skeleton_from_anchor_idl_rejects_missing_instructions() {
+        let response = generate_stylus_contract_skeleton_from_anchor_idl("my-program", "{}");
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_generate_stylus_contract_skeleton_from_anchor_idl() {
+        let idl = r#"
+        {
+            "instructions": [
+                {
+                    "name": "initializeVault",
+                    "args": [
+                        {"name": "amount", "type": "u64"},
+                        {"name": "owner", "type": "publicKey"}
+                    ]
+                }
+            ],
+            "accounts": [
+                {
+                    "name": "Vault",
+                    "type": {
+                        "kind": "struct",
+                        "fields": [
+                            {"name": "balance", "type": "u64"},
+                            {"name": "authority", "type": "publicKey"}
+                        ]
+                    }
+                }
+            ],
+            "errors": [
+                {"code": 6000, "name": "Unauthorized", "msg": "signer is not the vault authority"}
+            ]
+        }
+        "#;
+
+        let response = generate_stylus_contract_skeleton_from_anchor_idl("my-vault", idl);
+        assert!(!response.is_error);
+        let lib_rs = &response.content[0];
+        assert!(lib_rs.contains("pub struct MyVaultContract"));
+        assert!(lib_rs.contains("pub fn initialize_vault(&mut self, amount: U256, owner: Address)"));
+        assert!(lib_rs.contains("vault_balance: StorageMap<Address, StorageU256>"));
+        assert!(lib_rs.contains("vault_authority: StorageMap<Address, StorageAddress>"));
+        assert!(lib_rs.contains("error Unauthorized();"));
+        assert!(lib_rs.contains("Unauthorized(Unauthorized),"));
+    }
+
+    #[test]
+    fn test_generate_solana_native_client_sdk_rejects_invalid_json() {
+        let response = generate_solana_native_client_sdk("my-vault", "not json");
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_generate_solana_native_client_sdk_rejects_missing_instructions() {
+        let response = generate_solana_native_client_sdk("my-vault", "{}");
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_generate_solana_native_client_sdk() {
+        let idl = r#"
+        {
+            "instructions": [
+                {
+                    "name": "initializeVault",
+                    "args": [
+                        {"name": "amount", "type": "u64"}
+                    ],
+                    "accounts": [
+                        {"name": "vault", "isMut": true, "isSigner": false},
+                        {"name": "authority", "isMut": false, "isSigner": true}
+                    ]
+                }
+            ],
+            "accounts": [
+                {
+                    "name": "Vault",
+                    "type": {
+                        "kind": "struct",
+                        "fields": [
+                            {"name": "balance", "type": "u64"},
+                            {"name": "authority", "type": "publicKey"}
+                        ]
+                    }
+                }
+            ],
+            "errors": [
+                {"code": 6000, "name": "Unauthorized", "msg": "signer is not the vault authority"}
+            ]
+        }
+        "#;
+
+        let response = generate_solana_native_client_sdk("my-vault", idl);
+        assert!(!response.is_error);
+        let sdk_rs = &response.content[0];
+        assert!(sdk_rs.contains("pub struct Vault {"));
+        assert!(sdk_rs.contains("pub balance: u64,"));
+        assert!(sdk_rs.contains("pub authority: Pubkey,"));
+        assert!(sdk_rs.contains("Unauthorized = 6000,"));
+        assert!(sdk_rs.contains("pub struct InitializeVaultAccounts {"));
+        assert!(sdk_rs.contains("pub vault: Pubkey,"));
+        assert!(sdk_rs.contains("AccountMeta::new(self.vault, false),"));
+        assert!(sdk_rs.contains("AccountMeta::new_readonly(self.authority, true),"));
+        assert!(sdk_rs
+            .contains("pub fn initialize_vault(program_id: &Pubkey, accounts: InitializeVaultAccounts, amount: u64)"));
+        assert!(sdk_rs.contains("InitializeVault {\n        amount: u64,\n    },"));
+    }
+
+    #[test]
+    fn test_generate_solana_native_program_layout_rejects_invalid_json() {
+        let response = generate_solana_native_program_layout("my-vault", "not json");
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_generate_solana_native_program_layout() {
+        let idl = r#"
+        {
+            "instructions": [
+                {
+                    "name": "initializeVault",
+                    "args": [
+                        {"name": "amount", "type": "u64"}
+                    ]
+                }
+            ],
+            "accounts": [
+                {
+                    "name": "Vault",
+                    "type": {
+                        "kind": "struct",
+                        "fields": [
+                            {"name": "balance", "type": "u64"}
+                        ]
+                    }
+                }
+            ],
+            "errors": [
+                {"code": 6000, "name": "Unauthorized", "msg": "signer is not the vault authority"}
+            ]
+        }
+        "#;
+
+        let response = generate_solana_native_program_layout("my-vault", idl);
+        assert!(!response.is_error);
+        assert_eq!(response.content.len(), 5);
+        let [entrypoint_rs, processor_rs, instruction_rs, state_rs, error_rs] =
+            <[String; 5]>::try_from(response.content.clone()).unwrap();
+        assert!(entrypoint_rs.contains("entrypoint!(process_instruction);"));
+        assert!(processor_rs.contains("fn process_initialize_vault(\n    _program_id: &Pubkey,\n    _accounts: &[AccountInfo], amount: u64,\n)"));
+        assert!(processor_rs.contains("Instruction::InitializeVault { amount, } => {"));
+        assert!(instruction_rs.contains("InitializeVault {\n        amount: u64,\n    },"));
+        assert!(state_rs.contains("pub struct Vault {"));
+        assert!(state_rs.contains("pub balance: u64,"));
+        assert!(error_rs.contains("pub enum ErrorCode {"));
+        assert!(error_rs.contains("Unauthorized,"));
+    }
+
+    #[test]
+    fn test_generate_stylus_contract_tests_rejects_invalid_contract_type_name() {
+        let response = generate_stylus_contract_tests(
+            "not a type",
+            "pub fn stake(&mut self, amount: U256) -> Result<(), ContractError>",
+        );
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_generate_stylus_contract_tests_rejects_empty_signatures() {
+        let response = generate_stylus_contract_tests("FungibleTokenContract", "");
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_generate_stylus_contract_tests_rejects_unparseable_signature() {
+        let response = generate_stylus_contract_tests("FungibleTokenContract", "not a signature");
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_generate_stylus_contract_tests() {
+        let signatures = "pub fn stake(&mut self, amount: U256) -> Result<(), ContractError>\n\
+                           pub fn staked_balance_of(&self, account: Address) -> U256";
+
+        let response = generate_stylus_contract_tests("FungibleTokenContract", signatures);
+        assert!(!response.is_error);
+        let tests = &response.content[0];
+        assert!(tests.contains("fn test_stake_happy_path(contract: Contract<FungibleTokenContract>, alice: Address, bob: Address)"));
+        assert!(tests.contains("let result = contract.sender(alice).stake(U256::from(1u64));"));
+        assert!(tests.contains("fn test_stake_reverts_on_invalid_input"));
+        assert!(tests.contains("fn test_staked_balance_of_happy_path"));
+        assert!(tests.contains("contract.sender(alice).staked_balance_of(alice);"));
+        assert!(!tests.contains("fn test_staked_balance_of_reverts_on_invalid_input"));
+    }
+
+    #[test]
+    fn test_generate_solana_migration_report_rejects_invalid_toml() {
+        let response = generate_solana_migration_report("not = [valid toml");
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_generate_solana_migration_report_rejects_missing_dependencies() {
+        let response = generate_solana_migration_report("[package]\nname = \"foo\"");
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_generate_solana_migration_report_no_recognized_crates() {
+        let manifest = "[package]\nname = \"foo\"\n\n[dependencies]\nserde = \"1.0\"";
+        let response = generate_solana_migration_report(manifest);
+        assert!(!response.is_error);
+        assert!(response.content[0].contains("no recognized Solana ecosystem crates"));
+    }
+
+    #[test]
+    fn test_generate_solana_migration_report_detects_known_crates() {
+        let manifest = r#"
+[package]
+name = "foo"
+
+[dependencies]
+anchor-lang = "0.30"
+spl-token = "4.0"
+mpl-token-metadata = "4.1"
+mpl-bubblegum = "1.0"
+serde = "1.0"
+"#;
+
+        let response = generate_solana_migration_report(manifest);
+        assert!(!response.is_error);
+        let report = &response.content[0];
+        assert!(report.contains("anchor-lang -> stylus_sdk::prelude"));
+        assert!(report.contains("spl-token -> openzeppelin_stylus::token::erc20"));
+        assert!(report.contains("mpl-token-metadata -> openzeppelin_stylus::token::erc721::extensions::Erc721Metadata"));
+        assert!(report.contains("mpl-bubblegum -> the Metaplex program family"));
+        assert!(!report.contains("serde ->"));
+    }
+
+    #[test]
+    fn test_analyze_solana_program_for_migration_rejects_empty_source() {
+        let response = analyze_solana_program_for_migration("");
+        assert!(response.is_error);
+    }
+
+    #[test]
+    fn test_analyze_solana_program_for_migration_no_recognized_constructs() {
+        let response = analyze_solana_program_for_migration("fn main() {}");
+        assert!(response.is_error);
+        assert!(response.content[0].contains("no recognized Anchor/Solana migration-relevant constructs"));
+    }
+
+    #[test]
+    fn test_analyze_solana_program_for_migration_detects_pda_and_cpi() {
+        let source = r#"
+#[account(seeds = [b"vault", authority.key().as_ref()], bump)]
+pub vault: Account<'info, Vault>,
+
+let cpi_ctx = CpiContext::new(program.to_account_info(), Transfer {});
+invoke_signed(&ix, &accounts, &[seeds])?;
+"#;
+        let response = analyze_solana_program_for_migration(source);
+        assert!(!response.is_error);
+        assert!(response.content.iter().any(|line| line.contains("PDA derivation")));
+        assert!(response.content.iter().any(|line| line.contains("Cross-program invocation")));
+    }
+
+    #[test]
+    fn test_analyze_solana_program_for_migration_detects_access_control_modifiers() {
+        let source = "#[access_control(is_admin, is_not_paused)]\npub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {}";
+        let response = analyze_solana_program_for_migration(source);
+        assert!(!response.is_error);
+        assert!(
+            response
+                .content
+                .iter()
+                .any(|line| line.contains("is_admin, is_not_paused"))
+        );
+    }
+
+    #[test]
+    fn test_analyze_solana_program_for_migration_detects_lamport_transfer() {
+        let source = "system_program::transfer(cpi_ctx, amount)?;";
+        let response = analyze_solana_program_for_migration(source);
+        assert!(!response.is_error);
+        assert!(
+            response
+                .content
+                .iter()
+                .any(|line| line.contains("Native lamport transfer"))
+        );
     }
 }
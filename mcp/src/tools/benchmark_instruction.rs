@@ -0,0 +1,313 @@
+//! Runs a single instruction against a compiled example program through Mollusk -
+//! the same machinery the example programs' own tests already use - and reports the
+//! compute units it consumed plus a before/after diff of every account it touched, so
+//! a client can judge whether a ported instruction fits a reasonable CU budget.
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use mollusk_svm::Mollusk;
+use rust_mcp_schema::{Tool, ToolInputSchema};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use solana_account::Account;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use super::CallResponse;
+
+#[derive(Deserialize)]
+struct AccountInput {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+    #[serde(default)]
+    lamports: u64,
+    #[serde(default)]
+    data_base64: String,
+    #[serde(default)]
+    owner: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BenchmarkRequest {
+    /// The example's directory under `examples/concepts`, e.g.
+    /// `"program-structure/native"` - used to derive both its compiled program name
+    /// and the crate it's loaded from.
+    example: String,
+    program_id: String,
+    #[serde(default)]
+    instruction_data_base64: String,
+    accounts: Vec<AccountInput>,
+}
+
+struct AccountDiff {
+    pubkey: Pubkey,
+    lamports_before: u64,
+    lamports_after: u64,
+    data_before: Vec<u8>,
+    data_after: Vec<u8>,
+}
+
+impl AccountDiff {
+    fn changed(&self) -> bool {
+        self.lamports_before != self.lamports_after || self.data_before != self.data_after
+    }
+}
+
+impl fmt::Display for AccountDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  {}:", self.pubkey)?;
+        writeln!(
+            f,
+            "    lamports: {} -> {}",
+            self.lamports_before, self.lamports_after
+        )?;
+        writeln!(f, "    data before: {}", BASE64.encode(&self.data_before))?;
+        write!(f, "    data after:  {}", BASE64.encode(&self.data_after))
+    }
+}
+
+struct Report {
+    compute_units_consumed: u64,
+    program_result: String,
+    account_diffs: Vec<AccountDiff>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "compute units consumed: {}", self.compute_units_consumed)?;
+        writeln!(f, "program result: {}", self.program_result)?;
+
+        let touched: Vec<_> = self.account_diffs.iter().filter(|d| d.changed()).collect();
+
+        if touched.is_empty() {
+            write!(f, "touched accounts: none changed")?;
+        } else {
+            writeln!(f, "touched accounts:")?;
+            for (index, diff) in touched.iter().enumerate() {
+                if index > 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "{diff}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn run(request: BenchmarkRequest) -> Result<Report, String> {
+    let program_id = Pubkey::from_str(&request.program_id)
+        .map_err(|err| format!("invalid program_id: {err}"))?;
+
+    let instruction_data = BASE64
+        .decode(&request.instruction_data_base64)
+        .map_err(|err| format!("invalid instruction_data_base64: {err}"))?;
+
+    let mut accounts = Vec::with_capacity(request.accounts.len());
+    let mut account_metas = Vec::with_capacity(request.accounts.len());
+
+    for account in request.accounts {
+        let pubkey =
+            Pubkey::from_str(&account.pubkey).map_err(|err| format!("invalid pubkey: {err}"))?;
+
+        let owner = match account.owner {
+            Some(owner) => {
+                Pubkey::from_str(&owner).map_err(|err| format!("invalid owner: {err}"))?
+            }
+            None => solana_sdk_ids::system_program::id(),
+        };
+
+        let data = BASE64
+            .decode(&account.data_base64)
+            .map_err(|err| format!("invalid data_base64 for {pubkey}: {err}"))?;
+
+        account_metas.push(AccountMeta {
+            pubkey,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        });
+
+        accounts.push((
+            pubkey,
+            Account {
+                lamports: account.lamports,
+                data,
+                owner,
+                executable: false,
+                rent_epoch: 0,
+            },
+        ));
+    }
+
+    let instruction = Instruction {
+        program_id,
+        accounts: account_metas,
+        data: instruction_data,
+    };
+
+    // The example's crate name follows the repo-wide convention of its
+    // `examples/concepts` path with `/` and `-` replaced by `_`, which is also the
+    // program name `cargo-build-sbf` writes its compiled `.so` under.
+    let program_name = request.example.replace(['/', '-'], "_");
+
+    let mollusk = Mollusk::new(&program_id, &program_name);
+
+    let before: HashMap<Pubkey, Account> = accounts.iter().cloned().collect();
+
+    let result = mollusk.process_instruction(&instruction, &accounts);
+
+    let account_diffs = result
+        .resulting_accounts
+        .iter()
+        .map(|(pubkey, after)| {
+            let before = before.get(pubkey).cloned().unwrap_or_default();
+
+            AccountDiff {
+                pubkey: *pubkey,
+                lamports_before: before.lamports,
+                lamports_after: after.lamports,
+                data_before: before.data,
+                data_after: after.data.clone(),
+            }
+        })
+        .collect();
+
+    Ok(Report {
+        compute_units_consumed: result.compute_units_consumed,
+        program_result: format!("{:?}", result.program_result),
+        account_diffs,
+    })
+}
+
+fn string_property(description: &str) -> Map<String, Value> {
+    let mut property = Map::new();
+    property.insert("type".to_owned(), Value::String("string".to_owned()));
+    property.insert(
+        "description".to_owned(),
+        Value::String(description.to_owned()),
+    );
+    property
+}
+
+fn accounts_property() -> Map<String, Value> {
+    let mut item_properties = Map::new();
+    item_properties.insert(
+        "pubkey".to_owned(),
+        Value::Object(string_property("Base58 account address")),
+    );
+    item_properties.insert(
+        "is_signer".to_owned(),
+        Value::Object({
+            let mut property = Map::new();
+            property.insert("type".to_owned(), Value::String("boolean".to_owned()));
+            property
+        }),
+    );
+    item_properties.insert(
+        "is_writable".to_owned(),
+        Value::Object({
+            let mut property = Map::new();
+            property.insert("type".to_owned(), Value::String("boolean".to_owned()));
+            property
+        }),
+    );
+    item_properties.insert(
+        "lamports".to_owned(),
+        Value::Object({
+            let mut property = Map::new();
+            property.insert("type".to_owned(), Value::String("integer".to_owned()));
+            property
+        }),
+    );
+    item_properties.insert(
+        "data_base64".to_owned(),
+        Value::Object(string_property("Base64-encoded initial account data")),
+    );
+    item_properties.insert(
+        "owner".to_owned(),
+        Value::Object(string_property(
+            "Base58 owning program, defaults to the system program",
+        )),
+    );
+
+    let mut item_schema = Map::new();
+    item_schema.insert("type".to_owned(), Value::String("object".to_owned()));
+    item_schema.insert("properties".to_owned(), Value::Object(item_properties));
+    item_schema.insert(
+        "required".to_owned(),
+        Value::Array(vec![
+            Value::String("pubkey".to_owned()),
+            Value::String("is_signer".to_owned()),
+            Value::String("is_writable".to_owned()),
+        ]),
+    );
+
+    let mut property = Map::new();
+    property.insert("type".to_owned(), Value::String("array".to_owned()));
+    property.insert(
+        "description".to_owned(),
+        Value::String("The keyed accounts the instruction runs against".to_owned()),
+    );
+    property.insert("items".to_owned(), Value::Object(item_schema));
+    property
+}
+
+pub fn tool() -> Tool {
+    let mut properties = HashMap::new();
+    properties.insert(
+        "example".to_owned(),
+        string_property(
+            "The example's directory under examples/concepts, e.g. \"program-structure/native\"",
+        ),
+    );
+    properties.insert(
+        "program_id".to_owned(),
+        string_property("Base58 program id the instruction targets"),
+    );
+    properties.insert(
+        "instruction_data_base64".to_owned(),
+        string_property("Base64-encoded instruction data"),
+    );
+    properties.insert("accounts".to_owned(), accounts_property());
+
+    Tool {
+        annotations: None,
+        description: Some(
+            "Run an instruction against a compiled example program through Mollusk, \
+             returning the compute units consumed, the program result, and a \
+             before/after diff of every touched account's lamports and data"
+                .to_owned(),
+        ),
+        input_schema: ToolInputSchema::new(
+            vec![
+                "example".to_owned(),
+                "program_id".to_owned(),
+                "accounts".to_owned(),
+            ],
+            Some(properties),
+        ),
+        meta: None,
+        name: "benchmark_instruction".to_owned(),
+        output_schema: None,
+        title: Some("Benchmark Instruction Compute Units".to_owned()),
+    }
+}
+
+pub fn call(arguments: Option<&Map<String, Value>>) -> CallResponse {
+    let Some(arguments) = arguments else {
+        return CallResponse::error("arguments missing");
+    };
+
+    let request: BenchmarkRequest = match serde_json::from_value(Value::Object(arguments.clone()))
+    {
+        Ok(request) => request,
+        Err(err) => return CallResponse::error(format!("invalid arguments: {err}")),
+    };
+
+    match run(request) {
+        Ok(report) => CallResponse::success(report.to_string()),
+        Err(err) => CallResponse::error(err),
+    }
+}
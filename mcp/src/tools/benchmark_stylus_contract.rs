@@ -0,0 +1,115 @@
+//! Shells out to `cargo stylus check` against a caller-supplied Stylus project and reports what it says about
+//! the compiled contract's deployability and one-time activation gas. Unlike [`super::benchmark_instruction`],
+//! which runs a single Solana instruction through Mollusk, `cargo-stylus` (as pinned by OpenZeppelin's own
+//! gas-bench CI, `cargo-stylus@0.5.x`) has no subcommand that breaks gas down per individual entrypoint - doing
+//! that requires calling each function against a live devnode, which is out of scope for a static project path.
+//! The `function_signatures` a caller lists are echoed back alongside the contract-wide numbers `cargo stylus
+//! check` actually reports, rather than pretending to measure each one separately.
+
+use std::process::Command;
+
+use rust_mcp_schema::{Tool, ToolInputSchema};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::CallResponse;
+
+#[derive(Deserialize)]
+struct BenchmarkRequest {
+    project_path: String,
+    function_signatures: Vec<String>,
+}
+
+fn run(request: BenchmarkRequest) -> Result<String, String> {
+    let output = Command::new("cargo")
+        .args(["stylus", "check"])
+        .current_dir(&request.project_path)
+        .output()
+        .map_err(|err| format!("failed to run `cargo stylus check`: {err}"))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !output.status.success() {
+        return Err(format!("`cargo stylus check` failed:\n{stdout}{stderr}"));
+    }
+
+    let mut report = format!("`cargo stylus check` output for {}:\n{stdout}{stderr}", request.project_path);
+
+    report.push_str(
+        "\n\ncargo-stylus has no subcommand that estimates gas per individual entrypoint - only the \
+         contract-wide activation numbers above are available without deploying to a live devnode. \
+         Requested function signatures (not individually measured):\n",
+    );
+    for signature in &request.function_signatures {
+        report.push_str(&format!("  - {signature}\n"));
+    }
+
+    Ok(report)
+}
+
+fn string_property(description: &str) -> Map<String, Value> {
+    let mut property = Map::new();
+    property.insert("type".to_owned(), Value::String("string".to_owned()));
+    property.insert(
+        "description".to_owned(),
+        Value::String(description.to_owned()),
+    );
+    property
+}
+
+pub fn tool() -> Tool {
+    let mut properties = std::collections::HashMap::new();
+    properties.insert(
+        "project_path".to_owned(),
+        string_property("Filesystem path to the Stylus contract project directory (containing Cargo.toml)"),
+    );
+    properties.insert("function_signatures".to_owned(), {
+        let mut property = Map::new();
+        property.insert("type".to_owned(), Value::String("array".to_owned()));
+        property.insert(
+            "description".to_owned(),
+            Value::String(
+                "Function signatures to list in the report, e.g. \"stake(uint256)\"; they are echoed back, not individually measured".to_owned(),
+            ),
+        );
+        property.insert(
+            "items".to_owned(),
+            Value::Object(string_property("A function signature")),
+        );
+        property
+    });
+
+    Tool {
+        annotations: None,
+        description: Some(
+            "Compile a Stylus contract project via `cargo stylus check` and report its activation gas and \
+             size-limit status, alongside the list of entrypoint signatures a caller wants gas costs for"
+                .to_owned(),
+        ),
+        input_schema: ToolInputSchema::new(
+            vec!["project_path".to_owned(), "function_signatures".to_owned()],
+            Some(properties),
+        ),
+        meta: None,
+        name: "benchmark_stylus_contract".to_owned(),
+        output_schema: None,
+        title: Some("Benchmark Stylus Contract Gas".to_owned()),
+    }
+}
+
+pub fn call(arguments: Option<&Map<String, Value>>) -> CallResponse {
+    let Some(arguments) = arguments else {
+        return CallResponse::error("arguments missing");
+    };
+
+    let request: BenchmarkRequest = match serde_json::from_value(Value::Object(arguments.clone())) {
+        Ok(request) => request,
+        Err(err) => return CallResponse::error(format!("invalid arguments: {err}")),
+    };
+
+    match run(request) {
+        Ok(report) => CallResponse::success(report),
+        Err(err) => CallResponse::error(err),
+    }
+}